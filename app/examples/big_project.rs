@@ -472,36 +472,36 @@ fn build_demo_container() -> anyhow::Result<SingleProjectContainer> {
 
     // ========== Назначение ресурсов ==========
     // Назначаем ресурсы на задачи (некоторые с частичной занятостью)
-    task_service.allocate_resource(project_id, t1_1_id, pm.id, 0.5, None)?;
-    task_service.allocate_resource(project_id, t1_2_id, pm.id, 0.3, None)?;
-    task_service.allocate_resource(project_id, t1_3_id, analyst.id, 0.7, None)?;
-
-    task_service.allocate_resource(project_id, t2_1_id, analyst.id, 0.8, None)?;
-    task_service.allocate_resource(project_id, t2_2_id, analyst.id, 0.6, None)?;
-    task_service.allocate_resource(project_id, t2_3_id, dev_lead.id, 0.6, None)?;
-    task_service.allocate_resource(project_id, t2_4_id, dev_lead.id, 0.3, None)?;
-    task_service.allocate_resource(project_id, t2_5_id, pm.id, 0.1, None)?;
-
-    task_service.allocate_resource(project_id, t3_1_1_id, devops.id, 0.5, None)?;
-    task_service.allocate_resource(project_id, t3_1_2_id, devops.id, 0.5, None)?;
-    task_service.allocate_resource(project_id, t3_2_1_id, dev.id, 0.3, None)?;
-    task_service.allocate_resource(project_id, t3_2_2_id, dev.id, 0.3, None)?;
-    task_service.allocate_resource(project_id, t3_3_1_id, dev.id, 0.3, None)?;
-    task_service.allocate_resource(project_id, t3_3_2_id, dev.id, 0.1, None)?;
-
-    task_service.allocate_resource(project_id, t4_1_id, tester.id, 0.25, None)?;
-    task_service.allocate_resource(project_id, t4_2_id, tester.id, 0.25, None)?;
-    task_service.allocate_resource(project_id, t4_3_id, tester.id, 0.25, None)?;
-    task_service.allocate_resource(project_id, t4_4_id, tester.id, 0.25, None)?;
-
-    task_service.allocate_resource(project_id, t5_1_id, analyst.id, 0.4, None)?;
-    task_service.allocate_resource(project_id, t5_2_id, analyst.id, 0.5, None)?;
-    task_service.allocate_resource(project_id, t5_3_id, dev.id, 0.3, None)?;
-    task_service.allocate_resource(project_id, t5_4_id, devops.id, 0.6, None)?;
-
-    task_service.allocate_resource(project_id, t6_1_id, analyst.id, 0.2, None)?;
-    task_service.allocate_resource(project_id, t6_2_id, dev.id, 0.4, None)?;
-    task_service.allocate_resource(project_id, t6_3_id, devops.id, 0.3, None)?;
+    task_service.allocate_resource(project_id, t1_1_id, pm.id, 0.5, None, None)?;
+    task_service.allocate_resource(project_id, t1_2_id, pm.id, 0.3, None, None)?;
+    task_service.allocate_resource(project_id, t1_3_id, analyst.id, 0.7, None, None)?;
+
+    task_service.allocate_resource(project_id, t2_1_id, analyst.id, 0.8, None, None)?;
+    task_service.allocate_resource(project_id, t2_2_id, analyst.id, 0.6, None, None)?;
+    task_service.allocate_resource(project_id, t2_3_id, dev_lead.id, 0.6, None, None)?;
+    task_service.allocate_resource(project_id, t2_4_id, dev_lead.id, 0.3, None, None)?;
+    task_service.allocate_resource(project_id, t2_5_id, pm.id, 0.1, None, None)?;
+
+    task_service.allocate_resource(project_id, t3_1_1_id, devops.id, 0.5, None, None)?;
+    task_service.allocate_resource(project_id, t3_1_2_id, devops.id, 0.5, None, None)?;
+    task_service.allocate_resource(project_id, t3_2_1_id, dev.id, 0.3, None, None)?;
+    task_service.allocate_resource(project_id, t3_2_2_id, dev.id, 0.3, None, None)?;
+    task_service.allocate_resource(project_id, t3_3_1_id, dev.id, 0.3, None, None)?;
+    task_service.allocate_resource(project_id, t3_3_2_id, dev.id, 0.1, None, None)?;
+
+    task_service.allocate_resource(project_id, t4_1_id, tester.id, 0.25, None, None)?;
+    task_service.allocate_resource(project_id, t4_2_id, tester.id, 0.25, None, None)?;
+    task_service.allocate_resource(project_id, t4_3_id, tester.id, 0.25, None, None)?;
+    task_service.allocate_resource(project_id, t4_4_id, tester.id, 0.25, None, None)?;
+
+    task_service.allocate_resource(project_id, t5_1_id, analyst.id, 0.4, None, None)?;
+    task_service.allocate_resource(project_id, t5_2_id, analyst.id, 0.5, None, None)?;
+    task_service.allocate_resource(project_id, t5_3_id, dev.id, 0.3, None, None)?;
+    task_service.allocate_resource(project_id, t5_4_id, devops.id, 0.6, None, None)?;
+
+    task_service.allocate_resource(project_id, t6_1_id, analyst.id, 0.2, None, None)?;
+    task_service.allocate_resource(project_id, t6_2_id, dev.id, 0.4, None, None)?;
+    task_service.allocate_resource(project_id, t6_3_id, devops.id, 0.3, None, None)?;
 
     Ok(container)
 }
@@ -134,12 +134,12 @@ fn build_demo_container() -> anyhow::Result<SingleProjectContainer> {
     )?;
 
     // --- Назначение ресурсов (теперь без конфликтов) ---
-    task_service.allocate_resource(project_id, task1_id, analyst.id, 0.8, None)?;
-    task_service.allocate_resource(project_id, task1b_id, dev.id, 0.3, None)?; // разработчик свободен
-    task_service.allocate_resource(project_id, task2_id, dev.id, 1.0, None)?;
-    task_service.allocate_resource(project_id, task2b_id, tester.id, 0.6, None)?; // тестировщик свободен
-    task_service.allocate_resource(project_id, task3_id, tester.id, 0.9, None)?;
-    task_service.allocate_resource(project_id, task3b_id, analyst.id, 0.5, None)?; // аналитик свободен после 6 марта (его критическая закончилась)
+    task_service.allocate_resource(project_id, task1_id, analyst.id, 0.8, None, None)?;
+    task_service.allocate_resource(project_id, task1b_id, dev.id, 0.3, None, None)?; // разработчик свободен
+    task_service.allocate_resource(project_id, task2_id, dev.id, 1.0, None, None)?;
+    task_service.allocate_resource(project_id, task2b_id, tester.id, 0.6, None, None)?; // тестировщик свободен
+    task_service.allocate_resource(project_id, task3_id, tester.id, 0.9, None, None)?;
+    task_service.allocate_resource(project_id, task3b_id, analyst.id, 0.5, None, None)?; // аналитик свободен после 6 марта (его критическая закончилась)
 
     Ok(container)
 }
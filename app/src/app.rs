@@ -1,6 +1,9 @@
 pub mod app_impl;
+pub mod autosave;
+pub mod cli;
 pub mod dialogs;
 pub mod handlers;
+pub mod logging;
 pub mod state;
 pub mod theme;
 pub mod ui;
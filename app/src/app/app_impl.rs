@@ -6,14 +6,24 @@ use crate::{
 };
 
 impl eframe::App for ProjectApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         match self.current_theme {
             AppTheme::Light => ctx.set_visuals(egui::Visuals::light()),
             AppTheme::Dark => ctx.set_visuals(egui::Visuals::dark()),
         }
+        if ctx.input(|i| i.viewport().close_requested()) {
+            self.exiting = true;
+        }
+        self.poll_autosave(frame.storage_mut());
+        self.handle_container_events();
+        if self.autosaver.is_busy() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(200));
+        }
         ui::top_panel::show(ctx, self);
+        ui::status_bar::show(ctx, self);
         ui::side_panel::show(ctx, self);
         ui::central_panel::show(ctx, self);
+        ui::toast::show(ctx, self);
         if self.show_new_project_dialog {
             dialogs::new_project::show(ctx, self);
         }
@@ -42,11 +52,28 @@ impl eframe::App for ProjectApp {
         if self.show_edit_project_dialog {
             dialogs::edit_project::show(ctx, self);
         }
+        if self.show_demo_tour {
+            dialogs::demo_tour::show(ctx, self);
+        }
+        if self.show_bulk_result_dialog {
+            dialogs::bulk_result::show(ctx, self);
+        }
+        if self.show_settings_dialog {
+            dialogs::settings::show(ctx, self);
+        }
+        if self.show_merge_dialog {
+            dialogs::merge_wizard::show(ctx, self);
+        }
     }
 
-    fn save(&mut self, _storage: &mut dyn eframe::Storage) {}
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        self.save_to_storage(storage);
+    }
 
-    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {}
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        // Не теряем начатое автосохранение, но и не зависаем на выходе
+        self.wait_autosave();
+    }
 
     fn auto_save_interval(&self) -> std::time::Duration {
         std::time::Duration::from_secs(30)
@@ -0,0 +1,217 @@
+//! Фоновое автосохранение проекта в файл
+//!
+//! Рабочему потоку передается снимок контейнера: сериализация и запись файла не занимают
+//! поток интерфейса. Файл пишется атомарно (через временный файл и переименование),
+//! предыдущие версии хранятся в `.bak1`..`.bakN`, самая свежая - `.bak1`. Если содержимое
+//! файла не изменилось, он не переписывается и копии не сдвигаются.
+//! Пока сохранение не завершено, новые запросы пропускаются.
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, RecvTimeoutError, TryRecvError},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use logic::SingleProjectContainer;
+
+/// Сколько предыдущих версий файла хранить рядом с ним
+pub const BACKUP_COUNT: usize = 3;
+
+/// Завершенное сохранение: JSON снимка (для хранилища eframe) и время записи файла,
+/// None - файла у проекта нет или его содержимое не изменилось
+pub struct SavedSnapshot {
+    pub json: String,
+    pub file_saved_at: Option<DateTime<Utc>>,
+}
+
+/// Результат фонового сохранения
+pub type SaveOutcome = Result<SavedSnapshot, String>;
+
+struct InFlight {
+    handle: JoinHandle<()>,
+    result: Receiver<SaveOutcome>,
+}
+
+#[derive(Default)]
+pub struct Autosaver {
+    in_flight: Option<InFlight>,
+}
+
+impl Autosaver {
+    pub fn is_busy(&self) -> bool {
+        self.in_flight.is_some()
+    }
+
+    /// Запустить сохранение снимка в фоне. Возвращает false, если предыдущее еще не завершено
+    pub fn start(&mut self, snapshot: SingleProjectContainer, path: Option<PathBuf>) -> bool {
+        if self.is_busy() {
+            return false;
+        }
+        let (sender, result) = mpsc::channel();
+        let span = tracing::info_span!(
+            "autosave",
+            path = path.as_ref().map(|p| p.display().to_string())
+        );
+        let handle = thread::spawn(move || {
+            let _span = span.entered();
+            let _ = sender.send(save_snapshot(&snapshot, path.as_deref()));
+        });
+        self.in_flight = Some(InFlight { handle, result });
+        true
+    }
+
+    /// Забрать результат завершенного сохранения, не блокируя поток интерфейса
+    pub fn poll(&mut self) -> Option<SaveOutcome> {
+        let in_flight = self.in_flight.as_ref()?;
+        let outcome = match in_flight.result.try_recv() {
+            Ok(outcome) => outcome,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err("поток сохранения завершился аварийно".into()),
+        };
+        self.finish();
+        Some(outcome)
+    }
+
+    /// Дождаться текущего сохранения не дольше `timeout` (при выходе из приложения)
+    pub fn wait(&mut self, timeout: Duration) -> Option<SaveOutcome> {
+        let in_flight = self.in_flight.as_ref()?;
+        let outcome = match in_flight.result.recv_timeout(timeout) {
+            Ok(outcome) => outcome,
+            Err(RecvTimeoutError::Timeout) => {
+                return Some(Err("сохранение не завершилось вовремя".into()));
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                Err("поток сохранения завершился аварийно".into())
+            }
+        };
+        self.finish();
+        Some(outcome)
+    }
+
+    fn finish(&mut self) {
+        if let Some(in_flight) = self.in_flight.take() {
+            let _ = in_flight.handle.join();
+        }
+    }
+}
+
+fn save_snapshot(snapshot: &SingleProjectContainer, path: Option<&Path>) -> SaveOutcome {
+    let json = snapshot.to_versioned_json().map_err(|e| e.to_string())?;
+    let file_saved_at = match path {
+        Some(path) => write_with_backup(path, json.as_bytes(), BACKUP_COUNT)
+            .map_err(|e| format!("{}: {}", path.display(), e))?
+            .then(Utc::now),
+        None => None,
+    };
+    Ok(SavedSnapshot {
+        json,
+        file_saved_at,
+    })
+}
+
+/// Путь рядом с файлом: к имени файла добавляется `suffix`
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Путь резервной копии с номером `index` (1 - самая свежая)
+pub fn backup_path(path: &Path, index: usize) -> PathBuf {
+    sibling(path, &format!(".bak{}", index))
+}
+
+/// Атомарная запись: временный файл рядом с целевым, затем переименование.
+/// Предыдущая версия файла становится `.bak1`, старые копии сдвигаются, больше
+/// `backups` копий не хранится. Возвращает false, если файл уже содержит `bytes`:
+/// тогда ничего не пишется, чтобы копии не вытеснялись одинаковыми версиями
+fn write_with_backup(path: &Path, bytes: &[u8], backups: usize) -> std::io::Result<bool> {
+    if std::fs::read(path).is_ok_and(|current| current == bytes) {
+        return Ok(false);
+    }
+    let tmp = sibling(path, ".tmp");
+    std::fs::write(&tmp, bytes)?;
+    if backups > 0 && path.exists() {
+        for index in (1..backups).rev() {
+            let older = backup_path(path, index);
+            if older.exists() {
+                std::fs::rename(&older, backup_path(path, index + 1))?;
+            }
+        }
+        std::fs::copy(path, backup_path(path, 1))?;
+    }
+    std::fs::rename(&tmp, path)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rsproject_autosave_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_write_with_backup_rotates_copies() {
+        let dir = temp_dir();
+        let path = dir.join("project.json");
+        let read = |path: &Path| std::fs::read_to_string(path).unwrap();
+
+        assert!(write_with_backup(&path, b"v1", 3).unwrap());
+        assert_eq!(read(&path), "v1");
+        assert!(!backup_path(&path, 1).exists());
+
+        for version in 2..=5 {
+            write_with_backup(&path, format!("v{}", version).as_bytes(), 3).unwrap();
+        }
+        assert_eq!(read(&path), "v5");
+        assert_eq!(read(&backup_path(&path, 1)), "v4");
+        assert_eq!(read(&backup_path(&path, 2)), "v3");
+        assert_eq!(read(&backup_path(&path, 3)), "v2");
+        assert!(!backup_path(&path, 4).exists());
+        assert!(!sibling(&path, ".tmp").exists());
+
+        // Повторная запись того же содержимого не вытесняет историю
+        for _ in 0..3 {
+            assert!(!write_with_backup(&path, b"v5", 3).unwrap());
+        }
+        assert_eq!(read(&backup_path(&path, 1)), "v4");
+        assert_eq!(read(&backup_path(&path, 3)), "v2");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_autosave_writes_snapshot_and_skips_overlapping_requests() {
+        let dir = temp_dir();
+        let path = dir.join("project.json");
+        let mut autosaver = Autosaver::default();
+
+        assert!(autosaver.start(SingleProjectContainer::new(), Some(path.clone())));
+        // Пока идет сохранение, следующее пропускается
+        assert!(!autosaver.start(SingleProjectContainer::new(), Some(path.clone())));
+        let saved = autosaver.wait(Duration::from_secs(10)).unwrap().unwrap();
+        assert!(saved.file_saved_at.is_some());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), saved.json);
+        assert!(!autosaver.is_busy());
+
+        // Тот же снимок файл не переписывает
+        assert!(autosaver.start(SingleProjectContainer::new(), Some(path.clone())));
+        let saved = autosaver.wait(Duration::from_secs(10)).unwrap().unwrap();
+        assert_eq!(saved.file_saved_at, None);
+        assert!(!backup_path(&path, 1).exists());
+
+        // Без файла снимок только сериализуется
+        assert!(autosaver.start(SingleProjectContainer::new(), None));
+        let saved = autosaver.wait(Duration::from_secs(10)).unwrap().unwrap();
+        assert_eq!(saved.file_saved_at, None);
+        assert!(SingleProjectContainer::from_versioned_json(&saved.json).is_ok());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
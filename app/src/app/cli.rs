@@ -0,0 +1,257 @@
+//! Запуск без окна: команды для cron и скриптов
+//!
+//! `rsproject export run --profile weekly --file project.json [--redact]` - выполнить профиль
+//! выгрузки из файла проекта (`--redact` скрывает ставки, стоимость и имена), `rsproject demo > demo.rsp` - вывести демо-проект,
+//! `rsproject merge --file project.json --incoming other.json --resolutions res.json` -
+//! слить проект из другого файла и вывести результат.
+//! Без аргументов запускается приложение. `-v`/`-vv` поднимают уровень журнала до debug/trace,
+//! у команд без окна журнал дублируется в stderr.
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use logic::{
+    MaintenanceService, MergeResolutions, MergeService, ProjectContainer, SingleProjectContainer,
+    export::ExportRedaction, testing,
+};
+
+/// Команда, разобранная из аргументов командной строки
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// Обычный запуск приложения
+    Gui,
+    /// Выполнить профиль выгрузки, `redact` - скрыть все чувствительные данные
+    ExportRun {
+        profile: String,
+        file: PathBuf,
+        redact: bool,
+    },
+    /// Демо-проект в stdout
+    Demo,
+    /// Слияние проекта из `incoming` в `file`, результат в stdout
+    Merge {
+        file: PathBuf,
+        incoming: PathBuf,
+        resolutions: Option<PathBuf>,
+    },
+}
+
+pub const USAGE: &str = "Использование:
+  rsproject
+  rsproject export run --profile <имя> --file <проект.json> [--redact]
+  rsproject demo > demo.rsp
+  rsproject merge --file <проект.json> --incoming <файл.json> [--resolutions <res.json>] > merged.json
+Флаги:
+  -v, -vv  подробный журнал (debug, trace)";
+
+/// Подробность журнала из флагов `-v` (`-vv`, `-v -v`, `--verbose`)
+pub fn verbosity(args: &[String]) -> u8 {
+    args.iter()
+        .filter_map(|arg| verbosity_flag(arg))
+        .fold(0u8, |total, level| total.saturating_add(level))
+}
+
+fn verbosity_flag(arg: &str) -> Option<u8> {
+    match arg {
+        "--verbose" => Some(1),
+        _ => {
+            let count = arg.strip_prefix('-')?;
+            (!count.is_empty() && count.chars().all(|c| c == 'v'))
+                .then_some(count.len().min(u8::MAX as usize) as u8)
+        }
+    }
+}
+
+/// Разобрать аргументы (без имени программы)
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    let mut positional = vec![];
+    let mut profile = None;
+    let mut file = None;
+    let mut incoming = None;
+    let mut resolutions = None;
+    let mut redact = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--profile" => profile = iter.next().cloned(),
+            "--file" => file = iter.next().map(PathBuf::from),
+            "--incoming" => incoming = iter.next().map(PathBuf::from),
+            "--resolutions" => resolutions = iter.next().map(PathBuf::from),
+            "--redact" => redact = true,
+            _ if verbosity_flag(arg).is_some() => {}
+            _ => positional.push(arg.as_str()),
+        }
+    }
+    match positional.as_slice() {
+        [] => Ok(Command::Gui),
+        ["export", "run"] => Ok(Command::ExportRun {
+            profile: profile.ok_or("не указан --profile")?,
+            file: file.ok_or("не указан --file")?,
+            redact,
+        }),
+        ["demo"] => Ok(Command::Demo),
+        ["merge"] => Ok(Command::Merge {
+            file: file.ok_or("не указан --file")?,
+            incoming: incoming.ok_or("не указан --incoming")?,
+            resolutions,
+        }),
+        _ => Err(format!("неизвестная команда: {}", positional.join(" "))),
+    }
+}
+
+/// Выполнить команду без окна. Возвращает код выхода процесса
+pub fn run(command: Command) -> i32 {
+    match command {
+        Command::Gui => 0,
+        Command::ExportRun {
+            profile,
+            file,
+            redact,
+        } => export_run(&profile, &file, redact),
+        Command::Demo => demo(),
+        Command::Merge {
+            file,
+            incoming,
+            resolutions,
+        } => match merge(&file, &incoming, resolutions.as_deref()) {
+            Ok(json) => {
+                println!("{}", json);
+                0
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                1
+            }
+        },
+    }
+}
+
+/// Слить первый проект `incoming` в первый проект `file`; итог слияния - в stderr
+fn merge(file: &Path, incoming: &Path, resolutions: Option<&Path>) -> anyhow::Result<String> {
+    let _span = tracing::info_span!("cli_merge", file = %file.display()).entered();
+    let mut container = SingleProjectContainer::load_from_path(file)?;
+    let other = SingleProjectContainer::load_from_path(incoming)?;
+    let resolutions: MergeResolutions = match resolutions {
+        Some(path) => serde_json::from_str(&std::fs::read_to_string(path)?)?,
+        None => MergeResolutions::default(),
+    };
+    let target_id = container
+        .list_project_ids()
+        .first()
+        .copied()
+        .ok_or_else(|| anyhow::anyhow!("{}: no project", file.display()))?;
+    let source_id = other
+        .list_project_ids()
+        .first()
+        .copied()
+        .ok_or_else(|| anyhow::anyhow!("{}: no project", incoming.display()))?;
+    let report =
+        MergeService::new(&mut container).merge_from(target_id, &other, source_id, &resolutions)?;
+    eprintln!(
+        "added: {}, renamed: {}, skipped: {}, replaced: {}, skipped allocations: {}, forced allocations: {}",
+        report.added_tasks.len(),
+        report.renamed_tasks.len(),
+        report.skipped_tasks.len(),
+        report.replaced_tasks.len(),
+        report.skipped_allocations.len(),
+        report.forced_allocations.len()
+    );
+    container.to_versioned_json()
+}
+
+fn demo() -> i32 {
+    let mut container = SingleProjectContainer::new();
+    let json = testing::create_demo_project(&mut container, testing::demo_start())
+        .and_then(|_| container.to_versioned_json());
+    match json {
+        Ok(json) => {
+            println!("{}", json);
+            0
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+/// `redact` действует только на этот запуск: профиль в файле проекта не меняется
+fn export_run(profile: &str, file: &Path, redact: bool) -> i32 {
+    let _span =
+        tracing::info_span!("cli_export", profile, redact, file = %file.display()).entered();
+    let mut container = match SingleProjectContainer::load_from_path(file) {
+        Ok(container) => container,
+        Err(e) => {
+            eprintln!("{}: {}", file.display(), e);
+            return 1;
+        }
+    };
+    if redact {
+        container
+            .export_profiles_mut()
+            .iter_mut()
+            .filter(|p| p.name == profile)
+            .for_each(|p| p.redaction = ExportRedaction::all());
+    }
+    let results = match MaintenanceService::new(&container).run_export_profile(profile, Utc::now())
+    {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    let mut failed = false;
+    for result in results {
+        match result.result {
+            Ok(path) => println!("{}", path.display()),
+            Err(e) => {
+                failed = true;
+                eprintln!("{:?}: {}", result.kind, e);
+            }
+        }
+    }
+    i32::from(failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_verbosity_flags() {
+        assert_eq!(verbosity(&args("")), 0);
+        assert_eq!(verbosity(&args("-v demo")), 1);
+        assert_eq!(verbosity(&args("-vv")), 2);
+        assert_eq!(verbosity(&args("-v --verbose")), 2);
+        assert_eq!(verbosity(&args("- -x")), 0);
+
+        // Флаги не мешают разбору команды
+        assert_eq!(parse(&args("-vv")), Ok(Command::Gui));
+        assert_eq!(parse(&args("demo -v")), Ok(Command::Demo));
+        assert!(parse(&args("-x")).is_err());
+    }
+
+    #[test]
+    fn test_parse_export_run_redact() {
+        assert_eq!(
+            parse(&args("export run --profile weekly --file p.json")),
+            Ok(Command::ExportRun {
+                profile: "weekly".into(),
+                file: PathBuf::from("p.json"),
+                redact: false,
+            })
+        );
+        assert_eq!(
+            parse(&args("export run --redact --profile weekly --file p.json")),
+            Ok(Command::ExportRun {
+                profile: "weekly".into(),
+                file: PathBuf::from("p.json"),
+                redact: true,
+            })
+        );
+    }
+}
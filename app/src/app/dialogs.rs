@@ -1,8 +1,12 @@
 pub mod assign_resource;
+pub mod bulk_result;
 pub mod close_project;
+pub mod demo_tour;
 pub mod edit_project;
+pub mod merge_wizard;
 pub mod new_project;
 pub mod new_resource;
 pub mod new_task;
+pub mod settings;
 pub mod task_details;
 pub mod unavailable_period;
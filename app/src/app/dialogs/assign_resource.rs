@@ -1,13 +1,47 @@
-use eframe::egui::{self, Widget};
-use logic::ResourceService;
+use eframe::egui;
+use logic::{BasicGettersForStructures, ResourceService, TaskService};
+use uuid::Uuid;
 
-use crate::ProjectApp;
+use crate::{ProjectApp, app::ui::date_input::date_input};
 
 pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
+    let calendar = app.input_calendar();
     let mut open = true;
     egui::Window::new("Назначение ресурса на задачу")
         .open(&mut open)
         .show(ctx, |ui| {
+            // Выбор задачи: при открытии со вкладки ресурсов задача еще не выбрана
+            let tasks: Vec<(Uuid, String)> = match app.selected_project_id {
+                Some(project_id) => {
+                    let task_service = TaskService::new(&mut app.container);
+                    let mut tasks: Vec<(Uuid, String)> = task_service
+                        .get_all_tasks(project_id)
+                        .into_iter()
+                        .filter(|t| !t.is_summary)
+                        .map(|t| (*t.get_id(), t.name.clone()))
+                        .collect();
+                    tasks.sort_by(|a, b| a.1.cmp(&b.1));
+                    tasks
+                }
+                None => Vec::new(),
+            };
+            ui.horizontal(|ui| {
+                ui.label("Задача:");
+                egui::ComboBox::from_id_salt("task_select")
+                    .selected_text(
+                        tasks
+                            .iter()
+                            .find(|(id, _)| Some(*id) == app.selected_task_id)
+                            .map(|(_, name)| name.clone())
+                            .unwrap_or_else(|| "Выберите задачу".to_string()),
+                    )
+                    .show_ui(ui, |ui| {
+                        for (id, name) in &tasks {
+                            ui.selectable_value(&mut app.selected_task_id, Some(*id), name);
+                        }
+                    });
+            });
+
             // Выбор ресурса (выпадающий список)
             let resource_service = ResourceService::new(&mut app.container);
             let resources = resource_service.list_resources();
@@ -30,8 +64,8 @@ pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
             });
 
             ui.horizontal(|ui| {
-                ui.label("Занятость (0.0-1.0):");
-                ui.text_edit_singleline(&mut app.assign_engagement);
+                ui.label("Занятость:");
+                ui.add(egui::Slider::new(&mut app.assign_engagement, 0.0..=100.0).suffix("%"));
             });
 
             ui.separator();
@@ -44,17 +78,21 @@ pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
             if !app.assign_use_full_window {
                 ui.horizontal(|ui| {
                     ui.label("Дата начала работ(измененая):");
-                    egui_extras::DatePickerButton::new(&mut app.assign_custom_start)
-                        .id_salt("assign_start_picker")
-                        .start_end_years(2020..=2035)
-                        .ui(ui);
+                    date_input(
+                        ui,
+                        "assign_start_picker",
+                        &mut app.assign_custom_start,
+                        &calendar,
+                    );
                 });
                 ui.horizontal(|ui| {
                     ui.label("Дата окончания работ(измененная):");
-                    egui_extras::DatePickerButton::new(&mut app.assign_custom_end)
-                        .id_salt("assign_end_picker")
-                        .start_end_years(2020..=2035)
-                        .ui(ui);
+                    date_input(
+                        ui,
+                        "assign_end_picker",
+                        &mut app.assign_custom_end,
+                        &calendar,
+                    );
                 });
             }
 
@@ -65,7 +103,7 @@ pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
                         app.selected_task_id = None;
                         app.selected_resource_id = None;
                         app.error_message = None;
-                        app.assign_engagement = String::from("0.5");
+                        app.assign_engagement = 50.0;
                         app.assign_use_full_window = true;
                     }
                     Err(e) => app.error_message = Some(e.to_string()),
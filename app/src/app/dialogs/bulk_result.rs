@@ -0,0 +1,33 @@
+use eframe::egui;
+
+use crate::ProjectApp;
+
+pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
+    let mut open = true;
+    egui::Window::new("Результат массовой операции")
+        .open(&mut open)
+        .show(ctx, |ui| {
+            ui.label(format!(
+                "Не удалось применить к задачам: {}",
+                app.bulk_failures.len()
+            ));
+            ui.separator();
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for failure in &app.bulk_failures {
+                        ui.colored_label(egui::Color32::RED, failure);
+                    }
+                });
+            ui.separator();
+            if ui.button("Закрыть").clicked() {
+                app.show_bulk_result_dialog = false;
+            }
+        });
+    if !open {
+        app.show_bulk_result_dialog = false;
+    }
+    if !app.show_bulk_result_dialog {
+        app.bulk_failures.clear();
+    }
+}
@@ -0,0 +1,57 @@
+use eframe::egui;
+
+use crate::{ProjectApp, app::views::View};
+
+// Вкладка и подсказка, что на ней смотреть в демо-проекте
+const STEPS: [(View, &str, &str); 4] = [
+    (
+        View::Project,
+        "📁 Общая информация",
+        "сроки проекта, стоимость и трудозатраты",
+    ),
+    (
+        View::Tasks,
+        "✅ Задачи",
+        "3 этапа и задачи с зависимостями, массовые операции по Ctrl/Shift+клику",
+    ),
+    (
+        View::Resources,
+        "👤 Ресурсы",
+        "5 сотрудников со ставками, отпусками и загрузкой",
+    ),
+    (
+        View::Gantt,
+        "📊 Диаграмма Ганта",
+        "план на временной шкале и критический путь",
+    ),
+];
+
+pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
+    let mut open = true;
+    egui::Window::new("Добро пожаловать в демо-проект")
+        .open(&mut open)
+        .anchor(egui::Align2::RIGHT_TOP, [-16.0, 48.0])
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label("Проект создан из примера и не сохранен. Вкладки слева:");
+            ui.separator();
+            for (view, title, hint) in STEPS {
+                ui.horizontal(|ui| {
+                    if ui
+                        .selectable_label(app.selected_tab == view, title)
+                        .clicked()
+                    {
+                        app.selected_tab = view;
+                    }
+                    ui.label(hint);
+                });
+            }
+            ui.separator();
+            if ui.button("Понятно").clicked() {
+                app.show_demo_tour = false;
+            }
+        });
+    if !open {
+        app.show_demo_tour = false;
+    }
+}
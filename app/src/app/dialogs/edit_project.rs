@@ -1,8 +1,9 @@
-use eframe::egui::{self, Widget};
+use eframe::egui;
 
-use crate::ProjectApp;
+use crate::{ProjectApp, app::ui::date_input::date_input};
 
 pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
+    let calendar = app.input_calendar();
     let mut open = true;
     egui::Window::new("Редактировать проект")
         .open(&mut open)
@@ -17,15 +18,16 @@ pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
             });
             ui.horizontal(|ui| {
                 ui.label("Дата начала:");
-                egui_extras::DatePickerButton::new(&mut app.new_project_start)
-                    .id_salt("edit_project_start")
-                    .ui(ui);
+                date_input(
+                    ui,
+                    "edit_project_start",
+                    &mut app.new_project_start,
+                    &calendar,
+                );
             });
             ui.horizontal(|ui| {
                 ui.label("Дата окончания:");
-                egui_extras::DatePickerButton::new(&mut app.new_project_end)
-                    .id_salt("edit_project_end")
-                    .ui(ui);
+                date_input(ui, "edit_project_end", &mut app.new_project_end, &calendar);
             });
             if ui.button("Сохранить").clicked() {
                 match app.update_project() {
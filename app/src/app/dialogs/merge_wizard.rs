@@ -0,0 +1,193 @@
+use std::path::PathBuf;
+
+use eframe::egui;
+use logic::{MergePreview, MergeReport, MergeResolution, MergeResolutions, SingleProjectContainer};
+
+use crate::ProjectApp;
+
+/// Состояние мастера слияния: файл, решения по конфликтам, предпросмотр и отмена
+#[derive(Default)]
+pub struct MergeWizard {
+    pub incoming: Option<(PathBuf, SingleProjectContainer)>,
+    pub resolutions: MergeResolutions,
+    pub preview: Option<MergePreview>,
+    pub report: Option<MergeReport>,
+    /// Контейнер до последнего слияния (JSON), одна запись отмены на все слияние
+    pub undo: Option<String>,
+}
+
+const ALL: &[MergeResolution] = &MergeResolution::ALL;
+const KEEP_OR_TAKE: &[MergeResolution] = &[MergeResolution::KeepMine, MergeResolution::TakeTheirs];
+const NO_RENAME: &[MergeResolution] = &[
+    MergeResolution::KeepMine,
+    MergeResolution::TakeTheirs,
+    MergeResolution::Skip,
+];
+
+fn label(resolution: MergeResolution) -> &'static str {
+    match resolution {
+        MergeResolution::KeepMine => "Оставить мое",
+        MergeResolution::TakeTheirs => "Взять из файла",
+        MergeResolution::Rename => "Переименовать",
+        MergeResolution::Skip => "Пропустить",
+    }
+}
+
+/// Выбор решения; true - решение изменилось
+fn resolution_combo(
+    ui: &mut egui::Ui,
+    id_salt: impl std::hash::Hash,
+    current: &mut MergeResolution,
+    options: &[MergeResolution],
+) -> bool {
+    let before = *current;
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(label(*current))
+        .show_ui(ui, |ui| {
+            for option in options {
+                ui.selectable_value(current, *option, label(*option));
+            }
+        });
+    before != *current
+}
+
+/// Действие, выбранное кнопкой мастера
+enum Action {
+    Apply,
+    SaveResolutions,
+    Undo,
+    Close,
+}
+
+pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
+    let mut open = true;
+    let mut changed = false;
+    let mut action = None;
+    egui::Window::new("Слияние проектов")
+        .open(&mut open)
+        .show(ctx, |ui| {
+            let wizard = &mut app.merge_wizard;
+            if let Some((path, _)) = &wizard.incoming {
+                ui.label(format!("Файл: {}", path.display()));
+            }
+            if let Some(report) = &wizard.report {
+                ui.label(format!(
+                    "Перенесено задач: {}, переименовано: {}, пропущено: {}, заменено: {}",
+                    report.added_tasks.len(),
+                    report.renamed_tasks.len(),
+                    report.skipped_tasks.len(),
+                    report.replaced_tasks.len()
+                ));
+                ui.label(format!(
+                    "Новых ресурсов: {}, назначений не перенесено: {}, с перегрузкой: {}",
+                    report.added_resources.len(),
+                    report.skipped_allocations.len(),
+                    report.forced_allocations.len()
+                ));
+            } else if let Some(preview) = &wizard.preview {
+                changed = conflicts(ui, preview, &mut wizard.resolutions);
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if wizard.report.is_none() {
+                    if ui.button("Применить").clicked() {
+                        action = Some(Action::Apply);
+                    }
+                    if ui.button("💾 Сохранить решения").clicked() {
+                        action = Some(Action::SaveResolutions);
+                    }
+                }
+                if wizard.undo.is_some() && ui.button("↶ Отменить слияние").clicked()
+                {
+                    action = Some(Action::Undo);
+                }
+                if ui.button("Закрыть").clicked() {
+                    action = Some(Action::Close);
+                }
+            });
+        });
+
+    if changed {
+        app.refresh_merge_preview();
+    }
+    match action {
+        Some(Action::Apply) => app.apply_merge(),
+        Some(Action::SaveResolutions) => app.save_merge_resolutions(),
+        Some(Action::Undo) => app.undo_merge(),
+        Some(Action::Close) => app.show_merge_dialog = false,
+        None => {}
+    }
+    if !open {
+        app.show_merge_dialog = false;
+    }
+}
+
+/// Конфликты по видам с выбором решения; true - какое-то решение изменилось
+fn conflicts(
+    ui: &mut egui::Ui,
+    preview: &MergePreview,
+    resolutions: &mut MergeResolutions,
+) -> bool {
+    let mut changed = false;
+    if preview.is_empty() {
+        ui.label("Конфликтов нет");
+    }
+    if !preview.task_names.is_empty() {
+        ui.heading("Задачи с совпадающими именами");
+        for name in &preview.task_names {
+            ui.horizontal(|ui| {
+                let mut resolution = resolutions.task(name);
+                if resolution_combo(ui, ("merge_task", name), &mut resolution, ALL) {
+                    resolutions.tasks.insert(name.clone(), resolution);
+                    changed = true;
+                }
+                ui.label(name);
+            });
+        }
+    }
+    if !preview.resources.is_empty() {
+        ui.heading("Ресурсы с совпадающими именами");
+        for name in &preview.resources {
+            ui.horizontal(|ui| {
+                let mut resolution = resolutions.resource(name);
+                if resolution_combo(ui, ("merge_resource", name), &mut resolution, ALL) {
+                    resolutions.resources.insert(name.clone(), resolution);
+                    changed = true;
+                }
+                ui.label(name);
+            });
+        }
+    }
+    if !preview.calendar.is_empty() {
+        ui.heading("Различия календарей");
+        ui.horizontal(|ui| {
+            let mut resolution = resolutions.calendar();
+            if resolution_combo(ui, "merge_calendar", &mut resolution, KEEP_OR_TAKE) {
+                resolutions.calendar = Some(resolution);
+                changed = true;
+            }
+            let diff = &preview.calendar;
+            ui.label(format!(
+                "праздников только у меня: {}, только в файле: {}, различий по дням недели: {}",
+                diff.holidays_only_left.len(),
+                diff.holidays_only_right.len(),
+                diff.working_days.len() + diff.hours.len()
+            ));
+        });
+    }
+    if !preview.overallocations.is_empty() {
+        ui.heading("Перегрузка ресурсов после слияния");
+        for name in &preview.overallocations {
+            ui.horizontal(|ui| {
+                let mut resolution = resolutions.overallocation(name);
+                if resolution_combo(ui, ("merge_overload", name), &mut resolution, NO_RENAME) {
+                    resolutions.overallocations.insert(name.clone(), resolution);
+                    changed = true;
+                }
+                ui.label(name);
+            });
+        }
+    }
+    changed
+}
@@ -1,8 +1,9 @@
-use eframe::egui::{self, Widget};
+use eframe::egui;
 
-use crate::ProjectApp;
+use crate::{ProjectApp, app::ui::date_input::date_input};
 
 pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
+    let calendar = app.input_calendar();
     let mut open = true;
 
     egui::Window::new("Создать новый проект")
@@ -19,17 +20,16 @@ pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
 
             ui.horizontal(|ui| {
                 ui.label("Дата начала проекта:");
-                egui_extras::DatePickerButton::new(&mut app.new_project_start)
-                    .id_salt("start_project_date")
-                    .start_end_years(2020..=2035)
-                    .ui(ui);
+                date_input(
+                    ui,
+                    "start_project_date",
+                    &mut app.new_project_start,
+                    &calendar,
+                );
             });
             ui.horizontal(|ui| {
                 ui.label("Дата окончания проекта:");
-                egui_extras::DatePickerButton::new(&mut app.new_project_end)
-                    .id_salt("end_project_date")
-                    .start_end_years(2020..=2035)
-                    .ui(ui);
+                date_input(ui, "end_project_date", &mut app.new_project_end, &calendar);
             });
             if ui.button("Создать проект").clicked() {
                 match app.create_project() {
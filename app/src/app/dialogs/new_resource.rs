@@ -39,6 +39,18 @@ pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
                     );
                 });
         });
+        ui.horizontal(|ui| {
+            ui.label("Навыки (через запятую)");
+            ui.text_edit_singleline(&mut app.new_resource_skills);
+        });
+        ui.horizontal(|ui| {
+            ui.label("Основной навык");
+            ui.text_edit_singleline(&mut app.new_resource_primary_skill);
+        });
+        ui.horizontal(|ui| {
+            ui.label("Валюта (ISO-4217)");
+            ui.add(egui::TextEdit::singleline(&mut app.new_resource_currency).desired_width(50.0));
+        });
         if ui.button("Сохранить").clicked() {
             match app.create_resource() {
                 Ok(()) => {
@@ -1,9 +1,10 @@
-use eframe::egui::{self, RichText, Widget};
+use eframe::egui::{self, RichText};
 use logic::{BasicGettersForStructures, DependencyType, ProjectContainer};
 
-use crate::ProjectApp;
+use crate::{ProjectApp, app::ui::date_input::date_input};
 
 pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
+    let calendar = app.input_calendar();
     let mut open = true;
     egui::Window::new(if app.edit_resource_id.is_some() {
         "Редактировать задачу"
@@ -18,17 +19,11 @@ pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
         ui.add_enabled_ui(!app.new_task_is_summary, |ui| {
             ui.horizontal(|ui| {
                 ui.label("Начало задачи:");
-                egui_extras::DatePickerButton::new(&mut app.new_task_start)
-                    .id_salt("task_start_picker")
-                    .start_end_years(2020..=2035)
-                    .ui(ui);
+                date_input(ui, "task_start_picker", &mut app.new_task_start, &calendar);
             });
             ui.horizontal(|ui| {
                 ui.label("Окончание задачи:");
-                egui_extras::DatePickerButton::new(&mut app.new_task_end)
-                    .id_salt("task_end_picker")
-                    .start_end_years(2020..=2035)
-                    .ui(ui);
+                date_input(ui, "task_end_picker", &mut app.new_task_end, &calendar);
             })
         });
         ui.separator();
@@ -0,0 +1,174 @@
+use std::path::PathBuf;
+
+use eframe::egui;
+use logic::{
+    ProjectContainer,
+    export::{ExportKind, ExportLocale, ExportProfile},
+};
+use rfd::FileDialog;
+
+use crate::{
+    ProjectApp,
+    app::{logging, ui::toast::Toast},
+};
+
+/// Новый профиль выгрузки, который заполняется в настройках
+pub struct ExportProfileDraft {
+    pub name: String,
+    pub destination_dir: Option<PathBuf>,
+    pub artifacts: Vec<ExportKind>,
+    pub locale: ExportLocale,
+}
+
+impl Default for ExportProfileDraft {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            destination_dir: None,
+            artifacts: vec![ExportKind::TasksCsv, ExportKind::HtmlReport],
+            locale: ExportLocale::default(),
+        }
+    }
+}
+
+const KINDS: [(ExportKind, &str); 4] = [
+    (ExportKind::TasksCsv, "CSV"),
+    (ExportKind::TasksTsv, "TSV"),
+    (ExportKind::HtmlReport, "HTML"),
+    (ExportKind::GanttPng, "PNG"),
+];
+
+/// Вид выгрузки доступен в этой сборке: PNG требует feature `png-export`
+fn kind_enabled(kind: ExportKind) -> bool {
+    kind != ExportKind::GanttPng || cfg!(feature = "png-export")
+}
+
+pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
+    let mut open = true;
+    egui::Window::new("Настройки")
+        .open(&mut open)
+        .show(ctx, |ui| {
+            ui.heading("Профили выгрузки");
+            ui.label("Отмеченные профили выполняются при каждом сохранении проекта");
+            let mut remove = None;
+            for (index, profile) in app.container.export_profiles_mut().iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut profile.run_on_save, &profile.name);
+                    ui.label(format!(
+                        "{:?} → {}",
+                        profile.artifacts,
+                        profile.destination_dir.display()
+                    ));
+                    locale_combo(ui, ("profile_locale", index), &mut profile.locale);
+                    if ui.small_button("🗑").clicked() {
+                        remove = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = remove {
+                app.container.export_profiles_mut().remove(index);
+            }
+
+            ui.separator();
+            let draft = &mut app.new_export_profile;
+            ui.horizontal(|ui| {
+                ui.label("Имя:");
+                ui.text_edit_singleline(&mut draft.name);
+            });
+            ui.horizontal(|ui| {
+                for (kind, label) in KINDS.into_iter().filter(|(kind, _)| kind_enabled(*kind)) {
+                    let mut selected = draft.artifacts.contains(&kind);
+                    if ui.checkbox(&mut selected, label).changed() {
+                        if selected {
+                            draft.artifacts.push(kind);
+                        } else {
+                            draft.artifacts.retain(|k| *k != kind);
+                        }
+                    }
+                }
+                locale_combo(ui, "draft_locale", &mut draft.locale);
+            });
+            ui.horizontal(|ui| {
+                if ui.button("📁 Каталог").clicked() {
+                    draft.destination_dir = FileDialog::new().pick_folder();
+                }
+                if let Some(dir) = &draft.destination_dir {
+                    ui.label(dir.display().to_string());
+                }
+            });
+            let ready = !draft.name.trim().is_empty()
+                && !draft.artifacts.is_empty()
+                && draft.destination_dir.is_some();
+            if ui
+                .add_enabled(ready, egui::Button::new("Добавить профиль"))
+                .clicked()
+            {
+                let draft = std::mem::take(&mut app.new_export_profile);
+                let mut profile = ExportProfile::new(
+                    draft.name.trim(),
+                    draft.artifacts,
+                    draft.destination_dir.unwrap_or_default(),
+                );
+                profile.locale = draft.locale;
+                app.container.export_profiles_mut().push(profile);
+            }
+
+            ui.separator();
+            log_settings(ui, app);
+        });
+    if !open {
+        app.show_settings_dialog = false;
+    }
+}
+
+/// Файл и уровень журнала: сохраняются сразу, применяются при следующем запуске
+fn log_settings(ui: &mut egui::Ui, app: &mut ProjectApp) {
+    ui.heading("Журнал");
+    ui.label("Изменения применяются при следующем запуске");
+    let mut settings = app.log_settings.clone();
+    ui.horizontal(|ui| {
+        ui.label("Файл:");
+        let file = settings.file.clone().or_else(logging::default_log_file);
+        ui.label(file.map_or("—".to_string(), |f| f.display().to_string()));
+    });
+    ui.horizontal(|ui| {
+        if ui.button("📁 Выбрать файл").clicked()
+            && let Some(file) = FileDialog::new().set_file_name("rsproject.log").save_file()
+        {
+            settings.file = Some(file);
+        }
+        if ui
+            .add_enabled(settings.file.is_some(), egui::Button::new("По умолчанию"))
+            .clicked()
+        {
+            settings.file = None;
+        }
+        let selected = settings.level.clone().unwrap_or_else(|| "info".into());
+        egui::ComboBox::from_id_salt("log_level")
+            .selected_text(&selected)
+            .show_ui(ui, |ui| {
+                for level in logging::LEVELS {
+                    let name = level.as_str().to_lowercase();
+                    if ui.selectable_label(selected == name, &name).clicked() {
+                        settings.level = Some(name);
+                    }
+                }
+            });
+    });
+    if settings != app.log_settings {
+        app.toast = Some(match settings.save() {
+            Ok(()) => Toast::info("Настройки журнала сохранены"),
+            Err(e) => Toast::error(format!("Не удалось сохранить настройки журнала: {}", e)),
+        });
+        app.log_settings = settings;
+    }
+}
+
+fn locale_combo(ui: &mut egui::Ui, id: impl std::hash::Hash, locale: &mut ExportLocale) {
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(format!("{:?}", locale))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(locale, ExportLocale::En, "En");
+            ui.selectable_value(locale, ExportLocale::Ru, "Ru");
+        });
+}
@@ -54,7 +54,12 @@ pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
                 }
                 ui.separator();
                 ui.strong("Назначенные ресурсы:");
-                if let Some(calendar) = app.container.calendar(&project_id) {
+                if let Some(calendar) = app.container.calendar(&project_id)
+                    && let Some(rate_config) = app
+                        .container
+                        .get_project(&project_id)
+                        .map(|p| p.rate_config)
+                {
                     let pool = app.container.resource_pool();
                     for alloc_id in alloc_ids {
                         if let Some(allocation) = pool.get_allocation(&alloc_id)
@@ -64,7 +69,7 @@ pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
                             let hours = tw.duration_hours(calendar) as f64
                                 * allocation.get_engagement_rate();
                             let cost = pool
-                                .calculate_allocation_cost(&alloc_id, calendar)
+                                .calculate_allocation_cost(&alloc_id, calendar, &rate_config)
                                 .unwrap_or(0.0);
                             ui.separator();
                             ui.label(format!("Ресурс: {}", resource.name));
@@ -1,9 +1,10 @@
-use eframe::egui::{self, Widget};
+use eframe::egui;
 use logic::ExceptionType;
 
-use crate::ProjectApp;
+use crate::{ProjectApp, app::ui::date_input::date_input};
 
 pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
+    let calendar = app.input_calendar();
     let mut open = true;
     egui::Window::new("Добавить период недоступности")
         .open(&mut open)
@@ -28,21 +29,30 @@ pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
                             ExceptionType::PersonalDay,
                             "Отгул",
                         );
+                        ui.selectable_value(
+                            &mut app.unavailable_type,
+                            ExceptionType::Training,
+                            "Обучение",
+                        );
                     });
             });
             ui.horizontal(|ui| {
                 ui.label("Дата начала периода:");
-                egui_extras::DatePickerButton::new(&mut app.unavailable_start)
-                    .id_salt("unavail_start_picker")
-                    .start_end_years(2020..=2035)
-                    .ui(ui);
+                date_input(
+                    ui,
+                    "unavail_start_picker",
+                    &mut app.unavailable_start,
+                    &calendar,
+                );
             });
             ui.horizontal(|ui| {
                 ui.label("Дата окончания периода:");
-                egui_extras::DatePickerButton::new(&mut app.unavailable_end)
-                    .id_salt("unavail_end_picker")
-                    .start_end_years(2020..=2035)
-                    .ui(ui);
+                date_input(
+                    ui,
+                    "unavail_end_picker",
+                    &mut app.unavailable_end,
+                    &calendar,
+                );
             });
             if ui.button("Добавить").clicked() {
                 match app.add_unavailable_period() {
@@ -1,26 +1,60 @@
+use chrono::{DateTime, NaiveDate, Utc};
 use logic::{
-    BasicGettersForStructures, Project, ProjectContainer, SingleProjectContainer, TaskService,
+    BasicGettersForStructures, MaintenanceService, MergeResolutions, MergeService, Project,
+    ProjectCalendar, ProjectContainer, ProjectService, ProjectUpdate, SingleProjectContainer,
+    TaskService, cust_exceptions::ProjectUpdateErrors,
 };
 use rfd::FileDialog;
 
-use crate::ProjectApp;
+use crate::{ProjectApp, app::views::View};
 
 impl ProjectApp {
+    /// Календарь для полей ввода дат: календарь выбранного проекта или стандартный
+    pub fn input_calendar(&self) -> ProjectCalendar {
+        self.selected_project_id
+            .and_then(|id| self.container.calendar(&id))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Моменты начала и окончания по датам полей ввода: полночь в часовом поясе календаря
+    /// полей. Окончание в полночь включает последний день целиком (как в расчетах рабочих
+    /// дней), поэтому «15.02» в дате окончания включает 15-е
+    pub fn input_bounds(&self, start: NaiveDate, end: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+        let calendar = self.input_calendar();
+        (calendar.local_midnight(start), calendar.local_midnight(end))
+    }
+
+    /// Дата момента для поля ввода в часовом поясе календаря полей
+    pub fn input_date(&self, moment: DateTime<Utc>) -> NaiveDate {
+        self.input_calendar().local_date(moment)
+    }
+
     pub fn open_edit_project_dialog(&mut self) {
         if let Some(project) = self.container.list_projects().first() {
             self.new_project_name = project.name.clone();
             self.new_project_desc = project.description.clone();
-            self.new_project_start = project.get_date_start().date_naive();
-            self.new_project_end = project.get_date_end().date_naive();
+            let (start, end) = (*project.get_date_start(), *project.get_date_end());
+            self.new_project_start = self.input_date(start);
+            self.new_project_end = self.input_date(end);
             self.show_edit_project_dialog = true;
         }
     }
 
     pub fn close_project_no_save(&mut self) {
-        self.container = SingleProjectContainer::new();
+        // Ресурсы остаются в пуле, снимаются только назначения закрываемого проекта
+        if let Some(project_id) = self.selected_project_id
+            && let Err(e) = self.container.remove_project(&project_id)
+        {
+            self.error_message = Some(e.to_string());
+            return;
+        }
+        // Автосохранение не должно перезаписать файл закрытого проекта
+        self.current_file = None;
         self.selected_project_id = None;
         self.critical_path = None;
         self.selected_task_id = None;
+        self.selected_task_rows.clear();
         self.selected_resource_id = None;
         self.error_message = None;
     }
@@ -33,30 +67,46 @@ impl ProjectApp {
 
     pub fn load_project(&mut self) {
         if let Some(path) = FileDialog::new().add_filter("JSON", &["json"]).pick_file() {
+            let _span = tracing::info_span!("load_project", path = %path.display()).entered();
             match std::fs::read_to_string(&path) {
-                Ok(content) => match serde_json::from_str::<SingleProjectContainer>(&content) {
+                Ok(content) => match SingleProjectContainer::from_versioned_json(&content) {
                     Ok(container) => {
-                        self.selected_project_id =
-                            Some(*container.list_projects().first().unwrap().get_id());
+                        tracing::info!(
+                            projects = container.list_projects().len(),
+                            "project loaded"
+                        );
+                        self.selected_project_id = container.list_project_ids().first().copied();
                         self.container = container;
+                        self.current_file = Some(path);
+                        self.dirty = true;
                         self.error_message = None;
                     }
                     Err(e) => {
+                        tracing::error!(error = %e, "project file parse failed");
                         self.error_message = Some(format!("Ошибка парсинга файла проекта: {}", e))
                     }
                 },
-                Err(e) => self.error_message = Some(format!("Ошибка чтения файла проекта: {}", e)),
+                Err(e) => {
+                    tracing::error!(error = %e, "project file read failed");
+                    self.error_message = Some(format!("Ошибка чтения файла проекта: {}", e))
+                }
             }
         }
     }
     pub fn save_project(&mut self) {
         if let Some(path) = FileDialog::new().add_filter("JSON", &["json"]).save_file() {
-            match serde_json::to_string_pretty(&self.container) {
+            let _span = tracing::info_span!("save_project", path = %path.display()).entered();
+            match self.container.to_versioned_json() {
                 Ok(json) => {
                     if let Err(e) = std::fs::write(&path, json) {
+                        tracing::error!(error = %e, "project file write failed");
                         self.error_message = Some(format!("Ошибка записи файла: {}", e));
                     } else {
+                        tracing::info!("project saved");
                         self.error_message = None;
+                        self.last_saved = Some(Utc::now());
+                        self.current_file = Some(path);
+                        self.run_on_save_exports();
                     }
                 }
                 Err(e) => {
@@ -65,46 +115,188 @@ impl ProjectApp {
             }
         }
     }
+    /// Выбрать файл для слияния с текущим проектом и открыть мастер
+    pub fn start_merge(&mut self) {
+        if self.selected_project_id.is_none() {
+            self.error_message = Some("Нет открытого проекта для слияния".to_string());
+            return;
+        }
+        let Some(path) = FileDialog::new().add_filter("JSON", &["json"]).pick_file() else {
+            return;
+        };
+        match SingleProjectContainer::load_from_path(&path) {
+            Ok(incoming) => {
+                let wizard = &mut self.merge_wizard;
+                wizard.incoming = Some((path, incoming));
+                wizard.resolutions = MergeResolutions::default();
+                wizard.report = None;
+                self.refresh_merge_preview();
+                self.show_merge_dialog = true;
+            }
+            Err(e) => self.error_message = Some(format!("Ошибка чтения файла проекта: {}", e)),
+        }
+    }
+
+    /// Пересчитать конфликты слияния по текущим решениям
+    pub fn refresh_merge_preview(&mut self) {
+        let (Some(target_id), Some((_, incoming))) =
+            (self.selected_project_id, &self.merge_wizard.incoming)
+        else {
+            return;
+        };
+        let Some(source_id) = incoming.list_project_ids().first().copied() else {
+            self.error_message = Some("В файле нет проекта".to_string());
+            return;
+        };
+        match MergeService::new(&mut self.container).preview_merge(
+            target_id,
+            incoming,
+            source_id,
+            &self.merge_wizard.resolutions,
+        ) {
+            Ok(preview) => self.merge_wizard.preview = Some(preview),
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+    }
+
+    /// Применить слияние целиком; состояние до слияния сохраняется для отмены
+    pub fn apply_merge(&mut self) {
+        let (Some(target_id), Some((_, incoming))) =
+            (self.selected_project_id, &self.merge_wizard.incoming)
+        else {
+            return;
+        };
+        let Some(source_id) = incoming.list_project_ids().first().copied() else {
+            return;
+        };
+        let snapshot = match self.container.to_versioned_json() {
+            Ok(json) => json,
+            Err(e) => {
+                self.error_message = Some(e.to_string());
+                return;
+            }
+        };
+        match MergeService::new(&mut self.container).merge_from(
+            target_id,
+            incoming,
+            source_id,
+            &self.merge_wizard.resolutions,
+        ) {
+            Ok(report) => {
+                self.merge_wizard.report = Some(report);
+                self.merge_wizard.undo = Some(snapshot);
+                self.critical_path = None;
+                self.error_message = None;
+            }
+            Err(e) => self.error_message = Some(format!("Слияние не выполнено: {}", e)),
+        }
+    }
+
+    /// Вернуть контейнер к состоянию до последнего слияния
+    pub fn undo_merge(&mut self) {
+        let Some(snapshot) = self.merge_wizard.undo.take() else {
+            return;
+        };
+        match SingleProjectContainer::from_versioned_json(&snapshot) {
+            Ok(container) => {
+                self.container = container;
+                self.dirty = true;
+                self.merge_wizard.report = None;
+                self.critical_path = None;
+                self.selected_task_id = None;
+                self.selected_task_rows.clear();
+                self.refresh_merge_preview();
+            }
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+    }
+
+    /// Сохранить решения мастера в JSON для `rsproject merge --resolutions`
+    pub fn save_merge_resolutions(&mut self) {
+        let Some(path) = FileDialog::new().add_filter("JSON", &["json"]).save_file() else {
+            return;
+        };
+        let result = serde_json::to_string_pretty(&self.merge_wizard.resolutions)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| std::fs::write(&path, json).map_err(anyhow::Error::from));
+        if let Err(e) = result {
+            self.error_message = Some(format!("Ошибка записи файла: {}", e));
+        }
+    }
+
+    /// Профили выгрузки, отмеченные "при сохранении". Ошибки отдельных файлов
+    /// показываются вместе, остальные файлы при этом выгружаются
+    fn run_on_save_exports(&mut self) {
+        let failures: Vec<String> = MaintenanceService::new(&self.container)
+            .run_on_save_profiles(Utc::now())
+            .into_iter()
+            .filter_map(|r| {
+                r.result
+                    .err()
+                    .map(|e| format!("{} ({:?}): {}", r.profile, r.kind, e))
+            })
+            .collect();
+        if !failures.is_empty() {
+            self.error_message = Some(format!("Ошибка выгрузки:\n{}", failures.join("\n")));
+        }
+    }
+
     pub fn create_project(&mut self) -> anyhow::Result<()> {
+        let (start, end) = self.input_bounds(self.new_project_start, self.new_project_end);
         let project = Project::new(
             self.new_project_name.clone(),
             self.new_project_desc.clone(),
-            self.new_project_start
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc(),
-            self.new_project_end.and_hms_opt(0, 0, 0).unwrap().and_utc(),
+            start,
+            end,
         )?;
         self.container.add_project(project)?;
-        self.selected_project_id = self.container.list_projects().last().map(|p| *p.get_id());
+        self.selected_project_id = self.container.list_project_ids().last().copied();
         Ok(())
     }
+    /// Открыть демо-проект (без сохранения) и показать подсказки по вкладкам
+    pub fn create_demo_project(&mut self) {
+        match logic::testing::create_demo_project(&mut self.container, logic::testing::demo_start())
+        {
+            Ok(project_id) => {
+                self.selected_project_id = Some(project_id);
+                self.selected_tab = View::Project;
+                self.critical_path = None;
+                self.error_message = None;
+                self.show_demo_tour = true;
+            }
+            Err(e) => {
+                self.error_message = Some(format!(
+                    "Не удалось создать демо-проект (закройте текущий проект): {}",
+                    e
+                ))
+            }
+        }
+    }
     pub fn update_project(&mut self) -> anyhow::Result<()> {
         let project_id = *self.selected_project_id.as_ref().unwrap();
-        let new_start = self
-            .new_project_start
-            .and_hms_opt(0, 0, 0)
-            .unwrap()
-            .and_utc();
-        let new_end = self.new_project_end.and_hms_opt(0, 0, 0).unwrap().and_utc();
-
-        // Проверяем, что все задачи помещаются в новые даты
-        let task_service = TaskService::new(&mut self.container);
-        let tasks = task_service.get_all_tasks(project_id);
-        for task in tasks {
-            if *task.get_date_start() < new_start || *task.get_date_end() > new_end {
-                anyhow::bail!("Задача '{}' выходит за новые границы проекта", task.name);
-            }
+        let (start, end) = self.input_bounds(self.new_project_start, self.new_project_end);
+        let changes = ProjectUpdate {
+            name: Some(self.new_project_name.clone()),
+            description: Some(self.new_project_desc.clone()),
+            date_start: Some(start),
+            date_end: Some(end),
+        };
+        let result = ProjectService::new(&mut self.container).update_project(project_id, changes);
+        // Для пользователя показываем имена задач, а не их id
+        if let Err(e) = &result
+            && let Some(ProjectUpdateErrors::TasksOutsideProject { task_ids }) = e.downcast_ref()
+        {
+            let task_service = TaskService::new(&mut self.container);
+            let names: Vec<String> = task_ids
+                .iter()
+                .filter_map(|id| task_service.get_task_by_id(&project_id, id))
+                .map(|t| t.get_display_name())
+                .collect();
+            anyhow::bail!(
+                "Задачи выходят за новые границы проекта: {}",
+                names.join(", ")
+            );
         }
-
-        // Обновляем проект
-        let project = self.container.get_project_mut(&project_id).unwrap();
-        project.name = self.new_project_name.clone();
-        project.description = self.new_project_desc.clone();
-        project.date_start = new_start;
-        project.date_end = new_end;
-        project.duration = new_end - new_start;
-
-        Ok(())
+        result
     }
 }
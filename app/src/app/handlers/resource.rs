@@ -12,6 +12,9 @@ impl ProjectApp {
             self.new_resource_name = resource.name.clone();
             self.new_resource_rate = resource.rate.to_string();
             self.new_resource_measure = resource.rate_measure.clone();
+            self.new_resource_skills = resource.skills.join(", ");
+            self.new_resource_primary_skill = resource.primary_skill.clone().unwrap_or_default();
+            self.new_resource_currency = resource.get_currency().to_string();
             self.edit_resource_id = Some(resource_id);
             self.show_new_resource_dialog = true;
         }
@@ -20,7 +23,7 @@ impl ProjectApp {
     pub fn create_resource(&mut self) -> anyhow::Result<()> {
         let rate: f64 = self.new_resource_rate.parse()?;
         let mut resource_service = ResourceService::new(&mut self.container);
-        if let Some(id) = self.edit_resource_id {
+        let resource_id = if let Some(id) = self.edit_resource_id {
             // Обновление
             resource_service.update_resource(
                 id,
@@ -28,6 +31,7 @@ impl ProjectApp {
                 Some(rate),
                 Some(self.new_resource_measure.clone()),
             )?;
+            id
         } else {
             // Создание
             let resource = resource_service.create_resource(
@@ -35,10 +39,24 @@ impl ProjectApp {
                 rate,
                 self.new_resource_measure.clone(),
             )?;
+            let id = resource.id;
             resource_service.add_resource(resource)?;
-        }
+            id
+        };
+        resource_service.set_resource_skills(
+            resource_id,
+            self.new_resource_skills
+                .split(',')
+                .map(String::from)
+                .collect(),
+            Some(self.new_resource_primary_skill.clone()),
+        )?;
+        resource_service.set_resource_currency(resource_id, &self.new_resource_currency)?;
         self.new_resource_name.clear();
         self.new_resource_rate = String::from("1000");
+        self.new_resource_skills.clear();
+        self.new_resource_primary_skill.clear();
+        self.new_resource_currency = logic::DEFAULT_CURRENCY.to_string();
         self.edit_resource_id = None;
         Ok(())
     }
@@ -47,13 +65,8 @@ impl ProjectApp {
         let resource_id = self
             .selected_resource_id
             .ok_or_else(|| anyhow::anyhow!("Не выбран ресурс"))?;
-        let period = TimeWindow::new(
-            self.unavailable_start
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc(),
-            self.unavailable_end.and_hms_opt(0, 0, 0).unwrap().and_utc(),
-        )?;
+        let (start, end) = self.input_bounds(self.unavailable_start, self.unavailable_end);
+        let period = TimeWindow::new(start, end)?;
         let exception_period = ExceptionPeriod {
             period,
             exception_type: self.unavailable_type.clone(),
@@ -63,6 +76,13 @@ impl ProjectApp {
         Ok(())
     }
 
+    pub fn open_assign_resource_dialog(&mut self, resource_id: Uuid) {
+        self.selected_resource_id = Some(resource_id);
+        self.selected_task_id = None;
+        self.assign_use_full_window = true;
+        self.show_assign_resource_dialog = true;
+    }
+
     pub fn assing_resource(&mut self) -> anyhow::Result<()> {
         let binding = self.container.list_projects();
         let project = binding
@@ -76,23 +96,14 @@ impl ProjectApp {
         let resource_id = self
             .selected_resource_id
             .ok_or_else(|| anyhow::anyhow!("Не выбран ресурс"))?;
-        let engagement: f64 = self.assign_engagement.parse()?;
+        let engagement = self.assign_engagement / 100.0;
         if !(0.0..=1.0).contains(&engagement) {
-            anyhow::bail!("Занятость должна быть между 0.0 и 1.0");
+            anyhow::bail!("Занятость должна быть между 0% и 100%");
         }
         let time_window = if self.assign_use_full_window {
             None
         } else {
-            let start = self
-                .assign_custom_start
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc();
-            let end = self
-                .assign_custom_end
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc();
+            let (start, end) = self.input_bounds(self.assign_custom_start, self.assign_custom_end);
             Some(TimeWindow::new(start, end)?)
         };
         let mut task_service = TaskService::new(&mut self.container);
@@ -102,6 +113,7 @@ impl ProjectApp {
             resource_id,
             engagement,
             time_window,
+            None,
         )?;
         Ok(())
     }
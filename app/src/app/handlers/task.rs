@@ -1,5 +1,10 @@
+use std::collections::HashMap;
+
 use chrono::{Duration, Utc};
-use logic::{BasicGettersForStructures, DependencyType, ProjectContainer, TaskService};
+use logic::{
+    BasicGettersForStructures, BulkResult, DependencyType, ProjectContainer,
+    SingleProjectContainer, TaskService,
+};
 use uuid::Uuid;
 
 use crate::ProjectApp;
@@ -7,13 +12,14 @@ use crate::ProjectApp;
 impl ProjectApp {
     pub fn open_edit_task_dialog(&mut self, task_id: Uuid) {
         if let Some(project_id) = self.selected_project_id {
+            let calendar = self.input_calendar();
             let task_service = TaskService::new(&mut self.container);
             if let Some(project) = task_service.get_project(&project_id)
                 && let Some(task) = project.tasks.get(&task_id)
             {
                 self.new_task_name = task.name.clone();
-                self.new_task_start = task.get_date_start().date_naive();
-                self.new_task_end = task.get_date_end().date_naive();
+                self.new_task_start = calendar.local_date(*task.get_date_start());
+                self.new_task_end = calendar.local_date(*task.get_date_end());
                 self.new_task_is_summary = task.is_summary;
                 self.selected_task_parent_id = task.parent_id;
                 self.new_task_dependency_task = if task.get_dependencies().is_empty() {
@@ -35,8 +41,7 @@ impl ProjectApp {
         let project = self.container.list_projects().first().cloned();
         if let Some(project) = project {
             let project_id = *project.get_id();
-            let start = self.new_task_start.and_hms_opt(0, 0, 0).unwrap().and_utc();
-            let end = self.new_task_end.and_hms_opt(0, 0, 0).unwrap().and_utc();
+            let (start, end) = self.input_bounds(self.new_task_start, self.new_task_end);
 
             let mut task_service = TaskService::new(&mut self.container);
             if let Some(task_id) = self.edit_task_id {
@@ -50,12 +55,12 @@ impl ProjectApp {
                     self.selected_task_parent_id,
                 )?;
                 // TODO: Здесь должно быть место для удаления зависимости с задачи
-                if self.new_task_dependency_task.is_some() {
-                    eprintln!("Добавляю новую зависимую задачу");
+                if let Some(dependency_task) = self.new_task_dependency_task {
+                    tracing::debug!(%task_id, %dependency_task, "adding dependency");
                     task_service.add_dependency(
                         project_id,
                         task_id,
-                        self.new_task_dependency_task.unwrap(),
+                        dependency_task,
                         self.new_task_dependency_type
                             .unwrap_or(DependencyType::Blocking),
                         Some(Duration::zero()),
@@ -69,12 +74,12 @@ impl ProjectApp {
                     end,
                     self.selected_task_parent_id,
                 )?;
-                if self.new_task_dependency_task.is_some() {
-                    eprintln!("Добавляю новую зависимую задачу");
+                if let Some(dependency_task) = self.new_task_dependency_task {
+                    tracing::debug!(task_id = %task.get_id(), %dependency_task, "adding dependency");
                     task_service.add_dependency(
                         project_id,
                         *task.get_id(),
-                        self.new_task_dependency_task.unwrap(),
+                        dependency_task,
                         self.new_task_dependency_type.unwrap(),
                         Some(Duration::zero()),
                     )?;
@@ -90,15 +95,76 @@ impl ProjectApp {
             self.clear_task_fields();
             Ok(())
         } else {
-            eprintln!("No project found");
+            tracing::warn!("task dialog submitted without a project");
             Err(anyhow::anyhow!("No project"))
         }
     }
 
+    /// Выполнить массовую операцию над выделенными задачами
+    ///
+    /// Ошибки по отдельным задачам не прерывают операцию и показываются списком в отдельном окне.
+    pub fn run_bulk_operation(
+        &mut self,
+        operation: impl FnOnce(
+            &mut TaskService<SingleProjectContainer>,
+            Uuid,
+            &[Uuid],
+        ) -> anyhow::Result<BulkResult>,
+    ) {
+        let Some(project_id) = self.selected_project_id else {
+            return;
+        };
+        let Some(project) = self.container.get_project(&project_id) else {
+            return;
+        };
+        // Имена запоминаем заранее - после удаления задач их уже не найти
+        let names: HashMap<Uuid, String> = project
+            .tasks
+            .values()
+            .map(|t| (*t.get_id(), t.get_display_name()))
+            .collect();
+        let mut task_ids: Vec<Uuid> = self.selected_task_rows.iter().copied().collect();
+        task_ids.sort_by_key(|id| names.get(id).cloned());
+
+        let mut task_service = TaskService::new(&mut self.container);
+        match operation(&mut task_service, project_id, &task_ids) {
+            Ok(mut result) => {
+                if let Some(undo) = result.undo.take() {
+                    self.bulk_undo = Some(undo);
+                }
+                let project = self.container.get_project(&project_id);
+                self.selected_task_rows
+                    .retain(|id| project.is_some_and(|p| p.tasks.contains_key(id)));
+                if !result.is_complete() {
+                    self.bulk_failures = result
+                        .failures
+                        .iter()
+                        .map(|(id, error)| {
+                            let name = names.get(id).cloned().unwrap_or_else(|| id.to_string());
+                            format!("{}: {}", name, error)
+                        })
+                        .collect();
+                    self.show_bulk_result_dialog = true;
+                }
+            }
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+    }
+
+    /// Отменить последнюю массовую операцию целиком
+    pub fn undo_bulk_operation(&mut self) {
+        let Some(undo) = self.bulk_undo.take() else {
+            return;
+        };
+        if let Err(e) = TaskService::new(&mut self.container).undo_bulk(undo) {
+            self.error_message = Some(e.to_string());
+        }
+    }
+
     fn clear_task_fields(&mut self) {
         self.new_task_name.clear();
-        self.new_task_start = Utc::now().date_naive();
-        self.new_task_end = Utc::now().date_naive();
+        self.new_task_start = self.input_date(Utc::now());
+        self.new_task_end = self.new_task_start;
         self.new_task_is_summary = false;
         self.selected_task_parent_id = None;
         self.edit_task_id = None;
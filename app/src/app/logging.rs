@@ -0,0 +1,159 @@
+//! Журнал приложения
+//!
+//! События logic и app пишутся в файл с ежедневной ротацией. Файл выбирается в настройках
+//! (`logging.json` в каталоге данных приложения), `RSPROJECT_LOG_DIR` переопределяет каталог,
+//! по умолчанию - `<каталог данных>/logs/rsproject.log`. Уровень: `-v`/`-vv` в командной строке,
+//! затем переменная `RSPROJECT_LOG` (`error`, `warn`, `info`, `debug`, `trace`), затем
+//! настройки, по умолчанию `info`. Изменения настроек применяются при следующем запуске.
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::Level;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::{
+    Layer, filter::LevelFilter, layer::SubscriberExt, util::SubscriberInitExt,
+};
+
+pub const APP_ID: &str = "Project Manager";
+
+const DEFAULT_FILE_NAME: &str = "rsproject.log";
+const SETTINGS_FILE_NAME: &str = "logging.json";
+
+/// Уровни, доступные в настройках
+pub const LEVELS: [Level; 5] = [
+    Level::ERROR,
+    Level::WARN,
+    Level::INFO,
+    Level::DEBUG,
+    Level::TRACE,
+];
+
+/// Настройки журнала из окна настроек
+/// file - файл журнала (к имени добавляется дата ротации), None - файл по умолчанию
+/// level - уровень журнала, None - `info`
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct LogSettings {
+    pub file: Option<PathBuf>,
+    pub level: Option<String>,
+}
+
+impl LogSettings {
+    /// Настройки из каталога данных приложения, при их отсутствии - по умолчанию
+    pub fn load() -> Self {
+        settings_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = settings_path()
+            .ok_or_else(|| anyhow::anyhow!("Каталог данных приложения не найден"))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn level(&self) -> Option<Level> {
+        self.level.as_deref().and_then(|level| level.parse().ok())
+    }
+}
+
+fn settings_path() -> Option<PathBuf> {
+    eframe::storage_dir(APP_ID).map(|dir| dir.join(SETTINGS_FILE_NAME))
+}
+
+/// Файл журнала по умолчанию: в `RSPROJECT_LOG_DIR` или `<каталог данных>/logs`
+pub fn default_log_file() -> Option<PathBuf> {
+    std::env::var_os("RSPROJECT_LOG_DIR")
+        .map(PathBuf::from)
+        .or_else(|| eframe::storage_dir(APP_ID).map(|dir| dir.join("logs")))
+        .map(|dir| dir.join(DEFAULT_FILE_NAME))
+}
+
+/// Файл журнала с учетом переменной окружения и настроек
+pub fn log_file(settings: &LogSettings) -> Option<PathBuf> {
+    if std::env::var_os("RSPROJECT_LOG_DIR").is_some() {
+        return default_log_file();
+    }
+    settings.file.clone().or_else(default_log_file)
+}
+
+/// Уровень журнала: `-v` - debug, `-vv` и больше - trace, без флагов - переменная
+/// окружения, затем настройки
+pub fn level(verbosity: u8, settings: &LogSettings) -> Level {
+    match verbosity {
+        0 => std::env::var("RSPROJECT_LOG")
+            .ok()
+            .and_then(|value| value.parse::<Level>().ok())
+            .or_else(|| settings.level())
+            .unwrap_or(Level::INFO),
+        1 => Level::DEBUG,
+        _ => Level::TRACE,
+    }
+}
+
+/// Подключить запись журнала. `to_stderr` дублирует события в stderr (команды без окна
+/// с `-v`). Guard должен жить до выхода из приложения, иначе последние события не попадут в файл
+pub fn init(verbosity: u8, to_stderr: bool) -> Option<WorkerGuard> {
+    let settings = LogSettings::load();
+    let filter = LevelFilter::from_level(level(verbosity, &settings));
+    let (file_layer, guard) = match log_file(&settings).as_deref().and_then(split_file) {
+        Some((dir, name)) => {
+            let appender = tracing_appender::rolling::daily(dir, name);
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            let layer = tracing_subscriber::fmt::layer()
+                .with_writer(writer)
+                .with_ansi(false);
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+    let stderr_layer = to_stderr.then(|| {
+        tracing_subscriber::fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_filter(filter)
+    });
+    tracing_subscriber::registry()
+        .with(file_layer.map(|layer| layer.with_filter(filter)))
+        .with(stderr_layer)
+        .try_init()
+        .ok()?;
+    guard
+}
+
+/// Каталог и имя файла журнала
+fn split_file(path: &Path) -> Option<(PathBuf, PathBuf)> {
+    let name = path.file_name()?;
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    Some((dir.to_path_buf(), PathBuf::from(name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity_overrides_settings_level() {
+        let settings = LogSettings {
+            file: None,
+            level: Some("warn".into()),
+        };
+        assert_eq!(level(1, &settings), Level::DEBUG);
+        assert_eq!(level(2, &settings), Level::TRACE);
+        assert_eq!(level(5, &settings), Level::TRACE);
+        assert_eq!(settings.level(), Some(Level::WARN));
+        assert_eq!(LogSettings::default().level(), None);
+
+        let path = Path::new("/var/log/rsproject/app.log");
+        assert_eq!(
+            split_file(path),
+            Some((
+                PathBuf::from("/var/log/rsproject"),
+                PathBuf::from("app.log")
+            ))
+        );
+    }
+}
@@ -1,24 +1,45 @@
-use chrono::{NaiveDate, Utc};
+use std::{collections::HashSet, path::PathBuf, time::Duration};
+
+use chrono::{DateTime, NaiveDate, Utc};
 use logic::{
-    BasicGettersForStructures, DependencyType, ExceptionType, ProjectContainer, RateMeasure,
-    SingleProjectContainer,
+    BulkUndo, ContainerEvent, DEFAULT_TASK_PRIORITY, DependencyType, ExceptionType,
+    ProjectContainer, RateMeasure, ResourceSort, SingleProjectContainer, TaskStatus,
+    export::ExportRedaction,
 };
 use uuid::Uuid;
 
-use crate::app::{AppTheme, views::View};
+use crate::app::{
+    AppTheme,
+    autosave::{Autosaver, SaveOutcome},
+    dialogs::{merge_wizard::MergeWizard, settings::ExportProfileDraft},
+    logging::LogSettings,
+    ui::toast::Toast,
+    views::View,
+};
 
 pub struct ProjectApp {
     pub(crate) container: SingleProjectContainer,
     pub(crate) selected_tab: View,
     pub(crate) selected_project_id: Option<Uuid>,
     pub(crate) selected_task_id: Option<Uuid>,
+    pub(crate) selected_task_rows: HashSet<Uuid>,
+    // Строка, от которой выделяется диапазон по Shift+клику
+    pub(crate) task_selection_anchor: Option<Uuid>,
     pub(crate) selected_resource_id: Option<Uuid>,
+    pub(crate) resource_sort: ResourceSort,
+    pub(crate) export_redaction: ExportRedaction,
     pub(crate) critical_path: Option<Vec<Uuid>>,
     pub(crate) edit_resource_id: Option<Uuid>,
     pub(crate) edit_task_id: Option<Uuid>,
     pub(crate) show_edit_project_dialog: bool,
+    pub(crate) show_demo_tour: bool,
     pub(crate) current_theme: AppTheme,
 
+    // Settings dialog
+    pub(crate) show_settings_dialog: bool,
+    pub(crate) new_export_profile: ExportProfileDraft,
+    pub(crate) log_settings: LogSettings,
+
     pub(crate) show_close_project_dialog: bool,
     // Create project dialog
     pub(crate) show_new_project_dialog: bool,
@@ -28,6 +49,19 @@ pub struct ProjectApp {
     pub(crate) new_project_end: NaiveDate,
     pub(crate) error_message: Option<String>,
 
+    // Autosave
+    /// Файл, в который проект был сохранен или из которого загружен
+    pub(crate) current_file: Option<PathBuf>,
+    pub(crate) autosaver: Autosaver,
+    pub(crate) last_saved: Option<DateTime<Utc>>,
+    /// JSON последнего завершенного фонового сохранения, еще не отданный хранилищу eframe
+    pub(crate) storage_json: Option<String>,
+    /// В контейнере есть изменения, еще не переданные автосохранению
+    pub(crate) dirty: bool,
+    /// Окно закрывается: последнее сохранение дожидается фонового потока
+    pub(crate) exiting: bool,
+    pub(crate) toast: Option<Toast>,
+
     // Create task dialog
     pub(crate) show_new_task_dialog: bool,
     pub(crate) new_task_name: String,
@@ -43,10 +77,14 @@ pub struct ProjectApp {
     pub(crate) new_resource_name: String,
     pub(crate) new_resource_rate: String,
     pub(crate) new_resource_measure: RateMeasure,
+    /// Навыки через запятую
+    pub(crate) new_resource_skills: String,
+    pub(crate) new_resource_primary_skill: String,
+    pub(crate) new_resource_currency: String,
 
     // Assign Resource dialog
     pub(crate) show_assign_resource_dialog: bool,
-    pub(crate) assign_engagement: String,
+    pub(crate) assign_engagement: f64,
     pub(crate) assign_use_full_window: bool,
     pub(crate) assign_custom_start: NaiveDate,
     pub(crate) assign_custom_end: NaiveDate,
@@ -56,11 +94,29 @@ pub struct ProjectApp {
     pub(crate) unavailable_end: NaiveDate,
     pub(crate) unavailable_type: ExceptionType,
 
+    // Calendar tab
+    pub(crate) new_holiday: NaiveDate,
+
     // Gantt chart state
     pub(crate) gantt_day_width: f32,
     pub(crate) gantt_only_critical: bool,
     pub(crate) details_task_id: Option<Uuid>,
     pub(crate) show_task_details_dialog: bool,
+
+    // Bulk actions
+    pub(crate) bulk_status: TaskStatus,
+    pub(crate) bulk_shift_days: i64,
+    pub(crate) bulk_parent_id: Option<Uuid>,
+    pub(crate) bulk_priority: u8,
+    pub(crate) bulk_tag: String,
+    pub(crate) bulk_failures: Vec<String>,
+    // Снимок до последней массовой операции, одна запись отмены на всю операцию
+    pub(crate) bulk_undo: Option<BulkUndo>,
+    pub(crate) show_bulk_result_dialog: bool,
+
+    // Merge wizard
+    pub(crate) show_merge_dialog: bool,
+    pub(crate) merge_wizard: MergeWizard,
 }
 
 impl Default for ProjectApp {
@@ -86,16 +142,31 @@ impl Default for ProjectApp {
             new_task_start: now,
             new_task_end: now,
             error_message: None,
+            current_file: None,
+            autosaver: Autosaver::default(),
+            last_saved: None,
+            storage_json: None,
+            dirty: false,
+            exiting: false,
+            toast: None,
             selected_project_id: None,
             selected_task_id: None,
+            selected_task_rows: HashSet::new(),
+            task_selection_anchor: None,
+            resource_sort: ResourceSort::ByName,
+            export_redaction: ExportRedaction::default(),
             selected_resource_id: None,
-            assign_engagement: String::from("0.5"),
+            assign_engagement: 50.0,
             new_resource_name: String::new(),
             new_resource_rate: String::from("1000"),
             new_resource_measure: RateMeasure::Hourly,
+            new_resource_skills: String::new(),
+            new_resource_primary_skill: String::new(),
+            new_resource_currency: logic::DEFAULT_CURRENCY.to_string(),
             unavailable_start: now,
             unavailable_end: now,
             unavailable_type: ExceptionType::Vacation,
+            new_holiday: now,
             assign_use_full_window: false,
             assign_custom_start: now,
             assign_custom_end: now,
@@ -105,21 +176,177 @@ impl Default for ProjectApp {
             gantt_only_critical: false,
             details_task_id: None,
             show_task_details_dialog: false,
+            bulk_status: TaskStatus::New,
+            bulk_shift_days: 1,
+            bulk_parent_id: None,
+            bulk_priority: DEFAULT_TASK_PRIORITY,
+            bulk_tag: String::new(),
+            bulk_failures: Vec::new(),
+            bulk_undo: None,
+            show_bulk_result_dialog: false,
             edit_resource_id: None,
             edit_task_id: None,
 
             show_edit_project_dialog: false,
+            show_demo_tour: false,
             current_theme: AppTheme::Light,
+            show_settings_dialog: false,
+            new_export_profile: ExportProfileDraft::default(),
+            log_settings: LogSettings::load(),
+            show_merge_dialog: false,
+            merge_wizard: MergeWizard::default(),
         }
     }
 }
 
+/// Ключ, под которым контейнер проекта хранится в `eframe::Storage`
+const STORAGE_KEY: &str = "rsproject_container";
+/// Ключ пути к файлу проекта, пустая строка - файла нет
+const CURRENT_FILE_KEY: &str = "rsproject_current_file";
+/// Ключ флажков скрытия данных при выгрузке (JSON `ExportRedaction`)
+const EXPORT_REDACTION_KEY: &str = "rsproject_export_redaction";
+/// Сколько ждать фонового сохранения при закрытии окна
+const EXIT_SAVE_TIMEOUT: Duration = Duration::from_secs(5);
+
 impl ProjectApp {
+    /// Создание приложения с восстановлением состояния из хранилища eframe.
+    /// При первом запуске (или если данные не читаются) стартуем с пустым контейнером.
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let container = cc
+            .storage
+            .and_then(|storage| storage.get_string(STORAGE_KEY))
+            .and_then(|json| SingleProjectContainer::from_versioned_json(&json).ok());
+        let mut app = match container {
+            Some(container) if !container.list_projects().is_empty() => {
+                let mut app = Self::with_container(container);
+                // Автосохранение продолжает писать в тот же файл, если он на месте
+                app.current_file = cc
+                    .storage
+                    .and_then(|storage| storage.get_string(CURRENT_FILE_KEY))
+                    .filter(|path| !path.is_empty())
+                    .map(PathBuf::from)
+                    .filter(|path| path.exists());
+                // Контейнер только что прочитан из хранилища
+                app.dirty = false;
+                app
+            }
+            _ => Self::default(),
+        };
+        // Флажки скрытия данных не зависят от проекта и переживают перезапуск
+        if let Some(redaction) = cc
+            .storage
+            .and_then(|storage| storage.get_string(EXPORT_REDACTION_KEY))
+            .and_then(|json| serde_json::from_str(&json).ok())
+        {
+            app.export_redaction = redaction;
+        }
+        app
+    }
+
+    /// Сохранение контейнера в хранилище eframe (вызывается при автосохранении и выходе)
+    ///
+    /// Сохраняется только измененный контейнер (см. `dirty`), тики без изменений пропускаются.
+    /// Снимок контейнера сериализуется и пишется в файл проекта в фоновом потоке; если
+    /// предыдущее сохранение еще идет, тик пропускается. JSON завершенного сохранения уходит
+    /// в хранилище сразу из `poll_autosave`. При закрытии окна сохранение дожидается потока.
+    pub fn save_to_storage(&mut self, storage: &mut dyn eframe::Storage) {
+        let _span = tracing::debug_span!("save_to_storage", exiting = self.exiting).entered();
+        // Изменения последнего кадра еще не разобраны в update
+        self.handle_container_events();
+        if self.exiting {
+            self.wait_autosave();
+        }
+        if self.dirty
+            && self
+                .autosaver
+                .start(self.container.clone(), self.current_file.clone())
+        {
+            self.dirty = false;
+        }
+        if self.exiting {
+            self.wait_autosave();
+        }
+        if let Some(json) = self.storage_json.take() {
+            storage.set_string(STORAGE_KEY, json);
+        }
+        let current_file = self
+            .current_file
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        storage.set_string(CURRENT_FILE_KEY, current_file);
+        if let Ok(json) = serde_json::to_string(&self.export_redaction) {
+            storage.set_string(EXPORT_REDACTION_KEY, json);
+        }
+    }
+
+    /// Забрать результат фонового автосохранения. JSON сохранения сразу отдается хранилищу
+    /// eframe, не дожидаясь следующего тика автосохранения
+    pub fn poll_autosave(&mut self, storage: Option<&mut (dyn eframe::Storage + 'static)>) {
+        let outcome = self.autosaver.poll();
+        self.apply_save_outcome(outcome);
+        if let Some(storage) = storage
+            && let Some(json) = self.storage_json.take()
+        {
+            storage.set_string(STORAGE_KEY, json);
+            storage.flush();
+        }
+    }
+
+    /// Дождаться фонового автосохранения (при закрытии окна)
+    pub fn wait_autosave(&mut self) {
+        let outcome = self.autosaver.wait(EXIT_SAVE_TIMEOUT);
+        self.apply_save_outcome(outcome);
+    }
+
+    fn apply_save_outcome(&mut self, outcome: Option<SaveOutcome>) {
+        match outcome {
+            Some(Ok(saved)) => {
+                self.storage_json = Some(saved.json);
+                if let Some(saved_at) = saved.file_saved_at {
+                    tracing::info!(%saved_at, "autosave finished");
+                    self.last_saved = Some(saved_at);
+                    self.toast = Some(Toast::info("Проект автосохранен"));
+                }
+            }
+            Some(Err(e)) => {
+                tracing::error!(error = %e, "autosave failed");
+                self.toast = Some(Toast::error(format!("Ошибка автосохранения: {}", e)));
+            }
+            None => {}
+        }
+    }
+
+    /// Разобрать события контейнера: любое событие помечает контейнер измененным для
+    /// автосохранения, изменения задач и назначений сбрасывают критический путь
+    pub fn handle_container_events(&mut self) {
+        let events = self.container.take_events();
+        if !events.is_empty() {
+            self.dirty = true;
+        }
+        let schedule_changed = events.iter().any(|event| {
+            matches!(
+                event,
+                ContainerEvent::TaskCreated { .. }
+                    | ContainerEvent::TaskUpdated { .. }
+                    | ContainerEvent::TaskDeleted { .. }
+                    | ContainerEvent::ProjectUpdated { .. }
+            )
+        });
+        if schedule_changed {
+            tracing::debug!(
+                events = events.len(),
+                "schedule changed, critical path reset"
+            );
+            self.critical_path = None;
+        }
+    }
+
     pub fn with_container(container: SingleProjectContainer) -> Self {
         let project_id = container
-            .list_projects()
+            .list_project_ids()
             .first()
-            .map(|p| *p.get_id())
+            .copied()
             .unwrap_or_else(Uuid::new_v4);
         Self {
             container,
@@ -135,6 +362,14 @@ impl ProjectApp {
             new_project_start: Utc::now().date_naive(),
             new_project_end: Utc::now().date_naive(),
             error_message: None,
+            current_file: None,
+            autosaver: Autosaver::default(),
+            last_saved: None,
+            storage_json: None,
+            // Новый контейнер (демо-проект) еще нигде не сохранен
+            dirty: true,
+            exiting: false,
+            toast: None,
             show_new_task_dialog: false,
             new_task_name: String::new(),
             new_task_start: Utc::now().date_naive(),
@@ -143,10 +378,17 @@ impl ProjectApp {
             new_resource_name: String::new(),
             new_resource_rate: String::from("1000"),
             new_resource_measure: RateMeasure::Hourly,
+            new_resource_skills: String::new(),
+            new_resource_primary_skill: String::new(),
+            new_resource_currency: logic::DEFAULT_CURRENCY.to_string(),
             show_assign_resource_dialog: false,
             selected_task_id: None,
+            selected_task_rows: HashSet::new(),
+            task_selection_anchor: None,
+            resource_sort: ResourceSort::ByName,
+            export_redaction: ExportRedaction::default(),
             selected_resource_id: None,
-            assign_engagement: String::from("0.5"),
+            assign_engagement: 50.0,
             assign_use_full_window: true,
             assign_custom_start: Utc::now().date_naive(),
             assign_custom_end: Utc::now().date_naive(),
@@ -154,6 +396,7 @@ impl ProjectApp {
             unavailable_start: Utc::now().date_naive(),
             unavailable_end: Utc::now().date_naive(),
             unavailable_type: ExceptionType::Vacation,
+            new_holiday: Utc::now().date_naive(),
             critical_path: None,
             new_task_is_summary: false,
             selected_task_parent_id: None,
@@ -161,10 +404,24 @@ impl ProjectApp {
             gantt_only_critical: false,
             details_task_id: None,
             show_task_details_dialog: false,
+            bulk_status: TaskStatus::New,
+            bulk_shift_days: 1,
+            bulk_parent_id: None,
+            bulk_priority: DEFAULT_TASK_PRIORITY,
+            bulk_tag: String::new(),
+            bulk_failures: Vec::new(),
+            bulk_undo: None,
+            show_bulk_result_dialog: false,
             edit_resource_id: None,
             edit_task_id: None,
 
             show_edit_project_dialog: false,
+            show_demo_tour: false,
+            show_settings_dialog: false,
+            new_export_profile: ExportProfileDraft::default(),
+            log_settings: LogSettings::load(),
+            show_merge_dialog: false,
+            merge_wizard: MergeWizard::default(),
         }
     }
 }
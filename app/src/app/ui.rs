@@ -1,3 +1,6 @@
 pub mod central_panel;
+pub mod date_input;
 pub mod side_panel;
+pub mod status_bar;
+pub mod toast;
 pub mod top_panel;
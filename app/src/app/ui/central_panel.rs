@@ -3,7 +3,7 @@ use logic::ProjectContainer;
 
 use crate::{
     ProjectApp,
-    app::views::{View, gantt, project, resources, task},
+    app::views::{View, calendar, gantt, project, resources, task},
 };
 
 pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
@@ -24,6 +24,7 @@ pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
                 View::Tasks => task::show(ui, app),
                 View::Resources => resources::show(ui, app),
                 View::Gantt => gantt::show(ui, app),
+                View::Calendar => calendar::show(ui, app),
             }
         }
         // Отображение ошибки (если есть)
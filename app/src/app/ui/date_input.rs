@@ -0,0 +1,67 @@
+use chrono::{NaiveDate, Utc};
+use eframe::egui::{self, Widget};
+use logic::{ProjectCalendar, cust_exceptions::DateInputError, date_input::parse_date_input};
+
+/// Поле даты с вводом с клавиатуры
+///
+/// Текст разбирается при каждом изменении (дд.мм.гггг, дд.мм, гггг-мм-дд, сегодня, пн,
+/// +5d, +5wd, -1w), результат или ошибка показываются рядом. Кнопка открывает календарь.
+/// Рабочие дни и «сегодня» считаются по `calendar`, в его часовом поясе.
+pub fn date_input(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    date: &mut NaiveDate,
+    calendar: &ProjectCalendar,
+) {
+    let id = ui.make_persistent_id(id_salt);
+    // Текст поля и дата, для которой он введен: если дату поменяли снаружи, текст сбрасывается
+    let mut text = ui
+        .data_mut(|d| d.get_temp::<(String, NaiveDate)>(id))
+        .filter(|(_, shown)| shown == date)
+        .map(|(text, _)| text)
+        .unwrap_or_else(|| format_date(date));
+    let today = calendar.local_date(Utc::now());
+
+    ui.horizontal(|ui| {
+        let response = egui::TextEdit::singleline(&mut text)
+            .desired_width(110.0)
+            .hint_text("дд.мм.гггг, +5wd, пн")
+            .ui(ui);
+
+        let picked = *date;
+        egui_extras::DatePickerButton::new(date)
+            .id_salt(id_salt)
+            .start_end_years(2020..=2035)
+            .ui(ui);
+        if *date != picked {
+            text = format_date(date);
+        } else if response.changed()
+            && let Ok(parsed) = parse_date_input(&text, today, calendar)
+        {
+            *date = parsed;
+        }
+
+        // Для относительных и неполных форм показываем, во что они превратились
+        if text.trim() != format_date(date) {
+            match parse_date_input(&text, today, calendar) {
+                Ok(parsed) => ui.weak(format!("→ {}", format_date(&parsed))),
+                Err(e) => ui.colored_label(ui.visuals().error_fg_color, describe_error(&e)),
+            };
+        }
+    });
+
+    ui.data_mut(|d| d.insert_temp(id, (text, *date)));
+}
+
+fn format_date(date: &NaiveDate) -> String {
+    date.format("%d.%m.%Y").to_string()
+}
+
+fn describe_error(error: &DateInputError) -> String {
+    match error {
+        DateInputError::Empty => "⚠ Введите дату".to_string(),
+        DateInputError::Unrecognized { input } => format!("⚠ Не понимаю «{}»", input),
+        DateInputError::InvalidDate { input } => format!("⚠ Нет такой даты: {}", input),
+        DateInputError::Calendar(e) => format!("⚠ Не удалось сдвинуть по календарю: {}", e),
+    }
+}
@@ -13,6 +13,7 @@ pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
             });
         ui.selectable_value(&mut app.selected_tab, View::Tasks, "✅ Задачи");
         ui.selectable_value(&mut app.selected_tab, View::Resources, "👤 Ресурсы");
-        ui.selectable_value(&mut app.selected_tab, View::Gantt, "📊 Диаграмма Ганта")
+        ui.selectable_value(&mut app.selected_tab, View::Gantt, "📊 Диаграмма Ганта");
+        ui.selectable_value(&mut app.selected_tab, View::Calendar, "🗓 Календарь")
     });
 }
@@ -0,0 +1,51 @@
+use chrono::{Local, Utc};
+use eframe::egui;
+use logic::ProjectContainer;
+
+use crate::ProjectApp;
+
+pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
+    egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+        let Some(project) = app
+            .selected_project_id
+            .and_then(|id| app.container.get_project(&id))
+        else {
+            ui.label("Проект не выбран");
+            return;
+        };
+        let now = Utc::now();
+        let performance = project.schedule_performance(now, &project.calendar);
+        let flagged = project
+            .tasks
+            .values()
+            .filter(|t| !t.is_summary)
+            .filter(|t| {
+                t.schedule_performance(now, &project.calendar)
+                    .needs_attention()
+            })
+            .count();
+
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "Прошло {:.0}% срока, выполнено {:.0}% (индекс {:.2})",
+                performance.time_elapsed * 100.0,
+                performance.progress * 100.0,
+                performance.index
+            ));
+            if flagged > 0 {
+                ui.separator();
+                ui.colored_label(
+                    egui::Color32::RED,
+                    format!("⚠ Отстают от графика: {}", flagged),
+                );
+            }
+            if let Some(saved_at) = app.last_saved {
+                ui.separator();
+                ui.label(format!(
+                    "Сохранено {}",
+                    saved_at.with_timezone(&Local).format("%H:%M:%S")
+                ));
+            }
+        });
+    });
+}
@@ -0,0 +1,55 @@
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+
+use crate::ProjectApp;
+
+/// Сколько показывается всплывающее сообщение
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+
+/// Всплывающее сообщение в правом нижнем углу окна, пропадает само
+pub struct Toast {
+    text: String,
+    is_error: bool,
+    shown_at: Instant,
+}
+
+impl Toast {
+    pub fn info(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            is_error: false,
+            shown_at: Instant::now(),
+        }
+    }
+
+    pub fn error(text: impl Into<String>) -> Self {
+        Self {
+            is_error: true,
+            ..Self::info(text)
+        }
+    }
+}
+
+pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
+    let Some(toast) = &app.toast else {
+        return;
+    };
+    let elapsed = toast.shown_at.elapsed();
+    if elapsed >= TOAST_DURATION {
+        app.toast = None;
+        return;
+    }
+    egui::Area::new(egui::Id::new("toast"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, [-12.0, -36.0])
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                if toast.is_error {
+                    ui.colored_label(ui.visuals().error_fg_color, &toast.text);
+                } else {
+                    ui.label(&toast.text);
+                }
+            });
+        });
+    ctx.request_repaint_after(TOAST_DURATION - elapsed);
+}
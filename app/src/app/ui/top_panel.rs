@@ -9,6 +9,10 @@ pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
                 app.show_new_project_dialog = true;
                 ui.close()
             }
+            if ui.button("🎓 Демо-проект").clicked() {
+                app.create_demo_project();
+                ui.close();
+            }
             if ui.button("Закрыть проект").clicked() {
                 app.show_close_project_dialog = true;
                 ui.close();
@@ -22,6 +26,10 @@ pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
                 app.save_project();
                 ui.close();
             }
+            if ui.button(" ⇄ Слить с файлом").clicked() {
+                app.start_merge();
+                ui.close();
+            }
 
             ui.menu_button("Отображение", |ui| {
                 if ui.button("☀️ Светлая тема").clicked() {
@@ -34,6 +42,18 @@ pub fn show(ctx: &egui::Context, app: &mut ProjectApp) {
                 }
             });
 
+            if ui.button("⚙ Настройки").clicked() {
+                app.show_settings_dialog = true;
+                ui.close();
+            }
+
+            ui.menu_button("Экспорт", |ui| {
+                let redaction = &mut app.export_redaction;
+                ui.checkbox(&mut redaction.hide_rates, "Скрывать ставки");
+                ui.checkbox(&mut redaction.hide_costs, "Скрывать стоимость");
+                ui.checkbox(&mut redaction.hide_resource_names, "Инициалы вместо имен");
+            });
+
             ui.separator();
             if ui.button("Выход").clicked() {
                 std::process::exit(0)
@@ -1,3 +1,4 @@
+pub mod calendar;
 pub mod gantt;
 pub mod project;
 pub mod resources;
@@ -9,4 +10,5 @@ pub enum View {
     Tasks,
     Resources,
     Gantt,
+    Calendar,
 }
@@ -0,0 +1,131 @@
+use std::collections::HashSet;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use eframe::egui::{self, Ui};
+use logic::{BasicGettersForStructures, CalendarService, ProjectContainer, TimeWindow};
+
+use crate::{ProjectApp, app::ui::date_input::date_input};
+
+const WEEKDAYS: [(Weekday, &str); 7] = [
+    (Weekday::Mon, "Пн"),
+    (Weekday::Tue, "Вт"),
+    (Weekday::Wed, "Ср"),
+    (Weekday::Thu, "Чт"),
+    (Weekday::Fri, "Пт"),
+    (Weekday::Sat, "Сб"),
+    (Weekday::Sun, "Вс"),
+];
+
+/// Изменение календаря, выбранное на этом кадре
+enum CalendarEdit {
+    WorkingDays(HashSet<Weekday>),
+    WeekdayHours(Weekday, u32),
+    AddHoliday(NaiveDate),
+    RemoveHoliday(NaiveDate),
+}
+
+pub fn show(ui: &mut Ui, app: &mut ProjectApp) {
+    ui.heading("🗓 Календарь проекта");
+    ui.separator();
+
+    let Some(project_id) = app.selected_project_id else {
+        return;
+    };
+    let Some(project) = app.container.get_project(&project_id) else {
+        return;
+    };
+    let window = TimeWindow {
+        date_start: *project.get_date_start(),
+        date_end: *project.get_date_end(),
+    };
+    let Some(calendar) = app.container.calendar(&project_id).cloned() else {
+        return;
+    };
+
+    let mut edit = None;
+    let mut working_days = calendar.get_working_days();
+
+    ui.group(|ui| {
+        ui.set_width(ui.available_width());
+        ui.heading("📅 Рабочая неделя");
+        // Часы задаются для каждого дня отдельно: сокращенная пятница не сбрасывается
+        egui::Grid::new("working_week_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (weekday, label) in WEEKDAYS {
+                    let mut checked = working_days.contains(&weekday);
+                    if ui.checkbox(&mut checked, label).changed() {
+                        if checked {
+                            working_days.insert(weekday);
+                        } else {
+                            working_days.remove(&weekday);
+                        }
+                        edit = Some(CalendarEdit::WorkingDays(working_days.clone()));
+                    }
+                    let mut hours = calendar.hours_for(weekday);
+                    if ui
+                        .add_enabled(
+                            checked,
+                            egui::DragValue::new(&mut hours).range(1..=24).suffix(" ч"),
+                        )
+                        .changed()
+                    {
+                        edit = Some(CalendarEdit::WeekdayHours(weekday, hours));
+                    }
+                    ui.end_row();
+                }
+            });
+        ui.label(
+            egui::RichText::new(format!(
+                "Рабочих дней в датах проекта: {}",
+                calendar.count_working_days(&window)
+            ))
+            .strong(),
+        );
+    });
+
+    ui.add_space(8.0);
+    ui.group(|ui| {
+        ui.set_width(ui.available_width());
+        ui.heading("🎉 Праздники");
+        ui.horizontal(|ui| {
+            date_input(ui, "new_holiday_picker", &mut app.new_holiday, &calendar);
+            if ui.button("➕ Добавить").clicked() {
+                edit = Some(CalendarEdit::AddHoliday(app.new_holiday));
+            }
+        });
+        let holidays = calendar.holidays();
+        if holidays.is_empty() {
+            ui.weak("Праздников нет");
+        }
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("holidays_grid")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for date in holidays {
+                        ui.label(date.format("%d.%m.%Y").to_string());
+                        ui.weak(WEEKDAYS[date.weekday().num_days_from_monday() as usize].1);
+                        if ui.small_button("🗑").on_hover_text("Убрать").clicked() {
+                            edit = Some(CalendarEdit::RemoveHoliday(date));
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+    });
+
+    let Some(edit) = edit else {
+        return;
+    };
+    let mut service = CalendarService::new(&mut app.container);
+    let result = match edit {
+        CalendarEdit::WorkingDays(days) => service.set_working_days(project_id, days),
+        CalendarEdit::WeekdayHours(weekday, hours) => {
+            service.set_hours_for(project_id, weekday, hours)
+        }
+        CalendarEdit::AddHoliday(date) => service.add_holiday(project_id, date),
+        CalendarEdit::RemoveHoliday(date) => service.remove_holiday(project_id, date),
+    };
+    app.error_message = result.err().map(|e| e.to_string());
+}
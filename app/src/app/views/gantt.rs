@@ -210,7 +210,7 @@ fn collect_gantt_data(
         tasks_data.insert(
             *task.get_id(),
             (
-                task.name.clone(),
+                task.get_display_name(),
                 *task.get_date_start(),
                 *task.get_date_end(),
                 task.is_summary,
@@ -1,6 +1,7 @@
 use crate::ProjectApp;
+use chrono::Utc;
 use eframe::egui::{self, Ui};
-use logic::{BasicGettersForStructures, ProjectContainer, TaskService};
+use logic::{BasicGettersForStructures, ProjectContainer, ProjectService, TaskService};
 
 pub fn show(ui: &mut Ui, app: &mut ProjectApp) {
     ui.horizontal(|ui| {
@@ -11,21 +12,41 @@ pub fn show(ui: &mut Ui, app: &mut ProjectApp) {
     });
     ui.separator();
 
-    let resources_count = app.container.resource_pool().get_resources().len();
+    let resources_count = app.container.resource_pool().resource_count();
     let project_id = *app.selected_project_id.as_ref().unwrap();
-    let (regular_count, summary_count, total_cost, full_time) = {
+    let (regular_count, summary_count, full_time) = {
         let task_service = TaskService::new(&mut app.container);
         let all_tasks = task_service.get_all_tasks(project_id);
         let regular = all_tasks.iter().filter(|t| !t.is_summary).count();
         let summary = all_tasks.iter().filter(|t| t.is_summary).count();
-        let cost = task_service
-            .calculate_project_cost(project_id)
-            .unwrap_or(0.0);
         let full_time = task_service
             .calculate_project_time(project_id)
             .unwrap_or(0.0);
-        (regular, summary, cost, full_time)
+        (regular, summary, full_time)
+    };
+    // Суммы в разных валютах не складываются: по строке на валюту
+    let total_cost = {
+        let project = app.container.get_project(&project_id).unwrap();
+        let calendar = app.container.calendar(&project_id).unwrap();
+        let mut costs: Vec<(String, f64)> = project
+            .total_cost(calendar, app.container.resource_pool())
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        costs.sort_by(|a, b| a.0.cmp(&b.0));
+        if costs.is_empty() {
+            "0.00".to_string()
+        } else {
+            costs
+                .iter()
+                .map(|(currency, cost)| format!("{:.2} {}", cost, currency))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
     };
+    let progress = ProjectService::new(&mut app.container)
+        .progress(project_id, Utc::now())
+        .ok();
 
     if app.selected_project_id.is_some() {
         let project = app.container.get_project(&project_id).unwrap();
@@ -49,6 +70,19 @@ pub fn show(ui: &mut Ui, app: &mut ProjectApp) {
                         ui.label("📅 Дата окончания:");
                         ui.label(project.get_date_end().format("%Y-%m-%d").to_string());
                         ui.end_row();
+                        ui.label("👤 Ответственный:");
+                        ui.label(project.get_owner().unwrap_or("—"));
+                        ui.end_row();
+                        ui.label("🚦 Статус:");
+                        ui.label(format!("{:?}", project.get_status()));
+                        ui.end_row();
+                        ui.label("🏷 Теги:");
+                        ui.label(if project.get_tags().is_empty() {
+                            "—".to_string()
+                        } else {
+                            project.get_tags().join(", ")
+                        });
+                        ui.end_row();
                     });
             });
 
@@ -74,7 +108,7 @@ pub fn show(ui: &mut Ui, app: &mut ProjectApp) {
                         ui.end_row();
                         ui.label("💰 Общая стоимость:");
                         ui.label(
-                            egui::RichText::new(format!("{:.2}", total_cost))
+                            egui::RichText::new(&total_cost)
                                 .color(egui::Color32::DARK_GREEN)
                                 .strong(),
                         );
@@ -86,6 +120,23 @@ pub fn show(ui: &mut Ui, app: &mut ProjectApp) {
                                 .strong(),
                         );
                         ui.end_row();
+                        if let Some(progress) = progress {
+                            ui.label("✅ Выполнено:");
+                            let color = if progress.is_behind() {
+                                egui::Color32::RED
+                            } else {
+                                egui::Color32::DARK_GREEN
+                            };
+                            ui.label(
+                                egui::RichText::new(format!("{:.0}%", progress.completed * 100.0))
+                                    .color(color)
+                                    .strong(),
+                            );
+                            ui.end_row();
+                            ui.label("📈 По графику:");
+                            ui.label(format!("{:.0}%", progress.expected * 100.0));
+                            ui.end_row();
+                        }
                     });
             });
         });
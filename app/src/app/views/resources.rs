@@ -1,7 +1,7 @@
 use crate::ProjectApp;
 use eframe::egui::{self, Ui};
 use egui_extras::{Column, TableBuilder};
-use logic::{ProjectContainer, RateMeasure, ResourceService};
+use logic::{ProjectContainer, RateMeasure, ResourceQuery, ResourceService, ResourceSort};
 use uuid::Uuid;
 
 // Структура для хранения данных ресурса для отображения
@@ -17,9 +17,23 @@ struct ResourceViewData {
 pub fn show(ui: &mut Ui, app: &mut ProjectApp) {
     ui.heading("Ресурсы");
 
-    if ui.button("➕ Добавить ресурс").clicked() {
-        app.show_new_resource_dialog = true;
-    }
+    ui.horizontal(|ui| {
+        if ui.button("➕ Добавить ресурс").clicked() {
+            app.show_new_resource_dialog = true;
+        }
+        egui::ComboBox::from_label("Сортировка")
+            .selected_text(sort_label(&app.resource_sort))
+            .show_ui(ui, |ui| {
+                for sort in [
+                    ResourceSort::ByName,
+                    ResourceSort::ByRate,
+                    ResourceSort::ByUtilization(None),
+                ] {
+                    let label = sort_label(&sort);
+                    ui.selectable_value(&mut app.resource_sort, sort, label);
+                }
+            });
+    });
     ui.separator();
 
     if app.container.list_projects().is_empty() {
@@ -30,15 +44,14 @@ pub fn show(ui: &mut Ui, app: &mut ProjectApp) {
     // Собираем все данные для отображения, копируя нужные поля
     let resources_data = {
         let resource_service = ResourceService::new(&mut app.container);
-        let resources = resource_service.list_resources();
-        let mut data = Vec::with_capacity(resources.len());
-        for resource in resources {
-            let utilization = resource_service
-                .calculate_resource_utilization(
-                    resource.id,
-                    app.selected_project_id.expect("Не выбран проект"),
-                )
-                .unwrap_or(0.0);
+        let page = resource_service.query_resources(&ResourceQuery {
+            sort: app.resource_sort,
+            ..Default::default()
+        });
+        let mut data = Vec::with_capacity(page.rows.len());
+        for row in page.rows {
+            let resource = row.resource;
+            let utilization = row.utilization;
 
             let unavail_count = resource.get_unavailable_periods().len();
             data.push(ResourceViewData {
@@ -53,6 +66,21 @@ pub fn show(ui: &mut Ui, app: &mut ProjectApp) {
         data
     }; // resource_service уничтожен, данные скопированы
 
+    // Перегрузки могли появиться до проверки при назначении или из загруженного файла
+    let conflicts = app.container.resource_pool().find_conflicts();
+    for conflict in &conflicts {
+        ui.colored_label(
+            egui::Color32::RED,
+            format!(
+                "⚠ {} загружен на {:.0}% с {} по {}",
+                conflict.resource_name,
+                conflict.total_engagement * 100.0,
+                conflict.window.date_start.format("%d.%m.%Y"),
+                conflict.window.date_end.format("%d.%m.%Y")
+            ),
+        );
+    }
+
     if resources_data.is_empty() {
         ui.label("Ресурсов не создано. Нажмите 'Добавить ресурс' для создания.");
         return;
@@ -102,7 +130,13 @@ pub fn show(ui: &mut Ui, app: &mut ProjectApp) {
                     ui.label(format!("{:?}", data.rate_measure));
                 });
                 row.col(|ui| {
-                    ui.label(format!("{:.1}%", data.utilization * 100.0));
+                    let text = format!("{:.1}%", data.utilization * 100.0);
+                    // Перегруженный ресурс подсвечиваем красным
+                    if data.utilization > 1.0 {
+                        ui.colored_label(egui::Color32::RED, text);
+                    } else {
+                        ui.label(text);
+                    }
                 });
                 row.col(|ui| {
                     if data.unavail_count > 0 {
@@ -113,6 +147,13 @@ pub fn show(ui: &mut Ui, app: &mut ProjectApp) {
                 });
                 row.col(|ui| {
                     ui.horizontal(|ui| {
+                        if ui
+                            .button("󰀔")
+                            .on_hover_text("Назначить на задачу")
+                            .clicked()
+                        {
+                            app.open_assign_resource_dialog(data.id);
+                        }
                         if ui.button("").clicked() {
                             app.selected_resource_id = Some(data.id);
                             app.show_unavailable_period_dialog = true;
@@ -132,3 +173,11 @@ pub fn show(ui: &mut Ui, app: &mut ProjectApp) {
             });
         });
 }
+
+fn sort_label(sort: &ResourceSort) -> &'static str {
+    match sort {
+        ResourceSort::ByName => "По имени",
+        ResourceSort::ByRate => "По ставке",
+        ResourceSort::ByUtilization(_) => "По загрузке",
+    }
+}
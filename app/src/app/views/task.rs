@@ -2,13 +2,14 @@ use crate::ProjectApp;
 use chrono::{DateTime, Utc};
 use eframe::egui::{self, Ui};
 use egui_extras::{Column, TableBuilder};
-use logic::{BasicGettersForStructures, DependencyType, ProjectContainer, TaskService};
+use logic::{BasicGettersForStructures, DependencyType, ProjectContainer, TaskService, TaskStatus};
 use std::collections::HashMap;
 use uuid::Uuid;
 
 // Структура для хранения данных задачи, необходимых для отрисовки
 struct TaskViewData {
     id: Uuid,
+    code: String,
     name: String,
     start_date: DateTime<Utc>,
     end_date: DateTime<Utc>,
@@ -58,6 +59,7 @@ pub fn show(ui: &mut Ui, app: &mut ProjectApp) {
             }
             let data = TaskViewData {
                 id: *task.get_id(),
+                code: task.get_code().unwrap_or_default(),
                 name: task.name.clone(),
                 start_date: *task.get_date_start(),
                 end_date: *task.get_date_end(),
@@ -128,6 +130,152 @@ pub fn show(ui: &mut Ui, app: &mut ProjectApp) {
         return;
     }
 
+    // Выделенные строки копируем в буфер обмена в порядке отображения
+    ui.horizontal(|ui| {
+        let selected_count = app.selected_task_rows.len();
+        if ui
+            .add_enabled(selected_count > 0, egui::Button::new("📋 Копировать"))
+            .on_hover_text("Скопировать выделенные задачи как TSV (Ctrl+клик - выделить несколько)")
+            .clicked()
+        {
+            let ordered_ids: Vec<Uuid> = flat_tasks
+                .iter()
+                .map(|t| t.id)
+                .filter(|id| app.selected_task_rows.contains(id))
+                .collect();
+            match logic::export::tasks_tsv(
+                &app.container,
+                &project_id,
+                &ordered_ids,
+                &app.export_redaction,
+                logic::export::ExportLocale::default(),
+            ) {
+                Ok(tsv) => ui.ctx().copy_text(tsv),
+                Err(e) => app.error_message = Some(e.to_string()),
+            }
+        }
+        if selected_count > 0 {
+            ui.label(format!("Выделено: {}", selected_count));
+        }
+        if app.bulk_undo.is_some()
+            && ui
+                .button("↶ Отменить")
+                .on_hover_text("Отменить последнюю массовую операцию")
+                .clicked()
+        {
+            app.undo_bulk_operation();
+        }
+        if ui
+            .button("⏩ Пересчитать сроки")
+            .on_hover_text("Сдвинуть задачи вперед по зависимостям")
+            .clicked()
+            && let Err(e) = TaskService::new(&mut app.container).reschedule_forward(project_id)
+        {
+            app.error_message = Some(e.to_string());
+        }
+    });
+
+    // Массовые операции над несколькими выделенными задачами
+    if app.selected_task_rows.len() > 1 {
+        ui.horizontal(|ui| {
+            ui.label("С выделенными:");
+            egui::ComboBox::from_id_salt("bulk_status")
+                .selected_text(format!("{:?}", app.bulk_status))
+                .show_ui(ui, |ui| {
+                    for status in [
+                        TaskStatus::New,
+                        TaskStatus::Wait,
+                        TaskStatus::Processed,
+                        TaskStatus::Complete,
+                        TaskStatus::Rejected,
+                        TaskStatus::Closed,
+                    ] {
+                        let label = format!("{:?}", status);
+                        ui.selectable_value(&mut app.bulk_status, status, label);
+                    }
+                });
+            if ui.button("Задать статус").clicked() {
+                let status = app.bulk_status.clone();
+                app.run_bulk_operation(|service, project_id, ids| {
+                    service.bulk_set_status(project_id, ids, status)
+                });
+            }
+            ui.separator();
+
+            ui.add(
+                egui::DragValue::new(&mut app.bulk_shift_days)
+                    .range(-365..=365)
+                    .suffix(" раб. дн."),
+            );
+            if ui.button("Сдвинуть").clicked() {
+                let days = app.bulk_shift_days;
+                app.run_bulk_operation(|service, project_id, ids| {
+                    service.bulk_shift_working_days(project_id, ids, days)
+                });
+            }
+            ui.separator();
+
+            let phase_name = |id: Option<Uuid>| {
+                id.and_then(|id| flat_tasks.iter().find(|t| t.id == id))
+                    .map(|t| t.name.clone())
+                    .unwrap_or_else(|| "Без этапа".to_string())
+            };
+            egui::ComboBox::from_id_salt("bulk_parent")
+                .selected_text(phase_name(app.bulk_parent_id))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut app.bulk_parent_id, None, "Без этапа");
+                    for summary in flat_tasks.iter().filter(|t| t.is_summary) {
+                        ui.selectable_value(
+                            &mut app.bulk_parent_id,
+                            Some(summary.id),
+                            &summary.name,
+                        );
+                    }
+                });
+            if ui.button("В этап").clicked() {
+                let parent_id = app.bulk_parent_id;
+                app.run_bulk_operation(|service, project_id, ids| {
+                    service.bulk_set_parent(project_id, ids, parent_id)
+                });
+            }
+            ui.separator();
+
+            ui.add(egui::DragValue::new(&mut app.bulk_priority).prefix("Приоритет: "));
+            if ui.button("Задать приоритет").clicked() {
+                let priority = app.bulk_priority;
+                app.run_bulk_operation(|service, project_id, ids| {
+                    service.bulk_set_priority(project_id, ids, priority)
+                });
+            }
+            ui.separator();
+
+            ui.add(
+                egui::TextEdit::singleline(&mut app.bulk_tag)
+                    .hint_text("Тег")
+                    .desired_width(80.0),
+            );
+            if ui.button("+ Тег").clicked() {
+                let tag = app.bulk_tag.clone();
+                app.run_bulk_operation(|service, project_id, ids| {
+                    service.bulk_add_tag(project_id, ids, &tag)
+                });
+            }
+            if ui.button("- Тег").clicked() {
+                let tag = app.bulk_tag.clone();
+                app.run_bulk_operation(|service, project_id, ids| {
+                    service.bulk_remove_tag(project_id, ids, &tag)
+                });
+            }
+            ui.separator();
+
+            if ui.button("󰩺 Удалить").clicked() {
+                app.run_bulk_operation(|service, project_id, ids| {
+                    service.bulk_delete(project_id, ids)
+                });
+            }
+        });
+    }
+
     // ---- Отрисовка таблицы с фиксированными колонками ----
     TableBuilder::new(ui)
         .striped(true)
@@ -165,15 +313,50 @@ pub fn show(ui: &mut Ui, app: &mut ProjectApp) {
         })
         .body(|body| {
             body.rows(22.0, flat_tasks.len(), |mut row| {
-                let task = &flat_tasks[row.index()];
+                let row_index = row.index();
+                let task = &flat_tasks[row_index];
+                let is_selected = app.selected_task_rows.contains(&task.id);
                 row.set_overline(task.is_summary);
+                row.set_selected(is_selected);
                 row.col(|ui| {
                     ui.horizontal(|ui| {
                         ui.add_space(task.depth as f32 * 20.0);
-                        if task.is_summary {
-                            ui.colored_label(egui::Color32::PURPLE, &task.name);
+                        if !task.code.is_empty() {
+                            ui.weak(&task.code);
+                        }
+                        let text = if task.is_summary {
+                            egui::RichText::new(&task.name).color(egui::Color32::PURPLE)
                         } else {
-                            ui.label(&task.name);
+                            egui::RichText::new(&task.name)
+                        };
+                        if ui.selectable_label(is_selected, text).clicked() {
+                            // Ctrl/Cmd+клик добавляет или убирает строку, Shift+клик выделяет
+                            // диапазон от предыдущего клика, обычный клик выделяет одну
+                            let modifiers = ui.input(|i| i.modifiers);
+                            let anchor = app
+                                .task_selection_anchor
+                                .and_then(|id| flat_tasks.iter().position(|t| t.id == id));
+                            if let (true, Some(anchor)) = (modifiers.shift, anchor) {
+                                let (from, to) = if anchor <= row_index {
+                                    (anchor, row_index)
+                                } else {
+                                    (row_index, anchor)
+                                };
+                                if !modifiers.command {
+                                    app.selected_task_rows.clear();
+                                }
+                                app.selected_task_rows
+                                    .extend(flat_tasks[from..=to].iter().map(|t| t.id));
+                            } else if modifiers.command {
+                                if !app.selected_task_rows.remove(&task.id) {
+                                    app.selected_task_rows.insert(task.id);
+                                }
+                                app.task_selection_anchor = Some(task.id);
+                            } else {
+                                app.selected_task_rows.clear();
+                                app.selected_task_rows.insert(task.id);
+                                app.task_selection_anchor = Some(task.id);
+                            }
                         }
                     });
                 });
@@ -207,8 +390,8 @@ pub fn show(ui: &mut Ui, app: &mut ProjectApp) {
                     if !task.is_summary {
                         if ui.button("󰀔").clicked() {
                             app.selected_task_id = Some(task.id);
-                            app.assign_custom_start = task.start_date.date_naive();
-                            app.assign_custom_end = task.end_date.date_naive();
+                            app.assign_custom_start = app.input_date(task.start_date);
+                            app.assign_custom_end = app.input_date(task.end_date);
                             app.show_assign_resource_dialog = true;
                         }
                     } else {
@@ -4,6 +4,19 @@ pub use app::ProjectApp;
 use eframe::egui;
 
 fn main() -> eframe::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let verbosity = app::cli::verbosity(&args);
+    let command = app::cli::parse(&args);
+    let headless = !matches!(command, Ok(app::cli::Command::Gui));
+    let _log_guard = app::logging::init(verbosity, headless && verbosity > 0);
+    match command {
+        Ok(app::cli::Command::Gui) => {}
+        Ok(command) => std::process::exit(app::cli::run(command)),
+        Err(e) => {
+            eprintln!("{}\n{}", e, app::cli::USAGE);
+            std::process::exit(2);
+        }
+    }
     // Большее и удобное стартовое окно приложения
     let options = eframe::NativeOptions {
         viewport: eframe::egui::ViewportBuilder::default()
@@ -13,7 +26,7 @@ fn main() -> eframe::Result<()> {
     };
 
     eframe::run_native(
-        "Project Manager",
+        app::logging::APP_ID,
         options,
         Box::new(|cc| {
             let mut fonts = egui::FontDefinitions::default();
@@ -34,7 +47,7 @@ fn main() -> eframe::Result<()> {
                 .or_default()
                 .insert(0, "FiraCodeNerd".to_owned());
             cc.egui_ctx.set_fonts(fonts);
-            Ok(Box::new(ProjectApp::default()))
+            Ok(Box::new(ProjectApp::new(cc)))
         }),
     )
 }
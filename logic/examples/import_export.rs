@@ -0,0 +1,43 @@
+//! Импорт задач из CSV, выгрузка проекта в CSV и сохранение контейнера в JSON
+//!
+//! Запуск: `cargo run -p logic --example import_export`
+use chrono::{TimeZone, Utc};
+use logic::{
+    BasicGettersForStructures, Project, ProjectContainer, SingleProjectContainer, TaskService,
+    export::ExportRedaction,
+};
+
+const TASKS_CSV: &str = "name,start,end
+Analysis,2026-02-02,2026-02-06
+Build,2026-02-09,2026-02-20
+Release,2026-02-23,2026-02-24
+";
+
+fn main() -> anyhow::Result<()> {
+    let mut container = SingleProjectContainer::new();
+    let project = Project::new(
+        "Imported",
+        "From CSV",
+        Utc.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2026, 3, 31, 0, 0, 0).unwrap(),
+    )?;
+    let project_id = *project.get_id();
+    container.add_project(project)?;
+
+    let imported = TaskService::new(&mut container).import_tasks_csv(project_id, TASKS_CSV)?;
+    println!("Imported {} tasks", imported.len());
+
+    let project = container.get_project(&project_id).unwrap();
+    print!(
+        "{}",
+        project.export_tasks_csv(container.resource_pool(), &ExportRedaction::default())
+    );
+
+    let json = serde_json::to_string_pretty(&container)?;
+    let loaded: SingleProjectContainer = serde_json::from_str(&json)?;
+    println!(
+        "Saved and loaded {} tasks",
+        loaded.get_project(&project_id).unwrap().tasks.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! Планирование проекта и отчет: задачи, ресурсы, стоимость и критический путь
+//!
+//! Запуск: `cargo run -p logic --example plan_and_report`
+use chrono::{TimeZone, Utc};
+use logic::{
+    BasicGettersForStructures, DependencyType, Project, ProjectContainer, RateMeasure,
+    ResourceService, Scheduler, SingleProjectContainer, TaskService,
+};
+
+fn main() -> anyhow::Result<()> {
+    let mut container = SingleProjectContainer::new();
+    let project = Project::new(
+        "Website",
+        "Relaunch",
+        Utc.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2026, 4, 30, 0, 0, 0).unwrap(),
+    )?;
+    let project_id = *project.get_id();
+    container.add_project(project)?;
+
+    let anna_id = {
+        let mut resource_service = ResourceService::new(&mut container);
+        let anna = resource_service.create_resource("Anna", 100.0, RateMeasure::Hourly)?;
+        let anna_id = anna.id;
+        resource_service.add_resource(anna)?;
+        anna_id
+    };
+
+    let mut task_service = TaskService::new(&mut container);
+    let design = task_service.create_regular_task(
+        project_id,
+        "Design".into(),
+        Utc.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2026, 2, 14, 0, 0, 0).unwrap(),
+        None,
+    )?;
+    let build = task_service.create_regular_task(
+        project_id,
+        "Build".into(),
+        Utc.with_ymd_and_hms(2026, 2, 16, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2026, 3, 14, 0, 0, 0).unwrap(),
+        None,
+    )?;
+    task_service.add_dependency(
+        project_id,
+        *build.get_id(),
+        *design.get_id(),
+        DependencyType::Blocking,
+        None,
+    )?;
+    task_service.allocate_resource(project_id, *design.get_id(), anna_id, 1.0, None, None)?;
+    task_service.allocate_resource(project_id, *build.get_id(), anna_id, 0.5, None, None)?;
+
+    println!(
+        "Project cost: {:.2}",
+        task_service.calculate_project_cost(project_id)?
+    );
+    let critical_path = Scheduler::new(&container).critical_path(project_id)?;
+    let project = container.get_project(&project_id).unwrap();
+    for task_id in critical_path {
+        println!("Critical: {}", project.tasks[&task_id].get_display_name());
+    }
+    Ok(())
+}
@@ -1,21 +1,65 @@
+mod availability;
+mod baseline;
+mod budget;
+mod calendar_diff;
+mod calendar_kind;
+mod currency;
 mod dependencies;
+mod earned_value;
+mod events;
+mod holiday_presets;
+mod id_generator;
+mod merge;
 mod project;
+mod project_builder;
 mod project_calendar;
 mod project_containers;
+mod project_stats;
 mod resource;
 mod resource_pool;
+mod role_placeholder;
+mod schedule_performance;
+mod shared_container;
 mod tasks;
 mod time_window;
 mod traits;
+mod validation_policy;
 
 pub use crate::cust_exceptions::ProjectCreationErrors;
-pub use time_window::TimeWindow;
+pub use time_window::{TimeWindow, merge_overlapping};
 
-pub use dependencies::{Dependency, DependencyType};
-pub use project::Project;
-pub use project_calendar::ProjectCalendar;
-pub use project_containers::SingleProjectContainer;
-pub use resource::{ExceptionPeriod, ExceptionType, RateMeasure, Resource};
-pub use resource_pool::AllocationRequest;
-pub use tasks::Task;
+pub use availability::{AvailabilityException, ExceptionType};
+pub use baseline::{BaselineVariance, TaskVariance};
+pub use budget::{BudgetPeriod, BudgetPeriodStatus, BudgetStatus};
+pub use calendar_diff::{CalendarDiff, MergePolicy};
+pub use calendar_kind::{CalendarKind, DayKind};
+pub use currency::{DEFAULT_CURRENCY, convert_costs, normalize_currency};
+pub use dependencies::{Dependency, DependencyType, DependencyViolation};
+pub use earned_value::EarnedValue;
+pub use events::ContainerEvent;
+pub use holiday_presets::CountryPreset;
+pub use id_generator::{IdGenerator, RandomIds, SequentialIds};
+pub use merge::{
+    DuplicateAllocation, MergePreview, MergeReport, MergeResolution, MergeResolutions,
+    MergeStrategy, NameCollision, WindowConflict,
+};
+pub use project::{Project, ProjectStatus, ProjectUpdate};
+pub use project_builder::ProjectBuilder;
+pub use project_calendar::{ProjectCalendar, SnapDirection};
+pub use project_containers::{
+    ArchiveFilter, CONTAINER_FILE_VERSION, MultiProjectContainer, ProjectFilter,
+    SingleProjectContainer,
+};
+pub use project_stats::{ProjectStats, ResourceCosts};
+pub use resource::{ExceptionPeriod, RateConfig, RateMeasure, Resource};
+pub(crate) use resource_pool::fits_capacity;
+pub use resource_pool::{
+    AllocationRequest, DEFAULT_ALLOCATION_PRIORITY, LocalResourcePool, ResourceAllocation,
+    ResourceConflict,
+};
+pub use role_placeholder::RolePlaceholder;
+pub use schedule_performance::{ProjectProgress, SchedulePerformance};
+pub use shared_container::SharedProjectContainer;
+pub use tasks::{DEFAULT_TASK_PRIORITY, Task, TaskStatus};
 pub use traits::{BasicGettersForStructures, ProjectContainer};
+pub use validation_policy::ValidationPolicy;
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use crate::base_structures::time_window::TimeWindow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ExceptionType {
+    Vacation,    // Полностью не работает
+    SickLeave,   // Не работает
+    PersonalDay, // Не работает
+    Training,    // Обучение: для планирования можно считать условно доступным
+    Overtime,    // Работает сверх нормы (можно указать часы)
+}
+
+/// Период недоступности: отпуск ресурса или закрытие офиса для всего календаря
+/// Имена полей старого `ExceptionPeriod` принимаются при чтении файлов
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AvailabilityException {
+    #[serde(alias = "period")]
+    pub window: TimeWindow,
+    #[serde(alias = "exception_type")]
+    pub kind: ExceptionType,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl AvailabilityException {
+    pub fn new(window: TimeWindow, kind: ExceptionType) -> Self {
+        Self {
+            window,
+            kind,
+            reason: None,
+        }
+    }
+
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+}
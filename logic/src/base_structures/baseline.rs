@@ -0,0 +1,21 @@
+use chrono::TimeDelta;
+use uuid::Uuid;
+
+/// Сдвиг задачи относительно базового плана, положительное значение - задача сдвинулась позже
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TaskVariance {
+    pub task_id: Uuid,
+    pub start_slip: TimeDelta,
+    pub end_slip: TimeDelta,
+}
+
+/// Отклонения проекта от базового плана
+/// shifted - задачи, у которых изменилось начало или окончание
+/// added - задачи, появившиеся после снятия базового плана
+/// removed - задачи базового плана, которых больше нет в проекте
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BaselineVariance {
+    pub shifted: Vec<TaskVariance>,
+    pub added: Vec<Uuid>,
+    pub removed: Vec<Uuid>,
+}
@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::base_structures::TimeWindow;
+
+/// Лимит затрат проекта на период (квартал, месяц и т.п.)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct BudgetPeriod {
+    pub window: TimeWindow,
+    pub amount: f64,
+}
+
+impl BudgetPeriod {
+    pub fn new(window: TimeWindow, amount: f64) -> Self {
+        Self { window, amount }
+    }
+}
+
+/// Плановые затраты периода против его лимита
+/// planned - стоимость назначений в валюте бюджета, приходящаяся на рабочие дни периода
+/// other_currencies - то же для ресурсов в других валютах, в лимит не входит
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetPeriodStatus {
+    pub window: TimeWindow,
+    pub budget: f64,
+    pub planned: f64,
+    pub other_currencies: HashMap<String, f64>,
+}
+
+impl BudgetPeriodStatus {
+    pub fn is_over_limit(&self) -> bool {
+        self.planned > self.budget
+    }
+}
+
+/// Бюджет проекта против стоимости всех его назначений
+/// committed_cost - стоимость назначений в валюте бюджета
+/// other_currencies - стоимость назначений ресурсов в других валютах, в бюджет не входит
+/// unpriced - назначения ресурсов, удаленных из пула: оценить их нельзя
+/// remaining/over_budget считаются только при заданном бюджете
+/// periods - плановые затраты по периодам бюджета
+/// periods_mismatch - сумма лимитов периодов минус бюджет, если они расходятся
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetStatus {
+    pub budget: Option<f64>,
+    pub currency: String,
+    pub committed_cost: f64,
+    pub remaining: Option<f64>,
+    pub over_budget: bool,
+    pub other_currencies: HashMap<String, f64>,
+    pub unpriced: Vec<Uuid>,
+    pub periods: Vec<BudgetPeriodStatus>,
+    pub periods_mismatch: Option<f64>,
+}
@@ -0,0 +1,33 @@
+use chrono::{NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Как сливать календарь с другим (например, мастер-календарем компании)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergePolicy {
+    /// Праздники объединяются, часы по дням недели остаются локальными
+    #[default]
+    Union,
+    /// При расхождениях берется вариант другого календаря: его праздники и часы дней недели
+    PreferOther,
+}
+
+/// Расхождения двух календарей (левый - `self`, правый - `other`)
+/// holidays_only_left/right - праздники только в одном календаре, по возрастанию
+/// working_days - дни недели, рабочие только в одном календаре: (день, часы слева, часы справа)
+/// hours - дни недели, рабочие в обоих, но с разным числом часов
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CalendarDiff {
+    pub holidays_only_left: Vec<NaiveDate>,
+    pub holidays_only_right: Vec<NaiveDate>,
+    pub working_days: Vec<(Weekday, u32, u32)>,
+    pub hours: Vec<(Weekday, u32, u32)>,
+}
+
+impl CalendarDiff {
+    pub fn is_empty(&self) -> bool {
+        self.holidays_only_left.is_empty()
+            && self.holidays_only_right.is_empty()
+            && self.working_days.is_empty()
+            && self.hours.is_empty()
+    }
+}
@@ -0,0 +1,42 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// День цикла сменного графика
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayKind {
+    /// Рабочая смена, часов
+    Work(u32),
+    Off,
+}
+
+impl DayKind {
+    pub fn hours(&self) -> u32 {
+        match self {
+            DayKind::Work(hours) => *hours,
+            DayKind::Off => 0,
+        }
+    }
+}
+
+/// Модель рабочих дней календаря
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub enum CalendarKind {
+    /// Часы по дням недели (пн-пт и т.п.)
+    #[default]
+    Weekly,
+    /// Сменный график: `cycle` повторяется, начиная с даты `anchor`
+    /// (например, 2 дня по 12 часов и 2 выходных)
+    ShiftPattern {
+        cycle: Vec<DayKind>,
+        anchor: NaiveDate,
+    },
+}
+
+impl CalendarKind {
+    /// Сменный график "N через M": `work` дней по `hours` часов, затем `off` выходных
+    pub fn shifts(work: usize, off: usize, hours: u32, anchor: NaiveDate) -> Self {
+        let mut cycle = vec![DayKind::Work(hours); work];
+        cycle.extend(vec![DayKind::Off; off]);
+        CalendarKind::ShiftPattern { cycle, anchor }
+    }
+}
@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+
+use crate::cust_exceptions::CurrencyErrors;
+
+/// Валюта ресурсов, созданных без явного указания и сохраненных до появления валют
+pub const DEFAULT_CURRENCY: &str = "RUB";
+
+pub(crate) fn default_currency() -> String {
+    DEFAULT_CURRENCY.to_string()
+}
+
+/// Код валюты по ISO-4217: три латинские буквы, приводится к верхнему регистру
+pub fn normalize_currency(code: &str) -> Result<String, CurrencyErrors> {
+    let code = code.trim();
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(CurrencyErrors::InvalidCode {
+            code: code.to_string(),
+        });
+    }
+    Ok(code.to_ascii_uppercase())
+}
+
+/// Свести затраты по валютам к базовой валюте
+/// rates - курс каждой валюты к базовой (для самой базовой валюты - 1.0)
+pub fn convert_costs(
+    costs: &HashMap<String, f64>,
+    rates: &HashMap<String, f64>,
+) -> Result<f64, CurrencyErrors> {
+    costs
+        .iter()
+        .map(|(currency, cost)| {
+            rates
+                .get(currency)
+                .map(|rate| cost * rate)
+                .ok_or_else(|| CurrencyErrors::MissingRate {
+                    currency: currency.clone(),
+                })
+        })
+        .sum()
+}
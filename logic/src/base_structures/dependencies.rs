@@ -36,4 +36,30 @@ impl Dependency {
             lag,
         }
     }
+
+    /// Лаг зависимости, отсутствующий лаг - ноль
+    pub fn lag_or_zero(&self) -> TimeDelta {
+        self.lag.unwrap_or_else(TimeDelta::zero)
+    }
+
+    /// Блокирующая зависимость жестко требует окончания предшественника до начала задачи,
+    /// неблокирующая - только рекомендация
+    pub fn is_blocking(&self) -> bool {
+        self.dependency_type == DependencyType::Blocking
+    }
+}
+
+/// Нарушение зависимости: задача начинается раньше окончания предшественника (с учетом lag)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyViolation {
+    pub task_id: Uuid,
+    pub dependency: Dependency,
+    /// На сколько начало задачи раньше допустимого
+    pub overlap: TimeDelta,
+}
+
+impl DependencyViolation {
+    pub fn is_blocking(&self) -> bool {
+        self.dependency.is_blocking()
+    }
 }
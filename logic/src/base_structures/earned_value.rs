@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Метрики освоенного объема на дату
+/// budget - плановая стоимость всех задач (BAC)
+/// planned_value - плановая стоимость работ, запланированных к дате (PV)
+/// earned_value - плановая стоимость выполненной работы (EV = budget * progress)
+/// actual_cost - фактические затраты (AC), пока по прошедшей части назначений
+/// spi - EV / PV, меньше 1 - отставание от графика
+/// cpi - EV / AC, меньше 1 - перерасход
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct EarnedValue {
+    pub budget: f64,
+    pub planned_value: f64,
+    pub earned_value: f64,
+    pub actual_cost: f64,
+    pub spi: f64,
+    pub cpi: f64,
+}
+
+impl EarnedValue {
+    pub fn new(budget: f64, planned_value: f64, earned_value: f64, actual_cost: f64) -> Self {
+        let ratio = |value: f64, base: f64| if base > 0.0 { value / base } else { 1.0 };
+        Self {
+            budget,
+            planned_value,
+            earned_value,
+            actual_cost,
+            spi: ratio(earned_value, planned_value),
+            cpi: ratio(earned_value, actual_cost),
+        }
+    }
+}
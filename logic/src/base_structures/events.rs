@@ -0,0 +1,51 @@
+use uuid::Uuid;
+
+/// Изменение модели в контейнере
+///
+/// События копятся в контейнере в порядке изменений и забираются через
+/// `ProjectContainer::take_events` (интерфейс, будущая отмена действий).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContainerEvent {
+    ProjectAdded {
+        project_id: Uuid,
+    },
+    ProjectUpdated {
+        project_id: Uuid,
+    },
+    ProjectRemoved {
+        project_id: Uuid,
+    },
+    TaskCreated {
+        project_id: Uuid,
+        task_id: Uuid,
+    },
+    TaskUpdated {
+        project_id: Uuid,
+        task_id: Uuid,
+    },
+    TaskDeleted {
+        project_id: Uuid,
+        task_id: Uuid,
+    },
+    ResourceAdded {
+        resource_id: Uuid,
+    },
+    ResourceUpdated {
+        resource_id: Uuid,
+    },
+    ResourceRemoved {
+        resource_id: Uuid,
+    },
+    AllocationCreated {
+        allocation_id: Uuid,
+        resource_id: Uuid,
+        task_id: Uuid,
+    },
+    /// Назначение переведено на другой ресурс или сдвинуто при выравнивании
+    AllocationUpdated {
+        allocation_id: Uuid,
+    },
+    AllocationRemoved {
+        allocation_id: Uuid,
+    },
+}
@@ -0,0 +1,52 @@
+use std::ops::RangeInclusive;
+
+use chrono::NaiveDate;
+
+/// Встроенные государственные праздники стран (только с фиксированной датой)
+///
+/// Праздники считаются в коде, без загрузки из сети. Переносы выходных и плавающие
+/// праздники не вычисляются и пропускаются:
+/// - RU: ежегодные переносы выходных дней по постановлению правительства;
+/// - US: Martin Luther King Jr. Day, Presidents' Day, Memorial Day, Labor Day,
+///   Columbus Day, Thanksgiving, а также перенос праздника с выходного на пятницу/понедельник.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountryPreset {
+    Ru,
+    Us,
+}
+
+impl CountryPreset {
+    /// Праздники с фиксированной датой (месяц, день)
+    fn fixed_holidays(&self) -> &'static [(u32, u32)] {
+        match self {
+            CountryPreset::Ru => &[
+                (1, 1),
+                (1, 2),
+                (1, 3),
+                (1, 4),
+                (1, 5),
+                (1, 6),
+                (1, 7),
+                (1, 8),
+                (2, 23),
+                (3, 8),
+                (5, 1),
+                (5, 9),
+                (6, 12),
+                (11, 4),
+            ],
+            CountryPreset::Us => &[(1, 1), (6, 19), (7, 4), (11, 11), (12, 25)],
+        }
+    }
+
+    /// Даты праздников за годы `years`
+    pub fn holidays(&self, years: RangeInclusive<i32>) -> Vec<NaiveDate> {
+        years
+            .flat_map(|year| {
+                self.fixed_holidays()
+                    .iter()
+                    .filter_map(move |(month, day)| NaiveDate::from_ymd_opt(year, *month, *day))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,110 @@
+use uuid::Uuid;
+
+/// Источник идентификаторов для новых задач, ресурсов и проектов
+pub trait IdGenerator {
+    fn next_id(&mut self) -> Uuid;
+}
+
+/// Случайные UUID v4 - поведение по умолчанию
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RandomIds;
+
+impl IdGenerator for RandomIds {
+    fn next_id(&mut self) -> Uuid {
+        Uuid::new_v4()
+    }
+}
+
+/// Предсказуемые id для тестов и сравнения с эталонными файлами:
+/// 00000000-0000-0000-0000-000000000001, ...-000000000002 и т.д.
+#[derive(Debug, Default, Clone)]
+pub struct SequentialIds {
+    last: u128,
+}
+
+impl SequentialIds {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Первый выданный id будет `first`
+    pub fn starting_at(first: u128) -> Self {
+        Self {
+            last: first.saturating_sub(1),
+        }
+    }
+}
+
+impl IdGenerator for SequentialIds {
+    fn next_id(&mut self) -> Uuid {
+        self.last += 1;
+        Uuid::from_u128(self.last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::base_structures::{
+        BasicGettersForStructures, Project, RateMeasure, Resource, Task, ValidationPolicy,
+    };
+
+    #[test]
+    fn test_sequential_ids_are_predictable() {
+        let mut ids = SequentialIds::new();
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+
+        let project = Project::new_with_ids(
+            "Golden",
+            "",
+            start,
+            end,
+            ValidationPolicy::default(),
+            &mut ids,
+        )
+        .unwrap();
+        let summary = Task::new_with_ids("Phase", start, end, None, true, &mut ids).unwrap();
+        let task = Task::new_with_ids(
+            "Build",
+            start,
+            end,
+            Some(*summary.get_id()),
+            false,
+            &mut ids,
+        )
+        .unwrap();
+        let resource =
+            Resource::new_with_ids("Dev".into(), 100.0, RateMeasure::Hourly, &mut ids).unwrap();
+
+        assert_eq!(*project.get_id(), Uuid::from_u128(1));
+        assert_eq!(*summary.get_id(), Uuid::from_u128(2));
+        assert_eq!(
+            task.get_id().to_string(),
+            "00000000-0000-0000-0000-000000000003"
+        );
+        assert_eq!(resource.id, Uuid::from_u128(4));
+
+        // Сериализация не зависит от запуска: тот же json при повторном создании
+        let json = serde_json::to_string(&task).unwrap();
+        let mut again = SequentialIds::starting_at(3);
+        let same = Task::new_with_ids(
+            "Build",
+            start,
+            end,
+            Some(Uuid::from_u128(2)),
+            false,
+            &mut again,
+        )
+        .unwrap();
+        assert_eq!(serde_json::to_string(&same).unwrap(), json);
+    }
+
+    #[test]
+    fn test_random_ids_differ() {
+        let mut ids = RandomIds;
+        assert_ne!(ids.next_id(), ids.next_id());
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::base_structures::CalendarDiff;
+
+/// Что делать, если имя задачи из присоединяемого проекта уже занято
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NameCollision {
+    /// Переименовать с суффиксом: "Build (2)"
+    #[default]
+    Rename,
+    Reject,
+}
+
+/// Что делать с задачами, выходящими за даты целевого проекта
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WindowConflict {
+    /// Расширить даты целевого проекта
+    #[default]
+    Expand,
+    Reject,
+}
+
+/// Что делать с назначением, на которое уже ссылается задача целевого проекта
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateAllocation {
+    /// Убрать повторную ссылку из присоединяемой задачи
+    #[default]
+    Skip,
+    Reject,
+}
+
+/// Правила разрешения конфликтов при слиянии проектов
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MergeStrategy {
+    pub task_names: NameCollision,
+    pub window: WindowConflict,
+    pub allocations: DuplicateAllocation,
+}
+
+/// Итог слияния проектов
+/// added_tasks - перенесенные задачи (id сохраняются)
+/// renamed_tasks - (задача, старое имя, новое имя)
+/// expanded_window - прежние даты целевого проекта, если они были расширены
+/// skipped_allocations - повторные ссылки на назначения, убранные из задач
+/// allocations - назначения перенесенных задач; в пуле они по-прежнему указывают
+/// на исходный проект
+/// При слиянии файла (`MergeService::merge_from`) дополнительно заполняются:
+/// skipped_tasks - задачи файла, которые не перенесены по решениям
+/// replaced_tasks - задачи целевого проекта, замененные задачами файла
+/// added_resources - ресурсы файла, добавленные в пул
+/// forced_allocations - назначения, перенесенные с перегрузкой ресурса
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeReport {
+    pub added_tasks: Vec<Uuid>,
+    pub renamed_tasks: Vec<(Uuid, String, String)>,
+    pub expanded_window: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    pub skipped_allocations: Vec<Uuid>,
+    pub allocations: Vec<Uuid>,
+    pub skipped_tasks: Vec<Uuid>,
+    pub replaced_tasks: Vec<Uuid>,
+    pub added_resources: Vec<Uuid>,
+    pub forced_allocations: Vec<Uuid>,
+}
+
+/// Решение по одному конфликту слияния
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeResolution {
+    KeepMine,
+    TakeTheirs,
+    Rename,
+    Skip,
+}
+
+impl MergeResolution {
+    pub const ALL: [MergeResolution; 4] = [
+        MergeResolution::KeepMine,
+        MergeResolution::TakeTheirs,
+        MergeResolution::Rename,
+        MergeResolution::Skip,
+    ];
+}
+
+/// Решения по конфликтам слияния файла, переопределяют поведение по умолчанию для
+/// отдельных сущностей. Тот же формат читает `rsproject merge --resolutions res.json`.
+///
+/// tasks - по имени задачи: Rename (по умолчанию) - задача файла переносится с суффиксом,
+/// TakeTheirs - моя задача заменяется задачей файла, KeepMine и Skip - задача файла
+/// не переносится.
+/// resources - по имени ресурса: KeepMine (по умолчанию) - назначения переходят на мой
+/// ресурс, TakeTheirs - мой ресурс получает данные ресурса из файла, Rename - ресурс файла
+/// добавляется отдельно с суффиксом, Skip - его назначения не переносятся.
+/// calendar - KeepMine и Skip (по умолчанию) - календарь не меняется, TakeTheirs - праздники
+/// и часы берутся из файла.
+/// overallocations - по имени ресурса: TakeTheirs - назначения переносятся с перегрузкой,
+/// KeepMine и Skip (по умолчанию) - перегружающие назначения не переносятся.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct MergeResolutions {
+    #[serde(default)]
+    pub tasks: BTreeMap<String, MergeResolution>,
+    #[serde(default)]
+    pub resources: BTreeMap<String, MergeResolution>,
+    #[serde(default)]
+    pub calendar: Option<MergeResolution>,
+    #[serde(default)]
+    pub overallocations: BTreeMap<String, MergeResolution>,
+}
+
+impl MergeResolutions {
+    pub fn task(&self, name: &str) -> MergeResolution {
+        self.tasks
+            .get(name)
+            .copied()
+            .unwrap_or(MergeResolution::Rename)
+    }
+
+    pub fn resource(&self, name: &str) -> MergeResolution {
+        self.resources
+            .get(name)
+            .copied()
+            .unwrap_or(MergeResolution::KeepMine)
+    }
+
+    pub fn calendar(&self) -> MergeResolution {
+        self.calendar.unwrap_or(MergeResolution::KeepMine)
+    }
+
+    pub fn overallocation(&self, name: &str) -> MergeResolution {
+        self.overallocations
+            .get(name)
+            .copied()
+            .unwrap_or(MergeResolution::KeepMine)
+    }
+}
+
+/// Конфликты слияния файла по видам, с учетом уже выбранных решений
+/// task_names - имена задач, занятые в обоих проектах
+/// resources - ресурсы файла, совпавшие по имени с ресурсами пула
+/// calendar - расхождения календарей (левый - мой, правый - из файла)
+/// overallocations - ресурсы, которые слияние перегрузит
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergePreview {
+    pub task_names: Vec<String>,
+    pub resources: Vec<String>,
+    pub calendar: CalendarDiff,
+    pub overallocations: Vec<String>,
+}
+
+impl MergePreview {
+    pub fn is_empty(&self) -> bool {
+        self.task_names.is_empty()
+            && self.resources.is_empty()
+            && self.calendar.is_empty()
+            && self.overallocations.is_empty()
+    }
+}
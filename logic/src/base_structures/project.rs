@@ -1,12 +1,84 @@
-use chrono::{DateTime, TimeDelta, Utc};
+use chrono::{DateTime, SecondsFormat, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
 use uuid::Uuid;
 
-use crate::base_structures::{
-    project_calendar::ProjectCalendar, tasks::Task, traits::BasicGettersForStructures,
+use crate::{
+    base_structures::{
+        BaselineVariance, BudgetPeriod, BudgetPeriodStatus, BudgetStatus, DependencyViolation,
+        DuplicateAllocation, EarnedValue, IdGenerator, MergeReport, MergeStrategy, NameCollision,
+        ProjectProgress, ProjectStats, RandomIds, RateConfig, ResourceCosts, RolePlaceholder,
+        SchedulePerformance, TaskVariance, TimeWindow, ValidationPolicy, WindowConflict,
+        currency::{default_currency, normalize_currency},
+        project_calendar::ProjectCalendar,
+        tasks::{Task, TaskStatus},
+        traits::{BasicGettersForStructures, ResourcePool},
+    },
+    cust_exceptions::{
+        AddTaskErrors, BudgetErrors, MergeErrors, ProjectCreationErrors, ProjectDefinitionErrors,
+        ProjectUpdateErrors, RolePlaceholderErrors,
+    },
+    export::ExportRedaction,
 };
 
+/// Изменения проекта, None - поле не меняется
+#[derive(Debug, Clone, Default)]
+pub struct ProjectUpdate {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub date_start: Option<DateTime<Utc>>,
+    pub date_end: Option<DateTime<Utc>>,
+}
+
+/// Статус проекта
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProjectStatus {
+    Draft,
+    #[default]
+    Active,
+    OnHold,
+    Done,
+    Cancelled,
+}
+
+impl ProjectStatus {
+    /// Допустимые переходы: Draft -> Active/Cancelled, Active -> OnHold/Done/Cancelled,
+    /// OnHold -> Active/Cancelled, Done -> Active (возобновление), из Cancelled переходов нет
+    pub fn can_transition_to(&self, next: &ProjectStatus) -> bool {
+        use ProjectStatus::*;
+        matches!(
+            (self, next),
+            (Draft, Active | Cancelled)
+                | (Active, OnHold | Done | Cancelled)
+                | (OnHold, Active | Cancelled)
+                | (Done, Active)
+        )
+    }
+}
+
+/// Проверки имени и дат проекта - одни и те же при создании и при изменении
+fn check_definition(
+    name: &str,
+    date_start: DateTime<Utc>,
+    date_end: DateTime<Utc>,
+    validation_policy: &ValidationPolicy,
+) -> Result<(), ProjectDefinitionErrors> {
+    if name.trim().is_empty() {
+        return Err(ProjectDefinitionErrors::EmptyProjectName);
+    }
+    if date_start >= date_end {
+        return Err(ProjectDefinitionErrors::InvalidProjectDuration {
+            date_start,
+            date_end,
+        });
+    }
+    validation_policy.check_project(date_start, date_end)?;
+    Ok(())
+}
+
 /// Структура Project - главная структура всего проекта
 /// Она хранит в себе все задачи и зависимости между ними
 
@@ -20,6 +92,39 @@ pub struct Project {
     pub date_end: DateTime<Utc>,
     pub duration: TimeDelta,
     pub tasks: HashMap<Uuid, Task>,
+    /// Ограничения на длительности проекта, задач и назначений
+    #[serde(default)]
+    pub validation_policy: ValidationPolicy,
+    /// Последний выданный номер задачи. Номера не переиспользуются даже после удаления задач
+    #[serde(default)]
+    last_task_number: u64,
+    /// Ответственный за проект
+    #[serde(default)]
+    owner: Option<String>,
+    #[serde(default)]
+    status: ProjectStatus,
+    #[serde(default)]
+    tags: Vec<String>,
+    /// Лимиты затрат по периодам, периоды не пересекаются и лежат внутри проекта
+    #[serde(default)]
+    budget_periods: Vec<BudgetPeriod>,
+    /// Роли, под которые ресурсы еще не подобраны
+    #[serde(default)]
+    role_placeholders: Vec<RolePlaceholder>,
+    /// Базовый план: даты задач на момент утверждения
+    #[serde(default)]
+    baseline: HashMap<Uuid, TimeWindow>,
+    /// Когда снят базовый план, None - план не снят (или файл до появления отметки)
+    #[serde(default)]
+    baseline_taken_at: Option<DateTime<Utc>>,
+    /// Коэффициенты пересчета ставок ресурсов для проекта
+    #[serde(default)]
+    pub rate_config: RateConfig,
+    /// Общий бюджет проекта, None - не задан
+    #[serde(default)]
+    budget: Option<f64>,
+    #[serde(default = "default_currency")]
+    budget_currency: String,
 }
 
 impl Project {
@@ -28,29 +133,1061 @@ impl Project {
         desc: impl Into<String>,
         start: DateTime<Utc>,
         end: DateTime<Utc>,
-    ) -> anyhow::Result<Self> {
-        if start > end {
-            return Err(anyhow::Error::msg(format!(
-                "Start date of project later than End Date: {}>{}",
-                start, end
-            )));
-        }
+    ) -> Result<Self, ProjectCreationErrors> {
+        Self::new_with_policy(name, desc, start, end, ValidationPolicy::default())
+    }
+
+    /// Создание проекта с собственными ограничениями длительностей
+    pub fn new_with_policy(
+        name: impl Into<String>,
+        desc: impl Into<String>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        validation_policy: ValidationPolicy,
+    ) -> Result<Self, ProjectCreationErrors> {
+        Self::new_with_ids(name, desc, start, end, validation_policy, &mut RandomIds)
+    }
+
+    /// Создание проекта с id из `ids` (например, SequentialIds в тестах)
+    pub fn new_with_ids(
+        name: impl Into<String>,
+        desc: impl Into<String>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        validation_policy: ValidationPolicy,
+        ids: &mut impl IdGenerator,
+    ) -> Result<Self, ProjectCreationErrors> {
+        let name = name.into();
+        check_definition(&name, start, end, &validation_policy)?;
 
         Ok(Self {
-            id: Uuid::new_v4(),
-            name: name.into(),
+            id: ids.next_id(),
+            name,
             description: desc.into(),
             date_start: start,
             date_end: end,
             duration: end - start,
             calendar: ProjectCalendar::default(),
             tasks: HashMap::new(),
+            validation_policy,
+            last_task_number: 0,
+            owner: None,
+            status: ProjectStatus::default(),
+            tags: vec![],
+            budget_periods: vec![],
+            role_placeholders: vec![],
+            baseline: HashMap::new(),
+            baseline_taken_at: None,
+            rate_config: RateConfig::default(),
+            budget: None,
+            budget_currency: default_currency(),
+        })
+    }
+
+    /// Копия проекта с новыми id проекта, задач и потребностей в ролях из `ids`
+    ///
+    /// Зависимости и родительские задачи переписываются на новые id, все даты сдвигаются
+    /// на `shift`, статусы задач сбрасываются в New. Назначения ресурсов не копируются:
+    /// аллокации в пуле привязаны к исходному проекту и назначаются заново.
+    /// Id выдаются по порядку: проект, задачи по номеру, потребности в ролях.
+    pub fn clone_with_new_ids(
+        &self,
+        new_name: String,
+        shift: Option<TimeDelta>,
+        ids: &mut impl IdGenerator,
+    ) -> Project {
+        let shift = shift.unwrap_or_else(TimeDelta::zero);
+        let id = ids.next_id();
+        let mut tasks: Vec<&Task> = self.tasks.values().collect();
+        tasks.sort_by_key(|task| (task.get_number(), *task.get_id()));
+        let id_map: HashMap<Uuid, Uuid> = tasks
+            .iter()
+            .map(|task| (*task.get_id(), ids.next_id()))
+            .collect();
+        Project {
+            id,
+            name: new_name,
+            description: self.description.clone(),
+            calendar: self.calendar.clone(),
+            date_start: self.date_start + shift,
+            date_end: self.date_end + shift,
+            duration: self.duration,
+            tasks: self
+                .tasks
+                .values()
+                .map(|task| {
+                    let copy = task.duplicate(&id_map, shift);
+                    (*copy.get_id(), copy)
+                })
+                .collect(),
+            validation_policy: self.validation_policy,
+            last_task_number: self.last_task_number,
+            owner: self.owner.clone(),
+            status: ProjectStatus::default(),
+            tags: self.tags.clone(),
+            budget_periods: self
+                .budget_periods
+                .iter()
+                .map(|period| BudgetPeriod {
+                    window: TimeWindow {
+                        date_start: period.window.date_start + shift,
+                        date_end: period.window.date_end + shift,
+                    },
+                    amount: period.amount,
+                })
+                .collect(),
+            role_placeholders: self
+                .role_placeholders
+                .iter()
+                .map(|placeholder| {
+                    RolePlaceholder::new_with_ids(
+                        placeholder.role.clone(),
+                        placeholder.engagement_rate,
+                        TimeWindow {
+                            date_start: placeholder.window.date_start + shift,
+                            date_end: placeholder.window.date_end + shift,
+                        },
+                        ids,
+                    )
+                })
+                .collect(),
+            baseline: HashMap::new(),
+            baseline_taken_at: None,
+            rate_config: self.rate_config,
+            budget: self.budget,
+            budget_currency: self.budget_currency.clone(),
+        }
+    }
+
+    pub fn get_owner(&self) -> Option<&str> {
+        self.owner.as_deref()
+    }
+
+    pub fn set_owner(&mut self, owner: Option<String>) {
+        self.owner = owner.filter(|o| !o.trim().is_empty());
+    }
+
+    pub fn get_status(&self) -> &ProjectStatus {
+        &self.status
+    }
+
+    /// Сменить статус проекта с проверкой перехода, тот же статус - без изменений
+    pub fn set_status(&mut self, status: ProjectStatus) -> anyhow::Result<()> {
+        if self.status == status {
+            return Ok(());
+        }
+        if !self.status.can_transition_to(&status) {
+            anyhow::bail!(
+                "Invalid project status transition: {:?} -> {:?}",
+                self.status,
+                status
+            );
+        }
+        self.status = status;
+        Ok(())
+    }
+
+    pub fn get_tags(&self) -> &Vec<String> {
+        &self.tags
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Добавить тег, пустые и повторные теги не добавляются
+    pub fn add_tag(&mut self, tag: impl Into<String>) {
+        let tag = tag.into().trim().to_string();
+        if !tag.is_empty() && !self.has_tag(&tag) {
+            self.tags.push(tag);
+        }
+    }
+
+    /// Убрать тег, false - такого тега не было
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let before = self.tags.len();
+        self.tags.retain(|t| t != tag);
+        self.tags.len() != before
+    }
+
+    /// Изменить имя, описание и даты проекта
+    ///
+    /// Новые даты должны вмещать окна всех задач проекта, иначе ничего не меняется
+    /// и возвращается ошибка со списком выходящих за границы задач.
+    pub fn update(&mut self, changes: ProjectUpdate) -> Result<(), ProjectUpdateErrors> {
+        let date_start = changes.date_start.unwrap_or(self.date_start);
+        let date_end = changes.date_end.unwrap_or(self.date_end);
+        let name = changes.name.as_deref().unwrap_or(&self.name);
+        check_definition(name, date_start, date_end, &self.validation_policy)?;
+        self.validate_window_against_tasks(date_start, date_end)
+            .map_err(|task_ids| ProjectUpdateErrors::TasksOutsideProject { task_ids })?;
+
+        if let Some(name) = changes.name {
+            self.name = name;
+        }
+        if let Some(description) = changes.description {
+            self.description = description;
+        }
+        self.date_start = date_start;
+        self.date_end = date_end;
+        self.duration = date_end - date_start;
+        Ok(())
+    }
+
+    /// Проверка нового окна проекта: id задач (по возрастанию), выходящих за окно.
+    /// Задача, лежащая ровно на границе окна, допустима
+    pub fn validate_window_against_tasks(
+        &self,
+        new_start: DateTime<Utc>,
+        new_end: DateTime<Utc>,
+    ) -> Result<(), Vec<Uuid>> {
+        let mut task_ids: Vec<Uuid> = self
+            .tasks
+            .values()
+            .filter(|t| t.date_start < new_start || t.date_end > new_end)
+            .map(|t| *t.get_id())
+            .collect();
+        if task_ids.is_empty() {
+            return Ok(());
+        }
+        task_ids.sort();
+        tracing::debug!(
+            project_id = %self.id,
+            tasks = task_ids.len(),
+            "project window rejected, tasks outside"
+        );
+        Err(task_ids)
+    }
+
+    /// Явное исправление: обрезать выходящие за окно проекта задачи по его границам
+    ///
+    /// Возвращает id обрезанных задач. Если какая-то задача целиком лежит вне окна (обрезать
+    /// нечего), проект не меняется, а в ошибке возвращаются такие задачи. Назначения ресурсов
+    /// на обрезанные задачи не пересчитываются.
+    pub fn clamp_tasks_to_window(&mut self) -> Result<Vec<Uuid>, Vec<Uuid>> {
+        let (window_start, window_end) = (self.date_start, self.date_end);
+        let Err(offending) = self.validate_window_against_tasks(window_start, window_end) else {
+            return Ok(vec![]);
+        };
+        let outside: Vec<Uuid> = offending
+            .iter()
+            .filter(|id| {
+                let task = &self.tasks[*id];
+                !task.is_summary && (task.date_end <= window_start || task.date_start >= window_end)
+            })
+            .copied()
+            .collect();
+        if !outside.is_empty() {
+            return Err(outside);
+        }
+        for task_id in &offending {
+            tracing::warn!(project_id = %self.id, %task_id, "task clamped to project window");
+            let task = self.tasks.get_mut(task_id).expect("task from this project");
+            task.date_start = task.date_start.clamp(window_start, window_end);
+            task.date_end = task.date_end.clamp(window_start, window_end);
+            if !task.is_summary {
+                task.duration = task.date_end - task.date_start;
+            }
+        }
+        Ok(offending)
+    }
+
+    /// Общее выполнение проекта: доли выполнения задач, взвешенные по их длительности.
+    /// Группирующие задачи не учитываются, без задач - 0
+    pub fn overall_progress(&self) -> f64 {
+        let (done, total) = self.tasks.values().filter(|t| !t.is_summary).fold(
+            (0.0, 0.0),
+            |(done, total), task| {
+                let weight = task.duration.num_seconds() as f64;
+                (done + weight * task.get_progress(), total + weight)
+            },
+        );
+        if total > 0.0 { done / total } else { 0.0 }
+    }
+
+    /// Освоенный объем проекта на дату `as_of` по валютам ставок ресурсов
+    ///
+    /// Бюджет задачи - стоимость ее назначений в пуле. PV распределяет бюджет по рабочим дням
+    /// окна задачи, EV - бюджет с учетом выполнения задачи. Учета фактических трудозатрат
+    /// пока нет, поэтому AC - стоимость прошедшей к дате части окон назначений.
+    /// Назначения удаленных из пула ресурсов не оцениваются, как и в `budget_status`.
+    pub fn earned_value(
+        &self,
+        as_of: DateTime<Utc>,
+        calendar: &ProjectCalendar,
+        pool: &dyn ResourcePool,
+    ) -> anyhow::Result<HashMap<String, EarnedValue>> {
+        // валюта -> (budget, planned, earned, actual)
+        let mut sums: HashMap<String, (f64, f64, f64, f64)> = HashMap::new();
+        for task in self.tasks.values().filter(|t| !t.is_summary) {
+            let task_window = TimeWindow {
+                date_start: task.date_start,
+                date_end: task.date_end,
+            };
+            let task_elapsed = task_window.elapsed_fraction(as_of, calendar);
+            for allocation_id in task.get_resource_allocations() {
+                let allocation = pool
+                    .get_allocation(allocation_id)
+                    .ok_or_else(|| anyhow::anyhow!("Allocation {} not found", allocation_id))?;
+                let Some(resource) = pool.get_resource(allocation.get_resource_id()) else {
+                    continue;
+                };
+                let cost =
+                    pool.calculate_allocation_cost(allocation_id, calendar, &self.rate_config)?;
+                let sum = sums.entry(resource.get_currency().to_string()).or_default();
+                sum.0 += cost;
+                sum.1 += cost * task_elapsed;
+                sum.2 += cost * task.get_progress();
+                sum.3 += cost
+                    * allocation
+                        .get_time_window()
+                        .elapsed_fraction(as_of, calendar);
+            }
+        }
+        Ok(sums
+            .into_iter()
+            .map(|(currency, (budget, planned, earned, actual))| {
+                (currency, EarnedValue::new(budget, planned, earned, actual))
+            })
+            .collect())
+    }
+
+    /// Затраты проекта по ресурсам, сгруппированные по валюте ставки ресурса.
+    /// Назначения удаленных из пула ресурсов попадают в `unpriced`, как и в `budget_status`
+    pub fn cost_by_resource(
+        &self,
+        calendar: &ProjectCalendar,
+        pool: &dyn ResourcePool,
+    ) -> anyhow::Result<ResourceCosts> {
+        let mut costs = ResourceCosts::default();
+        for allocation in pool.get_project_allocations(&self.id) {
+            let resource_id = allocation.get_resource_id();
+            let Some(resource) = pool.get_resource(resource_id) else {
+                costs.unpriced.push(allocation.get_id());
+                continue;
+            };
+            let cost =
+                pool.calculate_allocation_cost(&allocation.get_id(), calendar, &self.rate_config)?;
+            *costs
+                .by_currency
+                .entry(resource.get_currency().to_string())
+                .or_default()
+                .entry(*resource_id)
+                .or_default() += cost;
+        }
+        costs.unpriced.sort();
+        Ok(costs)
+    }
+
+    /// Трудозатраты проекта в человеко-днях, без учета ставок
+    ///
+    /// По каждому назначению - рабочие дни его окна по календарю ресурса, умноженные на
+    /// занятость. Окно назначения может быть короче окна задачи. Задачи без назначений дают 0.
+    /// Назначения хранятся в пуле, поэтому он передается так же, как для расчета затрат.
+    pub fn total_effort_person_days(
+        &self,
+        calendar: &ProjectCalendar,
+        pool: &dyn ResourcePool,
+    ) -> f64 {
+        self.tasks
+            .values()
+            .filter(|t| !t.is_summary)
+            .flat_map(|task| task.get_resource_allocations())
+            .filter_map(|id| pool.get_allocation(id))
+            .map(|allocation| {
+                let resource_calendar = pool
+                    .get_resource(allocation.get_resource_id())
+                    .map_or(calendar, |r| r.effective_calendar(calendar));
+                resource_calendar.count_working_days(allocation.get_time_window()) as f64
+                    * *allocation.get_engagement_rate()
+            })
+            .sum()
+    }
+
+    /// Итоговые затраты проекта по валютам. Свести к одной валюте - convert_costs.
+    /// Неоцененные назначения (см. `cost_by_resource`) в итог не входят
+    pub fn total_cost(
+        &self,
+        calendar: &ProjectCalendar,
+        pool: &dyn ResourcePool,
+    ) -> anyhow::Result<HashMap<String, f64>> {
+        Ok(self.cost_by_resource(calendar, pool)?.totals())
+    }
+
+    /// Сводные показатели по задачам и назначениям проекта
+    pub fn stats(
+        &self,
+        pool: &dyn ResourcePool,
+        calendar: &ProjectCalendar,
+    ) -> anyhow::Result<ProjectStats> {
+        let mut stats = ProjectStats::default();
+        let mut resources = HashSet::new();
+        for task in self.tasks.values().filter(|t| !t.is_summary) {
+            *stats
+                .tasks_by_status
+                .entry(task.get_status().clone())
+                .or_default() += 1;
+            let task_window = TimeWindow {
+                date_start: task.date_start,
+                date_end: task.date_end,
+            };
+            stats.planned_hours += calendar.working_hours_in_period(&task_window) as u64;
+            stats.earliest_start = Some(
+                stats
+                    .earliest_start
+                    .map_or(task.date_start, |d| d.min(task.date_start)),
+            );
+            stats.latest_end = Some(
+                stats
+                    .latest_end
+                    .map_or(task.date_end, |d| d.max(task.date_end)),
+            );
+            for allocation_id in task.get_resource_allocations() {
+                let allocation = pool
+                    .get_allocation(allocation_id)
+                    .ok_or_else(|| anyhow::anyhow!("Allocation {} not found", allocation_id))?;
+                resources.insert(*allocation.get_resource_id());
+                let Some(resource) = pool.get_resource(allocation.get_resource_id()) else {
+                    stats.unpriced.push(*allocation_id);
+                    continue;
+                };
+                *stats
+                    .total_cost
+                    .entry(resource.get_currency().to_string())
+                    .or_default() +=
+                    pool.calculate_allocation_cost(allocation_id, calendar, &self.rate_config)?;
+            }
+        }
+        stats.unpriced.sort();
+        stats.resource_count = resources.len();
+        Ok(stats)
+    }
+
+    pub fn get_budget(&self) -> Option<f64> {
+        self.budget
+    }
+
+    pub fn get_budget_currency(&self) -> &str {
+        &self.budget_currency
+    }
+
+    /// Задать общий бюджет (None - снять) и его валюту
+    pub fn set_budget(&mut self, amount: Option<f64>, currency: &str) -> Result<(), BudgetErrors> {
+        if let Some(amount) = amount
+            && amount <= 0.0
+        {
+            return Err(BudgetErrors::NonPositiveBudget { amount });
+        }
+        self.budget_currency = normalize_currency(currency)?;
+        self.budget = amount;
+        Ok(())
+    }
+
+    /// Бюджет против стоимости всех назначений проекта
+    ///
+    /// Стоимость назначения считается так же, как в отчетах и статистике проекта
+    /// (`calculate_allocation_cost` с коэффициентами проекта). Включает лимиты по периодам
+    /// и расхождение их суммы с общим бюджетом.
+    pub fn budget_status(
+        &self,
+        calendar: &ProjectCalendar,
+        pool: &dyn ResourcePool,
+    ) -> anyhow::Result<BudgetStatus> {
+        let mut committed_cost = 0.0;
+        let mut other_currencies: HashMap<String, f64> = HashMap::new();
+        let mut unpriced = vec![];
+        for allocation in pool.get_project_allocations(&self.id) {
+            let Some(resource) = pool.get_resource(allocation.get_resource_id()) else {
+                unpriced.push(allocation.get_id());
+                continue;
+            };
+            let cost =
+                pool.calculate_allocation_cost(&allocation.get_id(), calendar, &self.rate_config)?;
+            if resource.get_currency() == self.budget_currency {
+                committed_cost += cost;
+            } else {
+                *other_currencies
+                    .entry(resource.get_currency().to_string())
+                    .or_default() += cost;
+            }
+        }
+        unpriced.sort();
+        let remaining = self.budget.map(|budget| budget - committed_cost);
+        Ok(BudgetStatus {
+            budget: self.budget,
+            currency: self.budget_currency.clone(),
+            committed_cost,
+            remaining,
+            over_budget: remaining.is_some_and(|r| r < 0.0),
+            other_currencies,
+            unpriced,
+            periods: self.budget_periods_status(calendar, pool)?,
+            periods_mismatch: self.budget_periods_mismatch(),
         })
     }
 
+    /// Сумма лимитов периодов минус общий бюджет, если заданы оба и они расходятся
+    fn budget_periods_mismatch(&self) -> Option<f64> {
+        let budget = self.budget?;
+        if self.budget_periods.is_empty() {
+            return None;
+        }
+        let diff = self.budget_periods.iter().map(|p| p.amount).sum::<f64>() - budget;
+        (diff.abs() >= 0.01).then_some(diff)
+    }
+
+    pub fn get_budget_periods(&self) -> &Vec<BudgetPeriod> {
+        &self.budget_periods
+    }
+
+    /// Задать лимиты бюджета по периодам. Периоды сортируются по началу, должны лежать
+    /// внутри проекта и не пересекаться; при ошибке лимиты не меняются
+    ///
+    /// Даты периода включительны, как и при расчете затрат по рабочим дням, поэтому
+    /// соседние периоды не могут иметь общего дня по календарю (Q1 до 31.03, Q2 с 01.04).
+    pub fn set_budget_periods(
+        &mut self,
+        mut periods: Vec<BudgetPeriod>,
+        calendar: &ProjectCalendar,
+    ) -> Result<(), BudgetErrors> {
+        periods.sort_by_key(|p| p.window.date_start);
+        for period in &periods {
+            let (start, end) = (period.window.date_start, period.window.date_end);
+            if period.amount <= 0.0 {
+                return Err(BudgetErrors::NonPositiveAmount { start, end });
+            }
+            if start < self.date_start || end > self.date_end {
+                return Err(BudgetErrors::PeriodOutsideProject { start, end });
+            }
+        }
+        if let Some(pair) = periods.windows(2).find(|pair| {
+            calendar.local_date(pair[1].window.date_start)
+                <= calendar.local_date(pair[0].window.date_end)
+        }) {
+            return Err(BudgetErrors::PeriodsOverlap {
+                start: pair[1].window.date_start,
+            });
+        }
+        self.budget_periods = periods;
+        if let Some(diff) = self.budget_periods_mismatch() {
+            tracing::warn!(
+                project_id = %self.id,
+                diff,
+                "budget periods do not add up to the project budget"
+            );
+        }
+        Ok(())
+    }
+
+    pub fn get_role_placeholders(&self) -> &Vec<RolePlaceholder> {
+        &self.role_placeholders
+    }
+
+    /// Добавить потребность в роли. Роль не пустая, занятость > 0, окно внутри проекта
+    pub fn add_role_placeholder(
+        &mut self,
+        placeholder: RolePlaceholder,
+    ) -> Result<Uuid, RolePlaceholderErrors> {
+        if placeholder.role.is_empty() {
+            return Err(RolePlaceholderErrors::EmptyRole);
+        }
+        if placeholder.engagement_rate.is_nan() || placeholder.engagement_rate <= 0.0 {
+            return Err(RolePlaceholderErrors::NonPositiveRate {
+                rate: placeholder.engagement_rate,
+            });
+        }
+        let (start, end) = (placeholder.window.date_start, placeholder.window.date_end);
+        if start < self.date_start || end > self.date_end {
+            return Err(RolePlaceholderErrors::OutsideProject { start, end });
+        }
+        let id = placeholder.get_id();
+        self.role_placeholders.push(placeholder);
+        Ok(id)
+    }
+
+    /// Убрать потребность в роли (например, когда ресурс подобран)
+    pub fn remove_role_placeholder(
+        &mut self,
+        placeholder_id: &Uuid,
+    ) -> Result<RolePlaceholder, RolePlaceholderErrors> {
+        let index = self
+            .role_placeholders
+            .iter()
+            .position(|p| p.get_id() == *placeholder_id)
+            .ok_or(RolePlaceholderErrors::NotFound {
+                placeholder_id: *placeholder_id,
+            })?;
+        Ok(self.role_placeholders.remove(index))
+    }
+
+    /// Плановые затраты по периодам бюджета
+    ///
+    /// Стоимость назначения делится между периодами пропорционально его рабочим дням в периоде.
+    /// С лимитом сравниваются только затраты в валюте бюджета, остальные валюты показываются
+    /// отдельно. Назначения удаленных из пула ресурсов не оцениваются, как и в `budget_status`.
+    pub fn budget_periods_status(
+        &self,
+        calendar: &ProjectCalendar,
+        pool: &dyn ResourcePool,
+    ) -> anyhow::Result<Vec<BudgetPeriodStatus>> {
+        let mut statuses: Vec<BudgetPeriodStatus> = self
+            .budget_periods
+            .iter()
+            .map(|p| BudgetPeriodStatus {
+                window: p.window,
+                budget: p.amount,
+                planned: 0.0,
+                other_currencies: HashMap::new(),
+            })
+            .collect();
+        for allocation in pool.get_project_allocations(&self.id) {
+            let Some(resource) = pool.get_resource(allocation.get_resource_id()) else {
+                continue;
+            };
+            let window = allocation.get_time_window();
+            let total_days = calendar.count_working_days(window);
+            if total_days == 0 {
+                continue;
+            }
+            let cost =
+                pool.calculate_allocation_cost(&allocation.get_id(), calendar, &self.rate_config)?;
+            for status in statuses.iter_mut() {
+                let start = window.date_start.max(status.window.date_start);
+                let end = window.date_end.min(status.window.date_end);
+                if start > end {
+                    continue;
+                }
+                let days = calendar.count_working_days(&TimeWindow {
+                    date_start: start,
+                    date_end: end,
+                });
+                let share = cost * days as f64 / total_days as f64;
+                if resource.get_currency() == self.budget_currency {
+                    status.planned += share;
+                } else {
+                    *status
+                        .other_currencies
+                        .entry(resource.get_currency().to_string())
+                        .or_default() += share;
+                }
+            }
+        }
+        Ok(statuses)
+    }
+
+    /// Снять базовый план на момент `taken_at`: запомнить текущие даты всех задач,
+    /// прежний план заменяется. План проекта без задач тоже считается снятым
+    pub fn snapshot_baseline(&mut self, taken_at: DateTime<Utc>) {
+        self.baseline_taken_at = Some(taken_at);
+        self.baseline = self
+            .tasks
+            .values()
+            .map(|t| {
+                (
+                    *t.get_id(),
+                    TimeWindow {
+                        date_start: t.date_start,
+                        date_end: t.date_end,
+                    },
+                )
+            })
+            .collect();
+    }
+
+    /// Снят ли базовый план. В файлах до появления отметки времени план есть,
+    /// если он не пустой
+    pub fn has_baseline(&self) -> bool {
+        self.baseline_taken_at.is_some() || !self.baseline.is_empty()
+    }
+
+    pub fn get_baseline_taken_at(&self) -> Option<DateTime<Utc>> {
+        self.baseline_taken_at
+    }
+
+    /// Отклонения от базового плана (списки упорядочены по id задач), None - план не снят
+    pub fn baseline_variance(&self) -> Option<BaselineVariance> {
+        if !self.has_baseline() {
+            return None;
+        }
+        let mut variance = BaselineVariance::default();
+        for task in self.tasks.values() {
+            match self.baseline.get(task.get_id()) {
+                Some(planned) => {
+                    let start_slip = task.date_start - planned.date_start;
+                    let end_slip = task.date_end - planned.date_end;
+                    if !start_slip.is_zero() || !end_slip.is_zero() {
+                        variance.shifted.push(TaskVariance {
+                            task_id: *task.get_id(),
+                            start_slip,
+                            end_slip,
+                        });
+                    }
+                }
+                None => variance.added.push(*task.get_id()),
+            }
+        }
+        variance.removed = self
+            .baseline
+            .keys()
+            .filter(|id| !self.tasks.contains_key(id))
+            .copied()
+            .collect();
+        variance.shifted.sort_by_key(|v| v.task_id);
+        variance.added.sort();
+        variance.removed.sort();
+        Some(variance)
+    }
+
+    /// Сдвиг окончания задач от базового плана (по id задач), без плана - пусто
+    ///
+    /// Входят только задачи плана, которые остались в проекте, в том числе без сдвига.
+    pub fn schedule_variance(&self) -> Vec<(Uuid, TimeDelta)> {
+        let mut variance: Vec<(Uuid, TimeDelta)> = self
+            .baseline
+            .iter()
+            .filter_map(|(task_id, planned)| {
+                self.tasks
+                    .get(task_id)
+                    .map(|task| (*task_id, task.date_end - planned.date_end))
+            })
+            .collect();
+        variance.sort_by_key(|(task_id, _)| *task_id);
+        variance
+    }
+
     pub fn get_project_tasks(&self) -> Vec<&Task> {
         self.tasks.values().collect()
     }
+
+    /// Выполнение проекта по статусам задач против ожидаемого по графику на дату `as_of`
+    ///
+    /// Рабочее время считается по `calendar` (календарь контейнера, как в `budget_status`).
+    /// Группирующие задачи не учитываются; без задач выполнение - 0.
+    pub fn progress(&self, as_of: DateTime<Utc>, calendar: &ProjectCalendar) -> ProjectProgress {
+        let expected = TimeWindow {
+            date_start: self.date_start,
+            date_end: self.date_end,
+        }
+        .elapsed_fraction(as_of, calendar);
+        let (done, total) = self.tasks.values().filter(|t| !t.is_summary).fold(
+            (0.0, 0.0),
+            |(done, total), task| {
+                let hours = calendar
+                    .working_hours_in_period(&TimeWindow {
+                        date_start: task.date_start,
+                        date_end: task.date_end,
+                    })
+                    .max(1) as f64;
+                let finished =
+                    matches!(task.get_status(), TaskStatus::Complete | TaskStatus::Closed);
+                (done + if finished { hours } else { 0.0 }, total + hours)
+            },
+        );
+        ProjectProgress {
+            completed: if total > 0.0 { done / total } else { 0.0 },
+            expected,
+        }
+    }
+
+    /// Сравнение прошедшего срока проекта и выполненной работы на момент `now`
+    ///
+    /// Выполнение считается по обычным задачам с весом по числу рабочих дней в задаче,
+    /// группирующие задачи не учитываются.
+    pub fn schedule_performance(
+        &self,
+        now: DateTime<Utc>,
+        calendar: &ProjectCalendar,
+    ) -> SchedulePerformance {
+        let time_elapsed = TimeWindow {
+            date_start: self.date_start,
+            date_end: self.date_end,
+        }
+        .elapsed_fraction(now, calendar);
+
+        let (done, total) = self.tasks.values().filter(|t| !t.is_summary).fold(
+            (0.0, 0.0),
+            |(done, total), task| {
+                let weight = calendar
+                    .count_working_days(&TimeWindow {
+                        date_start: task.date_start,
+                        date_end: task.date_end,
+                    })
+                    .max(1) as f64;
+                (done + weight * task.get_progress(), total + weight)
+            },
+        );
+        let progress = if total > 0.0 { done / total } else { 0.0 };
+        SchedulePerformance::new(time_elapsed, progress)
+    }
+
+    /// Экспорт задач проекта в CSV
+    ///
+    /// Колонки: id, номер, имя, начало, окончание (ISO-8601), длительность в днях, статус
+    /// и id назначенных ресурсов через запятую (назначения ищутся в пуле). Задачи отсортированы
+    /// по дате начала и имени, чтобы выгрузки одного проекта можно было сравнивать построчно.
+    /// При `hide_resource_names` колонка ресурсов остается пустой: по id ресурс легко найти.
+    pub fn export_tasks_csv(&self, pool: &dyn ResourcePool, redaction: &ExportRedaction) -> String {
+        let mut tasks = self.get_project_tasks();
+        tasks.sort_by(|a, b| {
+            a.get_date_start()
+                .cmp(b.get_date_start())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        let mut csv =
+            String::from("id,number,name,date_start,date_end,duration_days,status,resource_ids\n");
+        for task in tasks {
+            let mut resource_ids: Vec<Uuid> = task
+                .get_resource_allocations()
+                .iter()
+                .filter_map(|id| pool.get_allocation(id))
+                .map(|a| *a.get_resource_id())
+                .collect();
+            resource_ids.sort();
+            resource_ids.dedup();
+            if redaction.hide_resource_names {
+                resource_ids.clear();
+            }
+            let resources = resource_ids
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            let row = [
+                task.get_id().to_string(),
+                task.get_code().unwrap_or_default(),
+                csv_field(&task.name),
+                task.get_date_start()
+                    .to_rfc3339_opts(SecondsFormat::Secs, true),
+                task.get_date_end()
+                    .to_rfc3339_opts(SecondsFormat::Secs, true),
+                task.get_duration().num_days().to_string(),
+                format!("{:?}", task.get_status()),
+                csv_field(&resources),
+            ];
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Задачи, начинающиеся раньше окончания своих предшественников (с учетом lag)
+    ///
+    /// Возвращаются и блокирующие, и неблокирующие зависимости: первые `reschedule_forward`
+    /// исправляет сдвигом, вторые только показываются пользователю.
+    pub fn dependency_violations(&self) -> Vec<DependencyViolation> {
+        let mut violations = vec![];
+        for task in self.tasks.values() {
+            for dependency in task.get_dependencies() {
+                let Some(predecessor) = self.tasks.get(&dependency.depends_on) else {
+                    continue;
+                };
+                let earliest = *predecessor.get_date_end() + dependency.lag_or_zero();
+                if *task.get_date_start() < earliest {
+                    violations.push(DependencyViolation {
+                        task_id: *task.get_id(),
+                        dependency: *dependency,
+                        overlap: earliest - *task.get_date_start(),
+                    });
+                }
+            }
+        }
+        violations.sort_by(|a, b| {
+            a.task_id
+                .cmp(&b.task_id)
+                .then_with(|| a.dependency.depends_on.cmp(&b.dependency.depends_on))
+        });
+        violations
+    }
+
+    /// Резерв времени каждой обычной задачи: насколько ее можно задержать без сдвига
+    /// окончания проекта (поздний старт минус ранний по CPM). У критических задач - ноль.
+    /// Цикл в зависимостях - ошибка, как и в `Scheduler::critical_path`
+    pub fn task_slack(&self) -> anyhow::Result<HashMap<Uuid, TimeDelta>> {
+        crate::services::total_float(self)
+    }
+
+    /// Выдать следующий номер задачи в проекте
+    pub fn next_task_number(&mut self) -> u64 {
+        self.last_task_number += 1;
+        self.last_task_number
+    }
+
+    pub fn get_last_task_number(&self) -> u64 {
+        self.last_task_number
+    }
+
+    /// Добавить задачу с проверками: даты внутри проекта и по ValidationPolicy,
+    /// родитель и предшественники есть в проекте. При ошибке проект не меняется
+    pub fn add_task(&mut self, task: Task) -> Result<&Task, AddTaskErrors> {
+        if self.tasks.contains_key(task.get_id()) {
+            return Err(AddTaskErrors::DuplicateTaskId {
+                task_id: *task.get_id(),
+            });
+        }
+        if task.date_start < self.date_start || task.date_end > self.date_end {
+            return Err(AddTaskErrors::OutsideProject {
+                date_start: task.date_start,
+                date_end: task.date_end,
+            });
+        }
+        if !task.is_summary {
+            self.validation_policy
+                .check_task(task.date_start, task.date_end)?;
+        }
+        if let Some(parent_id) = task.parent_id
+            && !self.tasks.contains_key(&parent_id)
+        {
+            return Err(AddTaskErrors::UnknownParent { parent_id });
+        }
+        if let Some(dependency) = task
+            .get_dependencies()
+            .iter()
+            .find(|d| !self.tasks.contains_key(&d.depends_on))
+        {
+            return Err(AddTaskErrors::UnknownDependency {
+                depends_on: dependency.depends_on,
+            });
+        }
+        Ok(self.insert_task(task))
+    }
+
+    /// Добавить задачу в проект без проверок, присвоив ей очередной номер
+    pub fn insert_task(&mut self, mut task: Task) -> &Task {
+        task.set_number(self.next_task_number());
+        let task_id = *task.get_id();
+        self.tasks.insert(task_id, task);
+        &self.tasks[&task_id]
+    }
+
+    /// Перенести задачи другого проекта в этот
+    ///
+    /// Id задач сохраняются, поэтому зависимости и родители перенесенных задач остаются
+    /// корректными; номера задач выдаются заново. Конфликты имен, дат и назначений
+    /// разрешаются по `strategy`, при ошибке проект не меняется.
+    #[tracing::instrument(skip_all, fields(project_id = %self.id, source_id = %other.id))]
+    pub fn merge(
+        &mut self,
+        other: Project,
+        strategy: MergeStrategy,
+    ) -> Result<MergeReport, MergeErrors> {
+        let mut report = MergeReport::default();
+        let mut incoming: Vec<Task> = other.tasks.into_values().collect();
+        incoming.sort_by_key(|t| (t.get_number(), t.date_start));
+        if let Some(task) = incoming
+            .iter()
+            .find(|t| self.tasks.contains_key(t.get_id()))
+        {
+            return Err(MergeErrors::DuplicateTaskId {
+                task_id: *task.get_id(),
+            });
+        }
+
+        // Даты
+        let mut new_start = self.date_start;
+        let mut new_end = self.date_end;
+        for task in &incoming {
+            if task.date_start >= self.date_start && task.date_end <= self.date_end {
+                continue;
+            }
+            if strategy.window == WindowConflict::Reject {
+                return Err(MergeErrors::OutsideProjectWindow {
+                    task_id: *task.get_id(),
+                });
+            }
+            new_start = new_start.min(task.date_start);
+            new_end = new_end.max(task.date_end);
+        }
+
+        // Имена
+        let mut names: HashSet<String> = self.tasks.values().map(|t| t.name.clone()).collect();
+        for task in incoming.iter_mut() {
+            if names.contains(&task.name) {
+                if strategy.task_names == NameCollision::Reject {
+                    return Err(MergeErrors::TaskNameCollision {
+                        name: task.name.clone(),
+                    });
+                }
+                let new_name = (2..)
+                    .map(|n| format!("{} ({})", task.name, n))
+                    .find(|candidate| !names.contains(candidate))
+                    .unwrap();
+                let old_name = std::mem::replace(&mut task.name, new_name.clone());
+                tracing::warn!(
+                    project_id = %self.id,
+                    task_id = %task.get_id(),
+                    %new_name,
+                    "merged task renamed"
+                );
+                names.insert(new_name.clone());
+                report
+                    .renamed_tasks
+                    .push((*task.get_id(), old_name, new_name));
+            }
+        }
+
+        // Назначения
+        let mut allocations: HashSet<Uuid> = self
+            .tasks
+            .values()
+            .flat_map(|t| t.get_resource_allocations().iter().copied())
+            .collect();
+        for task in incoming.iter_mut() {
+            for allocation_id in task.get_resource_allocations().clone() {
+                if allocations.insert(allocation_id) {
+                    report.allocations.push(allocation_id);
+                    continue;
+                }
+                if strategy.allocations == DuplicateAllocation::Reject {
+                    return Err(MergeErrors::DuplicateAllocation { allocation_id });
+                }
+                tracing::warn!(
+                    project_id = %self.id,
+                    %allocation_id,
+                    "duplicate allocation reference dropped"
+                );
+                task.remove_resource_allocation(&allocation_id);
+                report.skipped_allocations.push(allocation_id);
+            }
+        }
+
+        if (new_start, new_end) != (self.date_start, self.date_end) {
+            tracing::warn!(
+                project_id = %self.id,
+                date_start = %new_start,
+                date_end = %new_end,
+                "project window expanded by merge"
+            );
+            report.expanded_window = Some((self.date_start, self.date_end));
+            self.date_start = new_start;
+            self.date_end = new_end;
+            self.duration = new_end - new_start;
+        }
+        for task in incoming {
+            report.added_tasks.push(*task.get_id());
+            self.insert_task(task);
+        }
+        Ok(report)
+    }
+
+    /// Убрать задачу вместе со ссылками на нее: зависимости других задач на нее снимаются,
+    /// дочерние задачи остаются без родителя. Назначения в пуле не трогаются
+    pub fn remove_task_detached(&mut self, task_id: &Uuid) -> Option<Task> {
+        let task = self.tasks.remove(task_id)?;
+        for other in self.tasks.values_mut() {
+            other.remove_dependency(task_id);
+            if other.parent_id == Some(*task_id) {
+                other.parent_id = None;
+            }
+        }
+        Some(task)
+    }
+
+    /// Поиск задачи по человекочитаемому номеру ("T-142" -> задача)
+    pub fn get_task_by_code(&self, code: &str) -> Option<&Task> {
+        let number = Task::parse_code(code)?;
+        self.tasks.values().find(|t| t.get_number() == number)
+    }
 }
 
 impl BasicGettersForStructures for Project {
@@ -71,6 +1208,15 @@ impl BasicGettersForStructures for Project {
     }
 }
 
+/// Экранирование значения CSV: поля с запятыми, кавычками и переводами строк берутся в кавычки
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 impl Display for Project {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -89,7 +1235,46 @@ impl Display for Project {
 mod tests {
     use chrono::{TimeZone, Utc};
 
-    use crate::Project;
+    use chrono::TimeDelta;
+
+    use uuid::Uuid;
+
+    use super::{ProjectStatus, ProjectUpdate};
+    use crate::{BaselineVariance, TaskVariance};
+    use crate::{
+        BasicGettersForStructures, Dependency, DependencyType, Project, Task, ValidationPolicy,
+        base_structures::{
+            AllocationRequest, LocalResourcePool, RandomIds, Resource, SequentialIds, TimeWindow,
+            tasks::TaskStatus, traits::ResourcePool,
+        },
+        cust_exceptions::{ProjectCreationErrors, ProjectUpdateErrors, ValidationErrors},
+        export::ExportRedaction,
+    };
+
+    /// Назначить ресурс на задачу проекта по календарю проекта: ресурс добавляется в пул,
+    /// если его там еще нет, назначение записывается в задачу
+    fn allocate(
+        project: &mut Project,
+        pool: &mut LocalResourcePool,
+        resource: &Resource,
+        task_id: Uuid,
+        engagement: f64,
+        window: TimeWindow,
+    ) -> Uuid {
+        if pool.get_resource(&resource.id).is_none() {
+            pool.add_resource(resource.clone()).unwrap();
+        }
+        let request =
+            AllocationRequest::new(resource.id, task_id, *project.get_id(), engagement, window)
+                .unwrap();
+        let allocation_id = pool.allocate(request, &project.calendar).unwrap();
+        project
+            .tasks
+            .get_mut(&task_id)
+            .unwrap()
+            .set_resource_allocation(allocation_id);
+        allocation_id
+    }
 
     #[test]
     fn create_empty_project() {
@@ -98,8 +1283,1242 @@ mod tests {
 
         let project = Project::new("TestProject", "Some test project", date_start, date_end)
             .expect("Project is not created");
-        println!("{}", project.duration);
         assert_eq!(project.name, String::from("TestProject"));
         assert_eq!(project.duration, date_end - date_start)
     }
+
+    #[test]
+    fn test_project_new_rejects_invalid_input() {
+        let date_start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let date_end = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+
+        assert!(matches!(
+            Project::new("TestProject", "", date_end, date_start),
+            Err(ProjectCreationErrors::InvalidProjectDuration { .. })
+        ));
+        assert!(matches!(
+            Project::new("TestProject", "", date_start, date_start),
+            Err(ProjectCreationErrors::InvalidProjectDuration { .. })
+        ));
+        assert!(matches!(
+            Project::new("   ", "", date_start, date_end),
+            Err(ProjectCreationErrors::EmptyProjectName)
+        ));
+    }
+
+    #[test]
+    fn test_task_numbers_are_not_reused() {
+        let date_start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let date_end = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let mut project = Project::new("TestProject", "", date_start, date_end).unwrap();
+
+        let first = *project
+            .insert_task(Task::new_regular("First", date_start, date_end, None).unwrap())
+            .get_id();
+        let second = project
+            .insert_task(Task::new_regular("Second", date_start, date_end, None).unwrap())
+            .get_code();
+        assert_eq!(second.as_deref(), Some("T-2"));
+
+        project.tasks.remove(&first);
+        let third = project
+            .insert_task(Task::new_regular("Third", date_start, date_end, None).unwrap())
+            .get_number();
+        assert_eq!(third, 3);
+
+        assert_eq!(project.get_task_by_code("t-2").unwrap().name, "Second");
+        assert_eq!(project.get_task_by_code("3").unwrap().name, "Third");
+        assert!(project.get_task_by_code("T-1").is_none());
+        assert!(project.get_task_by_code("T-abc").is_none());
+    }
+
+    #[test]
+    fn test_update_project() {
+        let date_start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let date_end = Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap();
+        let mut project = Project::new("TestProject", "", date_start, date_end).unwrap();
+        let new_end = Utc.with_ymd_and_hms(2026, 6, 30, 0, 0, 0).unwrap();
+        project
+            .update(ProjectUpdate {
+                name: Some("Renamed".into()),
+                date_end: Some(new_end),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(project.name, "Renamed");
+        assert_eq!(project.date_end, new_end);
+        assert_eq!(project.duration, new_end - date_start);
+
+        let result = project.update(ProjectUpdate {
+            date_start: Some(new_end),
+            date_end: Some(date_start),
+            ..Default::default()
+        });
+        assert!(matches!(
+            result,
+            Err(ProjectUpdateErrors::InvalidProjectDuration { .. })
+        ));
+    }
+
+    #[test]
+    fn test_update_project_rejects_what_new_rejects() {
+        let date_start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let date_end = Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap();
+        let mut project = Project::new("TestProject", "", date_start, date_end).unwrap();
+
+        // Совпадающие даты
+        assert!(Project::new("Other", "", date_start, date_start).is_err());
+        let result = project.update(ProjectUpdate {
+            date_end: Some(date_start),
+            ..Default::default()
+        });
+        assert_eq!(
+            result,
+            Err(ProjectUpdateErrors::InvalidProjectDuration {
+                date_start,
+                date_end: date_start
+            })
+        );
+
+        // Пустое имя
+        assert!(matches!(
+            Project::new("  ", "", date_start, date_end),
+            Err(ProjectCreationErrors::EmptyProjectName)
+        ));
+        let result = project.update(ProjectUpdate {
+            name: Some("  ".into()),
+            ..Default::default()
+        });
+        assert_eq!(result, Err(ProjectUpdateErrors::EmptyProjectName));
+
+        // Ничего не изменилось
+        assert_eq!(project.name, "TestProject");
+        assert_eq!(project.date_end, date_end);
+    }
+
+    #[test]
+    fn test_task_slack_on_diamond() {
+        let day = |d: u32| Utc.with_ymd_and_hms(2025, 3, d, 0, 0, 0).unwrap();
+        let mut project = Project::new("Diamond", "", day(1), day(31)).unwrap();
+        let mut add = |name: &str, days: u32, after: &[uuid::Uuid]| {
+            let mut task = Task::new_regular(name, day(1), day(1 + days), None).unwrap();
+            for id in after {
+                task.add_dependency(Dependency::new(DependencyType::Blocking, *id, None));
+            }
+            *project.insert_task(task).get_id()
+        };
+        // Старт -> длинная ветка (5 дней) и короткая (2 дня) -> финиш
+        let start = add("Start", 1, &[]);
+        let long = add("Long", 5, &[start]);
+        let short = add("Short", 2, &[start]);
+        let finish = add("Finish", 1, &[long, short]);
+
+        let slack = project.task_slack().unwrap();
+        assert_eq!(slack.len(), 4);
+        for id in [start, long, finish] {
+            assert_eq!(slack[&id], TimeDelta::zero());
+        }
+        assert_eq!(slack[&short], TimeDelta::days(3));
+
+        // Цикл не выдается за проект без резервов
+        project
+            .tasks
+            .get_mut(&start)
+            .unwrap()
+            .add_dependency(Dependency::new(DependencyType::Blocking, finish, None));
+        assert!(project.task_slack().is_err());
+    }
+
+    #[test]
+    fn test_clone_with_new_ids() {
+        let date_start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let date_end = Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap();
+        let mut project = Project::new("Q1", "", date_start, date_end).unwrap();
+        let summary_id = *project
+            .insert_task(Task::new_summary("Phase", date_start, date_end, None).unwrap())
+            .get_id();
+        let design_id = *project
+            .insert_task(
+                Task::new_regular(
+                    "Design",
+                    Utc.with_ymd_and_hms(2025, 2, 3, 0, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2025, 2, 7, 0, 0, 0).unwrap(),
+                    Some(summary_id),
+                )
+                .unwrap(),
+            )
+            .get_id();
+        let mut build = Task::new_regular(
+            "Build",
+            Utc.with_ymd_and_hms(2025, 2, 10, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 2, 14, 0, 0, 0).unwrap(),
+            Some(summary_id),
+        )
+        .unwrap();
+        build.add_dependency(Dependency::new(
+            DependencyType::Blocking,
+            design_id,
+            Some(TimeDelta::days(1)),
+        ));
+        build.set_status(TaskStatus::Processed).unwrap();
+        build.set_status(TaskStatus::Complete).unwrap();
+        project.insert_task(build);
+
+        let shift = TimeDelta::days(91);
+        let copy = project.clone_with_new_ids("Q2".into(), Some(shift), &mut SequentialIds::new());
+
+        assert_eq!(copy.name, "Q2");
+        assert_eq!(*copy.get_id(), Uuid::from_u128(1));
+        assert_eq!(copy.tasks.len(), 3);
+        // Задачам id выдаются по номерам: Phase, Design, Build
+        for (number, id) in [(1, 2), (2, 3), (3, 4)] {
+            assert_eq!(copy.tasks[&Uuid::from_u128(id)].get_number(), number);
+        }
+        assert_eq!(copy.date_start, date_start + shift);
+        for task in copy.tasks.values() {
+            assert!(!project.tasks.contains_key(task.get_id()));
+            let original = project
+                .tasks
+                .values()
+                .find(|t| t.get_number() == task.get_number())
+                .unwrap();
+            assert_eq!(*task.get_date_start(), *original.get_date_start() + shift);
+            assert_eq!(*task.get_date_end(), *original.get_date_end() + shift);
+            assert!(matches!(task.get_status(), TaskStatus::New));
+            // Все ссылки ведут на задачи копии
+            if let Some(parent_id) = task.parent_id {
+                assert!(copy.tasks.contains_key(&parent_id));
+            }
+            for dependency in task.get_dependencies() {
+                assert!(copy.tasks.contains_key(&dependency.depends_on));
+            }
+        }
+        let copied_build = copy.get_task_by_code("T-3").unwrap();
+        let dependency = copied_build.get_dependencies()[0];
+        assert_eq!(copy.tasks[&dependency.depends_on].name, "Design");
+        assert_eq!(dependency.lag, Some(TimeDelta::days(1)));
+    }
+
+    #[test]
+    fn test_validate_and_clamp_window() {
+        let day = |d: u32| Utc.with_ymd_and_hms(2025, 3, d, 0, 0, 0).unwrap();
+        let mut project = Project::new("Window", "", day(1), day(31)).unwrap();
+        let edge_id = *project
+            .insert_task(Task::new_regular("Edge", day(1), day(10), None).unwrap())
+            .get_id();
+        let late_id = *project
+            .insert_task(Task::new_regular("Late", day(20), day(31), None).unwrap())
+            .get_id();
+
+        // Задача ровно на границе окна допустима
+        assert!(
+            project
+                .validate_window_against_tasks(day(1), day(31))
+                .is_ok()
+        );
+        assert_eq!(
+            project.validate_window_against_tasks(day(1), day(25)),
+            Err(vec![late_id])
+        );
+        let mut both = vec![edge_id, late_id];
+        both.sort();
+        assert_eq!(
+            project.validate_window_against_tasks(day(5), day(25)),
+            Err(both.clone())
+        );
+
+        // Окно сужено в обход update - задачи обрезаются по границам
+        project.date_start = day(5);
+        project.date_end = day(25);
+        assert_eq!(project.clamp_tasks_to_window(), Ok(both));
+        let edge = &project.tasks[&edge_id];
+        assert_eq!((edge.date_start, edge.date_end), (day(5), day(10)));
+        assert_eq!(edge.duration, TimeDelta::days(5));
+        let late = &project.tasks[&late_id];
+        assert_eq!((late.date_start, late.date_end), (day(20), day(25)));
+        assert_eq!(project.clamp_tasks_to_window(), Ok(vec![]));
+
+        // Задача целиком вне окна - обрезать нечего, проект не меняется
+        project.date_end = day(15);
+        assert_eq!(project.clamp_tasks_to_window(), Err(vec![late_id]));
+        assert_eq!(project.tasks[&late_id].date_end, day(25));
+        assert_eq!(project.tasks[&edge_id].date_start, day(5));
+    }
+
+    #[test]
+    fn test_add_task_surfaces_rejections() {
+        use crate::cust_exceptions::AddTaskErrors;
+        use uuid::Uuid;
+
+        let day = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap();
+        let mut project = Project::new("Checked", "", day(2), day(20)).unwrap();
+
+        let late = Task::new_regular("Late", day(16), day(25), None).unwrap();
+        assert_eq!(
+            project.add_task(late).unwrap_err(),
+            AddTaskErrors::OutsideProject {
+                date_start: day(16),
+                date_end: day(25)
+            }
+        );
+
+        let missing = Uuid::new_v4();
+        let mut dependent = Task::new_regular("Dependent", day(9), day(13), None).unwrap();
+        dependent.add_dependency(Dependency::new(DependencyType::Blocking, missing, None));
+        assert_eq!(
+            project.add_task(dependent).unwrap_err(),
+            AddTaskErrors::UnknownDependency {
+                depends_on: missing
+            }
+        );
+        let orphan = Task::new_regular("Orphan", day(9), day(13), Some(missing)).unwrap();
+        assert_eq!(
+            project.add_task(orphan).unwrap_err(),
+            AddTaskErrors::UnknownParent { parent_id: missing }
+        );
+        assert!(project.tasks.is_empty());
+        assert_eq!(project.get_last_task_number(), 0);
+
+        let first = *project
+            .add_task(Task::new_regular("First", day(2), day(6), None).unwrap())
+            .unwrap()
+            .get_id();
+        let mut second = Task::new_regular("Second", day(9), day(13), None).unwrap();
+        second.add_dependency(Dependency::new(DependencyType::Blocking, first, None));
+        assert_eq!(project.add_task(second).unwrap().get_number(), 2);
+        let copy = project.tasks[&first].clone();
+        assert_eq!(
+            project.add_task(copy).unwrap_err(),
+            AddTaskErrors::DuplicateTaskId { task_id: first }
+        );
+    }
+
+    #[test]
+    fn test_progress_by_status_and_schedule() {
+        use chrono::Weekday;
+
+        let day = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap();
+        // Март 2026: со 2 по 31 - 22 рабочих дня
+        let mut project = Project::new("Progress", "", day(2), day(31)).unwrap();
+        let calendar = project.calendar.clone();
+
+        // Без задач выполнено 0, ожидаемое - по прошедшему времени
+        let empty = project.progress(day(17), &calendar);
+        assert_eq!(empty.completed, 0.0);
+        assert_eq!(empty.expected, 11.0 / 22.0);
+
+        // 5 рабочих дней выполнено, 10 - нет
+        let mut done = Task::new_regular("Done", day(2), day(6), None).unwrap();
+        done.set_status(TaskStatus::Processed).unwrap();
+        done.set_status(TaskStatus::Complete).unwrap();
+        project.insert_task(done);
+        project.insert_task(Task::new_regular("Open", day(9), day(20), None).unwrap());
+        project.insert_task(Task::new_summary("Group", day(2), day(20), None).unwrap());
+
+        let progress = project.progress(day(17), &calendar);
+        assert_eq!(progress.completed, 40.0 / 120.0);
+        assert_eq!(progress.expected, 0.5);
+        assert!(progress.is_behind());
+
+        assert_eq!(project.progress(day(1), &calendar).expected, 0.0);
+        assert_eq!(
+            project
+                .progress(
+                    Utc.with_ymd_and_hms(2026, 4, 10, 0, 0, 0).unwrap(),
+                    &calendar
+                )
+                .expected,
+            1.0
+        );
+
+        // Считается по переданному календарю, а не по копии в проекте:
+        // без выходных к 9 марта прошло 7 дней из 30
+        let mut every_day = calendar.clone();
+        every_day.set_working_days([
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ]);
+        assert_eq!(project.progress(day(9), &calendar).expected, 5.0 / 22.0);
+        assert_eq!(project.progress(day(9), &every_day).expected, 7.0 / 30.0);
+    }
+
+    #[test]
+    fn test_overall_progress_weighted_by_duration() {
+        let day = |d: u32| Utc.with_ymd_and_hms(2025, 3, d, 0, 0, 0).unwrap();
+        let mut project = Project::new("Progress", "", day(1), day(31)).unwrap();
+        assert_eq!(project.overall_progress(), 0.0);
+
+        let long_id = *project
+            .insert_task(Task::new_regular("Long", day(1), day(9), None).unwrap())
+            .get_id();
+        let short_id = *project
+            .insert_task(Task::new_regular("Short", day(9), day(11), None).unwrap())
+            .get_id();
+        project.insert_task(Task::new_summary("Phase", day(1), day(11), None).unwrap());
+
+        // 8 дней выполнены на 50%, 2 дня не начаты: 4 / 10
+        let long = project.tasks.get_mut(&long_id).unwrap();
+        long.set_progress(0.5).unwrap();
+        assert!(long.set_progress(1.5).is_err());
+        assert!(long.set_progress(-0.1).is_err());
+        assert_eq!(project.overall_progress(), 0.4);
+
+        // Завершенная задача считается выполненной полностью
+        let short = project.tasks.get_mut(&short_id).unwrap();
+        short.set_status(TaskStatus::Processed).unwrap();
+        short.set_status(TaskStatus::Complete).unwrap();
+        assert_eq!(project.overall_progress(), 0.6);
+
+        // Файлы без поля progress загружаются с нулевым выполнением
+        let mut json = serde_json::to_value(&project.tasks[&long_id]).unwrap();
+        json.as_object_mut().unwrap().remove("progress");
+        let loaded: Task = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.get_progress(), 0.0);
+    }
+
+    #[test]
+    fn test_project_metadata() {
+        let date_start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let date_end = Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap();
+        let mut project = Project::new("Meta", "", date_start, date_end).unwrap();
+        assert_eq!(project.get_status(), &ProjectStatus::Active);
+        assert!(project.get_owner().is_none());
+
+        project.set_owner(Some("Anna".into()));
+        assert_eq!(project.get_owner(), Some("Anna"));
+        project.add_tag("backend");
+        project.add_tag(" backend ");
+        project.add_tag("");
+        assert_eq!(project.get_tags(), &vec!["backend".to_string()]);
+        assert!(project.remove_tag("backend"));
+        assert!(!project.remove_tag("backend"));
+
+        assert!(project.set_status(ProjectStatus::Draft).is_err());
+        assert!(project.set_status(ProjectStatus::OnHold).is_ok());
+        assert!(project.set_status(ProjectStatus::Done).is_err());
+        assert!(project.set_status(ProjectStatus::Active).is_ok());
+        assert!(project.set_status(ProjectStatus::Done).is_ok());
+        assert!(project.set_status(ProjectStatus::Active).is_ok());
+        assert!(project.set_status(ProjectStatus::Cancelled).is_ok());
+        // Отмененный проект - конечное состояние
+        for next in [
+            ProjectStatus::Draft,
+            ProjectStatus::Active,
+            ProjectStatus::OnHold,
+            ProjectStatus::Done,
+        ] {
+            assert!(project.set_status(next).is_err());
+        }
+        assert_eq!(project.get_status(), &ProjectStatus::Cancelled);
+
+        // Старые файлы без метаданных загружаются со значениями по умолчанию
+        let mut json = serde_json::to_value(&project).unwrap();
+        let object = json.as_object_mut().unwrap();
+        for key in ["owner", "status", "tags"] {
+            object.remove(key);
+        }
+        let loaded: Project = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.get_status(), &ProjectStatus::Active);
+        assert!(loaded.get_owner().is_none());
+        assert!(loaded.get_tags().is_empty());
+    }
+
+    #[test]
+    fn test_project_duration_limit() {
+        let date_start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let limit = ValidationPolicy::default().max_project_duration;
+        assert!(Project::new("AtLimit", "", date_start, date_start + limit).is_ok());
+        let error = Project::new(
+            "Century",
+            "",
+            date_start,
+            date_start + limit + TimeDelta::days(1),
+        )
+        .err()
+        .unwrap();
+        assert!(matches!(
+            error,
+            ProjectCreationErrors::Validation(ValidationErrors::ProjectTooLong { .. })
+        ));
+
+        let mut project = Project::new("Extend", "", date_start, date_start + limit).unwrap();
+        let result = project.update(ProjectUpdate {
+            date_end: Some(date_start + limit + TimeDelta::days(1)),
+            ..Default::default()
+        });
+        assert!(matches!(result, Err(ProjectUpdateErrors::Validation(_))));
+
+        let relaxed = ValidationPolicy {
+            max_project_duration: TimeDelta::days(100 * 365),
+            ..Default::default()
+        };
+        assert!(
+            Project::new_with_policy("Century", "", date_start, date_start + limit * 2, relaxed)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_update_project_rejects_shrink_past_tasks() {
+        let date_start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let date_end = Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap();
+        let mut project = Project::new("TestProject", "", date_start, date_end).unwrap();
+        let late_id = *project
+            .insert_task(
+                Task::new_regular(
+                    "Late",
+                    Utc.with_ymd_and_hms(2025, 11, 1, 0, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2025, 11, 20, 0, 0, 0).unwrap(),
+                    None,
+                )
+                .unwrap(),
+            )
+            .get_id();
+        project.insert_task(
+            Task::new_regular(
+                "Early",
+                Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 2, 20, 0, 0, 0).unwrap(),
+                None,
+            )
+            .unwrap(),
+        );
+
+        let result = project.update(ProjectUpdate {
+            name: Some("Shrunk".into()),
+            date_end: Some(Utc.with_ymd_and_hms(2025, 6, 30, 0, 0, 0).unwrap()),
+            ..Default::default()
+        });
+        assert_eq!(
+            result,
+            Err(ProjectUpdateErrors::TasksOutsideProject {
+                task_ids: vec![late_id]
+            })
+        );
+        // Проект не изменился
+        assert_eq!(project.name, "TestProject");
+        assert_eq!(project.date_end, date_end);
+    }
+
+    #[test]
+    fn test_export_tasks_csv() {
+        use crate::base_structures::RateMeasure;
+
+        let date_start = Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap();
+        let date_end = Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap();
+        let mut project = Project::new("TestProject", "", date_start, date_end).unwrap();
+        let build_id = *project
+            .insert_task(
+                Task::new_regular(
+                    "Build, test",
+                    Utc.with_ymd_and_hms(2025, 2, 10, 0, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2025, 2, 14, 12, 0, 0).unwrap(),
+                    None,
+                )
+                .unwrap(),
+            )
+            .get_id();
+        let design_id = *project
+            .insert_task(
+                Task::new_regular(
+                    "Design",
+                    Utc.with_ymd_and_hms(2025, 2, 3, 0, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2025, 2, 7, 0, 0, 0).unwrap(),
+                    None,
+                )
+                .unwrap(),
+            )
+            .get_id();
+
+        // Два назначения одного ресурса на дизайн дают один id в колонке ресурсов
+        let mut pool = LocalResourcePool::default();
+        let resource = Resource::new("Anna".into(), 100.0, RateMeasure::Hourly).unwrap();
+        for (start, end) in [(3, 5), (5, 7)] {
+            let window = TimeWindow::new(
+                Utc.with_ymd_and_hms(2025, 2, start, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 2, end, 0, 0, 0).unwrap(),
+            )
+            .unwrap();
+            allocate(&mut project, &mut pool, &resource, design_id, 0.5, window);
+        }
+
+        let csv = project.export_tasks_csv(&pool, &ExportRedaction::default());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "id,number,name,date_start,date_end,duration_days,status,resource_ids"
+        );
+        assert_eq!(
+            lines[1],
+            format!(
+                "{design_id},T-2,Design,2025-02-03T00:00:00Z,2025-02-07T00:00:00Z,4,New,{}",
+                resource.id
+            )
+        );
+        assert_eq!(
+            lines[2],
+            format!(
+                "{build_id},T-1,\"Build, test\",2025-02-10T00:00:00Z,2025-02-14T12:00:00Z,4,New,"
+            )
+        );
+        assert_eq!(
+            csv,
+            project.export_tasks_csv(&pool, &ExportRedaction::default())
+        );
+
+        let redacted = project.export_tasks_csv(&pool, &ExportRedaction::all());
+        assert!(!redacted.contains(&resource.id.to_string()));
+        assert!(!redacted.contains("Anna"));
+        assert_eq!(
+            redacted.lines().nth(1).unwrap(),
+            format!("{design_id},T-2,Design,2025-02-03T00:00:00Z,2025-02-07T00:00:00Z,4,New,")
+        );
+    }
+
+    #[test]
+    fn test_schedule_performance() {
+        let date_start = Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap();
+        let date_end = Utc.with_ymd_and_hms(2026, 3, 14, 0, 0, 0).unwrap();
+        let mut project = Project::new("TestProject", "", date_start, date_end).unwrap();
+        let calendar = project.calendar.clone();
+        // Первая неделя (5 рабочих дней) выполнена, вторая нет
+        let first_id = *project
+            .insert_task(
+                Task::new_regular(
+                    "First",
+                    date_start,
+                    Utc.with_ymd_and_hms(2026, 3, 6, 0, 0, 0).unwrap(),
+                    None,
+                )
+                .unwrap(),
+            )
+            .get_id();
+        project.insert_task(
+            Task::new_regular(
+                "Second",
+                Utc.with_ymd_and_hms(2026, 3, 9, 0, 0, 0).unwrap(),
+                date_end,
+                None,
+            )
+            .unwrap(),
+        );
+        let first = project.tasks.get_mut(&first_id).unwrap();
+        first.set_status(TaskStatus::Processed).unwrap();
+        first.set_status(TaskStatus::Complete).unwrap();
+
+        // Пн 9 марта: прошло 5 из 10 рабочих дней, выполнено 5 из 10
+        let now = Utc.with_ymd_and_hms(2026, 3, 9, 9, 0, 0).unwrap();
+        let performance = project.schedule_performance(now, &calendar);
+        assert_eq!(performance.time_elapsed, 0.5);
+        assert_eq!(performance.progress, 0.5);
+        assert_eq!(performance.index, 1.0);
+        assert!(!performance.needs_attention());
+
+        // Вторая задача к пятнице прошла на 80% при нулевом выполнении
+        let friday = Utc.with_ymd_and_hms(2026, 3, 13, 9, 0, 0).unwrap();
+        let second = project.get_task_by_code("T-2").unwrap();
+        let task_performance = second.schedule_performance(friday, &calendar);
+        assert_eq!(task_performance.time_elapsed, 0.8);
+        assert_eq!(task_performance.index, 0.0);
+        assert!(task_performance.needs_attention());
+    }
+
+    #[test]
+    fn test_earned_value_mid_project() {
+        use crate::base_structures::RateMeasure;
+
+        let day = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap();
+        let mut project = Project::new("Finance", "", day(2), day(14)).unwrap();
+        let calendar = project.calendar.clone();
+        let task_id = *project
+            .insert_task(Task::new_regular("Build", day(2), day(13), None).unwrap())
+            .get_id();
+
+        // Ресурс назначен только на первую неделю: 5 дней * 8 часов * 100 = 4000
+        let mut pool = LocalResourcePool::default();
+        let resource = Resource::new("Anna".into(), 100.0, RateMeasure::Hourly).unwrap();
+        let window = TimeWindow::new(day(2), day(6)).unwrap();
+        allocate(&mut project, &mut pool, &resource, task_id, 1.0, window);
+        let task = project.tasks.get_mut(&task_id).unwrap();
+        task.set_progress(0.5).unwrap();
+
+        // Пн 9 марта: окно задачи прошло наполовину, задача выполнена наполовину,
+        // а назначение на первую неделю уже израсходовано полностью
+        let now = Utc.with_ymd_and_hms(2026, 3, 9, 9, 0, 0).unwrap();
+        let by_currency = project.earned_value(now, &calendar, &pool).unwrap();
+        assert_eq!(by_currency.len(), 1);
+        let ev = by_currency["RUB"];
+        assert_eq!(ev.budget, 4000.0);
+        assert_eq!(ev.planned_value, 2000.0);
+        assert_eq!(ev.earned_value, 2000.0);
+        assert_eq!(ev.actual_cost, 4000.0);
+        assert_eq!(ev.spi, 1.0);
+        assert_eq!(ev.cpi, 0.5);
+
+        // Ресурс в другой валюте считается отдельно и не смешивается с рублями
+        let mut usd = Resource::new("John".into(), 10.0, RateMeasure::Hourly).unwrap();
+        usd.set_currency("USD").unwrap();
+        allocate(&mut project, &mut pool, &usd, task_id, 1.0, window);
+        let by_currency = project.earned_value(now, &calendar, &pool).unwrap();
+        assert_eq!(by_currency["RUB"], ev);
+        assert_eq!(by_currency["USD"].budget, 400.0);
+        assert_eq!(by_currency["USD"].earned_value, 200.0);
+
+        // Назначения удаленного ресурса не оцениваются
+        pool.remove_resource(&usd.id).unwrap();
+        assert!(
+            !project
+                .earned_value(now, &calendar, &pool)
+                .unwrap()
+                .contains_key("USD")
+        );
+    }
+
+    #[test]
+    fn test_total_effort_person_days() {
+        use crate::base_structures::RateMeasure;
+        use chrono::Weekday;
+
+        let day = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap();
+        let mut project = Project::new("Effort", "", day(2), day(31)).unwrap();
+        let calendar = project.calendar.clone();
+        let task_id = *project
+            .insert_task(Task::new_regular("Build", day(2), day(14), None).unwrap())
+            .get_id();
+        project.insert_task(Task::new_regular("Review", day(16), day(21), None).unwrap());
+
+        let mut pool = LocalResourcePool::default();
+        let resource = Resource::new("Anna".into(), 100.0, RateMeasure::Hourly).unwrap();
+        let window = TimeWindow::new(day(2), day(14)).unwrap();
+        allocate(&mut project, &mut pool, &resource, task_id, 0.5, window);
+
+        // 10 рабочих дней на половину ставки, задача без ресурсов не учитывается
+        assert_eq!(project.total_effort_person_days(&calendar, &pool), 5.0);
+
+        // Четырехнедельная задача, назначение на половину ставки только на одну неделю
+        let support_id = *project
+            .insert_task(Task::new_regular("Support", day(2), day(27), None).unwrap())
+            .get_id();
+        let week = TimeWindow::new(day(16), day(20)).unwrap();
+        allocate(&mut project, &mut pool, &resource, support_id, 0.5, week);
+        assert_eq!(project.total_effort_person_days(&calendar, &pool), 7.5);
+
+        // Дни считаются по календарю ресурса: Boris работает с понедельника по среду
+        let mut boris = Resource::new("Boris".into(), 100.0, RateMeasure::Hourly).unwrap();
+        let mut short_week = calendar.clone();
+        short_week.set_working_days([Weekday::Mon, Weekday::Tue, Weekday::Wed]);
+        boris.set_calendar(Some(short_week));
+        let week = TimeWindow::new(day(23), day(27)).unwrap();
+        allocate(&mut project, &mut pool, &boris, support_id, 1.0, week);
+        assert_eq!(project.total_effort_person_days(&calendar, &pool), 10.5);
+    }
+
+    #[test]
+    fn test_merge_projects() {
+        use crate::base_structures::{
+            Dependency, DependencyType, DuplicateAllocation, MergeStrategy, NameCollision,
+            WindowConflict,
+        };
+        use crate::cust_exceptions::MergeErrors;
+        use uuid::Uuid;
+
+        let day = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap();
+        let shared_allocation = Uuid::new_v4();
+        let mut main = Project::new("Main", "", day(2), day(20)).unwrap();
+        let mut build = Task::new_regular("Build", day(2), day(6), None).unwrap();
+        build.set_resource_allocation(shared_allocation);
+        main.insert_task(build);
+
+        let make_sub = || {
+            let mut sub = Project::new("Sub", "", day(9), day(27)).unwrap();
+            let design = *sub
+                .insert_task(Task::new_regular("Build", day(9), day(13), None).unwrap())
+                .get_id();
+            let mut test = Task::new_regular("Test", day(16), day(27), None).unwrap();
+            test.add_dependency(Dependency::new(DependencyType::Blocking, design, None));
+            test.set_resource_allocation(shared_allocation);
+            let test = *sub.insert_task(test).get_id();
+            (sub, design, test)
+        };
+        let strict = MergeStrategy {
+            task_names: NameCollision::Reject,
+            window: WindowConflict::Reject,
+            allocations: DuplicateAllocation::Reject,
+        };
+
+        // Каждое строгое правило отклоняет слияние, проект не меняется
+        let (sub, _, test) = make_sub();
+        assert_eq!(
+            main.merge(sub, strict).unwrap_err(),
+            MergeErrors::OutsideProjectWindow { task_id: test }
+        );
+        let (sub, _, _) = make_sub();
+        let window = MergeStrategy {
+            window: WindowConflict::Expand,
+            ..strict
+        };
+        assert_eq!(
+            main.merge(sub, window).unwrap_err(),
+            MergeErrors::TaskNameCollision {
+                name: "Build".into()
+            }
+        );
+        let (sub, _, _) = make_sub();
+        let names = MergeStrategy {
+            task_names: NameCollision::Rename,
+            ..window
+        };
+        assert_eq!(
+            main.merge(sub, names).unwrap_err(),
+            MergeErrors::DuplicateAllocation {
+                allocation_id: shared_allocation
+            }
+        );
+        assert_eq!(main.tasks.len(), 1);
+        assert_eq!(main.date_end, day(20));
+
+        // Мягкие правила: переименование, расширение дат, пропуск повторных назначений
+        let (sub, design, test) = make_sub();
+        let report = main.merge(sub, MergeStrategy::default()).unwrap();
+        assert_eq!(report.added_tasks, vec![design, test]);
+        assert_eq!(
+            report.renamed_tasks,
+            vec![(design, "Build".to_string(), "Build (2)".to_string())]
+        );
+        assert_eq!(report.expanded_window, Some((day(2), day(20))));
+        assert_eq!(report.skipped_allocations, vec![shared_allocation]);
+        assert!(report.allocations.is_empty());
+        assert_eq!(main.date_end, day(27));
+        assert_eq!(main.duration, day(27) - day(2));
+        assert_eq!(main.tasks[&design].name, "Build (2)");
+        assert!(main.tasks[&test].get_resource_allocations().is_empty());
+        // Зависимость по-прежнему указывает на перенесенную задачу, номера выданы заново
+        assert_eq!(main.tasks[&test].get_dependencies()[0].depends_on, design);
+        assert_eq!(main.tasks[&design].get_number(), 2);
+        assert_eq!(main.tasks[&test].get_number(), 3);
+        assert!(main.dependency_violations().is_empty());
+
+        // Повторное слияние тех же задач невозможно
+        let mut again = Project::new("Again", "", day(2), day(27)).unwrap();
+        again.tasks.insert(design, main.tasks[&design].clone());
+        assert_eq!(
+            main.merge(again, MergeStrategy::default()).unwrap_err(),
+            MergeErrors::DuplicateTaskId { task_id: design }
+        );
+    }
+
+    #[test]
+    fn test_cost_by_currency() {
+        use crate::base_structures::{DEFAULT_CURRENCY, RateMeasure, convert_costs};
+        use crate::cust_exceptions::CurrencyErrors;
+        use std::collections::HashMap;
+
+        let day = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap();
+        let mut project = Project::new("Global", "", day(2), day(31)).unwrap();
+        let calendar = project.calendar.clone();
+        let task_id = *project
+            .insert_task(Task::new_regular("Build", day(2), day(6), None).unwrap())
+            .get_id();
+
+        let mut pool = LocalResourcePool::default();
+        let mut anna = Resource::new("Anna".into(), 100.0, RateMeasure::Hourly).unwrap();
+        assert_eq!(anna.get_currency(), DEFAULT_CURRENCY);
+        anna.set_currency("usd").unwrap();
+        let mut boris = Resource::new("Boris".into(), 50.0, RateMeasure::Hourly).unwrap();
+        boris.set_currency("EUR").unwrap();
+        let mut carl = Resource::new("Carl".into(), 10.0, RateMeasure::Hourly).unwrap();
+        carl.set_currency("USD").unwrap();
+        assert_eq!(
+            boris.set_currency("euro"),
+            Err(CurrencyErrors::InvalidCode {
+                code: "euro".into()
+            })
+        );
+        assert_eq!(boris.get_currency(), "EUR");
+
+        // Каждый работает 5 дней по 8 часов
+        for resource in [&anna, &boris, &carl] {
+            let window = TimeWindow::new(day(2), day(6)).unwrap();
+            allocate(&mut project, &mut pool, resource, task_id, 1.0, window);
+        }
+
+        let by_resource = project.cost_by_resource(&calendar, &pool).unwrap();
+        assert_eq!(by_resource.by_currency["USD"][&anna.id], 4000.0);
+        assert_eq!(by_resource.by_currency["USD"][&carl.id], 400.0);
+        assert_eq!(by_resource.by_currency["EUR"].len(), 1);
+        assert!(by_resource.unpriced.is_empty());
+
+        let total = project.total_cost(&calendar, &pool).unwrap();
+        assert_eq!(
+            total,
+            HashMap::from([("USD".to_string(), 4400.0), ("EUR".to_string(), 2000.0)])
+        );
+
+        let rates = HashMap::from([("USD".to_string(), 1.0), ("EUR".to_string(), 1.1)]);
+        assert_eq!(convert_costs(&total, &rates).unwrap(), 4400.0 + 2200.0);
+        assert_eq!(
+            convert_costs(&total, &HashMap::from([("USD".to_string(), 1.0)])),
+            Err(CurrencyErrors::MissingRate {
+                currency: "EUR".into()
+            })
+        );
+
+        // Удаленный из пула ресурс не дает ошибки, его назначение просто не оценено
+        let boris_allocation = pool.get_resource_existing_allocations(&boris.id)[0].get_id();
+        pool.remove_resource(&boris.id).unwrap();
+        let by_resource = project.cost_by_resource(&calendar, &pool).unwrap();
+        assert!(!by_resource.by_currency.contains_key("EUR"));
+        assert_eq!(by_resource.unpriced, vec![boris_allocation]);
+        assert_eq!(
+            project.total_cost(&calendar, &pool).unwrap(),
+            HashMap::from([("USD".to_string(), 4400.0)])
+        );
+    }
+
+    #[test]
+    fn test_project_stats() {
+        use crate::base_structures::{ProjectStats, RateMeasure};
+        use std::collections::HashMap;
+
+        let day = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap();
+        let mut project = Project::new("Stats", "", day(2), day(31)).unwrap();
+        let calendar = project.calendar.clone();
+        assert_eq!(
+            project
+                .stats(&LocalResourcePool::default(), &calendar)
+                .unwrap(),
+            ProjectStats::default()
+        );
+
+        // Пн 2 - Пт 6: 5 рабочих дней, Пн 9 - Пт 13: еще 5
+        let build = *project
+            .insert_task(Task::new_regular("Build", day(2), day(6), None).unwrap())
+            .get_id();
+        let test = *project
+            .insert_task(Task::new_regular("Test", day(9), day(13), None).unwrap())
+            .get_id();
+        project
+            .tasks
+            .get_mut(&test)
+            .unwrap()
+            .set_status(TaskStatus::Processed)
+            .unwrap();
+
+        let mut pool = LocalResourcePool::default();
+        let anna = Resource::new("Anna".into(), 100.0, RateMeasure::Hourly).unwrap();
+        let boris = Resource::new("Boris".into(), 50.0, RateMeasure::Hourly).unwrap();
+        // Anna на обе задачи, Boris на половину ставки на вторую
+        for (resource, task_id, engagement, from, to) in [
+            (&anna, build, 1.0, 2, 6),
+            (&anna, test, 1.0, 9, 13),
+            (&boris, test, 0.5, 9, 13),
+        ] {
+            let window = TimeWindow::new(day(from), day(to)).unwrap();
+            allocate(
+                &mut project,
+                &mut pool,
+                resource,
+                task_id,
+                engagement,
+                window,
+            );
+        }
+
+        let stats = project.stats(&pool, &calendar).unwrap();
+        assert_eq!(stats.task_count(), 2);
+        assert_eq!(stats.count_with_status(&TaskStatus::New), 1);
+        assert_eq!(stats.count_with_status(&TaskStatus::Processed), 1);
+        assert_eq!(stats.count_with_status(&TaskStatus::Closed), 0);
+        assert_eq!(stats.planned_hours, 80);
+        assert_eq!(stats.resource_count, 2);
+        // 4000 + 4000 + 40 часов * 50 * 0.5
+        assert_eq!(
+            stats.total_cost,
+            HashMap::from([("RUB".to_string(), 9000.0)])
+        );
+        assert!(stats.unpriced.is_empty());
+        assert_eq!(stats.earliest_start, Some(day(2)));
+        assert_eq!(stats.latest_end, Some(day(13)));
+
+        let json = serde_json::to_string(&stats).unwrap();
+        assert_eq!(serde_json::from_str::<ProjectStats>(&json).unwrap(), stats);
+
+        // Стоимость в другой валюте не складывается с рублями, удаленный ресурс не оценен
+        let mut john = Resource::new("John".into(), 10.0, RateMeasure::Hourly).unwrap();
+        john.set_currency("USD").unwrap();
+        let window = TimeWindow::new(day(2), day(6)).unwrap();
+        allocate(&mut project, &mut pool, &john, build, 1.0, window);
+        let boris_allocation = pool.get_resource_existing_allocations(&boris.id)[0].get_id();
+        pool.remove_resource(&boris.id).unwrap();
+        let stats = project.stats(&pool, &calendar).unwrap();
+        assert_eq!(
+            stats.total_cost,
+            HashMap::from([("RUB".to_string(), 8000.0), ("USD".to_string(), 400.0)])
+        );
+        assert_eq!(stats.unpriced, vec![boris_allocation]);
+    }
+
+    #[test]
+    fn test_role_placeholders() {
+        use crate::{RolePlaceholder, cust_exceptions::RolePlaceholderErrors};
+
+        let date = |m: u32, d: u32| Utc.with_ymd_and_hms(2026, m, d, 0, 0, 0).unwrap();
+        let mut project = Project::new("Roles", "", date(1, 1), date(6, 30)).unwrap();
+        let window = TimeWindow::new(date(2, 1), date(3, 1)).unwrap();
+
+        assert_eq!(
+            project.add_role_placeholder(RolePlaceholder::new("  ", 1.0, window)),
+            Err(RolePlaceholderErrors::EmptyRole)
+        );
+        assert_eq!(
+            project.add_role_placeholder(RolePlaceholder::new("qa", 0.0, window)),
+            Err(RolePlaceholderErrors::NonPositiveRate { rate: 0.0 })
+        );
+        let late = TimeWindow::new(date(6, 1), date(7, 1)).unwrap();
+        assert_eq!(
+            project.add_role_placeholder(RolePlaceholder::new("qa", 1.0, late)),
+            Err(RolePlaceholderErrors::OutsideProject {
+                start: date(6, 1),
+                end: date(7, 1)
+            })
+        );
+
+        let id = project
+            .add_role_placeholder(RolePlaceholder::new(" qa ", 1.5, window))
+            .unwrap();
+        assert_eq!(project.get_role_placeholders()[0].role, "qa");
+        let copy = project.clone_with_new_ids(
+            "Roles copy".into(),
+            Some(TimeDelta::days(7)),
+            &mut RandomIds,
+        );
+        assert_eq!(
+            copy.get_role_placeholders()[0].window.date_start,
+            date(2, 8)
+        );
+        assert_eq!(
+            project
+                .remove_role_placeholder(&id)
+                .unwrap()
+                .engagement_rate,
+            1.5
+        );
+        assert!(project.get_role_placeholders().is_empty());
+        assert!(project.remove_role_placeholder(&id).is_err());
+    }
+
+    #[test]
+    fn test_budget_periods() {
+        use crate::{BudgetPeriod, base_structures::RateMeasure, cust_exceptions::BudgetErrors};
+
+        let date = |m: u32, d: u32| Utc.with_ymd_and_hms(2026, m, d, 0, 0, 0).unwrap();
+        let window = |from: (u32, u32), to: (u32, u32)| {
+            TimeWindow::new(date(from.0, from.1), date(to.0, to.1)).unwrap()
+        };
+        let mut project = Project::new("Budget", "", date(1, 1), date(12, 31)).unwrap();
+        let calendar = project.calendar.clone();
+        let q1 = BudgetPeriod::new(window((1, 1), (3, 31)), 5000.0);
+        let q2 = BudgetPeriod::new(window((4, 1), (6, 30)), 20000.0);
+
+        assert_eq!(
+            project.set_budget_periods(
+                vec![q1, BudgetPeriod::new(window((3, 1), (4, 30)), 1.0)],
+                &calendar
+            ),
+            Err(BudgetErrors::PeriodsOverlap { start: date(3, 1) })
+        );
+        // Общий день 01.04 попал бы в затраты обоих периодов
+        assert_eq!(
+            project.set_budget_periods(
+                vec![
+                    BudgetPeriod::new(window((1, 1), (4, 1)), 5000.0),
+                    BudgetPeriod::new(window((4, 1), (6, 30)), 5000.0),
+                ],
+                &calendar
+            ),
+            Err(BudgetErrors::PeriodsOverlap { start: date(4, 1) })
+        );
+        assert!(matches!(
+            project.set_budget_periods(
+                vec![BudgetPeriod::new(window((1, 1), (3, 31)), 0.0)],
+                &calendar
+            ),
+            Err(BudgetErrors::NonPositiveAmount { .. })
+        ));
+        let mut late = project.clone();
+        late.date_end = date(6, 1);
+        assert!(matches!(
+            late.set_budget_periods(vec![q1, q2], &calendar),
+            Err(BudgetErrors::PeriodOutsideProject { .. })
+        ));
+        assert!(project.get_budget_periods().is_empty());
+        project.set_budget_periods(vec![q2, q1], &calendar).unwrap();
+        assert_eq!(project.get_budget_periods(), &vec![q1, q2]);
+
+        // 10 рабочих дней на стыке кварталов: 7 в Q1 и 3 в Q2, всего 8000
+        let task_id = *project
+            .insert_task(Task::new_regular("Build", date(3, 23), date(4, 3), None).unwrap())
+            .get_id();
+        let mut pool = LocalResourcePool::default();
+        let resource = Resource::new("Anna".into(), 100.0, RateMeasure::Hourly).unwrap();
+        let allocation_id = allocate(
+            &mut project,
+            &mut pool,
+            &resource,
+            task_id,
+            1.0,
+            window((3, 23), (4, 3)),
+        );
+
+        // Общий лимит 25000 не превышен, но Q1 перерасходован
+        let statuses = project.budget_periods_status(&calendar, &pool).unwrap();
+        assert_eq!(statuses[0].planned, 5600.0);
+        assert!(statuses[0].is_over_limit());
+        assert_eq!(statuses[1].planned, 2400.0);
+        assert!(!statuses[1].is_over_limit());
+        let planned: f64 = statuses.iter().map(|s| s.planned).sum();
+        assert_eq!(
+            planned,
+            pool.calculate_allocation_cost(&allocation_id, &calendar, &project.rate_config)
+                .unwrap()
+        );
+        assert!(statuses.iter().all(|s| s.other_currencies.is_empty()));
+
+        // Затраты в другой валюте не входят в лимит периода, а показываются отдельно
+        let mut john = Resource::new("John".into(), 10.0, RateMeasure::Hourly).unwrap();
+        john.set_currency("USD").unwrap();
+        let john_allocation = allocate(
+            &mut project,
+            &mut pool,
+            &john,
+            task_id,
+            1.0,
+            window((3, 23), (4, 3)),
+        );
+        let with_usd = project.budget_periods_status(&calendar, &pool).unwrap();
+        assert_eq!(with_usd[0].planned, 5600.0);
+        assert_eq!(with_usd[0].other_currencies["USD"], 560.0);
+        assert_eq!(with_usd[1].other_currencies["USD"], 240.0);
+        project
+            .tasks
+            .get_mut(&task_id)
+            .unwrap()
+            .remove_resource_allocation(&john_allocation);
+        pool.deallocate(john_allocation).unwrap();
+
+        // Периоды входят в общий статус бюджета, расхождение суммы лимитов видно сразу
+        project.set_budget(Some(25000.0), "RUB").unwrap();
+        let status = project.budget_status(&calendar, &pool).unwrap();
+        assert_eq!(status.periods, statuses);
+        assert_eq!(status.periods_mismatch, None);
+        project.set_budget(Some(30000.0), "RUB").unwrap();
+        let status = project.budget_status(&calendar, &pool).unwrap();
+        assert_eq!(status.periods_mismatch, Some(-5000.0));
+    }
+
+    #[test]
+    fn test_baseline_variance() {
+        let day = |d: u32| Utc.with_ymd_and_hms(2025, 3, d, 0, 0, 0).unwrap();
+        let mut project = Project::new("Baseline", "", day(1), day(31)).unwrap();
+        assert!(project.baseline_variance().is_none());
+
+        // План пустого проекта снят, хотя дат в нем нет
+        let mut empty = project.clone();
+        empty.snapshot_baseline(day(1));
+        assert!(empty.has_baseline());
+        assert_eq!(empty.get_baseline_taken_at(), Some(day(1)));
+        assert_eq!(empty.baseline_variance(), Some(BaselineVariance::default()));
+
+        let design_id = *project
+            .insert_task(Task::new_regular("Design", day(2), day(6), None).unwrap())
+            .get_id();
+        let build_id = *project
+            .insert_task(Task::new_regular("Build", day(9), day(13), None).unwrap())
+            .get_id();
+        project.snapshot_baseline(day(1));
+        assert_eq!(
+            project.baseline_variance(),
+            Some(BaselineVariance::default())
+        );
+
+        let build = project.tasks.get_mut(&build_id).unwrap();
+        build.date_start += TimeDelta::days(2);
+        build.date_end += TimeDelta::days(2);
+        project.tasks.remove(&design_id);
+        let test_id = *project
+            .insert_task(Task::new_regular("Test", day(16), day(20), None).unwrap())
+            .get_id();
+
+        // План сохраняется вместе с проектом
+        let json = serde_json::to_string(&project).unwrap();
+        let project: Project = serde_json::from_str(&json).unwrap();
+        let variance = project.baseline_variance().unwrap();
+        assert_eq!(
+            variance.shifted,
+            vec![TaskVariance {
+                task_id: build_id,
+                start_slip: TimeDelta::days(2),
+                end_slip: TimeDelta::days(2),
+            }]
+        );
+        assert_eq!(variance.added, vec![test_id]);
+        assert_eq!(variance.removed, vec![design_id]);
+
+        // Повторный план заменяет прежний
+        let mut project = project;
+        project.snapshot_baseline(day(1));
+        assert_eq!(
+            project.baseline_variance(),
+            Some(BaselineVariance::default())
+        );
+
+        // Файл без отметки времени: план есть, если он не пустой
+        let mut legacy = serde_json::to_value(&project).unwrap();
+        legacy.as_object_mut().unwrap().remove("baseline_taken_at");
+        let legacy: Project = serde_json::from_value(legacy).unwrap();
+        assert!(legacy.has_baseline());
+        assert_eq!(legacy.get_baseline_taken_at(), None);
+    }
+
+    #[test]
+    fn test_schedule_variance() {
+        let day = |d: u32| Utc.with_ymd_and_hms(2025, 3, d, 0, 0, 0).unwrap();
+        let mut project = Project::new("Variance", "", day(1), day(31)).unwrap();
+        let design_id = *project
+            .insert_task(Task::new_regular("Design", day(2), day(6), None).unwrap())
+            .get_id();
+        assert!(project.schedule_variance().is_empty());
+        project.snapshot_baseline(day(1));
+
+        let design = project.tasks.get_mut(&design_id).unwrap();
+        design.date_end += TimeDelta::days(3);
+        let json = serde_json::to_string(&project).unwrap();
+        let project: Project = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            project.schedule_variance(),
+            vec![(design_id, TimeDelta::days(3))]
+        );
+    }
+
+    #[test]
+    fn test_task_number_counter_survives_save_load() {
+        let date_start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let date_end = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let mut project = Project::new("TestProject", "", date_start, date_end).unwrap();
+        let task_id = *project
+            .insert_task(Task::new_regular("First", date_start, date_end, None).unwrap())
+            .get_id();
+        project.tasks.remove(&task_id);
+
+        let json = serde_json::to_string(&project).unwrap();
+        let mut loaded: Project = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.get_last_task_number(), 1);
+        assert_eq!(loaded.next_task_number(), 2);
+    }
 }
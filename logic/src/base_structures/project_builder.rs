@@ -0,0 +1,166 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::base_structures::{
+    project::Project,
+    project_calendar::ProjectCalendar,
+    resource::Resource,
+    traits::{BasicGettersForStructures, ProjectContainer},
+    validation_policy::ValidationPolicy,
+};
+
+/// Пошаговое создание проекта
+///
+/// Имя и даты обязательны, календарь и ограничения по умолчанию берутся стандартные.
+/// Ресурсы живут в пуле контейнера, поэтому проект с ресурсами создается через `build_into`.
+#[derive(Debug, Default)]
+pub struct ProjectBuilder {
+    name: String,
+    description: String,
+    dates: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    calendar: Option<ProjectCalendar>,
+    validation_policy: ValidationPolicy,
+    resources: Vec<Resource>,
+}
+
+impl ProjectBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    pub fn dates(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.dates = Some((start, end));
+        self
+    }
+
+    pub fn calendar(mut self, calendar: ProjectCalendar) -> Self {
+        self.calendar = Some(calendar);
+        self
+    }
+
+    pub fn validation_policy(mut self, policy: ValidationPolicy) -> Self {
+        self.validation_policy = policy;
+        self
+    }
+
+    /// Ресурс, который добавится в пул контейнера вместе с проектом
+    pub fn with_resource(mut self, resource: Resource) -> Self {
+        self.resources.push(resource);
+        self
+    }
+
+    /// Создать проект. Начало должно быть раньше окончания (как в `Project::new`)
+    pub fn build(self) -> Result<Project> {
+        if !self.resources.is_empty() {
+            anyhow::bail!("Project with resources must be built into a container (build_into)");
+        }
+        self.build_project()
+    }
+
+    /// Создать проект, добавить его в контейнер, а ресурсы - в пул контейнера
+    pub fn build_into<C: ProjectContainer>(mut self, container: &mut C) -> Result<Uuid> {
+        let resources = std::mem::take(&mut self.resources);
+        let project = self.build_project()?;
+        let project_id = *project.get_id();
+        container.add_project(project)?;
+        for resource in resources {
+            container.resource_pool_mut().add_resource(resource)?;
+        }
+        Ok(project_id)
+    }
+
+    fn build_project(self) -> Result<Project> {
+        let (start, end) = self
+            .dates
+            .ok_or_else(|| anyhow::anyhow!("Project dates are not set"))?;
+        let mut project = Project::new_with_policy(
+            self.name,
+            self.description,
+            start,
+            end,
+            self.validation_policy,
+        )?;
+        if let Some(calendar) = self.calendar {
+            project.calendar = calendar;
+        }
+        Ok(project)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone, Weekday};
+
+    use super::*;
+    use crate::{RateMeasure, SingleProjectContainer};
+
+    #[test]
+    fn test_build_project_with_custom_calendar() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 6, 30, 0, 0, 0).unwrap();
+        let mut calendar = ProjectCalendar::default();
+        calendar.set_hours_for(Weekday::Fri, 6);
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2026, 3, 9).unwrap());
+
+        let project = ProjectBuilder::new()
+            .name("Website")
+            .description("Relaunch")
+            .dates(start, end)
+            .calendar(calendar)
+            .build()
+            .unwrap();
+        assert_eq!(project.name, "Website");
+        assert_eq!(project.description, "Relaunch");
+        assert_eq!(project.calendar.hours_for(Weekday::Fri), 6);
+        assert!(
+            !project
+                .calendar
+                .is_working_day(NaiveDate::from_ymd_opt(2026, 3, 9).unwrap())
+        );
+
+        // Даты обязательны и проверяются как в Project::new
+        assert!(ProjectBuilder::new().name("No dates").build().is_err());
+        assert!(
+            ProjectBuilder::new()
+                .name("Reversed")
+                .dates(end, start)
+                .build()
+                .is_err()
+        );
+
+        // Ресурсы попадают в пул контейнера, календарь - в контейнер
+        let resource = Resource::new("Max".into(), 100.0, RateMeasure::Hourly).unwrap();
+        let resource_id = resource.id;
+        let builder = ProjectBuilder::new()
+            .name("Seeded")
+            .dates(start, end)
+            .calendar(ProjectCalendar::new(7))
+            .with_resource(resource);
+        let mut container = SingleProjectContainer::new();
+        let project_id = builder.build_into(&mut container).unwrap();
+        assert!(
+            container
+                .resource_pool()
+                .get_resource(&resource_id)
+                .is_some()
+        );
+        assert_eq!(
+            container
+                .calendar(&project_id)
+                .unwrap()
+                .hours_for(Weekday::Mon),
+            7
+        );
+    }
+}
@@ -1,72 +1,673 @@
-use chrono::{Datelike, NaiveDate, Weekday};
+use chrono::{
+    DateTime, Datelike, NaiveDate, NaiveTime, TimeDelta, TimeZone, Timelike, Utc, Weekday,
+};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+};
 
-use crate::base_structures::time_window::TimeWindow;
+use crate::{
+    base_structures::{
+        availability::AvailabilityException,
+        calendar_diff::{CalendarDiff, MergePolicy},
+        calendar_kind::CalendarKind,
+        holiday_presets::CountryPreset,
+        time_window::TimeWindow,
+    },
+    cust_exceptions::CalendarErrors,
+};
+
+/// Предел поиска рабочего дня, в днях
+const MAX_SEARCH_DAYS: i64 = 366;
+
+/// Предел сдвига на рабочие дни за одну операцию (около ста лет)
+pub const MAX_SHIFT_WORKING_DAYS: u32 = 36_600;
+
+/// Часов в рабочем дне по умолчанию
+const DEFAULT_HOURS_PER_DAY: u32 = 8;
+
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+/// Рабочий интервал дня по умолчанию, 09:00-18:00
+fn default_workday_span() -> (NaiveTime, NaiveTime) {
+    (
+        NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+        NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+    )
+}
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+/// Направление поиска рабочего дня
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapDirection {
+    Forward,
+    Backward,
+}
 
 /// Глобальный календарь проекта/компании
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(from = "CalendarRepr")]
 pub struct ProjectCalendar {
-    /// Рабочие дни недели (по умолчанию пн-пт)
-    working_days: HashSet<Weekday>,
+    /// Модель рабочих дней: по дням недели или сменный график
+    kind: CalendarKind,
+
+    /// Рабочие часы по дням недели, день с 0 часов - нерабочий (по умолчанию пн-пт по 8)
+    working_hours: HashMap<Weekday, u32>,
 
     /// Праздничные/нерабочие дни (конкретные даты)
     holidays: HashSet<NaiveDate>,
 
-    /// Часов в рабочем дне (для пересчета в трудозатраты)
-    pub working_hours_per_day: u32,
+    /// Сокращенные дни (предпраздничные): дата -> часы, меньше обычных для дня недели
+    partial_days: HashMap<NaiveDate, u32>,
+
+    /// Перенесенные рабочие дни: выходные даты, объявленные рабочими
+    working_exceptions: HashSet<NaiveDate>,
+
+    /// Рабочий интервал дня: по нему обрезаются первый и последний день окна
+    workday_start: NaiveTime,
+    workday_end: NaiveTime,
+
+    /// Часовой пояс календаря: границы окон переводятся в его локальные даты (по умолчанию UTC)
+    timezone: Tz,
+
+    /// Закрытия для всех ресурсов календаря (например, офис закрыт неделю)
+    exceptions: Vec<AvailabilityException>,
 }
 
-impl Default for ProjectCalendar {
-    fn default() -> Self {
-        let mut working_days = HashSet::new();
-        working_days.insert(Weekday::Mon);
-        working_days.insert(Weekday::Tue);
-        working_days.insert(Weekday::Wed);
-        working_days.insert(Weekday::Thu);
-        working_days.insert(Weekday::Fri);
+/// Формат файла: старые файлы хранят набор рабочих дней и одно число часов в дне
+#[derive(Deserialize)]
+struct CalendarRepr {
+    #[serde(default)]
+    kind: CalendarKind,
+    #[serde(default)]
+    working_hours: Option<HashMap<Weekday, u32>>,
+    #[serde(default)]
+    working_days: Option<HashSet<Weekday>>,
+    #[serde(default)]
+    working_hours_per_day: Option<u32>,
+    #[serde(default)]
+    holidays: HashSet<NaiveDate>,
+    #[serde(default)]
+    partial_days: HashMap<NaiveDate, u32>,
+    #[serde(default)]
+    working_exceptions: HashSet<NaiveDate>,
+    #[serde(default)]
+    workday_start: Option<NaiveTime>,
+    #[serde(default)]
+    workday_end: Option<NaiveTime>,
+    #[serde(default)]
+    timezone: Option<Tz>,
+    #[serde(default)]
+    exceptions: Vec<AvailabilityException>,
+}
 
+impl From<CalendarRepr> for ProjectCalendar {
+    fn from(repr: CalendarRepr) -> Self {
+        let working_hours = repr.working_hours.unwrap_or_else(|| {
+            let hours = repr.working_hours_per_day.unwrap_or(DEFAULT_HOURS_PER_DAY);
+            let days = repr
+                .working_days
+                .unwrap_or_else(|| WEEKDAYS[..5].iter().copied().collect());
+            WEEKDAYS
+                .iter()
+                .map(|d| (*d, if days.contains(d) { hours } else { 0 }))
+                .collect()
+        });
+        let (default_start, default_end) = default_workday_span();
         Self {
-            working_days,
-            holidays: HashSet::new(),
-            working_hours_per_day: 8,
+            kind: repr.kind,
+            working_hours,
+            holidays: repr.holidays,
+            partial_days: repr.partial_days,
+            working_exceptions: repr.working_exceptions,
+            workday_start: repr.workday_start.unwrap_or(default_start),
+            workday_end: repr.workday_end.unwrap_or(default_end),
+            timezone: repr.timezone.unwrap_or(Tz::UTC),
+            exceptions: repr.exceptions,
         }
     }
 }
 
+impl Default for ProjectCalendar {
+    fn default() -> Self {
+        Self::new(DEFAULT_HOURS_PER_DAY)
+    }
+}
+
 impl ProjectCalendar {
+    /// Календарь пн-пт с одинаковым числом часов в каждом рабочем дне
     pub fn new(working_hours_per_day: u32) -> Self {
+        let working_hours = WEEKDAYS
+            .iter()
+            .enumerate()
+            .map(|(i, d)| (*d, if i < 5 { working_hours_per_day } else { 0 }))
+            .collect();
+        let (workday_start, workday_end) = default_workday_span();
         Self {
-            working_hours_per_day,
-            ..Default::default()
+            kind: CalendarKind::Weekly,
+            working_hours,
+            holidays: HashSet::new(),
+            partial_days: HashMap::new(),
+            working_exceptions: HashSet::new(),
+            workday_start,
+            workday_end,
+            timezone: Tz::UTC,
+            exceptions: vec![],
+        }
+    }
+
+    pub fn timezone(&self) -> Tz {
+        self.timezone
+    }
+
+    /// Задать часовой пояс календаря (IANA, например `Europe/Moscow`)
+    pub fn set_timezone(&mut self, timezone: Tz) {
+        self.timezone = timezone;
+    }
+
+    /// Локальная дата момента в часовом поясе календаря
+    pub fn local_date(&self, moment: DateTime<Utc>) -> NaiveDate {
+        moment.with_timezone(&self.timezone).date_naive()
+    }
+
+    /// Начало локальной даты календаря в UTC. Если полночь выпадает на перевод часов,
+    /// берется первый существующий момент после нее
+    ///
+    /// Так же задается и дата окончания: граница ровно в полночь означает, что день
+    /// входит в окно целиком (см. `working_hours_in_period`).
+    pub fn local_midnight(&self, date: NaiveDate) -> DateTime<Utc> {
+        let midnight = date.and_time(NaiveTime::MIN);
+        (0..=1)
+            .find_map(|h| {
+                self.timezone
+                    .from_local_datetime(&(midnight + TimeDelta::hours(h)))
+                    .earliest()
+            })
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|| midnight.and_utc())
+    }
+
+    /// Рабочий интервал дня (начало, конец)
+    pub fn workday_span(&self) -> (NaiveTime, NaiveTime) {
+        (self.workday_start, self.workday_end)
+    }
+
+    /// Задать рабочий интервал дня, начало должно быть раньше конца
+    pub fn set_workday_span(
+        &mut self,
+        start: NaiveTime,
+        end: NaiveTime,
+    ) -> Result<(), CalendarErrors> {
+        if start >= end {
+            return Err(CalendarErrors::InvalidWorkdaySpan { start, end });
         }
+        self.workday_start = start;
+        self.workday_end = end;
+        Ok(())
+    }
+
+    pub fn kind(&self) -> &CalendarKind {
+        &self.kind
+    }
+
+    /// Задать модель рабочих дней. Цикл сменного графика должен содержать рабочий день
+    pub fn set_kind(&mut self, kind: CalendarKind) -> Result<(), CalendarErrors> {
+        if let CalendarKind::ShiftPattern { cycle, .. } = &kind
+            && cycle.iter().all(|day| day.hours() == 0)
+        {
+            return Err(CalendarErrors::NoWorkingShiftDays);
+        }
+        self.kind = kind;
+        Ok(())
+    }
+
+    /// Рабочие часы в день недели (без учета праздников)
+    pub fn hours_for(&self, weekday: Weekday) -> u32 {
+        self.working_hours.get(&weekday).copied().unwrap_or(0)
+    }
+
+    /// Задать рабочие часы дня недели, 0 - день становится нерабочим
+    pub fn set_hours_for(&mut self, weekday: Weekday, hours: u32) {
+        self.working_hours.insert(weekday, hours);
+    }
+
+    /// Рабочие часы в конкретную дату
+    ///
+    /// Порядок: праздник (0 часов) важнее сокращенного дня, сокращенный день - переноса
+    /// рабочего дня, перенос - обычных часов дня недели.
+    pub fn hours_on(&self, date: NaiveDate) -> u32 {
+        if self.holidays.contains(&date) {
+            return 0;
+        }
+        match self.partial_days.get(&date) {
+            Some(hours) => *hours,
+            None => self.normal_hours(date),
+        }
+    }
+
+    /// Часы даты без праздников и сокращений: перенесенный рабочий день в выходной
+    /// получает самый длинный рабочий день календаря
+    fn normal_hours(&self, date: NaiveDate) -> u32 {
+        let hours = self.base_hours(date);
+        if hours == 0 && self.working_exceptions.contains(&date) {
+            return self.standard_hours();
+        }
+        hours
+    }
+
+    /// Часы даты по модели календаря: день недели или позиция в цикле смен от `anchor`
+    fn base_hours(&self, date: NaiveDate) -> u32 {
+        match &self.kind {
+            CalendarKind::Weekly => self.hours_for(date.weekday()),
+            CalendarKind::ShiftPattern { cycle, anchor } => {
+                if cycle.is_empty() {
+                    return 0;
+                }
+                let position = (date - *anchor).num_days().rem_euclid(cycle.len() as i64);
+                cycle[position as usize].hours()
+            }
+        }
+    }
+
+    /// Длина периода, через который повторяются часы: неделя или цикл смен
+    fn period_days(&self) -> i64 {
+        match &self.kind {
+            CalendarKind::ShiftPattern { cycle, .. } if !cycle.is_empty() => cycle.len() as i64,
+            _ => 7,
+        }
+    }
+
+    /// Самый длинный рабочий день (по умолчанию 8 часов, если рабочих дней нет)
+    fn standard_hours(&self) -> u32 {
+        let longest = match &self.kind {
+            CalendarKind::Weekly => self.working_hours.values().copied().max(),
+            CalendarKind::ShiftPattern { cycle, .. } => cycle.iter().map(|d| d.hours()).max(),
+        };
+        longest.filter(|h| *h > 0).unwrap_or(DEFAULT_HOURS_PER_DAY)
+    }
+
+    /// Объявить дату рабочей, даже если день недели выходной. Праздник на ту же дату
+    /// остается нерабочим
+    pub fn add_working_exception(&mut self, date: NaiveDate) {
+        self.working_exceptions.insert(date);
     }
 
-    /// Является ли дата рабочим днем?
+    pub fn remove_working_exception(&mut self, date: NaiveDate) {
+        self.working_exceptions.remove(&date);
+    }
+
+    /// Добавить сокращенный день: часов больше нуля и меньше обычного для этой даты
+    pub fn add_partial_day(&mut self, date: NaiveDate, hours: u32) -> Result<(), CalendarErrors> {
+        let normal_hours = self.normal_hours(date);
+        if hours == 0 || hours >= normal_hours {
+            return Err(CalendarErrors::InvalidPartialDay {
+                date,
+                hours,
+                normal_hours,
+            });
+        }
+        self.partial_days.insert(date, hours);
+        Ok(())
+    }
+
+    /// Убрать сокращенный день, дата снова работает по обычным часам
+    pub fn remove_partial_day(&mut self, date: NaiveDate) {
+        self.partial_days.remove(&date);
+    }
+
+    /// Задать рабочие дни недели (например, четырехдневная неделя подрядчика)
+    ///
+    /// Дни, которые уже были рабочими, сохраняют свои часы, новые получают самый длинный
+    /// рабочий день календаря.
+    pub fn set_working_days(&mut self, days: impl IntoIterator<Item = Weekday>) {
+        let days: HashSet<Weekday> = days.into_iter().collect();
+        let standard = self.standard_hours();
+        for weekday in WEEKDAYS {
+            let hours = match (days.contains(&weekday), self.hours_for(weekday)) {
+                (false, _) => 0,
+                (true, 0) => standard,
+                (true, hours) => hours,
+            };
+            self.working_hours.insert(weekday, hours);
+        }
+    }
+
+    pub fn get_working_days(&self) -> HashSet<Weekday> {
+        WEEKDAYS
+            .iter()
+            .copied()
+            .filter(|d| self.hours_for(*d) > 0)
+            .collect()
+    }
+
+    /// Проверить, что в календаре есть рабочие дни недели с ненулевыми часами
+    ///
+    /// Без них поиск рабочего дня и планировщик не могут продвинуться.
+    pub fn validate(&self) -> Result<(), CalendarErrors> {
+        match &self.kind {
+            CalendarKind::Weekly if self.get_working_days().is_empty() => {
+                Err(CalendarErrors::NoWorkingWeekdays)
+            }
+            CalendarKind::ShiftPattern { cycle, .. } if cycle.iter().all(|d| d.hours() == 0) => {
+                Err(CalendarErrors::NoWorkingShiftDays)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// То же, что `validate`, и вдобавок в окне есть хотя бы один рабочий день
+    /// (праздники не закрывают все окно)
+    pub fn validate_for_window(&self, window: &TimeWindow) -> Result<(), CalendarErrors> {
+        self.validate()?;
+        if self.count_working_days(window) == 0 {
+            return Err(CalendarErrors::NoWorkingDaysInWindow {
+                date_start: self.local_date(window.date_start),
+                date_end: self.local_date(window.date_end),
+            });
+        }
+        Ok(())
+    }
+
+    /// Является ли дата рабочим днем? Дата - локальная дата календаря
     pub fn is_working_day(&self, date: NaiveDate) -> bool {
-        let weekday = date.weekday();
-        self.working_days.contains(&weekday) && !self.holidays.contains(&date)
+        self.hours_on(date) > 0
+    }
+
+    /// Приходится ли момент на рабочий день в часовом поясе календаря
+    pub fn is_working_at(&self, moment: DateTime<Utc>) -> bool {
+        self.is_working_day(self.local_date(moment))
+    }
+
+    /// Ближайший рабочий день строго после `after`
+    pub fn next_working_day(&self, after: NaiveDate) -> Result<NaiveDate, CalendarErrors> {
+        self.find_working_day(after, 1)
+    }
+
+    /// Ближайший рабочий день строго до `before`
+    pub fn previous_working_day(&self, before: NaiveDate) -> Result<NaiveDate, CalendarErrors> {
+        self.find_working_day(before, -1)
+    }
+
+    /// Сама дата, если она рабочая, иначе ближайший рабочий день в направлении `direction`
+    pub fn snap_to_working_day(
+        &self,
+        date: NaiveDate,
+        direction: SnapDirection,
+    ) -> Result<NaiveDate, CalendarErrors> {
+        if self.is_working_day(date) {
+            return Ok(date);
+        }
+        match direction {
+            SnapDirection::Forward => self.next_working_day(date),
+            SnapDirection::Backward => self.previous_working_day(date),
+        }
+    }
+
+    /// Сам момент, если он приходится на рабочий день, иначе тот же момент суток
+    /// ближайшего следующего рабочего дня
+    pub fn snap_moment_forward(
+        &self,
+        moment: DateTime<Utc>,
+    ) -> Result<DateTime<Utc>, CalendarErrors> {
+        let local = self.local_date(moment);
+        let working = self.snap_to_working_day(local, SnapDirection::Forward)?;
+        Ok(moment + TimeDelta::days((working - local).num_days()))
+    }
+
+    /// Дата, до которой от `start` проходит `days` рабочих дней
+    ///
+    /// Нерабочее начало сначала переносится на ближайший рабочий день, поэтому 0 дней
+    /// возвращает сам рабочий день начала.
+    pub fn add_working_days(
+        &self,
+        start: NaiveDate,
+        days: u32,
+    ) -> Result<NaiveDate, CalendarErrors> {
+        if days > MAX_SHIFT_WORKING_DAYS {
+            return Err(CalendarErrors::ShiftTooLarge {
+                days: days as i64,
+                max: MAX_SHIFT_WORKING_DAYS,
+            });
+        }
+        let mut current = self.snap_to_working_day(start, SnapDirection::Forward)?;
+        for _ in 0..days {
+            current = self.next_working_day(current)?;
+        }
+        Ok(current)
+    }
+
+    /// Сдвиг даты на `days` рабочих дней вперед или назад
+    ///
+    /// Положительный сдвиг работает как `add_working_days`. При отрицательном нерабочее
+    /// начало переносится на предыдущий рабочий день, от которого отсчитываются дни назад.
+    pub fn shift_working_days(
+        &self,
+        start: NaiveDate,
+        days: i64,
+    ) -> Result<NaiveDate, CalendarErrors> {
+        let steps = u32::try_from(days.unsigned_abs())
+            .ok()
+            .filter(|steps| *steps <= MAX_SHIFT_WORKING_DAYS)
+            .ok_or(CalendarErrors::ShiftTooLarge {
+                days,
+                max: MAX_SHIFT_WORKING_DAYS,
+            })?;
+        if days >= 0 {
+            return self.add_working_days(start, steps);
+        }
+        let mut current = self.snap_to_working_day(start, SnapDirection::Backward)?;
+        for _ in 0..steps {
+            current = self.previous_working_day(current)?;
+        }
+        Ok(current)
+    }
+
+    /// Момент, когда от `start` пройдет `hours` рабочих часов
+    ///
+    /// Часы считаются так же, как в `working_hours_in_period`: только внутри рабочего интервала
+    /// дня и не больше часов этой даты (сокращенные дни дают меньше). 0 часов возвращает
+    /// `start`, если это рабочее время, иначе начало рабочего интервала ближайшего рабочего дня.
+    pub fn add_working_hours(
+        &self,
+        start: DateTime<Utc>,
+        hours: u32,
+    ) -> Result<DateTime<Utc>, CalendarErrors> {
+        let mut current = start;
+        let mut remaining = TimeDelta::hours(hours as i64);
+        loop {
+            let date = self.local_date(current);
+            let midnight = self.local_midnight(date);
+            let span_start = midnight + (self.workday_start - NaiveTime::MIN);
+            let span_end = midnight + (self.workday_end - NaiveTime::MIN);
+            let from = current.max(span_start);
+            let day = TimeDelta::hours(self.hours_on(date) as i64);
+            if from < span_end && day > TimeDelta::zero() {
+                let available = (span_end - from).min(day);
+                if remaining <= available {
+                    return Ok(from + remaining);
+                }
+                remaining -= available;
+            }
+            current = self.local_midnight(self.next_working_day(date)?);
+        }
+    }
+
+    /// Поиск рабочего дня с шагом `step` не дальше MAX_SEARCH_DAYS от `from`
+    fn find_working_day(&self, from: NaiveDate, step: i64) -> Result<NaiveDate, CalendarErrors> {
+        let mut current = from;
+        for _ in 0..MAX_SEARCH_DAYS {
+            // За пределами диапазона NaiveDate рабочих дней тоже нет
+            let Some(next) = current.checked_add_signed(chrono::Duration::days(step)) else {
+                break;
+            };
+            current = next;
+            if self.is_working_day(current) {
+                return Ok(current);
+            }
+        }
+        Err(CalendarErrors::NoWorkingDays {
+            from,
+            searched_days: MAX_SEARCH_DAYS,
+        })
+    }
+
+    /// Рабочие дни окна по возрастанию: локальные даты календаря, начало и окончание
+    /// включительно. Дни проверяются по мере обхода, поэтому первые дни огромного окна
+    /// получаются сразу
+    pub fn working_days_iter<'a>(
+        &'a self,
+        window: &TimeWindow,
+    ) -> impl Iterator<Item = NaiveDate> + 'a {
+        let end = self.local_date(window.date_end);
+        self.local_date(window.date_start)
+            .iter_days()
+            .take_while(move |d| *d <= end)
+            .filter(|d| self.is_working_day(*d))
     }
 
     /// Получить количество рабочих дней в периоде
+    ///
+    /// Целые недели (или циклы смен) считаются арифметически, перебираются только дни хвоста,
+    /// праздники и исключения учитываются поправками - время не зависит от длины периода.
     pub fn count_working_days(&self, window: &TimeWindow) -> u32 {
-        let mut count = 0;
-        let mut current = window.date_start.date_naive();
-        let end = window.date_end.date_naive();
+        self.sum_over_days(window, |hours| u32::from(hours > 0))
+    }
+
+    /// Получить трудозатраты в часах за период (сумма часов по рабочим дням)
+    ///
+    /// Первый и последний день обрезаются по времени окна в пределах рабочего интервала дня
+    /// и не дают больше часов, чем положено этой дате. Граница ровно в полночь означает
+    /// целый день (даты окна включительно). Результат округляется вниз до целых часов.
+    pub fn working_hours_in_period(&self, window: &TimeWindow) -> u32 {
+        let local_start = window.date_start.with_timezone(&self.timezone);
+        let local_end = window.date_end.with_timezone(&self.timezone);
+        let start = local_start.date_naive();
+        let end = local_end.date_naive();
+        if start > end {
+            return 0;
+        }
+        let from = minutes_of_day(local_start.time());
+        let to = match minutes_of_day(local_end.time()) {
+            0 => MINUTES_PER_DAY,
+            minutes => minutes,
+        };
+        if start == end {
+            return self.clipped_minutes(start, from, to) / 60;
+        }
+        let middle =
+            self.sum_by_periods(start + TimeDelta::days(1), end - TimeDelta::days(1), |h| h);
+        (self.clipped_minutes(start, from, MINUTES_PER_DAY)
+            + middle * 60
+            + self.clipped_minutes(end, 0, to))
+            / 60
+    }
+
+    /// Рабочие минуты даты в интервале `[from, to)` минут от полуночи
+    fn clipped_minutes(&self, date: NaiveDate, from: u32, to: u32) -> u32 {
+        let day = self.hours_on(date) * 60;
+        if from == 0 && to == MINUTES_PER_DAY {
+            return day;
+        }
+        let from = from.max(minutes_of_day(self.workday_start));
+        let to = to.min(minutes_of_day(self.workday_end));
+        to.saturating_sub(from).min(day)
+    }
+
+    /// Сумма `value(часы дня)` по локальным дням окна, даты начала и окончания включительно
+    fn sum_over_days(&self, window: &TimeWindow, value: impl Fn(u32) -> u32) -> u32 {
+        let start = self.local_date(window.date_start);
+        let end = self.local_date(window.date_end);
+        self.sum_by_periods(start, end, value)
+    }
+
+    fn sum_by_periods(&self, start: NaiveDate, end: NaiveDate, value: impl Fn(u32) -> u32) -> u32 {
+        if start > end {
+            return 0;
+        }
+        let total_days = (end - start).num_days() + 1;
+        let period = self.period_days();
+        let full_periods = total_days / period;
+        // Любые `period` дней подряд дают одну и ту же сумму
+        let one_period: i64 = (0..period)
+            .map(|i| value(self.base_hours(start + TimeDelta::days(i))) as i64)
+            .sum();
+        let mut total = full_periods * one_period;
 
+        // Хвост меньше периода досчитываем по дням
+        let mut current = start + chrono::Duration::days(full_periods * period);
         while current <= end {
-            if self.is_working_day(current) {
-                count += 1;
-            }
+            total += value(self.base_hours(current)) as i64;
             current += chrono::Duration::days(1);
         }
 
-        count
+        let in_range = |d: &&NaiveDate| **d >= start && **d <= end;
+        let holidays: i64 = self
+            .holidays
+            .iter()
+            .filter(in_range)
+            .map(|d| value(self.base_hours(*d)) as i64)
+            .sum();
+        // Сокращенные дни заменяют обычные часы своего дня
+        let partial: i64 = self
+            .partial_days
+            .iter()
+            .filter(|(d, _)| in_range(d) && !self.holidays.contains(*d))
+            .map(|(d, hours)| value(*hours) as i64 - value(self.base_hours(*d)) as i64)
+            .sum();
+        let exceptions: i64 = self
+            .working_exceptions
+            .iter()
+            .filter(|d| {
+                in_range(d) && !self.holidays.contains(*d) && !self.partial_days.contains_key(*d)
+            })
+            .map(|d| value(self.normal_hours(*d)) as i64 - value(self.base_hours(*d)) as i64)
+            .sum();
+        (total - holidays + partial + exceptions) as u32
     }
 
-    /// Получить трудозатраты в часах за период
-    pub fn working_hours_in_period(&self, window: &TimeWindow) -> u32 {
-        self.count_working_days(window) * self.working_hours_per_day
+    /// Расхождения с другим календарем: праздники, рабочие дни недели и их часы
+    pub fn diff(&self, other: &ProjectCalendar) -> CalendarDiff {
+        let mut diff = CalendarDiff {
+            holidays_only_left: sorted_dates(self.holidays.difference(&other.holidays)),
+            holidays_only_right: sorted_dates(other.holidays.difference(&self.holidays)),
+            ..Default::default()
+        };
+        for weekday in WEEKDAYS {
+            let (left, right) = (self.hours_for(weekday), other.hours_for(weekday));
+            if left == right {
+                continue;
+            }
+            if left == 0 || right == 0 {
+                diff.working_days.push((weekday, left, right));
+            } else {
+                diff.hours.push((weekday, left, right));
+            }
+        }
+        diff
+    }
+
+    /// Слить другой календарь в этот. Сокращенные дни, переносы, рабочий интервал
+    /// и часовой пояс остаются локальными
+    pub fn merge(&mut self, other: &ProjectCalendar, policy: MergePolicy) {
+        match policy {
+            MergePolicy::Union => self.holidays.extend(other.holidays.iter().copied()),
+            MergePolicy::PreferOther => {
+                self.holidays = other.holidays.clone();
+                self.working_hours = other.working_hours.clone();
+                self.kind = other.kind.clone();
+            }
+        }
     }
 
     /// Добавить праздник
@@ -78,4 +679,738 @@ impl ProjectCalendar {
     pub fn remove_holiday(&mut self, date: NaiveDate) {
         self.holidays.remove(&date);
     }
+
+    /// Добавить закрытие для всех ресурсов календаря
+    pub fn add_exception(&mut self, exception: AvailabilityException) {
+        self.exceptions.push(exception);
+    }
+
+    pub fn exceptions(&self) -> &[AvailabilityException] {
+        &self.exceptions
+    }
+
+    /// Праздники по возрастанию даты
+    pub fn holidays(&self) -> Vec<NaiveDate> {
+        sorted_dates(self.holidays.iter())
+    }
+
+    /// Календарь с праздниками страны за годы `years`
+    pub fn with_country_preset(
+        mut self,
+        country: CountryPreset,
+        years: RangeInclusive<i32>,
+    ) -> Self {
+        self.apply_country_preset(country, years);
+        self
+    }
+
+    /// Добавить праздники страны к уже заданным. Повторное применение ничего не меняет
+    pub fn apply_country_preset(&mut self, country: CountryPreset, years: RangeInclusive<i32>) {
+        self.holidays.extend(country.holidays(years));
+    }
+}
+
+fn sorted_dates<'a>(dates: impl Iterator<Item = &'a NaiveDate>) -> Vec<NaiveDate> {
+    let mut dates: Vec<NaiveDate> = dates.copied().collect();
+    dates.sort();
+    dates
+}
+
+fn minutes_of_day(time: NaiveTime) -> u32 {
+    time.num_seconds_from_midnight() / 60
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    /// Эталонный подсчет перебором дней
+    fn sum_naive(
+        calendar: &ProjectCalendar,
+        window: &TimeWindow,
+        value: impl Fn(u32) -> u32,
+    ) -> u32 {
+        let mut total = 0;
+        let mut current = window.date_start.date_naive();
+        while current <= window.date_end.date_naive() {
+            total += value(calendar.hours_on(current));
+            current += chrono::Duration::days(1);
+        }
+        total
+    }
+
+    /// Детерминированный генератор (LCG), чтобы случайные тесты были воспроизводимыми:
+    /// каждый вызов дает число в `[0, limit)`
+    fn lcg(mut seed: u64) -> impl FnMut(u64) -> u64 {
+        move |limit| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 33) % limit
+        }
+    }
+
+    #[test]
+    fn test_week_arithmetic_matches_naive_on_random_windows() {
+        let mut calendar = ProjectCalendar::default();
+        calendar.set_hours_for(Weekday::Fri, 6);
+        let base = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let mut next = lcg(0x2545_f491_4f6c_dd1d);
+        for _ in 0..40 {
+            let day = base + chrono::Duration::days(next(1500) as i64);
+            match next(3) {
+                0 => calendar.add_holiday(day),
+                1 => calendar.add_working_exception(day),
+                _ => {
+                    let _ = calendar.add_partial_day(day, 1 + next(5) as u32);
+                }
+            }
+        }
+
+        for _ in 0..300 {
+            let start = base + chrono::Duration::days(next(1500) as i64);
+            let end = start + chrono::Duration::days(next(1200) as i64);
+            let window = TimeWindow {
+                date_start: Utc.from_utc_datetime(&start.and_hms_opt(9, 0, 0).unwrap()),
+                date_end: Utc.from_utc_datetime(&end.and_hms_opt(18, 0, 0).unwrap()),
+            };
+            assert_eq!(
+                calendar.count_working_days(&window),
+                sum_naive(&calendar, &window, |h| u32::from(h > 0)),
+                "{} - {}",
+                start,
+                end
+            );
+            assert_eq!(
+                calendar.working_hours_in_period(&window),
+                sum_naive(&calendar, &window, |h| h),
+                "{} - {}",
+                start,
+                end
+            );
+        }
+    }
+
+    #[test]
+    fn test_working_days_iter_matches_count() {
+        let mut calendar = ProjectCalendar::default();
+        calendar.set_timezone(chrono_tz::Asia::Tokyo);
+        let base = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let mut next = lcg(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..20 {
+            let day = base + TimeDelta::days(next(700) as i64);
+            if next(2) == 0 {
+                calendar.add_holiday(day);
+            } else {
+                calendar.add_working_exception(day);
+            }
+        }
+
+        for _ in 0..200 {
+            let start = Utc.from_utc_datetime(
+                &(base + TimeDelta::days(next(700) as i64))
+                    .and_hms_opt(next(24) as u32, 0, 0)
+                    .unwrap(),
+            );
+            let window = TimeWindow {
+                date_start: start,
+                date_end: start + TimeDelta::hours(next(24 * 90) as i64),
+            };
+            let days: Vec<NaiveDate> = calendar.working_days_iter(&window).collect();
+            assert_eq!(days.len() as u32, calendar.count_working_days(&window));
+            assert!(days.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(days.iter().all(|d| calendar.is_working_day(*d)));
+        }
+
+        // 23:00 UTC 2 января - уже пятница 3 января в Токио
+        let window = TimeWindow {
+            date_start: Utc.with_ymd_and_hms(2025, 1, 2, 23, 0, 0).unwrap(),
+            date_end: Utc.with_ymd_and_hms(9000, 1, 1, 0, 0, 0).unwrap(),
+        };
+        let first: Vec<NaiveDate> = calendar.working_days_iter(&window).take(1).collect();
+        assert_eq!(
+            first[0],
+            calendar
+                .snap_to_working_day(
+                    NaiveDate::from_ymd_opt(2025, 1, 3).unwrap(),
+                    SnapDirection::Forward
+                )
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_long_periods_counted_by_weeks() {
+        let mut calendar = ProjectCalendar::default();
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()); // среда
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2025, 1, 4).unwrap()); // суббота
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        // Годы и десятилетия считаются неделями и совпадают с обходом по дням
+        for days in [1000, 3653, 36524] {
+            let window = TimeWindow {
+                date_start: start,
+                date_end: start + TimeDelta::days(days),
+            };
+            let by_days = calendar.working_days_iter(&window).count() as u32;
+            assert_eq!(
+                calendar.count_working_days(&window),
+                by_days,
+                "{} days",
+                days
+            );
+            assert_eq!(
+                calendar.working_hours_in_period(&window),
+                by_days * DEFAULT_HOURS_PER_DAY,
+                "{} days",
+                days
+            );
+        }
+    }
+
+    #[test]
+    fn test_partial_first_and_last_days_clipped() {
+        let calendar = ProjectCalendar::default();
+        let at = |d: u32, h: u32| Utc.with_ymd_and_hms(2026, 3, d, h, 0, 0).unwrap();
+        // Вторник 3 марта 2026, 13:00-17:00
+        let afternoon = TimeWindow::new(at(3, 13), at(3, 17)).unwrap();
+        assert_eq!(calendar.working_hours_in_period(&afternoon), 4);
+        // Весь день с 9:00 до 18:00 ограничен часами дня
+        let full_day = TimeWindow::new(at(3, 9), at(3, 18)).unwrap();
+        assert_eq!(calendar.working_hours_in_period(&full_day), 8);
+        // Вечер вне рабочего интервала
+        let evening = TimeWindow::new(at(3, 19), at(3, 23)).unwrap();
+        assert_eq!(calendar.working_hours_in_period(&evening), 0);
+
+        // Пятница 6 марта с 13:00 до понедельника 9 марта 12:00: 5 + 3 часа
+        let weekend = TimeWindow::new(at(6, 13), at(9, 12)).unwrap();
+        assert_eq!(calendar.working_hours_in_period(&weekend), 8);
+        // Окно в полночь по-прежнему считает даты целиком
+        let whole_days = TimeWindow::new(at(6, 0), at(9, 0)).unwrap();
+        assert_eq!(calendar.working_hours_in_period(&whole_days), 16);
+    }
+
+    #[test]
+    fn test_timezone_shifts_local_dates() {
+        let utc = ProjectCalendar::default();
+        let mut moscow = ProjectCalendar::default();
+        moscow.set_timezone(chrono_tz::Europe::Moscow);
+        // Понедельник 10 марта 2025, конец окна 22:00 UTC = 01:00 11 марта по Москве
+        let window = TimeWindow::new(
+            Utc.with_ymd_and_hms(2025, 3, 10, 6, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 3, 10, 22, 0, 0).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(utc.count_working_days(&window), 1);
+        assert_eq!(moscow.count_working_days(&window), 2);
+        // 09:00-18:00 по Москве приходится на 06:00-15:00 UTC
+        assert_eq!(utc.working_hours_in_period(&window), 8);
+        assert_eq!(moscow.working_hours_in_period(&window), 8);
+
+        // Суббота 22:00 UTC - уже воскресенье в Москве, понедельник 22:00 - вторник
+        let saturday_night = Utc.with_ymd_and_hms(2025, 3, 15, 22, 0, 0).unwrap();
+        assert_eq!(
+            moscow.local_date(saturday_night),
+            NaiveDate::from_ymd_opt(2025, 3, 16).unwrap()
+        );
+        assert!(!moscow.is_working_at(saturday_night));
+        let sunday_night = Utc.with_ymd_and_hms(2025, 3, 16, 22, 0, 0).unwrap();
+        assert!(!utc.is_working_at(sunday_night));
+        assert!(moscow.is_working_at(sunday_night));
+
+        // Рабочие часы отсчитываются от местных 09:00 (06:00 UTC)
+        assert_eq!(
+            moscow.add_working_hours(sunday_night, 2),
+            Ok(Utc.with_ymd_and_hms(2025, 3, 17, 8, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_timezone_serialized_as_iana_name() {
+        let mut calendar = ProjectCalendar::default();
+        calendar.set_timezone(chrono_tz::Europe::Lisbon);
+        let json = serde_json::to_string(&calendar).unwrap();
+        assert!(json.contains(r#""timezone":"Europe/Lisbon""#));
+        let loaded: ProjectCalendar = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.timezone(), chrono_tz::Europe::Lisbon);
+
+        let old: ProjectCalendar = serde_json::from_str(r#"{"holidays": []}"#).unwrap();
+        assert_eq!(old.timezone(), chrono_tz::UTC);
+    }
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, m, d).unwrap()
+    }
+
+    /// Локальный календарь с рабочей субботой и мастер-календарь компании
+    fn local_and_master() -> (ProjectCalendar, ProjectCalendar) {
+        let mut local = ProjectCalendar::default();
+        local.set_hours_for(Weekday::Sat, 5);
+        local.set_hours_for(Weekday::Fri, 7);
+        local.add_holiday(date(1, 1));
+        local.add_holiday(date(3, 9));
+        let mut master = ProjectCalendar::default();
+        master.add_holiday(date(1, 1));
+        master.add_holiday(date(5, 1));
+        master.add_holiday(date(2, 23));
+        (local, master)
+    }
+
+    #[test]
+    fn test_calendar_diff() {
+        let (local, master) = local_and_master();
+        let diff = local.diff(&master);
+        assert_eq!(diff.holidays_only_left, vec![date(3, 9)]);
+        assert_eq!(diff.holidays_only_right, vec![date(2, 23), date(5, 1)]);
+        assert_eq!(diff.working_days, vec![(Weekday::Sat, 5, 0)]);
+        assert_eq!(diff.hours, vec![(Weekday::Fri, 7, 8)]);
+        assert!(local.diff(&local).is_empty());
+
+        // Расхождения передаются в интерфейс в сериализованном виде
+        let json = serde_json::to_string(&diff).unwrap();
+        let loaded: CalendarDiff = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, diff);
+    }
+
+    #[test]
+    fn test_calendar_merge_policies() {
+        let (local, master) = local_and_master();
+
+        let mut union = local.clone();
+        union.merge(&master, MergePolicy::Union);
+        let diff = union.diff(&master);
+        assert_eq!(diff.holidays_only_left, vec![date(3, 9)]);
+        assert!(diff.holidays_only_right.is_empty());
+        assert_eq!(
+            union.diff(&local).holidays_only_left,
+            vec![date(2, 23), date(5, 1)]
+        );
+        // Локальная рабочая суббота сохраняется
+        assert_eq!(union.hours_for(Weekday::Sat), 5);
+        assert_eq!(union.hours_for(Weekday::Fri), 7);
+
+        let mut prefer_other = local.clone();
+        prefer_other.merge(&master, MergePolicy::PreferOther);
+        assert!(prefer_other.diff(&master).is_empty());
+        assert_eq!(prefer_other.hours_for(Weekday::Sat), 0);
+        assert!(prefer_other.is_working_day(date(3, 9)));
+    }
+
+    #[test]
+    fn test_validate_rejects_calendars_without_working_days() {
+        assert_eq!(ProjectCalendar::default().validate(), Ok(()));
+
+        let mut empty = ProjectCalendar::default();
+        empty.set_working_days([]);
+        assert_eq!(empty.validate(), Err(CalendarErrors::NoWorkingWeekdays));
+
+        // Рабочие дни со старым форматом, но без часов
+        let zero_hours: ProjectCalendar = serde_json::from_str(
+            r#"{"working_days": ["Mon", "Tue"], "working_hours_per_day": 0, "holidays": []}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            zero_hours.validate(),
+            Err(CalendarErrors::NoWorkingWeekdays)
+        );
+        assert_eq!(
+            ProjectCalendar::new(0).validate(),
+            Err(CalendarErrors::NoWorkingWeekdays)
+        );
+
+        // Праздники на все рабочие дни окна (пн 4 - пт 8 мая 2026, выходные вокруг)
+        let mut calendar = ProjectCalendar::default();
+        let window = TimeWindow::new(
+            Utc.with_ymd_and_hms(2026, 5, 2, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 5, 10, 0, 0, 0).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(calendar.validate_for_window(&window), Ok(()));
+        for day in 4..=8 {
+            calendar.add_holiday(NaiveDate::from_ymd_opt(2026, 5, day).unwrap());
+        }
+        assert_eq!(calendar.validate(), Ok(()));
+        assert_eq!(
+            calendar.validate_for_window(&window),
+            Err(CalendarErrors::NoWorkingDaysInWindow {
+                date_start: NaiveDate::from_ymd_opt(2026, 5, 2).unwrap(),
+                date_end: NaiveDate::from_ymd_opt(2026, 5, 10).unwrap(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_workday_span() {
+        let mut calendar = ProjectCalendar::default();
+        let time = |h: u32| NaiveTime::from_hms_opt(h, 0, 0).unwrap();
+        assert_eq!(
+            calendar.set_workday_span(time(18), time(9)),
+            Err(CalendarErrors::InvalidWorkdaySpan {
+                start: time(18),
+                end: time(9)
+            })
+        );
+        calendar.set_workday_span(time(8), time(17)).unwrap();
+        let window = TimeWindow::new(
+            Utc.with_ymd_and_hms(2026, 3, 3, 7, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 3, 10, 30, 0).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(calendar.working_hours_in_period(&window), 2);
+
+        // Интервал дня сохраняется в файле, старые файлы получают 09:00-18:00
+        let loaded: ProjectCalendar =
+            serde_json::from_str(&serde_json::to_string(&calendar).unwrap()).unwrap();
+        assert_eq!(loaded.workday_span(), (time(8), time(17)));
+        let old: ProjectCalendar = serde_json::from_str(r#"{"holidays": []}"#).unwrap();
+        assert_eq!(old.workday_span(), (time(9), time(18)));
+    }
+
+    #[test]
+    fn test_short_friday_hours() {
+        let mut calendar = ProjectCalendar::default();
+        calendar.set_hours_for(Weekday::Fri, 6);
+        // Две недели с понедельника 2 марта 2026 по воскресенье 15 марта
+        let window = TimeWindow {
+            date_start: Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap(),
+            date_end: Utc.with_ymd_and_hms(2026, 3, 15, 0, 0, 0).unwrap(),
+        };
+        assert_eq!(calendar.count_working_days(&window), 10);
+        assert_eq!(calendar.working_hours_in_period(&window), 2 * (4 * 8 + 6));
+        assert_eq!(window.duration_hours(&calendar), 76);
+
+        // Праздник в пятницу снимает 6 часов, день без часов - нерабочий
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2026, 3, 13).unwrap());
+        assert_eq!(calendar.working_hours_in_period(&window), 70);
+        calendar.set_hours_for(Weekday::Mon, 0);
+        assert!(!calendar.is_working_day(NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()));
+        assert_eq!(calendar.count_working_days(&window), 7);
+    }
+
+    #[test]
+    fn test_old_calendar_format_deserializes() {
+        let json = r#"{
+            "working_days": ["Mon", "Tue", "Wed", "Thu"],
+            "holidays": [],
+            "working_hours_per_day": 7
+        }"#;
+        let calendar: ProjectCalendar = serde_json::from_str(json).unwrap();
+        assert_eq!(calendar.hours_for(Weekday::Mon), 7);
+        assert_eq!(calendar.hours_for(Weekday::Fri), 0);
+
+        // Новый формат сохраняет часы по дням
+        let mut calendar = ProjectCalendar::new(8);
+        calendar.set_hours_for(Weekday::Fri, 6);
+        let json = serde_json::to_string(&calendar).unwrap();
+        assert!(!json.contains("working_hours_per_day"));
+        let loaded: ProjectCalendar = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.hours_for(Weekday::Fri), 6);
+        assert_eq!(loaded.hours_for(Weekday::Thu), 8);
+    }
+
+    #[test]
+    fn test_partial_day_reduces_hours() {
+        let mut calendar = ProjectCalendar::default();
+        // Среда 31 декабря 2025 - сокращенный день
+        let new_years_eve = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        calendar.add_partial_day(new_years_eve, 4).unwrap();
+        assert_eq!(
+            calendar.add_partial_day(new_years_eve, 8),
+            Err(CalendarErrors::InvalidPartialDay {
+                date: new_years_eve,
+                hours: 8,
+                normal_hours: 8
+            })
+        );
+        // Суббота нерабочая, сократить ее нельзя
+        assert!(
+            calendar
+                .add_partial_day(NaiveDate::from_ymd_opt(2025, 12, 27).unwrap(), 4)
+                .is_err()
+        );
+
+        let window = TimeWindow {
+            date_start: Utc.with_ymd_and_hms(2025, 12, 29, 0, 0, 0).unwrap(),
+            date_end: Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap(),
+        };
+        assert_eq!(calendar.count_working_days(&window), 3);
+        assert_eq!(calendar.working_hours_in_period(&window), 8 + 8 + 4);
+        assert!(calendar.is_working_day(new_years_eve));
+
+        // Длинный период считается по неделям с той же поправкой
+        let year = TimeWindow {
+            date_start: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            date_end: Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap(),
+        };
+        let full = ProjectCalendar::default().working_hours_in_period(&year);
+        assert_eq!(calendar.working_hours_in_period(&year), full - 4);
+    }
+
+    #[test]
+    fn test_two_by_two_shift_pattern() {
+        let anchor = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let mut calendar = ProjectCalendar::default();
+        calendar
+            .set_kind(CalendarKind::shifts(2, 2, 12, anchor))
+            .unwrap();
+
+        // Смены 1-2 января, выходные 3-4, снова смены 5-6, независимо от дней недели
+        assert!(calendar.is_working_day(anchor));
+        assert!(calendar.is_working_day(anchor + TimeDelta::days(1)));
+        assert!(!calendar.is_working_day(anchor + TimeDelta::days(2)));
+        assert!(calendar.is_working_day(anchor + TimeDelta::days(4)));
+        // До даты привязки цикл продолжается назад
+        assert!(!calendar.is_working_day(anchor - TimeDelta::days(1)));
+        assert!(calendar.is_working_day(anchor - TimeDelta::days(3)));
+
+        let mut total = 0;
+        for month in 1..=12 {
+            let first = NaiveDate::from_ymd_opt(2025, month, 1).unwrap();
+            let last = first
+                .checked_add_months(chrono::Months::new(1))
+                .unwrap()
+                .pred_opt()
+                .unwrap();
+            let window = TimeWindow {
+                date_start: Utc.from_utc_datetime(&first.and_time(NaiveTime::MIN)),
+                date_end: Utc.from_utc_datetime(&last.and_time(NaiveTime::MIN)),
+            };
+            let days = calendar.count_working_days(&window);
+            // 7 полных циклов дают 14 смен, хвост из 0-3 дней добавляет до двух
+            let expected = match last.day() {
+                28 => 14..=14,
+                30 => 14..=16,
+                _ => 15..=16,
+            };
+            assert!(expected.contains(&days), "month {}: {}", month, days);
+            assert_eq!(calendar.working_hours_in_period(&window), days * 12);
+            assert_eq!(days, sum_naive(&calendar, &window, |h| u32::from(h > 0)));
+            total += days;
+        }
+        // 365 дней = 91 цикл и один рабочий день
+        assert_eq!(total, 183);
+
+        // Праздник важнее смены
+        calendar.add_holiday(anchor);
+        assert!(!calendar.is_working_day(anchor));
+        let january = TimeWindow {
+            date_start: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            date_end: Utc.with_ymd_and_hms(2025, 1, 31, 0, 0, 0).unwrap(),
+        };
+        assert_eq!(calendar.count_working_days(&january), 15);
+        assert_eq!(calendar.working_hours_in_period(&january), 180);
+
+        assert_eq!(
+            calendar.set_kind(CalendarKind::shifts(0, 3, 12, anchor)),
+            Err(CalendarErrors::NoWorkingShiftDays)
+        );
+        let loaded: ProjectCalendar =
+            serde_json::from_str(&serde_json::to_string(&calendar).unwrap()).unwrap();
+        assert_eq!(loaded.kind(), calendar.kind());
+    }
+
+    #[test]
+    fn test_working_exception_on_weekend() {
+        let mut calendar = ProjectCalendar::default();
+        // Суббота 1 ноября 2025 объявлена рабочей
+        let saturday = NaiveDate::from_ymd_opt(2025, 11, 1).unwrap();
+        let window = TimeWindow {
+            date_start: Utc.with_ymd_and_hms(2025, 10, 27, 0, 0, 0).unwrap(),
+            date_end: Utc.with_ymd_and_hms(2025, 11, 2, 0, 0, 0).unwrap(),
+        };
+        assert_eq!(calendar.count_working_days(&window), 5);
+        calendar.add_working_exception(saturday);
+        assert!(calendar.is_working_day(saturday));
+        assert_eq!(calendar.count_working_days(&window), 6);
+        assert_eq!(calendar.working_hours_in_period(&window), 48);
+
+        // Праздник на ту же дату важнее переноса
+        calendar.add_holiday(saturday);
+        assert!(!calendar.is_working_day(saturday));
+        assert_eq!(calendar.count_working_days(&window), 5);
+        calendar.remove_holiday(saturday);
+
+        // Длинный период по неделям учитывает перенос так же
+        let long = TimeWindow {
+            date_start: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            date_end: Utc.with_ymd_and_hms(2026, 6, 30, 0, 0, 0).unwrap(),
+        };
+        let base = ProjectCalendar::default().count_working_days(&long);
+        assert_eq!(calendar.count_working_days(&long), base + 1);
+
+        calendar.remove_working_exception(saturday);
+        assert_eq!(calendar.count_working_days(&window), 5);
+    }
+
+    #[test]
+    fn test_working_day_navigation_around_new_year() {
+        let mut calendar = ProjectCalendar::default();
+        let date = |m: u32, d: u32| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        for day in 1..=8 {
+            calendar.add_holiday(date(1, day));
+        }
+        // Пятница 9 января - первый рабочий день года
+        let new_years_eve = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        assert_eq!(calendar.next_working_day(new_years_eve), Ok(date(1, 9)));
+        assert_eq!(calendar.next_working_day(date(1, 1)), Ok(date(1, 9)));
+        // Строго после: рабочий день не возвращает сам себя
+        assert_eq!(calendar.next_working_day(date(1, 9)), Ok(date(1, 12)));
+        assert_eq!(calendar.previous_working_day(date(1, 9)), Ok(new_years_eve));
+        assert_eq!(
+            calendar.snap_to_working_day(date(1, 5), SnapDirection::Forward),
+            Ok(date(1, 9))
+        );
+        assert_eq!(
+            calendar.snap_to_working_day(date(1, 9), SnapDirection::Backward),
+            Ok(date(1, 9))
+        );
+
+        // Перенесенный рабочий день в субботу 3 января праздник не отменяет
+        calendar.add_working_exception(date(1, 3));
+        assert_eq!(calendar.next_working_day(date(1, 1)), Ok(date(1, 9)));
+        calendar.remove_holiday(date(1, 3));
+        assert_eq!(calendar.next_working_day(date(1, 1)), Ok(date(1, 3)));
+    }
+
+    #[test]
+    fn test_working_day_search_without_working_days() {
+        let mut calendar = ProjectCalendar::default();
+        calendar.set_working_days([]);
+        let from = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        assert_eq!(
+            calendar.next_working_day(from),
+            Err(CalendarErrors::NoWorkingDays {
+                from,
+                searched_days: MAX_SEARCH_DAYS
+            })
+        );
+        assert!(
+            calendar
+                .snap_to_working_day(from, SnapDirection::Backward)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_add_working_days_and_hours() {
+        let mut calendar = ProjectCalendar::default();
+        let date = |d: u32| NaiveDate::from_ymd_opt(2026, 5, d).unwrap();
+        // Понедельник 11 мая - праздник
+        calendar.add_holiday(date(11));
+
+        // Из четверга 7 мая: пт 8, (выходные, праздник) вт 12, ср 13
+        assert_eq!(calendar.add_working_days(date(7), 3), Ok(date(13)));
+        assert_eq!(calendar.add_working_days(date(7), 0), Ok(date(7)));
+        // Суббота переносится на первый рабочий день
+        assert_eq!(calendar.add_working_days(date(9), 0), Ok(date(12)));
+        assert_eq!(calendar.add_working_days(date(9), 1), Ok(date(13)));
+
+        let at = |d: u32, h: u32| Utc.with_ymd_and_hms(2026, 5, d, h, 0, 0).unwrap();
+        // Пятница с 4:00: отсчет с 9:00, 8 часов в пятницу и 6 часов во вторник
+        assert_eq!(calendar.add_working_hours(at(8, 4), 14), Ok(at(12, 15)));
+        assert_eq!(calendar.add_working_hours(at(8, 4), 4), Ok(at(8, 13)));
+        assert_eq!(calendar.add_working_hours(at(8, 4), 0), Ok(at(8, 9)));
+        // Начало после рабочих часов переносится на следующий рабочий день
+        assert_eq!(calendar.add_working_hours(at(8, 20), 0), Ok(at(12, 9)));
+    }
+
+    #[test]
+    fn test_add_working_hours_round_trips_with_working_hours_in_period() {
+        let mut calendar = ProjectCalendar::default();
+        calendar.set_timezone(chrono_tz::Europe::Moscow);
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2026, 5, 11).unwrap());
+        let at = |d: u32, h: u32| {
+            calendar.local_midnight(NaiveDate::from_ymd_opt(2026, 5, d).unwrap())
+                + TimeDelta::hours(h as i64)
+        };
+
+        // Начала в рабочее время, до и после него, в выходной и в праздник
+        for start in [
+            at(7, 9),
+            at(7, 12),
+            at(7, 4),
+            at(7, 20),
+            at(9, 10),
+            at(11, 10),
+        ] {
+            for hours in [1, 3, 8, 9, 17, 40] {
+                let end = calendar.add_working_hours(start, hours).unwrap();
+                let window = TimeWindow::new(start, end).unwrap();
+                assert_eq!(
+                    calendar.working_hours_in_period(&window),
+                    hours,
+                    "{} + {}h = {}",
+                    start,
+                    hours,
+                    end
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_shift_working_days_both_directions() {
+        let mut calendar = ProjectCalendar::default();
+        let date = |d: u32| NaiveDate::from_ymd_opt(2026, 5, d).unwrap();
+        calendar.add_holiday(date(11));
+
+        assert_eq!(calendar.shift_working_days(date(7), 3), Ok(date(13)));
+        // Назад из среды 13 мая: вт 12, (праздник, выходные) пт 8
+        assert_eq!(calendar.shift_working_days(date(13), -2), Ok(date(8)));
+        // Суббота при сдвиге назад переносится на пятницу
+        assert_eq!(calendar.shift_working_days(date(9), 0), Ok(date(12)));
+        assert_eq!(calendar.shift_working_days(date(9), -1), Ok(date(7)));
+
+        for days in [
+            i64::from(MAX_SHIFT_WORKING_DAYS) + 1,
+            -999_999_999,
+            i64::MIN,
+        ] {
+            assert_eq!(
+                calendar.shift_working_days(date(7), days),
+                Err(CalendarErrors::ShiftTooLarge {
+                    days,
+                    max: MAX_SHIFT_WORKING_DAYS
+                })
+            );
+        }
+        assert_eq!(
+            calendar.add_working_days(date(7), u32::MAX),
+            Err(CalendarErrors::ShiftTooLarge {
+                days: u32::MAX as i64,
+                max: MAX_SHIFT_WORKING_DAYS
+            })
+        );
+    }
+
+    #[test]
+    fn test_country_presets() {
+        let date = |y: i32, m: u32, d: u32| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let mut calendar = ProjectCalendar::default();
+        let company_day = date(2025, 12, 26);
+        calendar.add_holiday(company_day);
+        let calendar = calendar.with_country_preset(CountryPreset::Ru, 2025..=2026);
+
+        for day in 1..=8 {
+            assert!(!calendar.is_working_day(date(2025, 1, day)));
+        }
+        assert!(!calendar.is_working_day(date(2026, 6, 12)));
+        assert!(!calendar.is_working_day(date(2025, 11, 4)));
+        assert!(!calendar.is_working_day(company_day));
+        assert!(calendar.is_working_day(date(2025, 1, 9)));
+        // Годы вне диапазона не затронуты
+        assert!(calendar.is_working_day(date(2027, 6, 11)));
+        assert!(calendar.is_working_day(date(2024, 11, 4)));
+
+        // Повторное применение и вторая страна не ломают уже заданные праздники
+        let mut twice = calendar.clone();
+        twice.apply_country_preset(CountryPreset::Ru, 2025..=2026);
+        assert_eq!(twice.holidays, calendar.holidays);
+        twice.apply_country_preset(CountryPreset::Us, 2025..=2025);
+        assert!(!twice.is_working_day(date(2025, 7, 4)));
+        assert!(!twice.is_working_day(company_day));
+        assert_eq!(twice.holidays.len(), calendar.holidays.len() + 4);
+    }
 }
@@ -4,23 +4,91 @@ use serde::{Deserialize, Serialize};
 /// Будем реализовывать 2 контейнера - одиночный и мульти контейнер
 /// Для контейнеров может быть реализована дополнительная логика обработки, но базово будем реализовывать
 /// трейт ProjectContainer
-use std::{collections::HashMap, vec};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    vec,
+};
 use uuid::Uuid;
 
 use crate::{
-    Project,
+    Project, ProjectStatus,
     base_structures::{
+        ContainerEvent,
         project_calendar::ProjectCalendar,
         resource_pool::LocalResourcePool,
+        time_window::TimeWindow,
         traits::{BasicGettersForStructures, ProjectContainer, ResourcePool},
     },
+    cust_exceptions::ContainerErrors,
+    export::ExportProfile,
 };
 
-#[derive(Serialize, Deserialize)]
+/// Снять из пула все назначения проекта
+fn release_project_allocations(
+    pool: &mut LocalResourcePool,
+    project_id: &Uuid,
+) -> anyhow::Result<()> {
+    let allocation_ids: Vec<Uuid> = pool
+        .get_project_allocations(project_id)
+        .iter()
+        .map(|a| a.get_id())
+        .collect();
+    for allocation_id in allocation_ids {
+        pool.deallocate(allocation_id)?;
+    }
+    Ok(())
+}
+
+/// Календарь проекта должен иметь рабочие дни, в том числе в датах проекта
+fn validate_project_calendar(project: &Project) -> anyhow::Result<()> {
+    let window = TimeWindow {
+        date_start: *project.get_date_start(),
+        date_end: *project.get_date_end(),
+    };
+    project.calendar.validate_for_window(&window)?;
+    Ok(())
+}
+
+/// Текущая версия формата файла проекта
+pub const CONTAINER_FILE_VERSION: u64 = 1;
+
+/// Контейнер для одного проекта
+///
+/// Контейнер сериализуется целиком (проект, пул ресурсов, календари), так выполняется
+/// сохранение и загрузка файла проекта.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use logic::{BasicGettersForStructures, Project, ProjectContainer, SingleProjectContainer};
+///
+/// let mut container = SingleProjectContainer::new();
+/// let project = Project::new(
+///     "Website",
+///     "Relaunch",
+///     Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2026, 6, 30, 0, 0, 0).unwrap(),
+/// )?;
+/// let project_id = *project.get_id();
+/// container.add_project(project)?;
+///
+/// // Сохранение и загрузка
+/// let json = container.to_versioned_json()?;
+/// let loaded = SingleProjectContainer::from_versioned_json(&json)?;
+/// assert_eq!(loaded.get_project(&project_id).unwrap().name, "Website");
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Serialize, Deserialize, Clone)]
 pub struct SingleProjectContainer {
     project: Option<Project>,
     resource_pool: LocalResourcePool,
     calendars: HashMap<Uuid, ProjectCalendar>,
+    #[serde(default)]
+    export_profiles: Vec<ExportProfile>,
+    #[serde(skip)]
+    events: Vec<ContainerEvent>,
 }
 
 impl SingleProjectContainer {
@@ -29,6 +97,78 @@ impl SingleProjectContainer {
             project: None,
             resource_pool: LocalResourcePool::default(),
             calendars: HashMap::new(),
+            export_profiles: vec![],
+            events: vec![],
+        }
+    }
+}
+
+impl SingleProjectContainer {
+    /// JSON файла проекта: `{ "version": CONTAINER_FILE_VERSION, "data": {...} }`
+    pub fn to_versioned_json(&self) -> anyhow::Result<String> {
+        let envelope = serde_json::json!({
+            "version": CONTAINER_FILE_VERSION,
+            "data": self,
+        });
+        Ok(serde_json::to_string_pretty(&envelope)?)
+    }
+
+    /// Разбор файла проекта с миграцией старых версий
+    ///
+    /// Файлы без конверта (сохраненные до появления версий) считаются версией 0.
+    /// Версия новее CONTAINER_FILE_VERSION - ошибка `UnsupportedFileVersion`.
+    pub fn from_versioned_json(json: &str) -> anyhow::Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let (version, data) = match value {
+            serde_json::Value::Object(mut envelope)
+                if envelope.contains_key("version") && envelope.contains_key("data") =>
+            {
+                let version = envelope["version"]
+                    .as_u64()
+                    .ok_or_else(|| anyhow::anyhow!("Project file version must be a number"))?;
+                (version, envelope.remove("data").unwrap_or_default())
+            }
+            legacy => (0, legacy),
+        };
+        if version > CONTAINER_FILE_VERSION {
+            return Err(ContainerErrors::UnsupportedFileVersion {
+                found: version,
+                supported: CONTAINER_FILE_VERSION,
+            }
+            .into());
+        }
+        let mut container: Self = serde_json::from_value(data)?;
+        container.migrate(version);
+        Ok(container)
+    }
+
+    pub fn save_to_path(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        std::fs::write(path, self.to_versioned_json()?)?;
+        Ok(())
+    }
+
+    pub fn load_from_path(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Self::from_versioned_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Шаги миграции от версии `from` до текущей
+    fn migrate(&mut self, from: u64) {
+        if from < 1 {
+            // До версии 1 удаление задачи могло оставить зависимости на нее
+            if let Some(project) = &mut self.project {
+                let task_ids: HashSet<Uuid> = project.tasks.keys().copied().collect();
+                for task in project.tasks.values_mut() {
+                    let dangling: Vec<Uuid> = task
+                        .get_dependencies()
+                        .iter()
+                        .map(|dep| dep.depends_on)
+                        .filter(|id| !task_ids.contains(id))
+                        .collect();
+                    for id in dangling {
+                        task.remove_dependency(&id);
+                    }
+                }
+            }
         }
     }
 }
@@ -43,8 +183,12 @@ impl ProjectContainer for SingleProjectContainer {
     // Если тут уже был проект, то его заменит
     fn add_project(&mut self, project: Project) -> anyhow::Result<()> {
         if self.project.is_none() {
-            self.project = Some(project.clone());
-            self.calendars.insert(*project.get_id(), project.calendar);
+            validate_project_calendar(&project)?;
+            let project_id = *project.get_id();
+            // Копируется только календарь, сам проект переносится в контейнер
+            self.calendars.insert(project_id, project.calendar.clone());
+            self.project = Some(project);
+            self.emit(ContainerEvent::ProjectAdded { project_id });
             Ok(())
         } else {
             Err(anyhow::Error::msg(
@@ -73,6 +217,10 @@ impl ProjectContainer for SingleProjectContainer {
         self.calendars.get(project_id)
     }
 
+    fn calendar_mut(&mut self, project_id: &Uuid) -> Option<&mut ProjectCalendar> {
+        self.calendars.get_mut(project_id)
+    }
+
     fn get_project_mut(&mut self, id: &Uuid) -> Option<&mut Project> {
         self.project
             .as_mut()
@@ -85,4 +233,655 @@ impl ProjectContainer for SingleProjectContainer {
             None => vec![],
         }
     }
+
+    fn remove_project(&mut self, id: &Uuid) -> anyhow::Result<Project> {
+        if self.get_project(id).is_none() {
+            anyhow::bail!("Project with id {} not found", id);
+        }
+        release_project_allocations(&mut self.resource_pool, id)?;
+        self.calendars.remove(id);
+        self.emit(ContainerEvent::ProjectRemoved { project_id: *id });
+        Ok(self.project.take().unwrap())
+    }
+
+    fn export_profiles(&self) -> &Vec<ExportProfile> {
+        &self.export_profiles
+    }
+
+    fn export_profiles_mut(&mut self) -> &mut Vec<ExportProfile> {
+        &mut self.export_profiles
+    }
+
+    fn events_mut(&mut self) -> &mut Vec<ContainerEvent> {
+        &mut self.events
+    }
+}
+
+/// Контейнер для нескольких проектов с общим пулом ресурсов
+///
+/// Каждому проекту соответствует свой календарь, ресурсы разделяются между всеми проектами.
+/// Проекты с одинаковым id или именем (без учета регистра) в контейнер не добавляются.
+#[derive(Serialize, Deserialize, Default)]
+pub struct MultiProjectContainer {
+    projects: HashMap<Uuid, Project>,
+    resource_pool: LocalResourcePool,
+    calendars: HashMap<Uuid, ProjectCalendar>,
+    #[serde(default)]
+    export_profiles: Vec<ExportProfile>,
+    #[serde(default)]
+    archived: HashSet<Uuid>,
+    #[serde(skip)]
+    events: Vec<ContainerEvent>,
+}
+
+/// Отбор проектов по признаку архива
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArchiveFilter {
+    #[default]
+    NotArchived,
+    Archived,
+    All,
+}
+
+/// Фильтр списка проектов: признак архива, статус и тег (None - без отбора)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ProjectFilter {
+    pub archive: ArchiveFilter,
+    pub status: Option<ProjectStatus>,
+    pub tag: Option<String>,
+}
+
+impl ProjectFilter {
+    /// Все проекты, включая архивные
+    pub fn all() -> Self {
+        Self {
+            archive: ArchiveFilter::All,
+            ..Default::default()
+        }
+    }
+
+    pub fn archived() -> Self {
+        Self {
+            archive: ArchiveFilter::Archived,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_status(mut self, status: ProjectStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Подходит ли проект; `archived` - находится ли он в архиве контейнера
+    pub fn matches(&self, project: &Project, archived: bool) -> bool {
+        let archive = match self.archive {
+            ArchiveFilter::NotArchived => !archived,
+            ArchiveFilter::Archived => archived,
+            ArchiveFilter::All => true,
+        };
+        archive
+            && self.status.is_none_or(|s| *project.get_status() == s)
+            && self.tag.as_deref().is_none_or(|t| project.has_tag(t))
+    }
+}
+
+impl MultiProjectContainer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Перенести проект в архив
+    ///
+    /// Проект остается в контейнере (его можно открыть и выгрузить), но его назначения
+    /// больше не занимают ресурсы при проверке загрузки.
+    pub fn archive_project(&mut self, id: &Uuid) -> anyhow::Result<()> {
+        if !self.projects.contains_key(id) {
+            anyhow::bail!("Project with id {} not found", id);
+        }
+        self.archived.insert(*id);
+        self.resource_pool.set_project_excluded(*id, true);
+        Ok(())
+    }
+
+    /// Вернуть проект из архива. Его назначения снова учитываются в загрузке ресурсов,
+    /// возникшие перегрузки видны через `find_conflicts`.
+    pub fn unarchive_project(&mut self, id: &Uuid) -> anyhow::Result<()> {
+        if !self.archived.remove(id) {
+            anyhow::bail!("Project with id {} is not archived", id);
+        }
+        self.resource_pool.set_project_excluded(*id, false);
+        Ok(())
+    }
+}
+
+impl ProjectContainer for MultiProjectContainer {
+    fn add_project(&mut self, project: Project) -> anyhow::Result<()> {
+        if self.projects.contains_key(project.get_id()) {
+            anyhow::bail!("Project with id {} already exists", project.get_id());
+        }
+        if self
+            .get_project_by_name_ignore_case(&project.name)
+            .is_some()
+        {
+            return Err(ContainerErrors::DuplicateProjectName { name: project.name }.into());
+        }
+        validate_project_calendar(&project)?;
+        let project_id = *project.get_id();
+        self.calendars.insert(project_id, project.calendar.clone());
+        self.projects.insert(project_id, project);
+        self.emit(ContainerEvent::ProjectAdded { project_id });
+        Ok(())
+    }
+
+    fn get_project(&self, id: &Uuid) -> Option<&Project> {
+        self.projects.get(id)
+    }
+
+    fn get_project_mut(&mut self, id: &Uuid) -> Option<&mut Project> {
+        self.projects.get_mut(id)
+    }
+
+    // Сортируем по имени, чтобы порядок проектов не зависел от HashMap
+    fn list_projects(&self) -> Vec<&Project> {
+        let mut projects: Vec<&Project> = self.projects.values().collect();
+        projects.sort_by(|a, b| a.name.cmp(&b.name));
+        projects
+    }
+
+    fn resource_pool(&self) -> &dyn ResourcePool {
+        &self.resource_pool
+    }
+
+    fn resource_pool_mut(&mut self) -> &mut dyn ResourcePool {
+        &mut self.resource_pool
+    }
+
+    fn calendar(&self, project_id: &Uuid) -> Option<&ProjectCalendar> {
+        self.calendars.get(project_id)
+    }
+
+    fn calendar_mut(&mut self, project_id: &Uuid) -> Option<&mut ProjectCalendar> {
+        self.calendars.get_mut(project_id)
+    }
+
+    fn is_archived(&self, id: &Uuid) -> bool {
+        self.archived.contains(id)
+    }
+
+    fn remove_project(&mut self, id: &Uuid) -> anyhow::Result<Project> {
+        let project = self
+            .projects
+            .remove(id)
+            .ok_or_else(|| anyhow::anyhow!("Project with id {} not found", id))?;
+        release_project_allocations(&mut self.resource_pool, id)?;
+        self.calendars.remove(id);
+        if self.archived.remove(id) {
+            self.resource_pool.set_project_excluded(*id, false);
+        }
+        self.emit(ContainerEvent::ProjectRemoved { project_id: *id });
+        Ok(project)
+    }
+
+    fn export_profiles(&self) -> &Vec<ExportProfile> {
+        &self.export_profiles
+    }
+
+    fn export_profiles_mut(&mut self) -> &mut Vec<ExportProfile> {
+        &mut self.export_profiles
+    }
+
+    fn events_mut(&mut self) -> &mut Vec<ContainerEvent> {
+        &mut self.events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::cust_exceptions::CalendarErrors;
+    use crate::export::ExportRedaction;
+    use crate::{ProjectStatus, RateMeasure, ResourceService, TaskService};
+
+    fn project(name: &str) -> Project {
+        Project::new(
+            name,
+            "",
+            Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_versioned_file_roundtrip_and_migration() {
+        let mut container = SingleProjectContainer::new();
+        let project = project("Versioned");
+        let project_id = *project.get_id();
+        container.add_project(project).unwrap();
+        let mut task_service = TaskService::new(&mut container);
+        let day = |d: u32| Utc.with_ymd_and_hms(2026, 2, d, 0, 0, 0).unwrap();
+        let design = *task_service
+            .create_regular_task(project_id, "Design".into(), day(2), day(6), None)
+            .unwrap()
+            .get_id();
+        let build = *task_service
+            .create_regular_task(project_id, "Build".into(), day(9), day(13), None)
+            .unwrap()
+            .get_id();
+        task_service
+            .add_dependency(
+                project_id,
+                build,
+                design,
+                crate::DependencyType::Blocking,
+                None,
+            )
+            .unwrap();
+
+        let json = container.to_versioned_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["version"], 1);
+        let loaded = SingleProjectContainer::from_versioned_json(&json).unwrap();
+        assert_eq!(loaded.get_project(&project_id).unwrap().tasks.len(), 2);
+
+        // Файл без конверта с зависимостью на удаленную задачу мигрирует
+        let mut legacy = value["data"].clone();
+        legacy["project"]["tasks"]
+            .as_object_mut()
+            .unwrap()
+            .remove(&design.to_string());
+        let loaded = SingleProjectContainer::from_versioned_json(&legacy.to_string()).unwrap();
+        let project = loaded.get_project(&project_id).unwrap();
+        assert!(project.tasks[&build].get_dependencies().is_empty());
+
+        let future = serde_json::json!({ "version": 999, "data": value["data"] });
+        let Err(err) = SingleProjectContainer::from_versioned_json(&future.to_string()) else {
+            panic!("version 999 must be rejected");
+        };
+        assert_eq!(
+            err.downcast_ref::<ContainerErrors>(),
+            Some(&ContainerErrors::UnsupportedFileVersion {
+                found: 999,
+                supported: CONTAINER_FILE_VERSION
+            })
+        );
+    }
+
+    #[test]
+    fn test_add_project_rejects_calendar_without_working_days() {
+        let mut no_weekdays = project("No weekdays");
+        no_weekdays.calendar.set_working_days([]);
+        let mut single = SingleProjectContainer::new();
+        let error = single.add_project(no_weekdays.clone()).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<CalendarErrors>(),
+            Some(&CalendarErrors::NoWorkingWeekdays)
+        );
+        assert!(single.list_projects().is_empty());
+
+        // Праздники закрывают все дни короткого проекта (пн 5 - вт 6 января 2026)
+        let mut holidays_only = Project::new(
+            "Holidays",
+            "",
+            Utc.with_ymd_and_hms(2026, 1, 5, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 1, 6, 0, 0, 0).unwrap(),
+        )
+        .unwrap();
+        holidays_only.calendar = holidays_only
+            .calendar
+            .with_country_preset(crate::CountryPreset::Ru, 2026..=2026);
+        let mut multi = MultiProjectContainer::new();
+        assert!(matches!(
+            multi
+                .add_project(holidays_only)
+                .unwrap_err()
+                .downcast_ref::<CalendarErrors>(),
+            Some(CalendarErrors::NoWorkingDaysInWindow { .. })
+        ));
+        assert!(multi.add_project(no_weekdays).is_err());
+        assert!(multi.list_projects().is_empty());
+    }
+
+    #[test]
+    fn test_multi_container_rejects_duplicates() {
+        let mut container = MultiProjectContainer::new();
+        let first = project("First");
+        let first_id = *first.get_id();
+        container.add_project(first.clone()).unwrap();
+        container.add_project(project("Second")).unwrap();
+
+        assert!(container.add_project(first).is_err());
+        assert!(container.add_project(project("Second")).is_err());
+        assert_eq!(container.list_projects().len(), 2);
+        assert_eq!(container.get_project(&first_id).unwrap().name, "First");
+        assert!(container.calendar(&first_id).is_some());
+        assert!(container.calendar(&Uuid::new_v4()).is_none());
+    }
+
+    #[test]
+    fn test_remove_project_releases_only_its_allocations() {
+        let mut container = MultiProjectContainer::new();
+        let alpha = project("Alpha");
+        let beta = project("Beta");
+        let (alpha_id, beta_id) = (*alpha.get_id(), *beta.get_id());
+        container.add_project(alpha).unwrap();
+        container.add_project(beta).unwrap();
+
+        let resource_id = {
+            let mut resource_service = ResourceService::new(&mut container);
+            let resource = resource_service
+                .create_resource("Max", 100.0, RateMeasure::Hourly)
+                .unwrap();
+            let resource_id = resource.id;
+            resource_service.add_resource(resource).unwrap();
+            resource_id
+        };
+        let mut task_service = TaskService::new(&mut container);
+        for project_id in [alpha_id, beta_id] {
+            let task = task_service
+                .create_regular_task(
+                    project_id,
+                    "Task".into(),
+                    Utc.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2026, 2, 6, 0, 0, 0).unwrap(),
+                    None,
+                )
+                .unwrap();
+            task_service
+                .allocate_resource(project_id, *task.get_id(), resource_id, 0.5, None, None)
+                .unwrap();
+        }
+
+        let removed = container.remove_project(&alpha_id).unwrap();
+        assert_eq!(removed.name, "Alpha");
+        assert!(container.get_project(&alpha_id).is_none());
+        assert!(container.calendar(&alpha_id).is_none());
+        assert!(container.remove_project(&alpha_id).is_err());
+
+        let pool = container.resource_pool();
+        assert!(pool.get_project_allocations(&alpha_id).is_empty());
+        assert_eq!(pool.get_project_allocations(&beta_id).len(), 1);
+        assert_eq!(
+            pool.get_resource_existing_allocations(&resource_id).len(),
+            1
+        );
+        assert!(pool.get_resource(&resource_id).is_some());
+    }
+
+    #[test]
+    fn test_archived_project_releases_resource_availability() {
+        let mut container = MultiProjectContainer::new();
+        let old = project("Old");
+        let new = project("New");
+        let (old_id, new_id) = (*old.get_id(), *new.get_id());
+        container.add_project(old).unwrap();
+        container.add_project(new).unwrap();
+
+        let resource_id = {
+            let mut resource_service = ResourceService::new(&mut container);
+            let resource = resource_service
+                .create_resource("Max", 100.0, RateMeasure::Hourly)
+                .unwrap();
+            let resource_id = resource.id;
+            resource_service.add_resource(resource).unwrap();
+            resource_id
+        };
+        let mut task_ids = vec![];
+        let mut task_service = TaskService::new(&mut container);
+        for project_id in [old_id, new_id] {
+            let task = task_service
+                .create_regular_task(
+                    project_id,
+                    "Task".into(),
+                    Utc.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2026, 2, 6, 0, 0, 0).unwrap(),
+                    None,
+                )
+                .unwrap();
+            task_ids.push(*task.get_id());
+        }
+        task_service
+            .allocate_resource(old_id, task_ids[0], resource_id, 1.0, None, None)
+            .unwrap();
+        // Ресурс полностью занят старым проектом
+        assert!(
+            task_service
+                .allocate_resource(new_id, task_ids[1], resource_id, 1.0, None, None)
+                .is_err()
+        );
+
+        container.archive_project(&old_id).unwrap();
+        assert!(container.is_archived(&old_id));
+        let mut task_service = TaskService::new(&mut container);
+        task_service
+            .allocate_resource(new_id, task_ids[1], resource_id, 1.0, None, None)
+            .unwrap();
+        assert!(container.resource_pool().find_conflicts().is_empty());
+
+        // Архивный проект по-прежнему доступен и выгружается
+        let archived = container.get_project(&old_id).unwrap();
+        assert!(
+            archived
+                .export_tasks_csv(container.resource_pool(), &ExportRedaction::default())
+                .contains("Task")
+        );
+        let names = |filter| -> Vec<String> {
+            container
+                .list_projects_filtered(&filter)
+                .iter()
+                .map(|p| p.name.clone())
+                .collect()
+        };
+        assert_eq!(names(ProjectFilter::default()), ["New"]);
+        assert_eq!(names(ProjectFilter::archived()), ["Old"]);
+        assert_eq!(names(ProjectFilter::all()), ["New", "Old"]);
+
+        // После возврата из архива перегрузка снова видна
+        container.unarchive_project(&old_id).unwrap();
+        assert!(container.unarchive_project(&old_id).is_err());
+        assert_eq!(container.resource_pool().find_conflicts().len(), 1);
+        assert!(container.archive_project(&Uuid::new_v4()).is_err());
+    }
+
+    #[test]
+    fn test_add_project_moves_without_clone() {
+        // Клон выделил бы новую память под строки и задачи, перенос сохраняет адреса
+        let mut project = project("Large");
+        project.insert_task(
+            crate::Task::new_regular(
+                "Task",
+                Utc.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 2, 6, 0, 0, 0).unwrap(),
+                None,
+            )
+            .unwrap(),
+        );
+        let project_id = *project.get_id();
+        let name_ptr = project.name.as_ptr();
+        let task_ptr = project.tasks.values().next().unwrap() as *const crate::Task;
+
+        let mut single = SingleProjectContainer::new();
+        single.add_project(project).unwrap();
+        let stored = single.get_project(&project_id).unwrap();
+        assert_eq!(stored.name.as_ptr(), name_ptr);
+        assert_eq!(
+            stored.tasks.values().next().unwrap() as *const crate::Task,
+            task_ptr
+        );
+
+        let project = single.remove_project(&project_id).unwrap();
+        let mut multi = MultiProjectContainer::new();
+        multi.add_project(project).unwrap();
+        assert_eq!(
+            multi.get_project(&project_id).unwrap().name.as_ptr(),
+            name_ptr
+        );
+    }
+
+    #[test]
+    fn test_single_container_get_project_mut() {
+        let mut container = SingleProjectContainer::new();
+        assert!(container.get_project_mut(&Uuid::new_v4()).is_none());
+        let first = project("First");
+        let first_id = *first.get_id();
+        container.add_project(first).unwrap();
+
+        // Чужой id не дает доступа к сохраненному проекту
+        assert!(container.get_project_mut(&Uuid::new_v4()).is_none());
+        container.get_project_mut(&first_id).unwrap().name = "Renamed".into();
+        assert_eq!(container.get_project(&first_id).unwrap().name, "Renamed");
+    }
+
+    #[test]
+    fn test_single_container_remove_project() {
+        let mut container = SingleProjectContainer::new();
+        let first = project("First");
+        let first_id = *first.get_id();
+        container.add_project(first).unwrap();
+
+        assert!(container.remove_project(&Uuid::new_v4()).is_err());
+        container.remove_project(&first_id).unwrap();
+        assert!(container.list_projects().is_empty());
+        assert!(container.calendar(&first_id).is_none());
+        // После закрытия можно открыть другой проект
+        container.add_project(project("Second")).unwrap();
+    }
+
+    // Перебор проектов только через трейт, без знания конкретного контейнера
+    fn project_names(container: &dyn ProjectContainer) -> Vec<String> {
+        container
+            .list_projects()
+            .iter()
+            .map(|p| p.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_get_project_by_name() {
+        let mut container = MultiProjectContainer::new();
+        let website = project("Website");
+        let website_id = *website.get_id();
+        container.add_project(website).unwrap();
+        container.add_project(project("Mobile")).unwrap();
+
+        let error = container.add_project(project("WEBSITE")).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ContainerErrors>(),
+            Some(&ContainerErrors::DuplicateProjectName {
+                name: "WEBSITE".into()
+            })
+        );
+        assert_eq!(container.list_projects().len(), 2);
+
+        assert_eq!(
+            container
+                .get_project_by_name("Website")
+                .map(|p| *p.get_id()),
+            Some(website_id)
+        );
+        assert!(container.get_project_by_name("website").is_none());
+        assert_eq!(
+            container
+                .get_project_by_name_ignore_case("website")
+                .map(|p| *p.get_id()),
+            Some(website_id)
+        );
+        assert!(
+            container
+                .get_project_by_name_ignore_case("Backend")
+                .is_none()
+        );
+
+        let mut single = SingleProjectContainer::new();
+        assert!(single.get_project_by_name("Website").is_none());
+        single.add_project(project("Website")).unwrap();
+        assert!(single.get_project_by_name_ignore_case("WEBSITE").is_some());
+    }
+
+    #[test]
+    fn test_list_projects_filtered_by_status_and_tag() {
+        let mut container = MultiProjectContainer::new();
+        let mut alpha = project("Alpha");
+        alpha.add_tag("backend");
+        let mut beta = project("Beta");
+        beta.add_tag("backend");
+        beta.set_status(ProjectStatus::OnHold).unwrap();
+        let mut gamma = project("Gamma");
+        gamma.add_tag("mobile");
+        for p in [alpha, beta, gamma] {
+            container.add_project(p).unwrap();
+        }
+
+        let names = |container: &MultiProjectContainer, filter: ProjectFilter| -> Vec<String> {
+            container
+                .list_projects_filtered(&filter)
+                .iter()
+                .map(|p| p.name.clone())
+                .collect()
+        };
+        let filter = ProjectFilter::default();
+        assert_eq!(
+            names(
+                &container,
+                filter.clone().with_status(ProjectStatus::Active)
+            ),
+            ["Alpha", "Gamma"]
+        );
+        assert_eq!(
+            names(&container, filter.clone().with_tag("backend")),
+            ["Alpha", "Beta"]
+        );
+        assert_eq!(
+            names(
+                &container,
+                filter
+                    .clone()
+                    .with_status(ProjectStatus::OnHold)
+                    .with_tag("backend")
+            ),
+            ["Beta"]
+        );
+        assert!(names(&container, filter.clone().with_status(ProjectStatus::Done)).is_empty());
+        assert_eq!(names(&container, filter.clone()).len(), 3);
+
+        // Статус и тег сочетаются с признаком архива
+        let beta_id = *container.get_project_by_name("Beta").unwrap().get_id();
+        container.archive_project(&beta_id).unwrap();
+        assert_eq!(names(&container, filter.with_tag("backend")), ["Alpha"]);
+        assert_eq!(
+            names(
+                &container,
+                ProjectFilter::archived().with_status(ProjectStatus::OnHold)
+            ),
+            ["Beta"]
+        );
+    }
+
+    #[test]
+    fn test_list_projects_through_trait_object() {
+        let mut single = SingleProjectContainer::new();
+        assert!(project_names(&single).is_empty());
+        let first = project("First");
+        let first_id = *first.get_id();
+        single.add_project(first).unwrap();
+
+        let mut multi = MultiProjectContainer::new();
+        let beta = project("Beta");
+        let beta_id = *beta.get_id();
+        multi.add_project(beta).unwrap();
+        multi.add_project(project("Alpha")).unwrap();
+
+        let containers: Vec<Box<dyn ProjectContainer>> = vec![Box::new(single), Box::new(multi)];
+        assert_eq!(project_names(containers[0].as_ref()), ["First"]);
+        assert_eq!(project_names(containers[1].as_ref()), ["Alpha", "Beta"]);
+        assert_eq!(containers[0].list_project_ids(), [first_id]);
+        assert_eq!(containers[1].list_project_ids()[1], beta_id);
+    }
 }
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::base_structures::TaskStatus;
+
+/// Сводные показатели проекта по задачам без суммарных
+/// planned_hours - рабочие часы по окнам задач
+/// resource_count - число различных ресурсов в назначениях
+/// total_cost - стоимость назначений по валютам ставок ресурсов
+/// unpriced - назначения ресурсов, удаленных из пула: оценить их нельзя
+/// earliest_start/latest_end - границы задач, None для проекта без задач
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ProjectStats {
+    pub tasks_by_status: HashMap<TaskStatus, usize>,
+    pub planned_hours: u64,
+    pub resource_count: usize,
+    pub total_cost: HashMap<String, f64>,
+    #[serde(default)]
+    pub unpriced: Vec<Uuid>,
+    pub earliest_start: Option<DateTime<Utc>>,
+    pub latest_end: Option<DateTime<Utc>>,
+}
+
+impl ProjectStats {
+    pub fn task_count(&self) -> usize {
+        self.tasks_by_status.values().sum()
+    }
+
+    pub fn count_with_status(&self, status: &TaskStatus) -> usize {
+        self.tasks_by_status.get(status).copied().unwrap_or(0)
+    }
+}
+
+/// Затраты проекта по ресурсам
+/// by_currency - валюта ставки -> ресурс -> стоимость его назначений
+/// unpriced - назначения ресурсов, удаленных из пула: оценить их нельзя
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResourceCosts {
+    pub by_currency: HashMap<String, HashMap<Uuid, f64>>,
+    pub unpriced: Vec<Uuid>,
+}
+
+impl ResourceCosts {
+    /// Итог по каждой валюте
+    pub fn totals(&self) -> HashMap<String, f64> {
+        self.by_currency
+            .iter()
+            .map(|(currency, costs)| (currency.clone(), costs.values().sum()))
+            .collect()
+    }
+}
@@ -11,21 +11,29 @@ use anyhow::Error;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::base_structures::{project_calendar::ProjectCalendar, time_window::TimeWindow};
+pub use crate::base_structures::availability::ExceptionType;
+use crate::{
+    base_structures::{
+        availability::AvailabilityException,
+        currency::{default_currency, normalize_currency},
+        id_generator::{IdGenerator, RandomIds},
+        project_calendar::ProjectCalendar,
+        time_window::TimeWindow,
+    },
+    cust_exceptions::CurrencyErrors,
+};
 
-/// Период исключения (отпуск, отгул)
+/// Период исключения (отпуск, отгул) в старом формате, хранится как `AvailabilityException`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExceptionPeriod {
     pub period: TimeWindow,
     pub exception_type: ExceptionType,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub enum ExceptionType {
-    Vacation,    // Полностью не работает
-    SickLeave,   // Не работает
-    PersonalDay, // Не работает
-    Overtime,    // Работает сверх нормы (можно указать часы)
+impl From<ExceptionPeriod> for AvailabilityException {
+    fn from(period: ExceptionPeriod) -> Self {
+        AvailabilityException::new(period.period, period.exception_type)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -54,25 +62,36 @@ pub enum RateMeasure {
     Hourly,
     Monthly,
 }
+/// Коэффициенты пересчета ставок: сколько часов в рабочем дне и дней в месяце
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct RateConfig {
+    pub hours_per_day: f64,
+    pub days_per_month: f64,
+}
+
+impl Default for RateConfig {
+    fn default() -> Self {
+        Self {
+            hours_per_day: 8.0,
+            days_per_month: 22.0,
+        }
+    }
+}
+
 impl RateMeasure {
+    /// Пересчет ставки с коэффициентами по умолчанию (8 часов, 22 дня)
     pub fn convert(&self, to_measure: RateMeasure, rate: f64) -> f64 {
-        match self {
-            RateMeasure::Daily => match to_measure {
-                RateMeasure::Daily => rate,
-                RateMeasure::Hourly => rate / 8.0,
-                RateMeasure::Monthly => rate * 22.0,
-            },
-            RateMeasure::Hourly => match to_measure {
-                RateMeasure::Hourly => rate,
-                RateMeasure::Daily => rate * 8.0,
-                RateMeasure::Monthly => rate * 22.0,
-            },
-            RateMeasure::Monthly => match to_measure {
-                RateMeasure::Daily => rate / 22.0,
-                RateMeasure::Hourly => rate / (22.0 * 8.0),
-                RateMeasure::Monthly => rate,
-            },
-        }
+        self.convert_with(to_measure, rate, &RateConfig::default())
+    }
+
+    /// Пересчет ставки с коэффициентами проекта
+    pub fn convert_with(&self, to_measure: RateMeasure, rate: f64, cfg: &RateConfig) -> f64 {
+        let hours_in = |measure: &RateMeasure| match measure {
+            RateMeasure::Hourly => 1.0,
+            RateMeasure::Daily => cfg.hours_per_day,
+            RateMeasure::Monthly => cfg.hours_per_day * cfg.days_per_month,
+        };
+        rate / hours_in(self) * hours_in(&to_measure)
     }
 }
 
@@ -85,17 +104,42 @@ impl RateMeasure {
 // Hourly - будет базовой ставкой
 // Daily = Hourly * 8 (8 рабочих часов в одном дне)
 // Monthly = Daily * 22 (в среднем столько дней в рабочем месяце) = Hourly * 8 * 22
+///
+/// Ресурсы сравниваются и хешируются только по `id`: копия с другой ставкой или именем
+/// остается тем же ресурсом.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Resource {
     pub id: Uuid,
     pub name: String,
     pub rate: f64,
     pub rate_measure: RateMeasure,
-    unavailable_periods: Vec<ExceptionPeriod>,
+    unavailable_periods: Vec<AvailabilityException>,
+    /// Навыки ресурса для планирования потребности по навыкам
+    #[serde(default)]
+    pub skills: Vec<String>,
+    /// Основной навык: вся загрузка ресурса относится к нему
+    #[serde(default)]
+    pub primary_skill: Option<String>,
+    /// Валюта ставки, код ISO-4217
+    #[serde(default = "default_currency")]
+    currency: String,
+    /// Собственный календарь ресурса, None - работает по календарю проекта
+    #[serde(default)]
+    pub calendar: Option<ProjectCalendar>,
 }
 
 impl Resource {
     pub fn new(name: String, rate: f64, measure: RateMeasure) -> anyhow::Result<Self> {
+        Self::new_with_ids(name, rate, measure, &mut RandomIds)
+    }
+
+    /// Создание ресурса с id из `ids`
+    pub fn new_with_ids(
+        name: String,
+        rate: f64,
+        measure: RateMeasure,
+        ids: &mut impl IdGenerator,
+    ) -> anyhow::Result<Self> {
         if rate <= 0f64 {
             return Err(Error::msg(format!(
                 "Rate for Resource must be > 0. {}",
@@ -103,14 +147,27 @@ impl Resource {
             )));
         }
         Ok(Self {
-            id: Uuid::new_v4(),
+            id: ids.next_id(),
             name,
             rate,
             rate_measure: measure,
             unavailable_periods: vec![],
+            skills: vec![],
+            primary_skill: None,
+            currency: default_currency(),
+            calendar: None,
         })
     }
 
+    pub fn get_currency(&self) -> &str {
+        &self.currency
+    }
+
+    pub fn set_currency(&mut self, code: &str) -> Result<(), CurrencyErrors> {
+        self.currency = normalize_currency(code)?;
+        Ok(())
+    }
+
     pub fn get_base_rate(&self) -> &f64 {
         &self.rate
     }
@@ -120,29 +177,131 @@ impl Resource {
     pub fn get_converted_rate(&self, to_measure: RateMeasure) -> f64 {
         self.rate_measure.convert(to_measure, self.rate)
     }
+
+    pub fn get_converted_rate_with(&self, to_measure: RateMeasure, cfg: &RateConfig) -> f64 {
+        self.rate_measure.convert_with(to_measure, self.rate, cfg)
+    }
     pub fn get_rate_measure(&self) -> &RateMeasure {
         &self.rate_measure
     }
 
-    pub fn add_unavailable_period(&mut self, exception_period: ExceptionPeriod) {
-        self.unavailable_periods.push(exception_period);
+    pub fn add_unavailable_period(&mut self, exception: impl Into<AvailabilityException>) {
+        self.unavailable_periods.push(exception.into());
     }
 
-    pub fn get_unavailable_periods(&self) -> &Vec<ExceptionPeriod> {
+    pub fn get_unavailable_periods(&self) -> &Vec<AvailabilityException> {
         &self.unavailable_periods
     }
 
+    /// Все периоды недоступности ресурса: собственные и закрытия календарей
+    /// (календаря проекта и собственного календаря ресурса)
+    pub fn exceptions<'a>(
+        &'a self,
+        project_cal: &'a ProjectCalendar,
+    ) -> impl Iterator<Item = &'a AvailabilityException> {
+        self.unavailable_periods
+            .iter()
+            .chain(project_cal.exceptions())
+            .chain(self.calendar.iter().flat_map(|c| c.exceptions()))
+    }
+
+    /// Задать собственный календарь ресурса, None - работать по календарю проекта
+    pub fn set_calendar(&mut self, calendar: Option<ProjectCalendar>) {
+        self.calendar = calendar;
+    }
+
+    /// Рабочие часы ресурса в периоде по его действующему календарю
+    pub fn working_hours(&self, period: &TimeWindow, project_cal: &ProjectCalendar) -> u32 {
+        period.working_hours(self.effective_calendar(project_cal))
+    }
+
+    /// Календарь, по которому работает ресурс: собственный или календарь проекта
+    pub fn effective_calendar<'a>(
+        &'a self,
+        project_cal: &'a ProjectCalendar,
+    ) -> &'a ProjectCalendar {
+        self.calendar.as_ref().unwrap_or(project_cal)
+    }
+
+    /// Ресурс доступен, если в периоде есть рабочие дни и период не пересекается
+    /// ни с одним исключением ресурса или календаря
     pub fn is_available(&self, period: &TimeWindow, calendar: &ProjectCalendar) -> bool {
-        if calendar.count_working_days(period) == 0 {
-            return false; // Нет рабочих дней в периоде
-        }
+        self.is_available_for(period, calendar, &[])
+    }
 
-        for unavailable in &self.unavailable_periods {
-            if unavailable.period.overlaps(period) {
-                return false;
-            }
-        }
+    /// То же, что `is_available`, но исключения с типами из `ignore` не мешают
+    /// (например, обучение при предварительном планировании)
+    pub fn is_available_for(
+        &self,
+        period: &TimeWindow,
+        calendar: &ProjectCalendar,
+        ignore: &[ExceptionType],
+    ) -> bool {
+        self.effective_calendar(calendar).count_working_days(period) > 0
+            && !self.exceptions(calendar).any(|exception| {
+                !ignore.contains(&exception.kind) && exception.window.overlaps(period)
+            })
+    }
+}
+
+impl PartialEq for Resource {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Resource {}
+
+impl std::hash::Hash for Resource {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_availability_ignores_selected_exception_types() {
+        use chrono::{TimeZone, Utc};
+
+        let day = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap();
+        let calendar = ProjectCalendar::default();
+        let mut resource = Resource::new("Dev".into(), 100.0, RateMeasure::Hourly).unwrap();
+        resource.add_unavailable_period(AvailabilityException::new(
+            TimeWindow::new(day(2), day(7)).unwrap(),
+            ExceptionType::Training,
+        ));
+        resource.add_unavailable_period(AvailabilityException::new(
+            TimeWindow::new(day(9), day(14)).unwrap(),
+            ExceptionType::Vacation,
+        ));
+
+        let training_week = TimeWindow::new(day(3), day(5)).unwrap();
+        let vacation_week = TimeWindow::new(day(10), day(12)).unwrap();
+        assert!(!resource.is_available(&training_week, &calendar));
+        let ignore = [ExceptionType::Training];
+        assert!(resource.is_available_for(&training_week, &calendar, &ignore));
+        assert!(!resource.is_available_for(&vacation_week, &calendar, &ignore));
+        // Окно через обе недели задевает отпуск
+        let both = TimeWindow::new(day(4), day(11)).unwrap();
+        assert!(!resource.is_available_for(&both, &calendar, &ignore));
+    }
+
+    #[test]
+    fn test_resource_equality_by_id() {
+        let resource = Resource::new("Dev".into(), 100.0, RateMeasure::Hourly).unwrap();
+        let mut raised = resource.clone();
+        raised.rate = 150.0;
+        raised.name = "Senior Dev".into();
+        assert_eq!(resource, raised);
 
-        true
+        let other = Resource::new("Dev".into(), 100.0, RateMeasure::Hourly).unwrap();
+        assert_ne!(resource, other);
+        let unique: HashSet<Resource> = [resource, raised, other].into_iter().collect();
+        assert_eq!(unique.len(), 2);
     }
 }
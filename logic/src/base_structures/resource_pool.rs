@@ -1,16 +1,31 @@
-use std::collections::HashMap;
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+};
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{
     RateMeasure,
     base_structures::{
-        project_calendar::ProjectCalendar, resource::Resource, time_window::TimeWindow,
+        events::ContainerEvent,
+        project_calendar::ProjectCalendar,
+        resource::{RateConfig, Resource},
+        time_window::{TimeWindow, merge_overlapping},
         traits::ResourcePool,
     },
+    cust_exceptions::AllocationErrors,
 };
 
+/// Приоритет назначения по умолчанию (середина шкалы u8)
+pub const DEFAULT_ALLOCATION_PRIORITY: u8 = 128;
+
+fn default_priority() -> u8 {
+    DEFAULT_ALLOCATION_PRIORITY
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct AllocationRequest {
     resource_id: Uuid,
@@ -18,23 +33,47 @@ pub struct AllocationRequest {
     project_id: Uuid,
     engagement_rate: f64,
     time_window: TimeWindow,
+    /// Заранее выбранный id назначения (демо-данные, эталонные файлы), None - случайный
+    #[serde(default)]
+    id: Option<Uuid>,
+    /// При выравнивании назначения с меньшим приоритетом сдвигаются первыми
+    #[serde(default = "default_priority")]
+    priority: u8,
 }
 
 impl AllocationRequest {
+    /// Запрос назначения, занятость - доля от 0.0 до 1.0
     pub fn new(
         resource_id: Uuid,
         task_id: Uuid,
         project_id: Uuid,
         engagement_rate: f64,
         time_window: TimeWindow,
-    ) -> Self {
-        Self {
+    ) -> Result<Self, AllocationErrors> {
+        if !(0.0..=1.0).contains(&engagement_rate) {
+            return Err(AllocationErrors::InvalidEngagement {
+                rate: engagement_rate,
+            });
+        }
+        Ok(Self {
             resource_id,
             task_id,
             project_id,
             engagement_rate,
             time_window,
-        }
+            id: None,
+            priority: DEFAULT_ALLOCATION_PRIORITY,
+        })
+    }
+
+    pub fn with_id(mut self, id: Uuid) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
     }
 }
 
@@ -43,19 +82,20 @@ pub struct AllocationQueryResult<'a> {
 }
 
 impl<'a> AllocationQueryResult<'a> {
+    /// Помещается ли запрос в загрузку ресурса. Загрузка считается по участкам между
+    /// границами назначений (`fits_capacity`), как и в `free_windows`: назначения, которые
+    /// не пересекаются между собой, не складываются
     pub fn check_correct_timewindow(self, allocation_request: &AllocationRequest) -> bool {
-        let overlapping_allocations: Vec<&&ResourceAllocation> = self
+        let placed: Vec<(TimeWindow, f64)> = self
             .allocations_list
             .iter()
-            .filter(|ra| ra.time_window.overlaps(&allocation_request.time_window))
+            .map(|ra| (ra.time_window, ra.engagement_rate))
             .collect();
-
-        let total_engagement: f64 = overlapping_allocations
-            .iter()
-            .map(|ra| *ra.get_engagement_rate())
-            .sum();
-
-        total_engagement + allocation_request.engagement_rate <= 1.0
+        fits_capacity(
+            &allocation_request.time_window,
+            allocation_request.engagement_rate,
+            &placed,
+        )
     }
     pub fn len(&self) -> usize {
         self.allocations_list.len()
@@ -66,7 +106,7 @@ impl<'a> AllocationQueryResult<'a> {
 }
 
 // Объект для описания назначения одного из ресурсов на задачу
-#[derive(Default, Debug, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceAllocation {
     id: Uuid,
     resource_id: Uuid,
@@ -74,17 +114,24 @@ pub struct ResourceAllocation {
     project_id: Uuid,
     engagement_rate: f64,
     time_window: TimeWindow,
+    /// Порядок создания в пуле: при выравнивании раньше созданные назначения приоритетнее
+    #[serde(default)]
+    sequence: u64,
+    #[serde(default = "default_priority")]
+    priority: u8,
 }
 
 impl ResourceAllocation {
     pub fn new(request: AllocationRequest) -> Self {
         Self {
-            id: Uuid::new_v4(),
+            id: request.id.unwrap_or_else(Uuid::new_v4),
             resource_id: request.resource_id,
             task_id: request.task_id,
             project_id: request.project_id,
             time_window: request.time_window,
             engagement_rate: request.engagement_rate,
+            sequence: 0,
+            priority: request.priority,
         }
     }
 
@@ -103,12 +150,41 @@ impl ResourceAllocation {
     pub fn get_time_window(&self) -> &TimeWindow {
         &self.time_window
     }
+
+    pub fn get_task_id(&self) -> &Uuid {
+        &self.task_id
+    }
+
+    pub fn get_project_id(&self) -> &Uuid {
+        &self.project_id
+    }
+
+    pub fn get_priority(&self) -> u8 {
+        self.priority
+    }
+}
+
+/// Перегрузка ресурса: окно, в котором сумма занятости по аллокациям больше 100%
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceConflict {
+    pub resource_id: Uuid,
+    pub resource_name: String,
+    pub window: TimeWindow,
+    pub total_engagement: f64,
+    pub allocation_ids: Vec<Uuid>,
 }
 
-#[derive(Default, Debug, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct LocalResourcePool {
     resources: HashMap<Uuid, Resource>,
     allocations: HashMap<Uuid, ResourceAllocation>,
+    /// Проекты (архивные), чьи аллокации не учитываются в проверке загрузки
+    #[serde(default)]
+    excluded_projects: HashSet<Uuid>,
+    #[serde(default)]
+    last_sequence: u64,
+    #[serde(skip)]
+    events: Vec<ContainerEvent>,
 }
 
 impl LocalResourcePool {
@@ -116,18 +192,373 @@ impl LocalResourcePool {
         self.resources.contains_key(resource_id)
     }
 
+    /// Исключить аллокации проекта из проверки загрузки ресурсов или вернуть их обратно
+    pub(crate) fn set_project_excluded(&mut self, project_id: Uuid, excluded: bool) {
+        if excluded {
+            self.excluded_projects.insert(project_id);
+        } else {
+            self.excluded_projects.remove(&project_id);
+        }
+    }
+
+    /// Выравнивание загрузки ресурса
+    ///
+    /// Назначения расставляются по убыванию приоритета (при равном - раньше созданные первыми):
+    /// каждое остается на месте, если помещается в свободную емкость и ресурс доступен
+    /// (`Resource::is_available`), иначе сдвигается позже - на ближайший момент освобождения
+    /// ресурса или окончания его недоступности, с началом в рабочий день календаря (если
+    /// рабочего дня нет - ошибка).
+    /// Длительность сохраняется, окончание не может выйти за текущее окончание задачи
+    /// назначения из `task_ends` (задача -> окончание). Если какое-то назначение не помещается
+    /// или его задачи нет в `task_ends`, пул не меняется и возвращается ошибка.
+    /// Возвращает id сдвинутых назначений.
+    pub fn level_resource(
+        &mut self,
+        resource_id: Uuid,
+        calendar: &ProjectCalendar,
+        task_ends: &HashMap<Uuid, DateTime<Utc>>,
+    ) -> anyhow::Result<Vec<Uuid>> {
+        let Some(resource) = self.resources.get(&resource_id) else {
+            anyhow::bail!("Resource with id {} not found", resource_id);
+        };
+        let resource_calendar = resource.effective_calendar(calendar);
+        let unavailable_ends: Vec<DateTime<Utc>> = resource
+            .exceptions(calendar)
+            .map(|e| e.window.date_end)
+            .collect();
+        let mut allocations = self.counted_allocations(&resource_id);
+        allocations.sort_by_key(|a| (Reverse(a.priority), a.sequence, a.id));
+
+        let mut placed: Vec<(TimeWindow, f64)> = vec![];
+        let mut moves = vec![];
+        for allocation in allocations {
+            let Some(task_end) = task_ends.get(&allocation.task_id).copied() else {
+                anyhow::bail!(
+                    "Cannot level resource: task {} of allocation {} not found",
+                    allocation.task_id,
+                    allocation.id
+                );
+            };
+            let window = allocation.time_window;
+            let duration = window.date_end - window.date_start;
+            let mut candidates: Vec<DateTime<Utc>> = placed
+                .iter()
+                .map(|(w, _)| w.date_end)
+                .chain(unavailable_ends.iter().copied())
+                .filter(|end| *end > window.date_start)
+                .collect();
+            candidates.push(window.date_start);
+            candidates.sort();
+            candidates.dedup();
+
+            let mut new_start = None;
+            for candidate in candidates {
+                let start = if candidate == window.date_start {
+                    candidate
+                } else {
+                    resource_calendar.snap_moment_forward(candidate)?
+                };
+                let shifted = TimeWindow {
+                    date_start: start,
+                    date_end: start + duration,
+                };
+                // Назначение, уже выходящее за окончание задачи, можно только оставить на месте
+                if shifted != window && shifted.date_end > task_end {
+                    break;
+                }
+                if resource.is_available(&shifted, calendar)
+                    && fits_capacity(&shifted, allocation.engagement_rate, &placed)
+                {
+                    new_start = Some(shifted);
+                    break;
+                }
+            }
+            let Some(shifted) = new_start else {
+                anyhow::bail!(
+                    "Cannot level resource: allocation {} does not fit before its task ends",
+                    allocation.id
+                );
+            };
+            placed.push((shifted, allocation.engagement_rate));
+            if shifted != window {
+                moves.push((allocation.id, shifted));
+            }
+        }
+
+        for (allocation_id, window) in &moves {
+            if let Some(allocation) = self.allocations.get_mut(allocation_id) {
+                allocation.time_window = *window;
+                self.events.push(ContainerEvent::AllocationUpdated {
+                    allocation_id: *allocation_id,
+                });
+            }
+        }
+        Ok(moves.into_iter().map(|(id, _)| id).collect())
+    }
+
+    /// Полностью свободное время ресурса внутри `within`: рабочие дни без единого назначения
+    /// и без периодов недоступности
+    ///
+    /// Назначения берутся так же, как в `free_windows` (`counted_allocations`, без архивных
+    /// проектов). Дни - локальные даты календаря ресурса. Соседние свободные рабочие дни
+    /// склеиваются в одно окно, выходные разрывают окна.
+    /// Для неизвестного ресурса возвращается пустой список.
+    pub fn idle_windows(
+        &self,
+        resource_id: Uuid,
+        within: &TimeWindow,
+        calendar: &ProjectCalendar,
+    ) -> Vec<TimeWindow> {
+        let Some(resource) = self.resources.get(&resource_id) else {
+            return vec![];
+        };
+        let busy = merge_overlapping(
+            self.counted_allocations(&resource_id)
+                .into_iter()
+                .map(|a| a.time_window)
+                .chain(resource.exceptions(calendar).map(|e| e.window))
+                .collect(),
+        );
+        let calendar = resource.effective_calendar(calendar);
+        let free = within.subtract_all(&busy);
+
+        let mut idle: Vec<TimeWindow> = vec![];
+        for day in free.iter().flat_map(|w| w.split_by_local_days(calendar)) {
+            if !calendar.is_working_at(day.date_start) {
+                continue;
+            }
+            match idle.last_mut() {
+                Some(last) if last.date_end == day.date_start => last.date_end = day.date_end,
+                _ => idle.push(day),
+            }
+        }
+        idle
+    }
+
+    /// Части `within`, где у ресурса остается не меньше `min_free_engagement` свободной занятости
+    ///
+    /// Время делится по границам назначений, загрузка участка - сумма занятостей назначений
+    /// на нем (та же модель, что и при проверке в `allocate`). Периоды недоступности ресурса
+    /// и закрытия календарей (`Resource::exceptions`) исключаются, соседние свободные участки
+    /// сливаются.
+    pub fn free_windows(
+        &self,
+        resource_id: Uuid,
+        within: &TimeWindow,
+        min_free_engagement: f64,
+        calendar: &ProjectCalendar,
+    ) -> Vec<TimeWindow> {
+        let Some(resource) = self.resources.get(&resource_id) else {
+            return vec![];
+        };
+        let allocations: Vec<&ResourceAllocation> = self
+            .counted_allocations(&resource_id)
+            .into_iter()
+            .filter(|a| a.time_window.overlaps(within))
+            .collect();
+        let mut boundaries: Vec<_> = allocations
+            .iter()
+            .flat_map(|a| [a.time_window.date_start, a.time_window.date_end])
+            .filter(|moment| within.contains(moment))
+            .chain([within.date_start, within.date_end])
+            .collect();
+        boundaries.sort();
+        boundaries.dedup();
+
+        let mut free: Vec<TimeWindow> = vec![];
+        for segment in boundaries.windows(2) {
+            let window = TimeWindow {
+                date_start: segment[0],
+                date_end: segment[1],
+            };
+            let load: f64 = allocations
+                .iter()
+                .filter(|a| a.time_window.overlaps(&window))
+                .map(|a| a.engagement_rate)
+                .sum();
+            if 1.0 - load + f64::EPSILON < min_free_engagement {
+                continue;
+            }
+            match free.last_mut() {
+                Some(last) if last.date_end == window.date_start => last.date_end = window.date_end,
+                _ => free.push(window),
+            }
+        }
+
+        let unavailable: Vec<TimeWindow> =
+            resource.exceptions(calendar).map(|e| e.window).collect();
+        free.iter()
+            .flat_map(|w| w.subtract_all(&unavailable))
+            .collect()
+    }
+
     pub fn get_resource_by_name(&self, find_name: String) -> Option<&Resource> {
         self.resources.values().find(|r| r.name == find_name)
     }
 
-    /// Функция должна проверить, что ресурс можно корректно назначить на
-    /// Несколько проверок перед назначением ресурса на задачу в пуле
-    /// 1. Ресурс с таким ID существует в пуле
+    /// Ресурсы, подходящие под условие, упорядоченные по имени
+    pub fn find_resources(&self, predicate: impl Fn(&Resource) -> bool) -> Vec<&Resource> {
+        let mut found: Vec<&Resource> = self.resources.values().filter(|r| predicate(r)).collect();
+        found.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+        found
+    }
+
+    /// Ресурсы, имя которых содержит подстроку без учета регистра (для автодополнения)
+    pub fn search_by_name_substring(&self, needle: &str) -> Vec<&Resource> {
+        let needle = needle.to_lowercase();
+        self.find_resources(|r| r.name.to_lowercase().contains(&needle))
+    }
+
+    /// Поиск перегрузок по всем ресурсам пула
+    ///
+    /// Для каждого ресурса проходим по границам его аллокаций и собираем отрезки, где суммарная
+    /// занятость больше 1.0. Соседние отрезки с одним и тем же набором аллокаций склеиваются.
+    /// Нужна для данных, созданных в обход проверки при назначении (импорт, старые файлы).
+    pub fn find_conflicts(&self) -> Vec<ResourceConflict> {
+        let mut resources: Vec<&Resource> = self.resources.values().collect();
+        resources.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+
+        let mut conflicts = vec![];
+        for resource in resources {
+            let allocations = self.counted_allocations(&resource.id);
+            let mut boundaries: Vec<_> = allocations
+                .iter()
+                .flat_map(|a| [a.time_window.date_start, a.time_window.date_end])
+                .collect();
+            boundaries.sort();
+            boundaries.dedup();
+
+            let mut current: Option<ResourceConflict> = None;
+            for segment in boundaries.windows(2) {
+                let window = TimeWindow {
+                    date_start: segment[0],
+                    date_end: segment[1],
+                };
+                let mut active: Vec<&&ResourceAllocation> = allocations
+                    .iter()
+                    .filter(|a| a.time_window.overlaps(&window))
+                    .collect();
+                active.sort_by_key(|a| a.id);
+                let total_engagement: f64 = active.iter().map(|a| a.engagement_rate).sum();
+                if total_engagement <= 1.0 + f64::EPSILON {
+                    conflicts.extend(current.take());
+                    continue;
+                }
+
+                let allocation_ids: Vec<Uuid> = active.iter().map(|a| a.id).collect();
+                match current.as_mut() {
+                    Some(conflict)
+                        if conflict.allocation_ids == allocation_ids
+                            && conflict.window.date_end == window.date_start =>
+                    {
+                        conflict.window.date_end = window.date_end;
+                    }
+                    _ => {
+                        conflicts.extend(current.take());
+                        current = Some(ResourceConflict {
+                            resource_id: resource.id,
+                            resource_name: resource.name.clone(),
+                            window,
+                            total_engagement,
+                            allocation_ids,
+                        });
+                    }
+                }
+            }
+            conflicts.extend(current);
+        }
+        conflicts
+    }
+
+    /// Запрос, по которому можно заново создать аллокацию
+    fn request_from(allocation: &ResourceAllocation) -> AllocationRequest {
+        AllocationRequest {
+            resource_id: allocation.resource_id,
+            task_id: allocation.task_id,
+            project_id: allocation.project_id,
+            engagement_rate: allocation.engagement_rate,
+            time_window: allocation.time_window,
+            id: None,
+            priority: allocation.priority,
+        }
+    }
+
+    /// Заменить аллокации `ids` запросами из `rebuild` по принципу "все или ничего".
+    /// id и порядок аллокаций сохраняются, при ошибках пул не меняется.
+    fn replace_allocations<'c>(
+        &mut self,
+        ids: &[Uuid],
+        rebuild: impl Fn(usize, &ResourceAllocation) -> (AllocationRequest, &'c ProjectCalendar),
+    ) -> Vec<(Uuid, String)> {
+        // Вынимаем все заменяемые аллокации, чтобы они не мешали проверке загрузки
+        let mut originals = vec![];
+        let mut errors = vec![];
+        for allocation_id in ids {
+            match self.allocations.remove(allocation_id) {
+                Some(allocation) => originals.push(allocation),
+                None => errors.push((*allocation_id, "This allocation not found".to_string())),
+            }
+        }
+
+        let mut inserted = vec![];
+        if errors.is_empty() {
+            for (i, original) in originals.iter().enumerate() {
+                let (request, calendar) = rebuild(i, original);
+                // Задача и проект не меняются, проверяем только ресурс
+                match self.check_allocation_correct(&request, calendar) {
+                    Ok(()) => {
+                        // id аллокации сохраняется - ссылки из задач остаются верными
+                        let mut allocation = ResourceAllocation::new(request);
+                        allocation.id = original.id;
+                        allocation.sequence = original.sequence;
+                        self.allocations.insert(allocation.id, allocation);
+                        inserted.push(original.id);
+                    }
+                    Err(e) => errors.push((original.id, e.to_string())),
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            tracing::debug!(
+                errors = errors.len(),
+                "allocation replacement rejected, allocations restored"
+            );
+            for allocation_id in inserted {
+                self.allocations.remove(&allocation_id);
+            }
+            for allocation in originals {
+                self.allocations.insert(allocation.id, allocation);
+            }
+        } else {
+            self.events.extend(
+                inserted
+                    .into_iter()
+                    .map(|allocation_id| ContainerEvent::AllocationUpdated { allocation_id }),
+            );
+        }
+        errors
+    }
+
+    /// Проверки перед назначением ресурса на задачу в пуле
+    /// 1. Заданный в запросе id назначения еще не занят
+    /// 2. Ресурс с таким ID существует в пуле
+    /// 3. Ресурс доступен в окне запроса по календарю проекта (отпуска, нерабочие дни)
+    /// 4. Суммарная занятость ресурса на этой задаче не больше 1.0
+    /// 5. На каждом участке окна загрузка ресурса вместе с запросом не больше 100%
+    ///
+    /// Принадлежность задачи проекту здесь не проверяется - это делает вызывающий код
     fn check_allocation_correct(
         &self,
         request: &AllocationRequest,
         calendar: &ProjectCalendar,
     ) -> anyhow::Result<()> {
+        if let Some(allocation_id) = request.id
+            && self.allocations.contains_key(&allocation_id)
+        {
+            return Err(AllocationErrors::DuplicateAllocationId { allocation_id }.into());
+        }
+
         let resource = self
             .resources
             .get(&request.resource_id)
@@ -139,8 +570,23 @@ impl LocalResourcePool {
             ));
         }
 
-        let existing_allocation_on_resource =
-            self.get_resource_existing_allocations(&request.resource_id);
+        // Один ресурс на одной задаче - не больше полной занятости, даже в разных окнах
+        let on_task: f64 = self
+            .get_resource_existing_allocations(&request.resource_id)
+            .iter()
+            .filter(|a| a.task_id == request.task_id)
+            .map(|a| a.engagement_rate)
+            .sum();
+        if on_task > 0.0 && on_task + request.engagement_rate > 1.0 + f64::EPSILON {
+            return Err(AllocationErrors::DuplicateResourceOnTask {
+                resource_id: request.resource_id,
+                task_id: request.task_id,
+                total: on_task + request.engagement_rate,
+            }
+            .into());
+        }
+
+        let existing_allocation_on_resource = self.counted_allocations(&request.resource_id);
 
         // Ресурс есть в пуле и у него еще нет никаких аллокаций - можем смело добавлять.
         if existing_allocation_on_resource.is_empty() {
@@ -151,9 +597,8 @@ impl LocalResourcePool {
             allocations_list: existing_allocation_on_resource,
         };
 
-        // Нашли существующие аллокации - нужно проверить, что
-        // 1. У ресуса есть свободное окно, чтобы заниматься работой
-        // 2. Если окна занятости пересекаются - сумма всех engagement_rate у всех пересекающихся аллокаций должна быть <= 1.0
+        // Нашли существующие аллокации - на каждом участке окна запроса сумма engagement_rate
+        // назначений, действующих на этом участке, вместе с запросом должна быть <= 1.0
         if !aqr.check_correct_timewindow(request) {
             return Err(anyhow::Error::msg(
                 "This allocation can't be created, because Resoure will be utilized more than 100%",
@@ -164,7 +609,70 @@ impl LocalResourcePool {
     }
 }
 
+/// Помещается ли загрузка `engagement` в окне `window` к уже расставленным назначениям
+pub(crate) fn fits_capacity(
+    window: &TimeWindow,
+    engagement: f64,
+    placed: &[(TimeWindow, f64)],
+) -> bool {
+    let mut boundaries = vec![window.date_start, window.date_end];
+    for (other, _) in placed.iter().filter(|(w, _)| w.overlaps(window)) {
+        boundaries.push(other.date_start.max(window.date_start));
+        boundaries.push(other.date_end.min(window.date_end));
+    }
+    boundaries.sort();
+    boundaries.dedup();
+    boundaries.windows(2).all(|segment| {
+        let part = TimeWindow {
+            date_start: segment[0],
+            date_end: segment[1],
+        };
+        let total: f64 = placed
+            .iter()
+            .filter(|(w, _)| w.overlaps(&part))
+            .map(|(_, e)| e)
+            .sum();
+        total + engagement <= 1.0 + f64::EPSILON
+    })
+}
+
 impl ResourcePool for LocalResourcePool {
+    fn reassign_allocations(
+        &mut self,
+        changes: &[(Uuid, Uuid, ProjectCalendar)],
+    ) -> Vec<(Uuid, String)> {
+        let ids: Vec<Uuid> = changes.iter().map(|(id, _, _)| *id).collect();
+        self.replace_allocations(&ids, |i, original| {
+            let (_, new_resource_id, calendar) = &changes[i];
+            let mut request = Self::request_from(original);
+            request.resource_id = *new_resource_id;
+            (request, calendar)
+        })
+    }
+
+    fn move_allocations(
+        &mut self,
+        moves: &[(Uuid, TimeWindow)],
+        calendar: &ProjectCalendar,
+    ) -> Vec<(Uuid, String)> {
+        let ids: Vec<Uuid> = moves.iter().map(|(id, _)| *id).collect();
+        self.replace_allocations(&ids, |i, original| {
+            let (_, time_window) = moves[i];
+            let mut request = Self::request_from(original);
+            request.time_window = time_window;
+            (request, calendar)
+        })
+    }
+
+    fn level_resource(
+        &mut self,
+        resource_id: Uuid,
+        calendar: &ProjectCalendar,
+        task_ends: &HashMap<Uuid, DateTime<Utc>>,
+    ) -> anyhow::Result<Vec<Uuid>> {
+        LocalResourcePool::level_resource(self, resource_id, calendar, task_ends)
+    }
+
     fn allocate(
         &mut self,
         request: AllocationRequest,
@@ -172,26 +680,117 @@ impl ResourcePool for LocalResourcePool {
     ) -> anyhow::Result<Uuid> {
         match self.check_allocation_correct(&request, calendar) {
             Ok(()) => {
-                let allocation = ResourceAllocation::new(request);
+                let mut allocation = ResourceAllocation::new(request);
+                self.last_sequence += 1;
+                allocation.sequence = self.last_sequence;
                 let allocation_id = allocation.get_id();
+                self.events.push(ContainerEvent::AllocationCreated {
+                    allocation_id,
+                    resource_id: allocation.resource_id,
+                    task_id: allocation.task_id,
+                });
                 self.allocations.insert(allocation.get_id(), allocation);
+                tracing::debug!(%allocation_id, "allocation created");
                 Ok(allocation_id)
             }
-            Err(e) => Err(e),
+            Err(e) => {
+                tracing::debug!(
+                    resource_id = %request.resource_id,
+                    task_id = %request.task_id,
+                    reason = %e,
+                    "allocation rejected"
+                );
+                Err(e)
+            }
         }
     }
-    fn get_resources(&self) -> Vec<&Resource> {
+    fn allocate_batch(
+        &mut self,
+        requests: &[AllocationRequest],
+        calendar: &ProjectCalendar,
+    ) -> anyhow::Result<Vec<Uuid>> {
+        let events_before = self.events.len();
+        let sequence_before = self.last_sequence;
+        let mut created = Vec::with_capacity(requests.len());
+        for (index, request) in requests.iter().enumerate() {
+            // Принятые запросы уже лежат в пуле, поэтому следующие проверяются с их загрузкой
+            match self.allocate(*request, calendar) {
+                Ok(allocation_id) => created.push(allocation_id),
+                Err(e) => {
+                    for allocation_id in created {
+                        self.allocations.remove(&allocation_id);
+                    }
+                    self.events.truncate(events_before);
+                    self.last_sequence = sequence_before;
+                    return Err(e.context(format!("allocation request #{} rejected", index)));
+                }
+            }
+        }
+        Ok(created)
+    }
+    fn resources(&self) -> Vec<&Resource> {
         self.resources.values().collect()
     }
+    fn allocations(&self) -> Vec<&ResourceAllocation> {
+        self.allocations.values().collect()
+    }
+    fn resource_count(&self) -> usize {
+        self.resources.len()
+    }
+    fn take_events(&mut self) -> Vec<ContainerEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    fn restore_project_allocations(
+        &mut self,
+        project_id: &Uuid,
+        allocations: Vec<ResourceAllocation>,
+    ) {
+        let restored: HashSet<Uuid> = allocations.iter().map(|a| a.id).collect();
+        let removed: Vec<Uuid> = self
+            .allocations
+            .values()
+            .filter(|a| &a.project_id == project_id && !restored.contains(&a.id))
+            .map(|a| a.id)
+            .collect();
+        for allocation_id in removed {
+            self.allocations.remove(&allocation_id);
+            self.events
+                .push(ContainerEvent::AllocationRemoved { allocation_id });
+        }
+        for allocation in allocations {
+            let allocation_id = allocation.id;
+            self.last_sequence = self.last_sequence.max(allocation.sequence);
+            let event = if self.allocations.contains_key(&allocation_id) {
+                ContainerEvent::AllocationUpdated { allocation_id }
+            } else {
+                ContainerEvent::AllocationCreated {
+                    allocation_id,
+                    resource_id: allocation.resource_id,
+                    task_id: allocation.task_id,
+                }
+            };
+            self.events.push(event);
+            self.allocations.insert(allocation_id, allocation);
+        }
+    }
     fn deallocate(&mut self, allocation_id: Uuid) -> anyhow::Result<()> {
         let alocation = self.allocations.remove(&allocation_id);
         match alocation {
-            Some(_) => Ok(()),
+            Some(_) => {
+                self.events
+                    .push(ContainerEvent::AllocationRemoved { allocation_id });
+                tracing::debug!(%allocation_id, "allocation removed");
+                Ok(())
+            }
             None => Err(anyhow::Error::msg("This allocation not found")),
         }
     }
 
     fn add_resource(&mut self, resource: Resource) -> anyhow::Result<()> {
+        self.events.push(ContainerEvent::ResourceAdded {
+            resource_id: resource.id,
+        });
         self.resources.insert(resource.id, resource);
         Ok(())
     }
@@ -200,6 +799,9 @@ impl ResourcePool for LocalResourcePool {
         match self.resources.contains_key(id) {
             true => {
                 self.resources.remove(id);
+                self.events
+                    .push(ContainerEvent::ResourceRemoved { resource_id: *id });
+                tracing::info!(resource_id = %id, "resource removed from pool");
                 Ok(())
             }
             false => Err(anyhow::Error::msg(format!(
@@ -216,6 +818,24 @@ impl ResourcePool for LocalResourcePool {
             .collect()
     }
 
+    fn counted_allocations(&self, resource_id: &Uuid) -> Vec<&ResourceAllocation> {
+        self.get_resource_existing_allocations(resource_id)
+            .into_iter()
+            .filter(|a| !self.excluded_projects.contains(&a.project_id))
+            .collect()
+    }
+
+    fn get_project_allocations(&self, project_id: &Uuid) -> Vec<&ResourceAllocation> {
+        self.allocations
+            .values()
+            .filter(|a| &a.project_id == project_id)
+            .collect()
+    }
+
+    fn find_conflicts(&self) -> Vec<ResourceConflict> {
+        LocalResourcePool::find_conflicts(self)
+    }
+
     fn get_mut_resource_by_uuid(&mut self, resource_id: Uuid) -> Option<&mut Resource> {
         self.resources.get_mut(&resource_id)
     }
@@ -232,6 +852,7 @@ impl ResourcePool for LocalResourcePool {
         &self,
         allocation_id: &Uuid,
         calendar: &ProjectCalendar,
+        rate_config: &RateConfig,
     ) -> anyhow::Result<f64> {
         let allocation = self
             .allocations
@@ -241,18 +862,25 @@ impl ResourcePool for LocalResourcePool {
             .resources
             .get(&allocation.resource_id)
             .ok_or_else(|| anyhow::anyhow!("Ресурс из назначения не найден!"))?;
-        // Определяем длительность работы из назначения
-
-        let hours = allocation.time_window.duration_hours(calendar) as f64;
-        let hourly_rate = match resource.get_rate_measure() {
-            RateMeasure::Hourly => *resource.get_base_rate(),
-            RateMeasure::Daily => resource.get_base_rate() / calendar.working_hours_per_day as f64,
+        let calendar = resource.effective_calendar(calendar);
+        let window = &allocation.time_window;
+        let cost = match resource.get_rate_measure() {
+            RateMeasure::Hourly => {
+                resource.get_base_rate() * window.duration_hours(calendar) as f64
+            }
+            // Дневная ставка платится за рабочий день, сколько бы часов в нем ни было
+            RateMeasure::Daily => {
+                resource.get_base_rate() * calendar.count_working_days(window) as f64
+            }
             RateMeasure::Monthly => {
-                resource.get_base_rate()
-                    / calendar.working_hours_in_period(&allocation.time_window) as f64
+                RateMeasure::Monthly.convert_with(
+                    RateMeasure::Daily,
+                    *resource.get_base_rate(),
+                    rate_config,
+                ) * calendar.count_working_days(window) as f64
             }
         };
-        Ok(hourly_rate * hours * allocation.engagement_rate)
+        Ok(cost * allocation.engagement_rate)
     }
 
     fn calculate_allocation_time(
@@ -268,6 +896,7 @@ impl ResourcePool for LocalResourcePool {
             .resources
             .get(&allocation.resource_id)
             .ok_or_else(|| anyhow::anyhow!("Ресурс из назначения не найден!"))?;
+        let calendar = resource.effective_calendar(calendar);
         // Определяем длительность работы из назначения
 
         let hours = allocation.time_window.duration_hours(calendar) as f64;
@@ -277,15 +906,55 @@ impl ResourcePool for LocalResourcePool {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use chrono::{DateTime, TimeZone, Utc};
+    use uuid::Uuid;
 
     use crate::base_structures::{
+        availability::AvailabilityException,
         project_calendar::ProjectCalendar,
-        resource::{RateMeasure, Resource},
-        resource_pool::{AllocationRequest, LocalResourcePool},
+        resource::{ExceptionPeriod, ExceptionType, RateConfig, RateMeasure, Resource},
+        resource_pool::{AllocationRequest, DEFAULT_ALLOCATION_PRIORITY, LocalResourcePool},
         time_window::TimeWindow,
         traits::ResourcePool,
     };
+    use crate::cust_exceptions::AllocationErrors;
+
+    #[test]
+    fn test_monthly_cost_uses_rate_config() {
+        let mut lrp = LocalResourcePool::default();
+        let project_calendar = ProjectCalendar::default();
+        let resource = Resource::new(String::from("Monthly"), 22000.0, RateMeasure::Monthly)
+            .expect("Can't create resource");
+        lrp.add_resource(resource.clone()).unwrap();
+        // Пн 2 - Пт 6 февраля 2026: 5 рабочих дней
+        let week = AllocationRequest::new(
+            resource.id,
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            0.5,
+            TimeWindow::new(
+                Utc.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 2, 6, 0, 0, 0).unwrap(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let allocation_id = lrp.allocate(week, &project_calendar).unwrap();
+
+        // Не вся месячная ставка, а 5 дней из 22 (по умолчанию) или из 20
+        let cost = |cfg: &RateConfig| {
+            lrp.calculate_allocation_cost(&allocation_id, &project_calendar, cfg)
+                .unwrap()
+        };
+        assert_eq!(cost(&RateConfig::default()), 22000.0 / 22.0 * 5.0 * 0.5);
+        let cfg = RateConfig {
+            hours_per_day: 7.5,
+            days_per_month: 20.0,
+        };
+        assert_eq!(cost(&cfg), 22000.0 / 20.0 * 5.0 * 0.5);
+    }
 
     #[test]
     fn test_calculate_cost() {
@@ -306,17 +975,219 @@ mod tests {
                 Utc.with_ymd_and_hms(2025, 1, 11, 0, 0, 0).unwrap(),
             )
             .unwrap(),
-        );
+        )
+        .unwrap();
 
         let allocation_id = lrp.allocate(allocation_request, &project_calendar).unwrap();
         let cost = lrp
-            .calculate_allocation_cost(&allocation_id, &project_calendar)
+            .calculate_allocation_cost(&allocation_id, &project_calendar, &RateConfig::default())
             .unwrap();
         // cost = hourly_rate * hours * engagement_rate
         // hourly_rate = 1000
         // hours = 8 working days * 8 hours/day = 64 hours
         // engagement_rate = 0.8
         assert_eq!(cost, 1000.0 * 64.0 * 0.8);
+
+        // Полдня: оплачиваются только часы окна (четверг 16 января 2025, 13:00-17:00)
+        let afternoon = AllocationRequest::new(
+            resource.id,
+            uuid::Uuid::new_v4(),
+            project_id,
+            1.0,
+            TimeWindow::new(
+                Utc.with_ymd_and_hms(2025, 1, 16, 13, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 1, 16, 17, 0, 0).unwrap(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let allocation_id = lrp.allocate(afternoon, &project_calendar).unwrap();
+        let cost = lrp
+            .calculate_allocation_cost(&allocation_id, &project_calendar, &RateConfig::default())
+            .unwrap();
+        assert_eq!(cost, 1000.0 * 4.0);
+    }
+
+    #[test]
+    fn test_pool_enumeration_through_trait_object() {
+        let mut pool: Box<dyn ResourcePool> = Box::new(LocalResourcePool::default());
+        let calendar = ProjectCalendar::default();
+        assert_eq!(pool.resource_count(), 0);
+        assert!(pool.allocations().is_empty());
+
+        let first = Resource::new("First".into(), 10.0, RateMeasure::Hourly).unwrap();
+        let second = Resource::new("Second".into(), 20.0, RateMeasure::Hourly).unwrap();
+        let first_id = first.id;
+        pool.add_resource(first).unwrap();
+        pool.add_resource(second).unwrap();
+        let request = AllocationRequest::new(
+            first_id,
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            0.5,
+            TimeWindow::new(
+                Utc.with_ymd_and_hms(2025, 1, 6, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 1, 10, 0, 0, 0).unwrap(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let allocation_id = pool.allocate(request, &calendar).unwrap();
+
+        assert_eq!(pool.resource_count(), 2);
+        let mut names: Vec<&str> = pool.resources().iter().map(|r| r.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["First", "Second"]);
+        let allocations = pool.allocations();
+        assert_eq!(allocations.len(), 1);
+        assert_eq!(allocations[0].get_id(), allocation_id);
+    }
+
+    #[test]
+    fn test_idle_windows_after_allocation() {
+        let mut lrp = LocalResourcePool::default();
+        let calendar = ProjectCalendar::default();
+        let at = |m: u32, d: u32| Utc.with_ymd_and_hms(2026, m, d, 0, 0, 0).unwrap();
+        let mut resource = Resource::new("Bench".into(), 100.0, RateMeasure::Hourly).unwrap();
+        // Отпуск в последнюю неделю марта
+        resource.add_unavailable_period(ExceptionPeriod {
+            period: TimeWindow::new(at(3, 23), at(3, 28)).unwrap(),
+            exception_type: ExceptionType::Vacation,
+        });
+        let resource_id = resource.id;
+        lrp.add_resource(resource).unwrap();
+        let request = AllocationRequest::new(
+            resource_id,
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            0.2,
+            TimeWindow::new(at(1, 1), at(3, 1)).unwrap(),
+        )
+        .unwrap();
+        lrp.allocate(request, &calendar).unwrap();
+
+        let quarter = TimeWindow::new(at(1, 1), at(4, 1)).unwrap();
+        let idle = lrp.idle_windows(resource_id, &quarter, &calendar);
+        // Март 2026: недели с понедельника 2, 9, 16 и 30 числа; 23-27 - отпуск
+        assert_eq!(
+            idle,
+            vec![
+                TimeWindow::new(at(3, 2), at(3, 7)).unwrap(),
+                TimeWindow::new(at(3, 9), at(3, 14)).unwrap(),
+                TimeWindow::new(at(3, 16), at(3, 21)).unwrap(),
+                TimeWindow::new(at(3, 30), at(4, 1)).unwrap(),
+            ]
+        );
+        assert!(
+            lrp.idle_windows(Uuid::new_v4(), &quarter, &calendar)
+                .is_empty()
+        );
+
+        // Назначения архивного проекта не занимают ресурс, как и в free_windows
+        let archived = Uuid::new_v4();
+        let march = TimeWindow::new(at(3, 2), at(3, 7)).unwrap();
+        let request =
+            AllocationRequest::new(resource_id, Uuid::new_v4(), archived, 0.5, march).unwrap();
+        lrp.allocate(request, &calendar).unwrap();
+        assert!(
+            !lrp.idle_windows(resource_id, &quarter, &calendar)
+                .contains(&march)
+        );
+        lrp.set_project_excluded(archived, true);
+        assert_eq!(lrp.idle_windows(resource_id, &quarter, &calendar), idle);
+        assert_eq!(
+            lrp.free_windows(resource_id, &march, 1.0, &calendar),
+            lrp.idle_windows(resource_id, &march, &calendar)
+        );
+    }
+
+    #[test]
+    fn test_idle_windows_split_by_local_days() {
+        let mut lrp = LocalResourcePool::default();
+        let mut calendar = ProjectCalendar::default();
+        calendar.set_timezone(chrono_tz::Asia::Tokyo);
+        let date = |d: u32| chrono::NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let resource = Resource::new("Tokyo".into(), 100.0, RateMeasure::Hourly).unwrap();
+        let resource_id = resource.id;
+        lrp.add_resource(resource).unwrap();
+
+        // Пн 2 - Пт 6 марта по Токио; суббота 7 марта начинается в 15:00 UTC пятницы
+        let week = TimeWindow::new(
+            calendar.local_midnight(date(2)),
+            calendar.local_midnight(date(9)),
+        )
+        .unwrap();
+        assert_eq!(
+            lrp.idle_windows(resource_id, &week, &calendar),
+            vec![
+                TimeWindow::new(
+                    calendar.local_midnight(date(2)),
+                    calendar.local_midnight(date(7))
+                )
+                .unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_allocation_request_engagement_range() {
+        let window = TimeWindow::new(
+            Utc.with_ymd_and_hms(2025, 1, 6, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 1, 10, 0, 0, 0).unwrap(),
+        )
+        .unwrap();
+        let request = |rate: f64| {
+            AllocationRequest::new(Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), rate, window)
+        };
+        assert!(request(0.0).is_ok());
+        assert!(request(1.0).is_ok());
+        assert_eq!(
+            request(1.5).unwrap_err(),
+            AllocationErrors::InvalidEngagement { rate: 1.5 }
+        );
+        assert!(request(-0.1).is_err());
+        assert!(request(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_same_resource_on_task_limited_to_full_engagement() {
+        let mut lrp = LocalResourcePool::default();
+        let calendar = ProjectCalendar::default();
+        let resource = Resource::new("Dev".into(), 100.0, RateMeasure::Hourly).unwrap();
+        let resource_id = resource.id;
+        lrp.add_resource(resource).unwrap();
+        let (task_id, project_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let window = |from: u32, to: u32| {
+            TimeWindow::new(
+                Utc.with_ymd_and_hms(2025, 1, from, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 1, to, 0, 0, 0).unwrap(),
+            )
+            .unwrap()
+        };
+        let request = |rate: f64, from: u32, to: u32| {
+            AllocationRequest::new(resource_id, task_id, project_id, rate, window(from, to))
+                .unwrap()
+        };
+
+        lrp.allocate(request(0.5, 6, 10), &calendar).unwrap();
+        // Вторая половина занятости на той же задаче допустима
+        lrp.allocate(request(0.5, 13, 17), &calendar).unwrap();
+        // Окна не пересекаются, но суммарно ресурс на задаче занят больше чем на 100%
+        let error = lrp.allocate(request(0.25, 20, 24), &calendar).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<AllocationErrors>(),
+            Some(&AllocationErrors::DuplicateResourceOnTask {
+                resource_id,
+                task_id,
+                total: 1.25
+            })
+        );
+        // На другую задачу тот же ресурс назначается
+        let other_task = Uuid::new_v4();
+        let other =
+            AllocationRequest::new(resource_id, other_task, project_id, 0.5, window(20, 24))
+                .unwrap();
+        lrp.allocate(other, &calendar).unwrap();
     }
 
     #[test]
@@ -338,7 +1209,8 @@ mod tests {
                 Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
             )
             .unwrap(),
-        );
+        )
+        .unwrap();
 
         assert!(lrp.allocate(allocation_request, &project_calendar).is_ok());
 
@@ -347,6 +1219,295 @@ mod tests {
 
         assert!(lrp.deallocate(al_id.get_id()).is_ok())
     }
+    #[test]
+    fn test_resource_calendar_overrides_project_calendar() {
+        let mut lrp = LocalResourcePool::default();
+        let project_calendar = ProjectCalendar::default();
+        let mut resource = Resource::new(String::from("Contractor"), 100.0, RateMeasure::Hourly)
+            .expect("Can't create resource");
+        // Подрядчик работает с понедельника по четверг
+        let mut four_days = ProjectCalendar::default();
+        four_days.set_working_days([
+            chrono::Weekday::Mon,
+            chrono::Weekday::Tue,
+            chrono::Weekday::Wed,
+            chrono::Weekday::Thu,
+        ]);
+        resource.calendar = Some(four_days);
+        lrp.add_resource(resource.clone()).unwrap();
+        let project_id = Uuid::new_v4();
+
+        // Пятница рабочая по календарю проекта, но не для ресурса
+        let friday = AllocationRequest::new(
+            resource.id,
+            Uuid::new_v4(),
+            project_id,
+            0.5,
+            TimeWindow::new(
+                Utc.with_ymd_and_hms(2026, 2, 6, 9, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 2, 6, 18, 0, 0).unwrap(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(project_calendar.is_working_day(friday.time_window.date_start.date_naive()));
+        assert!(lrp.allocate(friday, &project_calendar).is_err());
+
+        // Неделя пн-пт считается по 4 рабочим дням ресурса
+        let week = AllocationRequest::new(
+            resource.id,
+            Uuid::new_v4(),
+            project_id,
+            0.5,
+            TimeWindow::new(
+                Utc.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 2, 6, 0, 0, 0).unwrap(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let allocation_id = lrp.allocate(week, &project_calendar).unwrap();
+        assert_eq!(
+            lrp.calculate_allocation_time(&allocation_id, &project_calendar)
+                .unwrap(),
+            4.0 * 8.0 * 0.5
+        );
+        assert_eq!(
+            lrp.calculate_allocation_cost(
+                &allocation_id,
+                &project_calendar,
+                &RateConfig::default()
+            )
+            .unwrap(),
+            4.0 * 8.0 * 0.5 * 100.0
+        );
+    }
+
+    #[test]
+    fn test_resource_calendar_works_on_project_holiday() {
+        // 7 марта 2026 - суббота и праздник в календаре проекта
+        let saturday = Utc.with_ymd_and_hms(2026, 3, 7, 9, 0, 0).unwrap();
+        let window = TimeWindow::new(saturday, saturday + chrono::TimeDelta::hours(8)).unwrap();
+        let mut project_calendar = ProjectCalendar::default();
+        project_calendar.add_holiday(saturday.date_naive());
+
+        let mut resource = Resource::new(String::from("Weekend"), 100.0, RateMeasure::Hourly)
+            .expect("Can't create resource");
+        assert!(!resource.is_available(&window, &project_calendar));
+        assert_eq!(resource.working_hours(&window, &project_calendar), 0);
+
+        let mut six_days = ProjectCalendar::default();
+        six_days.set_hours_for(chrono::Weekday::Sat, 8);
+        resource.set_calendar(Some(six_days));
+        assert!(resource.is_available(&window, &project_calendar));
+        assert_eq!(resource.working_hours(&window, &project_calendar), 8);
+
+        resource.set_calendar(None);
+        assert!(!resource.is_available(&window, &project_calendar));
+    }
+
+    #[test]
+    fn test_free_windows_around_staggered_allocations() {
+        let day = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap();
+        let mut lrp = LocalResourcePool::default();
+        let calendar = ProjectCalendar::default();
+        let mut max = Resource::new(String::from("Max"), 100.0, RateMeasure::Hourly).unwrap();
+        let max_id = max.id;
+        max.add_unavailable_period(AvailabilityException::new(
+            TimeWindow::new(day(20), day(23)).unwrap(),
+            ExceptionType::Vacation,
+        ));
+        lrp.add_resource(max).unwrap();
+        for (start, end) in [(5, 10), (10, 15)] {
+            let window = TimeWindow::new(day(start), day(end)).unwrap();
+            let request =
+                AllocationRequest::new(max_id, Uuid::new_v4(), Uuid::new_v4(), 0.6, window)
+                    .unwrap();
+            lrp.allocate(request, &calendar).unwrap();
+        }
+
+        // Под задачу на 0.5 свободны только начало и конец месяца (без отпуска)
+        let within = TimeWindow::new(day(2), day(30)).unwrap();
+        assert_eq!(
+            lrp.free_windows(max_id, &within, 0.5, &calendar),
+            vec![
+                TimeWindow::new(day(2), day(5)).unwrap(),
+                TimeWindow::new(day(15), day(20)).unwrap(),
+                TimeWindow::new(day(23), day(30)).unwrap(),
+            ]
+        );
+        // Оставшихся 0.4 хватает на все время, кроме отпуска
+        assert_eq!(
+            lrp.free_windows(max_id, &within, 0.4, &calendar),
+            vec![
+                TimeWindow::new(day(2), day(20)).unwrap(),
+                TimeWindow::new(day(23), day(30)).unwrap(),
+            ]
+        );
+        assert!(
+            lrp.free_windows(Uuid::new_v4(), &within, 0.5, &calendar)
+                .is_empty()
+        );
+
+        // Закрытие календаря проекта тоже не свободное время
+        let mut closed = calendar.clone();
+        closed.add_exception(AvailabilityException::new(
+            TimeWindow::new(day(25), day(27)).unwrap(),
+            ExceptionType::Vacation,
+        ));
+        assert_eq!(
+            lrp.free_windows(max_id, &within, 0.4, &closed),
+            vec![
+                TimeWindow::new(day(2), day(20)).unwrap(),
+                TimeWindow::new(day(23), day(25)).unwrap(),
+                TimeWindow::new(day(27), day(30)).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_allocate_accepts_what_free_windows_reports() {
+        let day = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap();
+        let mut lrp = LocalResourcePool::default();
+        let calendar = ProjectCalendar::default();
+        let anna = Resource::new(String::from("Anna"), 100.0, RateMeasure::Hourly).unwrap();
+        let anna_id = anna.id;
+        lrp.add_resource(anna).unwrap();
+        // Половина занятости на [2, 10) и на [10, 20): друг с другом не пересекаются
+        for (start, end) in [(2, 10), (10, 20)] {
+            let window = TimeWindow::new(day(start), day(end)).unwrap();
+            let request =
+                AllocationRequest::new(anna_id, Uuid::new_v4(), Uuid::new_v4(), 0.5, window)
+                    .unwrap();
+            lrp.allocate(request, &calendar).unwrap();
+        }
+
+        // [5, 15) свободна на 0.5 и назначение на нее проходит
+        let across = TimeWindow::new(day(5), day(15)).unwrap();
+        assert_eq!(
+            lrp.free_windows(anna_id, &across, 0.5, &calendar),
+            vec![across]
+        );
+        let request =
+            AllocationRequest::new(anna_id, Uuid::new_v4(), Uuid::new_v4(), 0.5, across).unwrap();
+        lrp.allocate(request, &calendar).unwrap();
+
+        // Теперь на [5, 15) занято полностью, лишние 0.1 не помещаются
+        assert!(
+            lrp.free_windows(anna_id, &across, 0.1, &calendar)
+                .is_empty()
+        );
+        let request =
+            AllocationRequest::new(anna_id, Uuid::new_v4(), Uuid::new_v4(), 0.1, across).unwrap();
+        assert!(lrp.allocate(request, &calendar).is_err());
+    }
+
+    #[test]
+    fn test_calendar_closure_blocks_allocation() {
+        let mut lrp = LocalResourcePool::default();
+        let resource = Resource::new(String::from("Dev"), 100.0, RateMeasure::Hourly).unwrap();
+        let resource_id = resource.id;
+        lrp.add_resource(resource).unwrap();
+
+        // Офис закрыт неделю 9-13 марта 2026, у самого ресурса отпусков нет
+        let monday = Utc.with_ymd_and_hms(2026, 3, 9, 0, 0, 0).unwrap();
+        let closure = TimeWindow::new(monday, monday + chrono::TimeDelta::days(5)).unwrap();
+        let mut calendar = ProjectCalendar::default();
+        calendar.add_exception(
+            AvailabilityException::new(closure, ExceptionType::Vacation)
+                .with_reason("Office closed"),
+        );
+
+        let window = TimeWindow::new(
+            monday + chrono::TimeDelta::days(1),
+            monday + chrono::TimeDelta::days(2),
+        )
+        .unwrap();
+        let request =
+            AllocationRequest::new(resource_id, Uuid::new_v4(), Uuid::new_v4(), 1.0, window)
+                .unwrap();
+        assert!(lrp.allocate(request, &calendar).is_err());
+        assert!(
+            lrp.idle_windows(resource_id, &closure, &calendar)
+                .is_empty()
+        );
+
+        // Без закрытия тот же запрос проходит
+        let open_calendar = ProjectCalendar::default();
+        assert!(lrp.allocate(request, &open_calendar).is_ok());
+    }
+
+    #[test]
+    fn test_old_exception_period_migrates() {
+        let mut resource = Resource::new(String::from("Old"), 100.0, RateMeasure::Hourly).unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap();
+        let period = TimeWindow::new(start, start + chrono::TimeDelta::days(3)).unwrap();
+        resource.add_unavailable_period(ExceptionPeriod {
+            period,
+            exception_type: ExceptionType::SickLeave,
+        });
+        assert_eq!(resource.get_unavailable_periods()[0].window, period);
+
+        // Файлы со старыми именами полей читаются
+        let json = serde_json::to_string(&ExceptionPeriod {
+            period,
+            exception_type: ExceptionType::Vacation,
+        })
+        .unwrap();
+        let exception: AvailabilityException = serde_json::from_str(&json).unwrap();
+        assert_eq!(exception.kind, ExceptionType::Vacation);
+        assert_eq!(exception.reason, None);
+    }
+
+    #[test]
+    fn test_resource_without_calendar_field_deserializes() {
+        let resource = Resource::new(String::from("Old"), 100.0, RateMeasure::Hourly).unwrap();
+        let mut json = serde_json::to_value(&resource).unwrap();
+        json.as_object_mut().unwrap().remove("calendar");
+        let loaded: Resource = serde_json::from_value(json).unwrap();
+        let project_calendar = ProjectCalendar::new(6);
+        assert!(loaded.calendar.is_none());
+        assert_eq!(
+            loaded
+                .effective_calendar(&project_calendar)
+                .hours_for(chrono::Weekday::Mon),
+            6
+        );
+    }
+
+    #[test]
+    fn test_allocate_batch_counts_its_own_requests() {
+        let mut lrp = LocalResourcePool::default();
+        let calendar = ProjectCalendar::default();
+        let resource = Resource::new(String::from("Max"), 100.0, RateMeasure::Hourly).unwrap();
+        lrp.add_resource(resource.clone()).unwrap();
+        lrp.take_events();
+        let project_id = Uuid::new_v4();
+        let window = TimeWindow::new(
+            Utc.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 2, 6, 0, 0, 0).unwrap(),
+        )
+        .unwrap();
+        let request = |engagement: f64| {
+            AllocationRequest::new(resource.id, Uuid::new_v4(), project_id, engagement, window)
+                .unwrap()
+        };
+        // Каждый запрос по отдельности проходит, вместе - перегрузка
+        let overloaded = [request(0.7), request(0.7)];
+        let error = lrp.allocate_batch(&overloaded, &calendar).unwrap_err();
+        assert!(error.to_string().contains("#1"));
+        assert!(
+            lrp.get_resource_existing_allocations(&resource.id)
+                .is_empty()
+        );
+        assert!(lrp.take_events().is_empty());
+
+        let fitting = [request(0.5), request(0.5)];
+        let ids = lrp.allocate_batch(&fitting, &calendar).unwrap();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(lrp.get_resource_existing_allocations(&resource.id).len(), 2);
+    }
+
     #[test]
     fn test_allocation_check() {
         let mut lrp = LocalResourcePool::default();
@@ -366,7 +1527,8 @@ mod tests {
                 Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
             )
             .unwrap(),
-        );
+        )
+        .unwrap();
         assert!(!lrp.check_resource_exists(&resource.id));
         // Нельзя назначить, пока ресурс не в пуле
         assert!(lrp.allocate(allocation_request, &project_calendar).is_err());
@@ -384,7 +1546,8 @@ mod tests {
                 Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
             )
             .unwrap(),
-        );
+        )
+        .unwrap();
         assert!(lrp.allocate(allocation_request2, &project_calendar).is_ok());
 
         let allocation_request3 = AllocationRequest::new(
@@ -397,13 +1560,76 @@ mod tests {
                 Utc.with_ymd_and_hms(2025, 7, 1, 0, 0, 0).unwrap(),
             )
             .unwrap(),
-        );
+        )
+        .unwrap();
         assert!(
             lrp.allocate(allocation_request3, &project_calendar)
                 .is_err()
         );
     }
 
+    #[test]
+    fn test_failed_allocation_emits_event() {
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing_subscriber::{Layer, layer::SubscriberExt};
+
+        /// Уровень и поля событий в виде строк
+        type Captured = Vec<(tracing::Level, HashMap<String, String>)>;
+        #[derive(Clone, Default)]
+        struct Capture(Arc<Mutex<Captured>>);
+        struct Fields(HashMap<String, String>);
+        impl Visit for Fields {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0
+                    .insert(field.name().to_string(), format!("{:?}", value));
+            }
+        }
+        impl<S: tracing::Subscriber> Layer<S> for Capture {
+            fn on_event(
+                &self,
+                event: &tracing::Event<'_>,
+                _ctx: tracing_subscriber::layer::Context<'_, S>,
+            ) {
+                let mut fields = Fields(HashMap::new());
+                event.record(&mut fields);
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((*event.metadata().level(), fields.0));
+            }
+        }
+
+        let mut lrp = LocalResourcePool::default();
+        // Ресурса нет в пуле
+        let resource_id = Uuid::new_v4();
+        let window = TimeWindow::new(
+            Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 6, 0, 0, 0).unwrap(),
+        )
+        .unwrap();
+        let request =
+            AllocationRequest::new(resource_id, Uuid::new_v4(), Uuid::new_v4(), 1.0, window)
+                .unwrap();
+        let task_id = request.task_id;
+
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(lrp.allocate(request, &ProjectCalendar::default()).is_err());
+        });
+
+        let events = capture.0.lock().unwrap();
+        let (level, fields) = events
+            .iter()
+            .find(|(_, f)| f.get("message").is_some_and(|m| m == "allocation rejected"))
+            .expect("rejection event");
+        assert_eq!(*level, tracing::Level::DEBUG);
+        assert_eq!(fields["resource_id"], resource_id.to_string());
+        assert_eq!(fields["task_id"], task_id.to_string());
+        assert!(fields["reason"].contains("Resource not found"));
+    }
+
     #[test]
     fn test_resource_measure_converter() {
         let resource = Resource::new(String::from("Test"), 1000.0, RateMeasure::Hourly)
@@ -413,9 +1639,35 @@ mod tests {
             resource.get_converted_rate(crate::base_structures::resource::RateMeasure::Daily),
             8000.0
         );
+        // Месяц - 22 дня по 8 часов
         assert_eq!(
             resource.get_converted_rate(crate::base_structures::resource::RateMeasure::Monthly),
-            22000.0
+            176000.0
+        );
+    }
+
+    #[test]
+    fn test_resource_measure_converter_with_config() {
+        let cfg = RateConfig {
+            hours_per_day: 7.5,
+            days_per_month: 21.0,
+        };
+        assert_eq!(
+            RateMeasure::Hourly.convert_with(RateMeasure::Daily, 1000.0, &cfg),
+            7500.0
+        );
+        assert_eq!(
+            RateMeasure::Daily.convert_with(RateMeasure::Monthly, 7500.0, &cfg),
+            157500.0
+        );
+        assert_eq!(
+            RateMeasure::Monthly.convert_with(RateMeasure::Hourly, 157500.0, &cfg),
+            1000.0
+        );
+        // Умолчания совпадают с прежними 8 часами и 22 днями
+        assert_eq!(
+            RateMeasure::Daily.convert_with(RateMeasure::Hourly, 800.0, &RateConfig::default()),
+            100.0
         );
     }
 
@@ -457,7 +1709,8 @@ mod tests {
                 Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
             )
             .unwrap(),
-        );
+        )
+        .unwrap();
 
         let ar2 = AllocationRequest::new(
             resource_from_lrp,
@@ -469,7 +1722,8 @@ mod tests {
                 Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
             )
             .unwrap(),
-        );
+        )
+        .unwrap();
 
         lrp.allocate(ar1, &project_calendar)
             .expect("Allocation not completed");
@@ -483,4 +1737,284 @@ mod tests {
         let two_allocations = lrp.get_resource_existing_allocations(&resource_from_lrp);
         assert_eq!(two_allocations.len(), 2);
     }
+
+    #[test]
+    fn test_tasks_for_resource() {
+        let calendar = ProjectCalendar::default();
+        let mut lrp = LocalResourcePool::default();
+        let resource = Resource::new(String::from("Test"), 1000.0, RateMeasure::Hourly).unwrap();
+        let other = Resource::new(String::from("Other"), 1000.0, RateMeasure::Hourly).unwrap();
+        let (resource_id, other_id) = (resource.id, other.id);
+        lrp.add_resource(resource).unwrap();
+        lrp.add_resource(other).unwrap();
+        assert!(lrp.tasks_for_resource(resource_id).is_empty());
+
+        let project_id = Uuid::new_v4();
+        let (first_task, second_task) = (Uuid::new_v4(), Uuid::new_v4());
+        let march = TimeWindow::new(
+            Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 31, 0, 0, 0).unwrap(),
+        )
+        .unwrap();
+        let april = TimeWindow::new(
+            Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 4, 30, 0, 0, 0).unwrap(),
+        )
+        .unwrap();
+        for (resource_id, task_id, engagement, window) in [
+            (resource_id, second_task, 0.5, april),
+            (resource_id, first_task, 0.3, march),
+            (other_id, first_task, 1.0, march),
+        ] {
+            lrp.allocate(
+                AllocationRequest::new(resource_id, task_id, project_id, engagement, window)
+                    .unwrap(),
+                &calendar,
+            )
+            .unwrap();
+        }
+
+        assert_eq!(
+            lrp.tasks_for_resource(resource_id),
+            vec![(first_task, 0.3, march), (second_task, 0.5, april)]
+        );
+    }
+
+    #[test]
+    fn test_search_resources() {
+        let mut lrp = LocalResourcePool::default();
+        for (name, rate) in [
+            ("Anna Ivanova", 100.0),
+            ("Ivan Petrov", 80.0),
+            ("Boris", 120.0),
+        ] {
+            lrp.add_resource(Resource::new(name.into(), rate, RateMeasure::Hourly).unwrap())
+                .unwrap();
+        }
+        let names = |found: Vec<&Resource>| -> Vec<String> {
+            found.iter().map(|r| r.name.clone()).collect()
+        };
+
+        assert_eq!(
+            names(lrp.search_by_name_substring("IVAN")),
+            ["Anna Ivanova", "Ivan Petrov"]
+        );
+        assert_eq!(names(lrp.search_by_name_substring("bor")), ["Boris"]);
+        assert!(lrp.search_by_name_substring("Olga").is_empty());
+        assert_eq!(lrp.search_by_name_substring("").len(), 3);
+        assert_eq!(
+            names(lrp.find_resources(|r| r.rate >= 100.0)),
+            ["Anna Ivanova", "Boris"]
+        );
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        let mut lrp = LocalResourcePool::default();
+        let resource = Resource::new(String::from("Test"), 1000.0, RateMeasure::Hourly).unwrap();
+        lrp.add_resource(resource.clone()).unwrap();
+        let day = |d: u32| Utc.with_ymd_and_hms(2025, 1, d, 0, 0, 0).unwrap();
+
+        // Импортированные данные: кладем аллокации в обход проверки
+        let mut ids = vec![];
+        for (start, end, engagement) in [(1, 10, 0.6), (5, 15, 0.6), (20, 25, 1.0)] {
+            let allocation = super::ResourceAllocation::new(
+                AllocationRequest::new(
+                    resource.id,
+                    uuid::Uuid::new_v4(),
+                    uuid::Uuid::new_v4(),
+                    engagement,
+                    TimeWindow::new(day(start), day(end)).unwrap(),
+                )
+                .unwrap(),
+            );
+            ids.push(allocation.get_id());
+            lrp.allocations.insert(allocation.get_id(), allocation);
+        }
+
+        let conflicts = lrp.find_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert_eq!(conflict.resource_name, "Test");
+        assert_eq!(conflict.window, TimeWindow::new(day(5), day(10)).unwrap());
+        assert!((conflict.total_engagement - 1.2).abs() < 1e-9);
+        let mut expected = vec![ids[0], ids[1]];
+        expected.sort();
+        assert_eq!(conflict.allocation_ids, expected);
+    }
+
+    fn march(d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, d, 0, 0, 0).unwrap()
+    }
+
+    /// Пул для выравнивания: ресурс `resource` и по назначению 0.6 на пн 3 - сб 8 марта
+    /// на каждый приоритет из `priorities` (в порядке создания), задачи назначений
+    /// заканчиваются `task_end`. Возвращает пул, id назначений и окончания задач
+    fn leveling_pool(
+        resource: &Resource,
+        priorities: &[u8],
+        task_end: DateTime<Utc>,
+    ) -> (LocalResourcePool, Vec<Uuid>, HashMap<Uuid, DateTime<Utc>>) {
+        let mut lrp = LocalResourcePool::default();
+        lrp.add_resource(resource.clone()).unwrap();
+        let mut ids = vec![];
+        let mut task_ends = HashMap::new();
+        for (sequence, priority) in (1..).zip(priorities) {
+            let task_id = Uuid::new_v4();
+            task_ends.insert(task_id, task_end);
+            let mut allocation = super::ResourceAllocation::new(
+                AllocationRequest::new(
+                    resource.id,
+                    task_id,
+                    Uuid::new_v4(),
+                    0.6,
+                    TimeWindow::new(march(3), march(8)).unwrap(),
+                )
+                .unwrap()
+                .with_priority(*priority),
+            );
+            allocation.sequence = sequence;
+            ids.push(allocation.get_id());
+            lrp.allocations.insert(allocation.get_id(), allocation);
+        }
+        (lrp, ids, task_ends)
+    }
+
+    #[test]
+    fn test_level_resource_serializes_overlapping_allocations() {
+        let resource = Resource::new(String::from("Test"), 1000.0, RateMeasure::Hourly).unwrap();
+        let calendar = ProjectCalendar::default();
+        // Две задачи по 0.6 на пн 3 - сб 8 марта, вторая может закончиться до 31-го
+        let (mut lrp, ids, task_ends) = leveling_pool(
+            &resource,
+            &[DEFAULT_ALLOCATION_PRIORITY, DEFAULT_ALLOCATION_PRIORITY],
+            march(31),
+        );
+        assert_eq!(lrp.find_conflicts().len(), 1);
+
+        let shifted = lrp
+            .level_resource(resource.id, &calendar, &task_ends)
+            .unwrap();
+        assert_eq!(shifted, [ids[1]]);
+        assert_eq!(
+            lrp.get_allocation(&ids[0]).unwrap().get_time_window(),
+            &TimeWindow::new(march(3), march(8)).unwrap()
+        );
+        // Первая заканчивается в субботу 8-го, вторая начинается в понедельник 10-го
+        assert_eq!(
+            lrp.get_allocation(&ids[1]).unwrap().get_time_window(),
+            &TimeWindow::new(march(10), march(15)).unwrap()
+        );
+        assert!(lrp.find_conflicts().is_empty());
+        assert!(
+            lrp.level_resource(resource.id, &calendar, &task_ends)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_level_resource_moves_lower_priority_first() {
+        let resource = Resource::new(String::from("Test"), 1000.0, RateMeasure::Hourly).unwrap();
+        let calendar = ProjectCalendar::default();
+        // Раньше созданное назначение с низким приоритетом уступает место более позднему
+        let (mut lrp, ids, task_ends) = leveling_pool(&resource, &[50, 200], march(31));
+
+        let shifted = lrp
+            .level_resource(resource.id, &calendar, &task_ends)
+            .unwrap();
+        assert_eq!(shifted, [ids[0]]);
+        assert_eq!(lrp.get_allocation(&ids[1]).unwrap().get_priority(), 200);
+        assert_eq!(
+            lrp.get_allocation(&ids[1]).unwrap().get_time_window(),
+            &TimeWindow::new(march(3), march(8)).unwrap()
+        );
+        assert_eq!(
+            lrp.get_allocation(&ids[0]).unwrap().get_time_window(),
+            &TimeWindow::new(march(10), march(15)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_level_resource_skips_unavailable_periods() {
+        let mut resource =
+            Resource::new(String::from("Test"), 1000.0, RateMeasure::Hourly).unwrap();
+        // Отпуск всю неделю после первого назначения
+        resource.add_unavailable_period(AvailabilityException::new(
+            TimeWindow::new(march(10), march(16)).unwrap(),
+            ExceptionType::Vacation,
+        ));
+        let calendar = ProjectCalendar::default();
+        let (mut lrp, ids, task_ends) = leveling_pool(
+            &resource,
+            &[DEFAULT_ALLOCATION_PRIORITY, DEFAULT_ALLOCATION_PRIORITY],
+            march(31),
+        );
+
+        assert_eq!(
+            lrp.level_resource(resource.id, &calendar, &task_ends)
+                .unwrap(),
+            [ids[1]]
+        );
+        // Вторая начинается в понедельник после отпуска, а не сразу после первой
+        assert_eq!(
+            lrp.get_allocation(&ids[1]).unwrap().get_time_window(),
+            &TimeWindow::new(march(17), march(22)).unwrap()
+        );
+
+        // Без рабочих дней после первой назначение не сдвигается на нерабочий день
+        let (mut lrp, ids, task_ends) = leveling_pool(
+            &resource,
+            &[DEFAULT_ALLOCATION_PRIORITY, DEFAULT_ALLOCATION_PRIORITY],
+            march(31),
+        );
+        let mut closed = ProjectCalendar::default();
+        for date in march(8).date_naive().iter_days().take(400) {
+            closed.add_holiday(date);
+        }
+        assert!(
+            lrp.level_resource(resource.id, &closed, &task_ends)
+                .is_err()
+        );
+        assert_eq!(
+            lrp.get_allocation(&ids[1]).unwrap().get_time_window(),
+            &TimeWindow::new(march(3), march(8)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_level_resource_reports_impossible_leveling() {
+        let resource = Resource::new(String::from("Test"), 1000.0, RateMeasure::Hourly).unwrap();
+        let (mut lrp, ids, mut task_ends) = leveling_pool(
+            &resource,
+            &[DEFAULT_ALLOCATION_PRIORITY, DEFAULT_ALLOCATION_PRIORITY],
+            march(12),
+        );
+
+        let error = lrp
+            .level_resource(resource.id, &ProjectCalendar::default(), &task_ends)
+            .unwrap_err();
+        assert!(error.to_string().contains(&ids[1].to_string()));
+        assert_eq!(
+            lrp.get_allocation(&ids[1]).unwrap().get_time_window(),
+            &TimeWindow::new(march(3), march(8)).unwrap()
+        );
+        // Окончание задачи берется текущее: продленная задача дает место для сдвига
+        task_ends.values_mut().for_each(|end| *end = march(31));
+        assert_eq!(
+            lrp.level_resource(resource.id, &ProjectCalendar::default(), &task_ends)
+                .unwrap(),
+            [ids[1]]
+        );
+        // Назначение без известной задачи не выравнивается
+        task_ends.clear();
+        assert!(
+            lrp.level_resource(resource.id, &ProjectCalendar::default(), &task_ends)
+                .is_err()
+        );
+        assert!(
+            lrp.level_resource(Uuid::new_v4(), &ProjectCalendar::default(), &task_ends)
+                .is_err()
+        );
+    }
 }
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::base_structures::{IdGenerator, RandomIds, TimeWindow};
+
+/// Потребность проекта в роли, под которую ресурс еще не подобран
+///
+/// role - имя роли, в отчетах сопоставляется с навыками ресурсов (`Resource::skills`),
+/// engagement_rate - требуемая занятость в FTE (может быть больше 1 - несколько человек)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RolePlaceholder {
+    id: Uuid,
+    pub role: String,
+    pub engagement_rate: f64,
+    pub window: TimeWindow,
+}
+
+impl RolePlaceholder {
+    pub fn new(role: impl Into<String>, engagement_rate: f64, window: TimeWindow) -> Self {
+        Self::new_with_ids(role, engagement_rate, window, &mut RandomIds)
+    }
+
+    /// Создание потребности с id из `ids`
+    pub fn new_with_ids(
+        role: impl Into<String>,
+        engagement_rate: f64,
+        window: TimeWindow,
+        ids: &mut impl IdGenerator,
+    ) -> Self {
+        Self {
+            id: ids.next_id(),
+            role: role.into().trim().to_string(),
+            engagement_rate,
+            window,
+        }
+    }
+
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+}
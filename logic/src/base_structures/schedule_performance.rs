@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// Разрыв между прошедшим временем и выполнением, после которого задачу стоит показать
+const ATTENTION_GAP: f64 = 0.5;
+
+/// Сравнение прошедшего времени и выполненной работы
+/// time_elapsed - доля окна, прошедшая в рабочих днях (0..1)
+/// progress - доля выполненной работы (0..1)
+/// index - progress / time_elapsed, меньше 1 - отставание от графика
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct SchedulePerformance {
+    pub time_elapsed: f64,
+    pub progress: f64,
+    pub index: f64,
+}
+
+impl SchedulePerformance {
+    pub fn new(time_elapsed: f64, progress: f64) -> Self {
+        let index = if time_elapsed > 0.0 {
+            progress / time_elapsed
+        } else {
+            1.0
+        };
+        Self {
+            time_elapsed,
+            progress,
+            index,
+        }
+    }
+
+    /// Время уходит заметно быстрее работы ("прошло 90% окна, выполнено 10%")
+    pub fn needs_attention(&self) -> bool {
+        self.time_elapsed - self.progress >= ATTENTION_GAP
+    }
+}
+
+/// Выполнение проекта на дату
+/// completed - доля задач в статусах Complete/Closed, взвешенная по рабочим часам задач
+/// expected - доля рабочих часов окна проекта, прошедшая к дате
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ProjectProgress {
+    pub completed: f64,
+    pub expected: f64,
+}
+
+impl ProjectProgress {
+    /// Завершено меньше, чем должно быть по графику
+    pub fn is_behind(&self) -> bool {
+        self.completed < self.expected
+    }
+}
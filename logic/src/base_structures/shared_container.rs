@@ -0,0 +1,171 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use uuid::Uuid;
+
+use crate::base_structures::{
+    project::Project, project_containers::SingleProjectContainer, tasks::Task,
+    traits::ProjectContainer,
+};
+
+/// Контейнер проекта, общий для нескольких потоков
+///
+/// Доступ только через замыкания: блокировка снимается сразу после вызова, поэтому
+/// вызывающий код не держит ее между кадрами интерфейса или точками ожидания.
+/// Клонирование дает еще одну ссылку на тот же контейнер.
+#[derive(Clone, Default)]
+pub struct SharedProjectContainer {
+    inner: Arc<RwLock<SingleProjectContainer>>,
+}
+
+impl SharedProjectContainer {
+    pub fn new(container: SingleProjectContainer) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(container)),
+        }
+    }
+
+    /// Чтение контейнера. Блокировка, отравленная паникой другого потока, не мешает чтению
+    pub fn read<R>(&self, f: impl FnOnce(&SingleProjectContainer) -> R) -> R {
+        let guard = self.inner.read().unwrap_or_else(PoisonError::into_inner);
+        f(&guard)
+    }
+
+    /// Изменение контейнера под блокировкой на запись
+    pub fn write<R>(&self, f: impl FnOnce(&mut SingleProjectContainer) -> R) -> R {
+        let mut guard = self.inner.write().unwrap_or_else(PoisonError::into_inner);
+        f(&mut guard)
+    }
+
+    pub fn with_project<R>(&self, project_id: &Uuid, f: impl FnOnce(&Project) -> R) -> Option<R> {
+        self.read(|container| container.get_project(project_id).map(f))
+    }
+
+    pub fn with_project_mut<R>(
+        &self,
+        project_id: &Uuid,
+        f: impl FnOnce(&mut Project) -> R,
+    ) -> Option<R> {
+        self.write(|container| container.get_project_mut(project_id).map(f))
+    }
+
+    pub fn with_tasks<R>(
+        &self,
+        project_id: &Uuid,
+        f: impl FnOnce(&HashMap<Uuid, Task>) -> R,
+    ) -> Option<R> {
+        self.with_project(project_id, |project| f(&project.tasks))
+    }
+}
+
+impl From<SingleProjectContainer> for SharedProjectContainer {
+    fn from(container: SingleProjectContainer) -> Self {
+        Self::new(container)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use chrono::{TimeDelta, TimeZone, Utc};
+
+    use super::*;
+    use crate::{BasicGettersForStructures, RateMeasure, ResourceService, TaskService};
+
+    #[test]
+    fn test_shared_container_across_threads() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap();
+        let project = Project::new("Shared", "", start, end).unwrap();
+        let project_id = *project.get_id();
+        let mut container = SingleProjectContainer::new();
+        container.add_project(project).unwrap();
+        let shared = SharedProjectContainer::new(container);
+
+        let resource_id = ResourceService::with_shared(&shared, |service| {
+            let resource = service
+                .create_resource("Max", 100.0, RateMeasure::Hourly)
+                .unwrap();
+            let resource_id = resource.id;
+            service.add_resource(resource).unwrap();
+            resource_id
+        });
+
+        let writer = {
+            let shared = shared.clone();
+            thread::spawn(move || {
+                for week in 0..20 {
+                    let task_start =
+                        Utc.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap() + TimeDelta::weeks(week);
+                    TaskService::with_shared(&shared, |service| {
+                        let task = service
+                            .create_regular_task(
+                                project_id,
+                                format!("Task {week}"),
+                                task_start,
+                                task_start + TimeDelta::days(4),
+                                None,
+                            )
+                            .unwrap();
+                        service
+                            .allocate_resource(
+                                project_id,
+                                *task.get_id(),
+                                resource_id,
+                                0.5,
+                                None,
+                                None,
+                            )
+                            .unwrap();
+                    });
+                }
+            })
+        };
+        let reader = {
+            let shared = shared.clone();
+            thread::spawn(move || {
+                (0..20)
+                    .map(|_| {
+                        ResourceService::with_shared(&shared, |service| {
+                            service.get_resource_utilization(resource_id)
+                        })
+                    })
+                    .collect::<Vec<f64>>()
+            })
+        };
+        writer.join().unwrap();
+        // Читатель видит только целые шаги писателя: загрузка растет по 0.5 за задачу
+        let readings = reader.join().unwrap();
+        assert!(readings.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(
+            readings
+                .iter()
+                .all(|u| (u * 2.0).fract() == 0.0 && *u <= 10.0)
+        );
+        assert_eq!(
+            ResourceService::with_shared(&shared, |service| service
+                .get_resource_utilization(resource_id)),
+            10.0
+        );
+
+        assert_eq!(
+            shared.with_tasks(&project_id, |tasks| tasks.len()),
+            Some(20)
+        );
+        let allocations = shared.read(|container| {
+            container
+                .resource_pool()
+                .get_resource_existing_allocations(&resource_id)
+                .len()
+        });
+        assert_eq!(allocations, 20);
+        assert!(
+            shared
+                .with_tasks(&Uuid::new_v4(), |tasks| tasks.len())
+                .is_none()
+        );
+    }
+}
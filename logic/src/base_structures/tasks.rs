@@ -1,12 +1,14 @@
 use chrono::{DateTime, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::base_structures::{
-    Dependency, ProjectCreationErrors, traits::BasicGettersForStructures,
+    Dependency, IdGenerator, ProjectCalendar, ProjectCreationErrors, RandomIds,
+    SchedulePerformance, TimeWindow, traits::BasicGettersForStructures,
 };
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TaskStatus {
     New,
     Wait,
@@ -16,30 +18,68 @@ pub enum TaskStatus {
     Closed,
 }
 
+impl TaskStatus {
+    /// Допустимые переходы между статусами
+    /// New -> Wait/Processed/Rejected, Wait -> Processed/Rejected,
+    /// Processed -> Wait/Complete/Rejected, Complete/Rejected -> Closed, из Closed переходов нет
+    pub fn can_transition_to(&self, next: &TaskStatus) -> bool {
+        use TaskStatus::*;
+        matches!(
+            (self, next),
+            (New, Wait | Processed | Rejected)
+                | (Wait, Processed | Rejected)
+                | (Processed, Wait | Complete | Rejected)
+                | (Complete | Rejected, Closed)
+        )
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 
 /// Описание структуры
 /// id - UUID задачи
+/// number - человекочитаемый номер задачи внутри проекта (T-142), 0 - номер не присвоен
 /// name - Имя задачи (публичное)
 /// date_start - Дата начала
 /// date_end - Дата окончания
 /// duration - Продолжительность задачи
 /// status - статус задачи
+/// progress - доля выполнения задачи (0.0-1.0)
 /// resource_allocations - назначенные ресурсы
 /// dependencies - зависимые задачи (предшественники)
 /// parent_id - UUID группирующей задачи
 /// is_summary - признак, является ли задача группирующей
+/// priority - приоритет задачи (0-255, больше - важнее)
+/// tags - теги задачи
 pub struct Task {
     id: Uuid,
+    #[serde(default)]
+    number: u64,
     pub name: String,
     pub date_start: DateTime<Utc>,
     pub date_end: DateTime<Utc>,
     pub duration: TimeDelta,
     status: TaskStatus,
+    #[serde(default)]
+    progress: f64,
     resource_allocations: Vec<Uuid>,
     dependencies: Vec<Dependency>,
     pub parent_id: Option<Uuid>,
     pub is_summary: bool,
+    #[serde(default = "default_task_priority")]
+    priority: u8,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// Префикс человекочитаемого номера задачи
+pub const TASK_NUMBER_PREFIX: &str = "T-";
+
+/// Приоритет задачи по умолчанию - середина шкалы
+pub const DEFAULT_TASK_PRIORITY: u8 = 128;
+
+fn default_task_priority() -> u8 {
+    DEFAULT_TASK_PRIORITY
 }
 
 impl Task {
@@ -60,10 +100,12 @@ impl Task {
 
         Ok(Self {
             id: Uuid::new_v4(),
+            number: 0,
             name: name.into(),
             date_start,
             date_end,
             status: TaskStatus::New,
+            progress: 0.0,
             duration: if is_summary {
                 TimeDelta::zero()
             } else {
@@ -73,6 +115,8 @@ impl Task {
             dependencies: vec![],
             parent_id,
             is_summary,
+            priority: DEFAULT_TASK_PRIORITY,
+            tags: vec![],
         })
     }
 
@@ -82,25 +126,7 @@ impl Task {
         date_end: DateTime<Utc>,
         parent_id: Option<Uuid>,
     ) -> Result<Self, ProjectCreationErrors> {
-        if date_start >= date_end {
-            return Err(ProjectCreationErrors::InvalidTaskDuration {
-                date_start,
-                date_end,
-            });
-        }
-
-        Ok(Self {
-            id: Uuid::new_v4(),
-            name: name.into(),
-            date_start,
-            date_end,
-            status: TaskStatus::New,
-            duration: date_end - date_start,
-            resource_allocations: vec![],
-            dependencies: vec![],
-            parent_id,
-            is_summary: false,
-        })
+        Self::new_with_ids(name, date_start, date_end, parent_id, false, &mut RandomIds)
     }
 
     pub fn new_summary(
@@ -109,31 +135,175 @@ impl Task {
         date_end: DateTime<Utc>,
         parent_id: Option<Uuid>,
     ) -> Result<Self, ProjectCreationErrors> {
+        Self::new_with_ids(name, date_start, date_end, parent_id, true, &mut RandomIds)
+    }
+
+    /// Создание задачи с id из `ids`. Для обычной задачи начало должно быть раньше окончания
+    pub fn new_with_ids(
+        name: impl Into<String>,
+        date_start: DateTime<Utc>,
+        date_end: DateTime<Utc>,
+        parent_id: Option<Uuid>,
+        is_summary: bool,
+        ids: &mut impl IdGenerator,
+    ) -> Result<Self, ProjectCreationErrors> {
+        if date_start >= date_end && !is_summary {
+            return Err(ProjectCreationErrors::InvalidTaskDuration {
+                date_start,
+                date_end,
+            });
+        }
+
         Ok(Self {
-            id: Uuid::new_v4(),
+            id: ids.next_id(),
+            number: 0,
             name: name.into(),
             date_start,
             date_end,
             status: TaskStatus::New,
+            progress: 0.0,
             duration: date_end - date_start,
             resource_allocations: vec![],
             dependencies: vec![],
             parent_id,
-            is_summary: true,
+            is_summary,
+            priority: DEFAULT_TASK_PRIORITY,
+            tags: vec![],
         })
     }
+
+    pub fn get_number(&self) -> u64 {
+        self.number
+    }
+
+    pub(crate) fn set_number(&mut self, number: u64) {
+        self.number = number
+    }
+
+    /// Номер задачи в виде "T-142", None - если номер еще не присвоен
+    pub fn get_code(&self) -> Option<String> {
+        (self.number > 0).then(|| format!("{}{}", TASK_NUMBER_PREFIX, self.number))
+    }
+
+    /// Имя задачи для отображения в таблицах: "T-142 Название"
+    pub fn get_display_name(&self) -> String {
+        match self.get_code() {
+            Some(code) => format!("{} {}", code, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Разбор номера задачи: принимает "T-142", "t-142" или просто "142"
+    pub fn parse_code(code: &str) -> Option<u64> {
+        let code = code.trim();
+        let digits = match code.get(..TASK_NUMBER_PREFIX.len()) {
+            Some(prefix) if prefix.eq_ignore_ascii_case(TASK_NUMBER_PREFIX) => {
+                &code[TASK_NUMBER_PREFIX.len()..]
+            }
+            _ => code,
+        };
+        digits.parse::<u64>().ok().filter(|n| *n > 0)
+    }
+
     pub fn get_status(&self) -> &TaskStatus {
         &self.status
     }
 
-    pub fn change_status(&mut self, new_status: TaskStatus) {
-        self.status = new_status
+    /// Сменить статус с проверкой допустимости перехода, тот же статус - без изменений
+    pub fn set_status(&mut self, new_status: TaskStatus) -> anyhow::Result<()> {
+        if self.status == new_status {
+            return Ok(());
+        }
+        if !self.status.can_transition_to(&new_status) {
+            anyhow::bail!(
+                "Invalid task status transition: {:?} -> {:?}",
+                self.status,
+                new_status
+            );
+        }
+        self.status = new_status;
+        Ok(())
+    }
+
+    /// Выполнение задачи: завершенная или закрытая - 1, иначе заданная доля выполнения
+    pub fn get_progress(&self) -> f64 {
+        match self.status {
+            TaskStatus::Complete | TaskStatus::Closed => 1.0,
+            _ => self.progress,
+        }
+    }
+
+    /// Задать долю выполнения задачи, допустимы значения от 0.0 до 1.0
+    pub fn set_progress(&mut self, progress: f64) -> anyhow::Result<()> {
+        if !(0.0..=1.0).contains(&progress) {
+            anyhow::bail!(
+                "Task progress must be between 0.0 and 1.0, got {}",
+                progress
+            );
+        }
+        self.progress = progress;
+        Ok(())
+    }
+
+    pub fn get_priority(&self) -> u8 {
+        self.priority
+    }
+
+    pub fn set_priority(&mut self, priority: u8) {
+        self.priority = priority;
+    }
+
+    pub fn get_tags(&self) -> &Vec<String> {
+        &self.tags
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Добавить тег, пустые и повторные теги не добавляются
+    pub fn add_tag(&mut self, tag: impl Into<String>) {
+        let tag = tag.into().trim().to_string();
+        if !tag.is_empty() && !self.has_tag(&tag) {
+            self.tags.push(tag);
+        }
+    }
+
+    /// Убрать тег, false - такого тега не было
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let before = self.tags.len();
+        self.tags.retain(|t| t != tag);
+        self.tags.len() != before
+    }
+
+    /// Доля окна задачи, прошедшая к моменту `now`, в рабочих днях
+    pub fn elapsed_fraction(&self, now: DateTime<Utc>, calendar: &ProjectCalendar) -> f64 {
+        TimeWindow {
+            date_start: self.date_start,
+            date_end: self.date_end,
+        }
+        .elapsed_fraction(now, calendar)
+    }
+
+    pub fn schedule_performance(
+        &self,
+        now: DateTime<Utc>,
+        calendar: &ProjectCalendar,
+    ) -> SchedulePerformance {
+        SchedulePerformance::new(self.elapsed_fraction(now, calendar), self.get_progress())
     }
 
     pub fn set_resource_allocation(&mut self, allocation_id: Uuid) {
         self.resource_allocations.push(allocation_id)
     }
 
+    /// Убрать ссылку на назначение, false - такой ссылки не было
+    pub fn remove_resource_allocation(&mut self, allocation_id: &Uuid) -> bool {
+        let before = self.resource_allocations.len();
+        self.resource_allocations.retain(|id| id != allocation_id);
+        self.resource_allocations.len() != before
+    }
+
     pub fn is_resource_assigned(&self, allocation_id: &Uuid) -> bool {
         self.resource_allocations.contains(allocation_id)
     }
@@ -142,15 +312,69 @@ impl Task {
         &self.resource_allocations
     }
 
+    /// Добавить зависимость. На одного предшественника - одна связь: повторное добавление
+    /// заменяет тип и лаг существующей связи.
     pub fn add_dependency(&mut self, dependency: Dependency) {
-        if !self.dependencies.contains(&dependency) {
-            self.dependencies.push(dependency)
+        match self
+            .dependencies
+            .iter_mut()
+            .find(|d| d.depends_on == dependency.depends_on)
+        {
+            Some(existing) => *existing = dependency,
+            None => self.dependencies.push(dependency),
         }
     }
 
+    /// Убрать зависимость от предшественника, false - такой зависимости не было
+    pub fn remove_dependency(&mut self, depends_on: &Uuid) -> bool {
+        let before = self.dependencies.len();
+        self.dependencies.retain(|d| &d.depends_on != depends_on);
+        self.dependencies.len() != before
+    }
+
     pub fn get_dependencies(&self) -> &Vec<Dependency> {
         &self.dependencies
     }
+
+    /// Копия задачи для нового проекта: id из `id_map`, ссылки на родителя и предшественников
+    /// переписаны по той же карте, даты сдвинуты на `shift`, статус сброшен в New.
+    /// Назначения ресурсов не копируются - они принадлежат исходному проекту.
+    /// Заменить id задачи, ее зависимостей и родителя по `id_map` (остальные не меняются)
+    pub(crate) fn remap_ids(&mut self, id_map: &HashMap<Uuid, Uuid>) {
+        let remap = |id: &Uuid| id_map.get(id).copied().unwrap_or(*id);
+        self.id = remap(&self.id);
+        for dependency in self.dependencies.iter_mut() {
+            dependency.depends_on = remap(&dependency.depends_on);
+        }
+        self.parent_id = self.parent_id.as_ref().map(remap);
+    }
+
+    pub(crate) fn duplicate(&self, id_map: &HashMap<Uuid, Uuid>, shift: TimeDelta) -> Task {
+        let remap = |id: &Uuid| id_map.get(id).copied().unwrap_or(*id);
+        Task {
+            id: remap(&self.id),
+            number: self.number,
+            name: self.name.clone(),
+            date_start: self.date_start + shift,
+            date_end: self.date_end + shift,
+            duration: self.duration,
+            status: TaskStatus::New,
+            progress: 0.0,
+            resource_allocations: vec![],
+            dependencies: self
+                .dependencies
+                .iter()
+                .map(|d| Dependency {
+                    depends_on: remap(&d.depends_on),
+                    ..*d
+                })
+                .collect(),
+            parent_id: self.parent_id.as_ref().map(remap),
+            is_summary: self.is_summary,
+            priority: self.priority,
+            tags: self.tags.clone(),
+        }
+    }
 }
 
 impl BasicGettersForStructures for Task {
@@ -193,4 +417,81 @@ mod tests {
         let task = Task::new_regular("Test", date_start, date_end, None);
         assert!(task.is_ok());
     }
+
+    #[test]
+    fn test_one_dependency_per_predecessor() {
+        use crate::base_structures::{Dependency, DependencyType};
+        use chrono::TimeDelta;
+        use uuid::Uuid;
+
+        let mut task = Task::new_regular(
+            "Test",
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap(),
+            None,
+        )
+        .unwrap();
+        let predecessor = Uuid::new_v4();
+        task.add_dependency(Dependency::new(
+            DependencyType::NonBlocking,
+            predecessor,
+            None,
+        ));
+        task.add_dependency(Dependency::new(
+            DependencyType::Blocking,
+            predecessor,
+            Some(TimeDelta::days(2)),
+        ));
+        assert_eq!(task.get_dependencies().len(), 1);
+
+        // Тип и лаг сохраняются при сохранении и загрузке
+        let json = serde_json::to_string(&task).unwrap();
+        let loaded: Task = serde_json::from_str(&json).unwrap();
+        let dependency = loaded.get_dependencies()[0];
+        assert!(dependency.is_blocking());
+        assert_eq!(dependency.lag_or_zero(), TimeDelta::days(2));
+
+        assert!(task.remove_dependency(&predecessor));
+        assert!(!task.remove_dependency(&predecessor));
+        assert!(task.get_dependencies().is_empty());
+    }
+
+    #[test]
+    fn test_status_transitions() {
+        use crate::base_structures::tasks::TaskStatus;
+
+        let date_start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let date_end = Utc.with_ymd_and_hms(2025, 1, 10, 0, 0, 0).unwrap();
+        let mut task = Task::new_regular("Test", date_start, date_end, None).unwrap();
+
+        // Нельзя завершить задачу, которая не была в работе
+        assert!(task.set_status(TaskStatus::Complete).is_err());
+        assert!(task.set_status(TaskStatus::Wait).is_ok());
+        assert!(task.set_status(TaskStatus::Processed).is_ok());
+        assert!(task.set_status(TaskStatus::Processed).is_ok());
+        assert!(task.set_status(TaskStatus::Complete).is_ok());
+
+        let error = task.set_status(TaskStatus::New).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid task status transition: Complete -> New"
+        );
+        assert_eq!(task.get_status(), &TaskStatus::Complete);
+
+        assert!(task.set_status(TaskStatus::Closed).is_ok());
+        for next in [
+            TaskStatus::New,
+            TaskStatus::Wait,
+            TaskStatus::Processed,
+            TaskStatus::Complete,
+            TaskStatus::Rejected,
+        ] {
+            assert!(task.set_status(next).is_err());
+        }
+
+        let mut rejected = Task::new_regular("Rejected", date_start, date_end, None).unwrap();
+        assert!(rejected.set_status(TaskStatus::Rejected).is_ok());
+        assert!(rejected.set_status(TaskStatus::Processed).is_err());
+        assert!(rejected.set_status(TaskStatus::Closed).is_ok());
+    }
 }
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, Utc, Weekday};
 use serde::{Deserialize, Serialize};
 
 use crate::base_structures::ProjectCalendar;
@@ -20,18 +20,21 @@ impl TimeWindow {
         })
     }
 
-    fn calculate_working_days(&self, calendar: &ProjectCalendar) -> i64 {
-        let mut working_days = 0;
-        let mut current_date = self.date_start.date_naive();
+    /// Рабочие дни окна по календарю (даты начала и окончания включительно)
+    pub fn working_days(&self, calendar: &ProjectCalendar) -> u32 {
+        calendar.count_working_days(self)
+    }
 
-        while current_date <= self.date_end.date_naive() {
-            if calendar.is_working_day(current_date) {
-                working_days += 1;
-            }
-            current_date += chrono::Duration::days(1);
-        }
+    /// Части окна, не покрытые ни одним из `others`, по возрастанию начала
+    pub fn subtract_all(&self, others: &[TimeWindow]) -> Vec<TimeWindow> {
+        others.iter().fold(vec![*self], |free, other| {
+            free.iter().flat_map(|w| w.subtract(other)).collect()
+        })
+    }
 
-        working_days
+    /// Рабочие часы окна по календарю: выходные и праздники не считаются
+    pub fn working_hours(&self, calendar: &ProjectCalendar) -> u32 {
+        calendar.working_hours_in_period(self)
     }
 
     /// Проверяет, что есть пересечение с переданным объектом TimeWindow
@@ -40,36 +43,159 @@ impl TimeWindow {
         self.date_start < other.date_end && self.date_end > other.date_start
     }
 
+    /// Общая часть двух окон, None - если окна не пересекаются (касание границей
+    /// пересечением не считается, как и в `overlaps`)
+    pub fn intersection(&self, other: &Self) -> Option<TimeWindow> {
+        let date_start = self.date_start.max(other.date_start);
+        let date_end = self.date_end.min(other.date_end);
+        (date_start < date_end).then_some(TimeWindow {
+            date_start,
+            date_end,
+        })
+    }
+
     /// Проверить, попадает ли момент времени в окно
     pub fn contains(&self, dt: &DateTime<Utc>) -> bool {
         dt >= &self.date_start && dt < &self.date_end
     }
 
-    /// Длительность в часах
+    /// Проверить, что окно `other` целиком лежит внутри окна
+    pub fn contains_window(&self, other: &Self) -> bool {
+        other.date_start >= self.date_start && other.date_end <= self.date_end
+    }
+
+    /// Части окна, оставшиеся после вычитания `other`
+    ///
+    /// Возвращает 0 фрагментов, если `other` покрывает окно целиком, 1 - при пересечении
+    /// с одного края или без пересечения, 2 - если `other` вырезает середину окна.
+    pub fn subtract(&self, other: &Self) -> Vec<TimeWindow> {
+        if !self.overlaps(other) {
+            return vec![*self];
+        }
+        let mut result = Vec::with_capacity(2);
+        if self.date_start < other.date_start {
+            result.push(TimeWindow {
+                date_start: self.date_start,
+                date_end: other.date_start,
+            });
+        }
+        if other.date_end < self.date_end {
+            result.push(TimeWindow {
+                date_start: other.date_end,
+                date_end: self.date_end,
+            });
+        }
+        result
+    }
+
+    /// Длительность в рабочих часах (то же, что `working_hours`)
     pub fn duration_hours(&self, calendar: &ProjectCalendar) -> i64 {
-        self.calculate_working_days(calendar) * calendar.working_hours_per_day as i64
+        self.working_hours(calendar) as i64
+    }
+
+    /// Доля окна, прошедшая к моменту `now`, в рабочих днях календаря (от 0 до 1)
+    ///
+    /// День `now` считается еще не прошедшим, дни берутся в часовом поясе календаря.
+    /// Если в окне нет рабочих дней, доля считается по календарному времени.
+    pub fn elapsed_fraction(&self, now: DateTime<Utc>, calendar: &ProjectCalendar) -> f64 {
+        if now <= self.date_start {
+            return 0.0;
+        }
+        if now >= self.date_end {
+            return 1.0;
+        }
+        let total = calendar.count_working_days(self);
+        if total == 0 {
+            let elapsed = (now - self.date_start).num_seconds() as f64;
+            let total = (self.date_end - self.date_start).num_seconds() as f64;
+            return (elapsed / total).clamp(0.0, 1.0);
+        }
+
+        // Прошедшие дни - с первого дня окна по день перед `now` (полночь включает день)
+        let today = calendar.local_date(now);
+        let elapsed = if today > calendar.local_date(self.date_start) {
+            calendar.count_working_days(&TimeWindow {
+                date_start: self.date_start,
+                date_end: calendar.local_midnight(today - Days::new(1)),
+            })
+        } else {
+            0
+        };
+        (elapsed as f64 / total as f64).clamp(0.0, 1.0)
     }
 
     pub fn split_by_days(&self) -> Vec<TimeWindow> {
+        self.split_at(|date| date + Days::new(1))
+    }
+
+    /// Разбить окно по локальным полуночам календаря: каждый кусок лежит в одной
+    /// локальной дате, первый и последний обрезаются по окну
+    pub fn split_by_local_days(&self, calendar: &ProjectCalendar) -> Vec<TimeWindow> {
+        let mut result = Vec::new();
+        let mut current = self.date_start;
+        while current < self.date_end {
+            let next = calendar.local_midnight(calendar.local_date(current) + Days::new(1));
+            let end = next.min(self.date_end);
+            result.push(TimeWindow {
+                date_start: current,
+                date_end: end,
+            });
+            current = end;
+        }
+        result
+    }
+
+    /// Разбить окно на недели, начинающиеся с `week_start` (Weekday::Mon - недели ISO).
+    /// Первая и последняя недели обрезаются по окну
+    pub fn split_by_weeks(&self, week_start: Weekday) -> Vec<TimeWindow> {
+        self.split_at(|date| {
+            let into_week = date.weekday().days_since(week_start) as u64;
+            date + Days::new(7 - into_week)
+        })
+    }
+
+    /// Разбить окно на календарные месяцы, первый и последний обрезаются по окну
+    pub fn split_by_months(&self) -> Vec<TimeWindow> {
+        self.split_at(|date| date.with_day(1).unwrap() + Months::new(1))
+    }
+
+    /// Разбить окно по полуночам (UTC) дат, которые возвращает `next_boundary`
+    /// для даты текущего куска
+    fn split_at(&self, next_boundary: impl Fn(NaiveDate) -> NaiveDate) -> Vec<TimeWindow> {
         let mut result = Vec::new();
         let mut current = self.date_start;
 
         while current < self.date_end {
-            let next_day = (current + chrono::Duration::days(1))
-                .date_naive()
+            let next = next_boundary(current.date_naive())
                 .and_hms_opt(0, 0, 0)
                 .unwrap()
                 .and_utc();
-            let day_end = next_day.min(self.date_end);
+            let end = next.min(self.date_end);
 
-            result.push(TimeWindow::new(current, day_end).unwrap());
-            current = next_day;
+            result.push(TimeWindow::new(current, end).unwrap());
+            current = next;
         }
 
         result
     }
 }
 
+/// Объединить окна в минимальный набор непересекающихся окон по возрастанию начала.
+/// Пересекающиеся и соприкасающиеся границей окна сливаются
+pub fn merge_overlapping(mut windows: Vec<TimeWindow>) -> Vec<TimeWindow> {
+    windows.sort_by_key(|w| w.date_start);
+    let mut merged: Vec<TimeWindow> = Vec::with_capacity(windows.len());
+    for window in windows {
+        match merged.last_mut() {
+            Some(last) if window.date_start <= last.date_end => {
+                last.date_end = last.date_end.max(window.date_end);
+            }
+            _ => merged.push(window),
+        }
+    }
+    merged
+}
+
 impl PartialEq for TimeWindow {
     fn eq(&self, other: &Self) -> bool {
         self.date_start == other.date_start && self.date_end == other.date_end
@@ -94,4 +220,285 @@ mod tests {
         let tw = TimeWindow::new(start, end).unwrap();
         assert_eq!(tw.duration_hours(&calendar), 40);
     }
+
+    #[test]
+    fn test_working_time_across_weekend() {
+        let calendar = ProjectCalendar::default();
+        // Пятница 6 марта - понедельник 9 марта 2026
+        let start = Utc.with_ymd_and_hms(2026, 3, 6, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 3, 9, 0, 0, 0).unwrap();
+        let tw = TimeWindow::new(start, end).unwrap();
+        assert_eq!((end - start).num_hours(), 72);
+        assert_eq!(tw.working_days(&calendar), 2);
+        assert_eq!(tw.working_hours(&calendar), 16);
+    }
+
+    #[test]
+    fn test_elapsed_fraction() {
+        let calendar = ProjectCalendar::default();
+        // Пн 2 марта - Сб 7 марта 2026: 5 рабочих дней
+        let tw = TimeWindow::new(
+            Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 7, 0, 0, 0).unwrap(),
+        )
+        .unwrap();
+        let before = Utc.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap();
+        let wednesday = Utc.with_ymd_and_hms(2026, 3, 4, 12, 0, 0).unwrap();
+        let after = Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap();
+        assert_eq!(tw.elapsed_fraction(before, &calendar), 0.0);
+        assert_eq!(tw.elapsed_fraction(wednesday, &calendar), 0.4);
+        assert_eq!(tw.elapsed_fraction(after, &calendar), 1.0);
+
+        // Окно только из выходных - считаем по календарному времени
+        let weekend = TimeWindow::new(
+            Utc.with_ymd_and_hms(2026, 3, 7, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 7, 20, 0, 0).unwrap(),
+        )
+        .unwrap();
+        let middle = Utc.with_ymd_and_hms(2026, 3, 7, 5, 0, 0).unwrap();
+        assert_eq!(weekend.elapsed_fraction(middle, &calendar), 0.25);
+
+        // Дни считаются в часовом поясе календаря: 16:00 UTC 3 марта - уже 4 марта в Токио,
+        // прошли 2 и 3 марта
+        let mut tokyo = ProjectCalendar::default();
+        tokyo.set_timezone(chrono_tz::Asia::Tokyo);
+        let date = |d: u32| chrono::NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let tw =
+            TimeWindow::new(tokyo.local_midnight(date(2)), tokyo.local_midnight(date(7))).unwrap();
+        let evening_utc = Utc.with_ymd_and_hms(2026, 3, 3, 16, 0, 0).unwrap();
+        assert_eq!(tw.elapsed_fraction(evening_utc, &tokyo), 0.4);
+    }
+
+    fn window(start_day: u32, end_day: u32) -> TimeWindow {
+        TimeWindow::new(
+            Utc.with_ymd_and_hms(2026, 3, start_day, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, end_day, 0, 0, 0).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_intersection_properties() {
+        assert_eq!(
+            window(10, 20).intersection(&window(15, 25)),
+            Some(window(15, 20))
+        );
+        assert_eq!(window(10, 20).intersection(&window(20, 25)), None);
+
+        let base = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let mut seed: u64 = 0x853c_49e6_748f_ea9b;
+        let mut next = |limit: u64| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 33) % limit
+        };
+        let mut random_window = || {
+            let start = base + chrono::TimeDelta::hours(next(500) as i64);
+            TimeWindow {
+                date_start: start,
+                date_end: start + chrono::TimeDelta::hours(1 + next(200) as i64),
+            }
+        };
+        for _ in 0..500 {
+            let (a, b) = (random_window(), random_window());
+            let intersection = a.intersection(&b);
+            assert_eq!(a.overlaps(&b), intersection.is_some());
+            assert_eq!(intersection, b.intersection(&a));
+            if let Some(common) = intersection {
+                assert!(a.contains_window(&common) && b.contains_window(&common));
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_overlapping() {
+        // Вложенные
+        assert_eq!(
+            merge_overlapping(vec![window(10, 20), window(12, 15)]),
+            vec![window(10, 20)]
+        );
+        // Цепочка: пересечение и касание границей, вход не отсортирован
+        assert_eq!(
+            merge_overlapping(vec![
+                window(18, 25),
+                window(5, 10),
+                window(10, 12),
+                window(11, 18)
+            ]),
+            vec![window(5, 25)]
+        );
+        // Непересекающиеся остаются по отдельности
+        assert_eq!(
+            merge_overlapping(vec![window(20, 25), window(1, 5), window(10, 12)]),
+            vec![window(1, 5), window(10, 12), window(20, 25)]
+        );
+        assert!(merge_overlapping(vec![]).is_empty());
+    }
+
+    #[test]
+    fn test_merge_overlapping_preserves_coverage() {
+        let base = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let mut seed: u64 = 0xda3e_39cb_94b9_5bdb;
+        let mut next = |limit: u64| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 33) % limit
+        };
+        for _ in 0..100 {
+            let windows: Vec<TimeWindow> = (0..1 + next(12))
+                .map(|_| {
+                    let start = next(300) as i64;
+                    TimeWindow {
+                        date_start: base + chrono::TimeDelta::hours(start),
+                        date_end: base + chrono::TimeDelta::hours(start + 1 + next(40) as i64),
+                    }
+                })
+                .collect();
+            let merged = merge_overlapping(windows.clone());
+
+            // Покрытие по часовой сетке не меняется
+            let covered = |list: &[TimeWindow]| {
+                (0..400)
+                    .filter(|h| {
+                        let hour = base + chrono::TimeDelta::hours(*h);
+                        list.iter().any(|w| w.contains(&hour))
+                    })
+                    .count() as i64
+            };
+            assert_eq!(covered(&merged), covered(&windows));
+            let merged_hours: i64 = merged
+                .iter()
+                .map(|w| (w.date_end - w.date_start).num_hours())
+                .sum();
+            assert_eq!(merged_hours, covered(&windows));
+            let longest = windows
+                .iter()
+                .map(|w| (w.date_end - w.date_start).num_hours())
+                .max()
+                .unwrap();
+            assert!(merged_hours >= longest);
+            // Результат отсортирован, окна не пересекаются и не соприкасаются
+            assert!(merged.windows(2).all(|p| p[0].date_end < p[1].date_start));
+            assert!(
+                windows
+                    .iter()
+                    .all(|w| merged.iter().any(|m| m.contains_window(w)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_contains_window() {
+        let tw = window(10, 20);
+        assert!(tw.contains_window(&tw));
+        assert!(tw.contains_window(&window(12, 15)));
+        assert!(!tw.contains_window(&window(15, 21)));
+        assert!(!tw.contains_window(&window(9, 12)));
+    }
+
+    #[test]
+    fn test_subtract_without_overlap() {
+        let tw = window(10, 20);
+        assert_eq!(tw.subtract(&window(1, 5)), vec![tw]);
+        assert_eq!(tw.subtract(&window(25, 30)), vec![tw]);
+        // Окна, касающиеся границей, не пересекаются
+        assert_eq!(tw.subtract(&window(5, 10)), vec![tw]);
+        assert_eq!(tw.subtract(&window(20, 25)), vec![tw]);
+    }
+
+    #[test]
+    fn test_subtract_full_cover() {
+        let tw = window(10, 20);
+        assert!(tw.subtract(&window(10, 20)).is_empty());
+        assert!(tw.subtract(&window(5, 25)).is_empty());
+        assert!(tw.subtract(&window(10, 25)).is_empty());
+        assert!(tw.subtract(&window(5, 20)).is_empty());
+    }
+
+    #[test]
+    fn test_subtract_partial_overlap() {
+        let tw = window(10, 20);
+        // Пересечение слева
+        assert_eq!(tw.subtract(&window(5, 15)), vec![window(15, 20)]);
+        assert_eq!(tw.subtract(&window(10, 15)), vec![window(15, 20)]);
+        // Пересечение справа
+        assert_eq!(tw.subtract(&window(15, 25)), vec![window(10, 15)]);
+        assert_eq!(tw.subtract(&window(15, 20)), vec![window(10, 15)]);
+    }
+
+    #[test]
+    fn test_subtract_splits_middle() {
+        let tw = window(10, 20);
+        assert_eq!(
+            tw.subtract(&window(12, 15)),
+            vec![window(10, 12), window(15, 20)]
+        );
+        // Вырез длиной в час
+        let hour = TimeWindow::new(
+            Utc.with_ymd_and_hms(2026, 3, 12, 9, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 12, 10, 0, 0).unwrap(),
+        )
+        .unwrap();
+        let fragments = tw.subtract(&hour);
+        assert_eq!(fragments.len(), 2);
+        assert_eq!(fragments[0].date_end, hour.date_start);
+        assert_eq!(fragments[1].date_start, hour.date_end);
+    }
+
+    #[test]
+    fn test_subtract_all() {
+        let tw = window(10, 20);
+        // Пустой набор и окна без пересечения (в том числе касание границей)
+        assert_eq!(tw.subtract_all(&[]), vec![tw]);
+        assert_eq!(tw.subtract_all(&[window(1, 10), window(20, 25)]), vec![tw]);
+        // Полное покрытие несколькими окнами
+        assert!(tw.subtract_all(&[window(15, 22), window(8, 15)]).is_empty());
+        // Один остаток
+        assert_eq!(
+            tw.subtract_all(&[window(5, 12), window(11, 14)]),
+            vec![window(14, 20)]
+        );
+        // Два остатка
+        assert_eq!(
+            tw.subtract_all(&[window(12, 18)]),
+            vec![window(10, 12), window(18, 20)]
+        );
+        // Три остатка по возрастанию, порядок вычитаемых окон не важен
+        assert_eq!(
+            tw.subtract_all(&[window(16, 18), window(12, 14)]),
+            vec![window(10, 12), window(14, 16), window(18, 20)]
+        );
+    }
+
+    #[test]
+    fn test_split_by_months() {
+        let at = |m: u32, d: u32, h: u32| Utc.with_ymd_and_hms(2026, m, d, h, 0, 0).unwrap();
+        let tw = TimeWindow::new(at(1, 15, 12), at(3, 10, 0)).unwrap();
+        assert_eq!(
+            tw.split_by_months(),
+            vec![
+                TimeWindow::new(at(1, 15, 12), at(2, 1, 0)).unwrap(),
+                TimeWindow::new(at(2, 1, 0), at(3, 1, 0)).unwrap(),
+                TimeWindow::new(at(3, 1, 0), at(3, 10, 0)).unwrap(),
+            ]
+        );
+        // Окно внутри одного месяца не делится
+        assert_eq!(window(2, 5).split_by_months(), vec![window(2, 5)]);
+    }
+
+    #[test]
+    fn test_split_by_weeks() {
+        // Со среды 4 марта по понедельник 23 марта
+        let tw = window(4, 23);
+        assert_eq!(
+            tw.split_by_weeks(Weekday::Mon),
+            vec![window(4, 9), window(9, 16), window(16, 23)]
+        );
+        assert_eq!(
+            tw.split_by_weeks(Weekday::Sun),
+            vec![window(4, 8), window(8, 15), window(15, 22), window(22, 23)]
+        );
+    }
 }
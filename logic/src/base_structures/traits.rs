@@ -1,48 +1,175 @@
+use std::collections::HashMap;
+
 use crate::{
     Project,
     base_structures::{
+        ContainerEvent, ProjectFilter,
         project_calendar::ProjectCalendar,
-        resource::Resource,
-        resource_pool::{AllocationRequest, ResourceAllocation},
+        resource::{RateConfig, Resource},
+        resource_pool::{AllocationRequest, ResourceAllocation, ResourceConflict},
+        time_window::TimeWindow,
     },
+    export::ExportProfile,
 };
 use anyhow::Result;
 use chrono::{DateTime, TimeDelta, Utc};
 use uuid::Uuid;
 
-pub trait ResourcePool {
+/// Пул и контейнеры должны передаваться между потоками (`SharedProjectContainer`)
+pub trait ResourcePool: Send + Sync {
+    /// Назначить ресурс. Пул проверяет только ресурс (доступность, загрузку); то, что задача
+    /// принадлежит проекту запроса, проверяет вызывающий сервис по контейнеру
     fn allocate(&mut self, request: AllocationRequest, calendar: &ProjectCalendar) -> Result<Uuid>;
+    /// Назначить несколько запросов по принципу "все или ничего"
+    ///
+    /// Запросы проверяются по порядку, и каждый учитывает загрузку от уже принятых запросов
+    /// пакета: два запроса по 0.7 на одно окно не пройдут даже в пустом пуле. При первой ошибке
+    /// пул не меняется, в ошибке указан номер запроса.
+    fn allocate_batch(
+        &mut self,
+        requests: &[AllocationRequest],
+        calendar: &ProjectCalendar,
+    ) -> Result<Vec<Uuid>>;
     fn deallocate(&mut self, allocation_id: Uuid) -> Result<()>;
+    /// Переназначить аллокации на другие ресурсы (allocation_id, новый ресурс, календарь проекта)
+    /// по принципу "все или ничего". Возвращает ошибки по аллокациям, при ошибках пул не меняется.
+    fn reassign_allocations(
+        &mut self,
+        changes: &[(Uuid, Uuid, ProjectCalendar)],
+    ) -> Vec<(Uuid, String)>;
+    /// Перенести аллокации в новые окна (allocation_id, окно) по принципу "все или ничего".
+    /// Возвращает ошибки по аллокациям, при ошибках пул не меняется.
+    fn move_allocations(
+        &mut self,
+        moves: &[(Uuid, TimeWindow)],
+        calendar: &ProjectCalendar,
+    ) -> Vec<(Uuid, String)>;
+    /// Выровнять загрузку ресурса, не выходя за окончания задач `task_ends` (задача -> окончание)
+    fn level_resource(
+        &mut self,
+        resource_id: Uuid,
+        calendar: &ProjectCalendar,
+        task_ends: &HashMap<Uuid, DateTime<Utc>>,
+    ) -> Result<Vec<Uuid>>;
     fn add_resource(&mut self, resource: Resource) -> Result<()>;
     fn remove_resource(&mut self, id: &Uuid) -> Result<()>;
-    fn get_resources(&self) -> Vec<&Resource>;
+    /// Все ресурсы пула
+    fn resources(&self) -> Vec<&Resource>;
+    /// Все назначения пула, по всем проектам
+    fn allocations(&self) -> Vec<&ResourceAllocation>;
+    fn resource_count(&self) -> usize {
+        self.resources().len()
+    }
     fn get_mut_resource_by_uuid(&mut self, resource_id: Uuid) -> Option<&mut Resource>;
     fn get_resource_existing_allocations(&self, resource_id: &Uuid) -> Vec<&ResourceAllocation>;
+    /// Аллокации ресурса, которые учитываются в загрузке (без исключенных, архивных проектов)
+    fn counted_allocations(&self, resource_id: &Uuid) -> Vec<&ResourceAllocation>;
+    fn get_project_allocations(&self, project_id: &Uuid) -> Vec<&ResourceAllocation>;
+    /// Задачи, на которые назначен ресурс: (задача, занятость, окно), по началу окна
+    fn tasks_for_resource(&self, resource_id: Uuid) -> Vec<(Uuid, f64, TimeWindow)> {
+        let mut tasks: Vec<(Uuid, f64, TimeWindow)> = self
+            .get_resource_existing_allocations(&resource_id)
+            .into_iter()
+            .map(|a| {
+                (
+                    *a.get_task_id(),
+                    *a.get_engagement_rate(),
+                    *a.get_time_window(),
+                )
+            })
+            .collect();
+        tasks.sort_by_key(|(task_id, _, window)| (window.date_start, *task_id));
+        tasks
+    }
+    fn find_conflicts(&self) -> Vec<ResourceConflict>;
     fn get_allocation(&self, allocation_id: &Uuid) -> Option<&ResourceAllocation>;
     fn get_resource(&self, resource_id: &Uuid) -> Option<&Resource>;
+    /// Стоимость назначения. Часовая ставка платится за рабочие часы окна, дневная - за
+    /// рабочие дни, месячная - за рабочие дни по `rate_config.days_per_month` в месяце.
+    /// Единый расчет для бюджета, отчетов и статистики проекта
     fn calculate_allocation_cost(
         &self,
         allocation_id: &Uuid,
         calendar: &ProjectCalendar,
+        rate_config: &RateConfig,
     ) -> Result<f64>;
     fn calculate_allocation_time(
         &self,
         allocation_id: &Uuid,
         calendar: &ProjectCalendar,
     ) -> Result<f64>;
+    /// Забрать накопленные события пула (ресурсы и назначения)
+    fn take_events(&mut self) -> Vec<ContainerEvent>;
+    /// Вернуть назначения проекта к снимку `allocations` (отмена массовой операции).
+    /// Снимок снят с этого же пула, поэтому загрузка ресурсов повторно не проверяется
+    fn restore_project_allocations(
+        &mut self,
+        project_id: &Uuid,
+        allocations: Vec<ResourceAllocation>,
+    );
 }
 
-pub trait ProjectContainer {
+pub trait ProjectContainer: Send + Sync {
     fn add_project(&mut self, project: Project) -> Result<()>;
     fn get_project(&self, id: &Uuid) -> Option<&Project>;
     fn get_project_mut(&mut self, id: &Uuid) -> Option<&mut Project>;
     fn list_projects(&self) -> Vec<&Project>;
+    fn list_project_ids(&self) -> Vec<Uuid> {
+        self.list_projects().iter().map(|p| *p.get_id()).collect()
+    }
+    // Проекты, подходящие под фильтр (архив, статус, тег), в порядке list_projects
+    fn list_projects_filtered(&self, filter: &ProjectFilter) -> Vec<&Project> {
+        self.list_projects()
+            .into_iter()
+            .filter(|p| filter.matches(p, self.is_archived(p.get_id())))
+            .collect()
+    }
+    // Поиск проекта по точному имени
+    fn get_project_by_name(&self, name: &str) -> Option<&Project> {
+        self.list_projects().into_iter().find(|p| p.name == name)
+    }
+    // Поиск проекта по имени без учета регистра. Имена уникальны без учета регистра,
+    // поэтому результат однозначен
+    fn get_project_by_name_ignore_case(&self, name: &str) -> Option<&Project> {
+        let name = name.to_lowercase();
+        self.list_projects()
+            .into_iter()
+            .find(|p| p.name.to_lowercase() == name)
+    }
+    // Проект в архиве: его назначения и потребности не учитываются в загрузке и отчетах
+    fn is_archived(&self, _id: &Uuid) -> bool {
+        false
+    }
+    // Закрыть проект: убирает календарь проекта и его назначения из пула
+    fn remove_project(&mut self, id: &Uuid) -> Result<Project>;
     // общий пул ресурсов
     fn resource_pool(&self) -> &dyn ResourcePool;
     fn resource_pool_mut(&mut self) -> &mut dyn ResourcePool;
 
     // Доступ к календарю проекта
     fn calendar(&self, project_id: &Uuid) -> Option<&ProjectCalendar>;
+    fn calendar_mut(&mut self, project_id: &Uuid) -> Option<&mut ProjectCalendar>;
+
+    // Профили регулярной выгрузки, хранятся вместе с контейнером
+    fn export_profiles(&self) -> &Vec<ExportProfile>;
+    fn export_profiles_mut(&mut self) -> &mut Vec<ExportProfile>;
+
+    // Очередь событий об изменениях, не сохраняется вместе с контейнером
+    fn events_mut(&mut self) -> &mut Vec<ContainerEvent>;
+    // Записать событие. События пула переносятся в очередь раньше, так порядок сохраняется
+    fn emit(&mut self, event: ContainerEvent) {
+        let pooled = self.resource_pool_mut().take_events();
+        let events = self.events_mut();
+        events.extend(pooled);
+        events.push(event);
+    }
+    // Забрать все события с момента прошлого вызова
+    fn take_events(&mut self) -> Vec<ContainerEvent> {
+        let pooled = self.resource_pool_mut().take_events();
+        let mut events = std::mem::take(self.events_mut());
+        events.extend(pooled);
+        events
+    }
 }
 
 pub trait BasicGettersForStructures {
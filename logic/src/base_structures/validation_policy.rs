@@ -0,0 +1,109 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::cust_exceptions::ValidationErrors;
+
+/// Ограничения на длительности проекта, задач и назначений
+///
+/// Защищает алгоритмы, которые перебирают дни, от периодов в сотню лет.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ValidationPolicy {
+    pub max_project_duration: TimeDelta,
+    pub max_task_duration: TimeDelta,
+    pub max_allocation_window: TimeDelta,
+}
+
+impl Default for ValidationPolicy {
+    fn default() -> Self {
+        Self {
+            max_project_duration: TimeDelta::days(15 * 365),
+            max_task_duration: TimeDelta::days(5 * 365),
+            max_allocation_window: TimeDelta::days(5 * 365),
+        }
+    }
+}
+
+impl ValidationPolicy {
+    pub fn check_project(
+        &self,
+        date_start: DateTime<Utc>,
+        date_end: DateTime<Utc>,
+    ) -> Result<(), ValidationErrors> {
+        let duration = date_end - date_start;
+        if duration > self.max_project_duration {
+            return Err(ValidationErrors::ProjectTooLong {
+                duration_days: duration.num_days(),
+                max_days: self.max_project_duration.num_days(),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn check_task(
+        &self,
+        date_start: DateTime<Utc>,
+        date_end: DateTime<Utc>,
+    ) -> Result<(), ValidationErrors> {
+        let duration = date_end - date_start;
+        if duration > self.max_task_duration {
+            return Err(ValidationErrors::TaskTooLong {
+                duration_days: duration.num_days(),
+                max_days: self.max_task_duration.num_days(),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn check_allocation(
+        &self,
+        date_start: DateTime<Utc>,
+        date_end: DateTime<Utc>,
+    ) -> Result<(), ValidationErrors> {
+        let duration = date_end - date_start;
+        if duration > self.max_allocation_window {
+            return Err(ValidationErrors::AllocationWindowTooLong {
+                duration_days: duration.num_days(),
+                max_days: self.max_allocation_window.num_days(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_policy_boundaries() {
+        let policy = ValidationPolicy::default();
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        let at_limit = start + policy.max_project_duration;
+        assert!(policy.check_project(start, at_limit).is_ok());
+        assert_eq!(
+            policy.check_project(start, at_limit + TimeDelta::seconds(1)),
+            Err(ValidationErrors::ProjectTooLong {
+                duration_days: 15 * 365,
+                max_days: 15 * 365
+            })
+        );
+
+        let at_limit = start + policy.max_task_duration;
+        assert!(policy.check_task(start, at_limit).is_ok());
+        assert!(
+            policy
+                .check_task(start, at_limit + TimeDelta::days(1))
+                .is_err()
+        );
+
+        let at_limit = start + policy.max_allocation_window;
+        assert!(policy.check_allocation(start, at_limit).is_ok());
+        assert!(matches!(
+            policy.check_allocation(start, at_limit + TimeDelta::days(1)),
+            Err(ValidationErrors::AllocationWindowTooLong { .. })
+        ));
+    }
+}
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use thiserror::Error;
+use uuid::Uuid;
 
 #[derive(Error, Debug)]
 pub enum ProjectCreationErrors {
@@ -8,6 +9,244 @@ pub enum ProjectCreationErrors {
         date_start: DateTime<Utc>,
         date_end: DateTime<Utc>,
     },
+    #[error("invalid Project periods (date_start {date_start:?} >= {date_end:?})")]
+    InvalidProjectDuration {
+        date_start: DateTime<Utc>,
+        date_end: DateTime<Utc>,
+    },
+    #[error("project name must not be empty")]
+    EmptyProjectName,
+    #[error(transparent)]
+    Validation(#[from] ValidationErrors),
     #[error("unknown project customisation error")]
     Unknown,
 }
+
+/// Ошибки имени и дат проекта, общие для создания и изменения
+#[derive(Error, Debug, PartialEq)]
+pub enum ProjectDefinitionErrors {
+    #[error("invalid Project periods (date_start {date_start:?} >= {date_end:?})")]
+    InvalidProjectDuration {
+        date_start: DateTime<Utc>,
+        date_end: DateTime<Utc>,
+    },
+    #[error("project name must not be empty")]
+    EmptyProjectName,
+    #[error(transparent)]
+    Validation(#[from] ValidationErrors),
+}
+
+impl From<ProjectDefinitionErrors> for ProjectCreationErrors {
+    fn from(error: ProjectDefinitionErrors) -> Self {
+        match error {
+            ProjectDefinitionErrors::InvalidProjectDuration {
+                date_start,
+                date_end,
+            } => Self::InvalidProjectDuration {
+                date_start,
+                date_end,
+            },
+            ProjectDefinitionErrors::EmptyProjectName => Self::EmptyProjectName,
+            ProjectDefinitionErrors::Validation(e) => Self::Validation(e),
+        }
+    }
+}
+
+impl From<ProjectDefinitionErrors> for ProjectUpdateErrors {
+    fn from(error: ProjectDefinitionErrors) -> Self {
+        match error {
+            ProjectDefinitionErrors::InvalidProjectDuration {
+                date_start,
+                date_end,
+            } => Self::InvalidProjectDuration {
+                date_start,
+                date_end,
+            },
+            ProjectDefinitionErrors::EmptyProjectName => Self::EmptyProjectName,
+            ProjectDefinitionErrors::Validation(e) => Self::Validation(e),
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ProjectUpdateErrors {
+    #[error("invalid Project periods (date_start {date_start:?} >= {date_end:?})")]
+    InvalidProjectDuration {
+        date_start: DateTime<Utc>,
+        date_end: DateTime<Utc>,
+    },
+    #[error("project name must not be empty")]
+    EmptyProjectName,
+    #[error("tasks are outside of new project dates: {}", join_ids(task_ids))]
+    TasksOutsideProject { task_ids: Vec<Uuid> },
+    #[error(transparent)]
+    Validation(#[from] ValidationErrors),
+}
+
+/// Ошибки лимитов бюджета по периодам
+#[derive(Error, Debug, PartialEq)]
+pub enum BudgetErrors {
+    #[error("budget amount for period {start:?} - {end:?} must be > 0")]
+    NonPositiveAmount {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+    #[error("budget period {start:?} - {end:?} is outside of project dates")]
+    PeriodOutsideProject {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+    #[error("budget periods overlap at {start:?}")]
+    PeriodsOverlap { start: DateTime<Utc> },
+    #[error("project budget must be > 0, got {amount}")]
+    NonPositiveBudget { amount: f64 },
+    #[error(transparent)]
+    Currency(#[from] CurrencyErrors),
+}
+
+/// Ошибки добавления задачи в проект
+/// Ошибки потребностей проекта в ролях
+#[derive(Error, Debug, PartialEq)]
+pub enum RolePlaceholderErrors {
+    #[error("role name is empty")]
+    EmptyRole,
+    #[error("engagement rate for role must be > 0, got {rate}")]
+    NonPositiveRate { rate: f64 },
+    #[error("role placeholder {start:?} - {end:?} is outside of project dates")]
+    OutsideProject {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+    #[error("role placeholder {placeholder_id} not found")]
+    NotFound { placeholder_id: Uuid },
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AddTaskErrors {
+    #[error("task {task_id} already exists in project")]
+    DuplicateTaskId { task_id: Uuid },
+    #[error("task dates {date_start:?} - {date_end:?} must be within project dates")]
+    OutsideProject {
+        date_start: DateTime<Utc>,
+        date_end: DateTime<Utc>,
+    },
+    #[error("parent task {parent_id} not found in project")]
+    UnknownParent { parent_id: Uuid },
+    #[error("dependency on task {depends_on} which is not in project")]
+    UnknownDependency { depends_on: Uuid },
+    #[error(transparent)]
+    Validation(#[from] ValidationErrors),
+}
+
+/// Ошибки слияния проектов, при ошибке целевой проект не меняется
+#[derive(Error, Debug, PartialEq)]
+pub enum MergeErrors {
+    #[error("task {task_id} already exists in target project")]
+    DuplicateTaskId { task_id: Uuid },
+    #[error("task name '{name}' already exists in target project")]
+    TaskNameCollision { name: String },
+    #[error("task {task_id} is outside of target project dates")]
+    OutsideProjectWindow { task_id: Uuid },
+    #[error("allocation {allocation_id} is already referenced in target project")]
+    DuplicateAllocation { allocation_id: Uuid },
+    #[error("resolution '{resolution}' is not applicable to {item}")]
+    UnsupportedResolution { item: String, resolution: String },
+}
+
+/// Ошибки валют ресурсов и пересчета затрат
+#[derive(Error, Debug, PartialEq)]
+pub enum CurrencyErrors {
+    #[error("invalid ISO-4217 currency code '{code}'")]
+    InvalidCode { code: String },
+    #[error("no exchange rate for currency {currency}")]
+    MissingRate { currency: String },
+}
+
+/// Ошибки настройки календаря
+#[derive(Error, Debug, PartialEq)]
+pub enum CalendarErrors {
+    #[error("partial day {date} must have from 1 to {normal_hours} exclusive hours, got {hours}")]
+    InvalidPartialDay {
+        date: chrono::NaiveDate,
+        hours: u32,
+        normal_hours: u32,
+    },
+    #[error("no working day within {searched_days} days from {from}")]
+    NoWorkingDays {
+        from: chrono::NaiveDate,
+        searched_days: i64,
+    },
+    #[error("calendar has no working weekdays")]
+    NoWorkingWeekdays,
+    #[error("shift cycle has no working days")]
+    NoWorkingShiftDays,
+    #[error("no working days between {date_start} and {date_end}")]
+    NoWorkingDaysInWindow {
+        date_start: chrono::NaiveDate,
+        date_end: chrono::NaiveDate,
+    },
+    #[error("workday must start before it ends, got {start}-{end}")]
+    InvalidWorkdaySpan {
+        start: chrono::NaiveTime,
+        end: chrono::NaiveTime,
+    },
+    #[error("cannot shift by {days} working days, at most {max} allowed")]
+    ShiftTooLarge { days: i64, max: u32 },
+}
+
+/// Ошибки разбора введенной даты
+#[derive(Error, Debug, PartialEq)]
+pub enum DateInputError {
+    #[error("date is empty")]
+    Empty,
+    #[error("unrecognized date '{input}'")]
+    Unrecognized { input: String },
+    #[error("invalid date '{input}'")]
+    InvalidDate { input: String },
+    #[error(transparent)]
+    Calendar(#[from] CalendarErrors),
+}
+
+/// Ошибки контейнера проектов
+#[derive(Error, Debug, PartialEq)]
+pub enum ContainerErrors {
+    #[error("project with name '{name}' already exists")]
+    DuplicateProjectName { name: String },
+    #[error("project file version {found} is newer than supported version {supported}")]
+    UnsupportedFileVersion { found: u64, supported: u64 },
+}
+
+/// Ошибки назначения ресурса в пуле
+#[derive(Error, Debug, PartialEq)]
+pub enum AllocationErrors {
+    #[error("task {task_id} does not belong to project {project_id}")]
+    TaskNotInProject { task_id: Uuid, project_id: Uuid },
+    #[error("engagement rate must be between 0.0 and 1.0, got {rate}")]
+    InvalidEngagement { rate: f64 },
+    #[error("resource {resource_id} engagement on task {task_id} would reach {total}, max is 1.0")]
+    DuplicateResourceOnTask {
+        resource_id: Uuid,
+        task_id: Uuid,
+        total: f64,
+    },
+    #[error("allocation {allocation_id} already exists")]
+    DuplicateAllocationId { allocation_id: Uuid },
+}
+
+/// Нарушения ограничений ValidationPolicy
+#[derive(Error, Debug, PartialEq)]
+pub enum ValidationErrors {
+    #[error("project duration {duration_days} days exceeds limit of {max_days} days")]
+    ProjectTooLong { duration_days: i64, max_days: i64 },
+    #[error("task duration {duration_days} days exceeds limit of {max_days} days")]
+    TaskTooLong { duration_days: i64, max_days: i64 },
+    #[error("allocation window {duration_days} days exceeds limit of {max_days} days")]
+    AllocationWindowTooLong { duration_days: i64, max_days: i64 },
+}
+
+fn join_ids(ids: &[Uuid]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
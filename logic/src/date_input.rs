@@ -0,0 +1,224 @@
+//! Разбор дат, введенных с клавиатуры
+//!
+//! Поддерживаются абсолютные даты (`2026-02-15`, `15.02.2026`, `15.02` - текущий год),
+//! `сегодня`/`today`, дни недели (`пн`, `fri` - ближайший такой день, начиная с сегодняшнего)
+//! и сдвиги от сегодняшнего дня: `+5d` - календарные дни, `+5wd`/`+5рд` - рабочие дни
+//! по календарю проекта, `-2w` - недели.
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+use crate::{ProjectCalendar, cust_exceptions::DateInputError};
+
+/// Разобрать введенную дату относительно `today`
+pub fn parse_date_input(
+    input: &str,
+    today: NaiveDate,
+    calendar: &ProjectCalendar,
+) -> Result<NaiveDate, DateInputError> {
+    let text = input.trim().to_lowercase();
+    if text.is_empty() {
+        return Err(DateInputError::Empty);
+    }
+    if text == "сегодня" || text == "today" {
+        return Ok(today);
+    }
+    if let Some(weekday) = parse_weekday(&text) {
+        let days_ahead = (7 + weekday.num_days_from_monday() as i64
+            - today.weekday().num_days_from_monday() as i64)
+            % 7;
+        return shift_days(today, days_ahead, input);
+    }
+    if text.starts_with('+') || text.starts_with('-') {
+        return parse_offset(&text, today, calendar, input);
+    }
+    parse_absolute(&text, today, input)
+}
+
+fn parse_weekday(text: &str) -> Option<Weekday> {
+    let weekday = match text {
+        "пн" | "mon" => Weekday::Mon,
+        "вт" | "tue" => Weekday::Tue,
+        "ср" | "wed" => Weekday::Wed,
+        "чт" | "thu" => Weekday::Thu,
+        "пт" | "fri" => Weekday::Fri,
+        "сб" | "sat" => Weekday::Sat,
+        "вс" | "sun" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+fn parse_offset(
+    text: &str,
+    today: NaiveDate,
+    calendar: &ProjectCalendar,
+    input: &str,
+) -> Result<NaiveDate, DateInputError> {
+    let unrecognized = || DateInputError::Unrecognized {
+        input: input.to_string(),
+    };
+    let sign = if text.starts_with('-') { -1 } else { 1 };
+    let body = &text[1..];
+    let digits_end = body
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(body.len());
+    let amount: i64 = body[..digits_end].parse().map_err(|_| unrecognized())?;
+    let amount = sign * amount;
+    match &body[digits_end..] {
+        "" | "d" | "д" => shift_days(today, amount, input),
+        "w" | "н" => shift_days(
+            today,
+            amount.checked_mul(7).ok_or_else(unrecognized)?,
+            input,
+        ),
+        "wd" | "рд" => Ok(calendar.shift_working_days(today, amount)?),
+        _ => Err(unrecognized()),
+    }
+}
+
+fn parse_absolute(text: &str, today: NaiveDate, input: &str) -> Result<NaiveDate, DateInputError> {
+    let invalid = || DateInputError::InvalidDate {
+        input: input.to_string(),
+    };
+    if text.contains('-') {
+        return NaiveDate::parse_from_str(text, "%Y-%m-%d").map_err(|_| invalid());
+    }
+    let parts: Vec<&str> = text.split('.').collect();
+    let number = |s: &str| s.parse::<u32>().map_err(|_| invalid());
+    let (day, month, year) = match parts.as_slice() {
+        [day, month] => (number(day)?, number(month)?, today.year()),
+        [day, month, year] => (number(day)?, number(month)?, number(year)? as i32),
+        _ => {
+            return Err(DateInputError::Unrecognized {
+                input: input.to_string(),
+            });
+        }
+    };
+    NaiveDate::from_ymd_opt(year, month, day).ok_or_else(invalid)
+}
+
+fn shift_days(date: NaiveDate, days: i64, input: &str) -> Result<NaiveDate, DateInputError> {
+    let shifted = if days >= 0 {
+        date.checked_add_days(Days::new(days as u64))
+    } else {
+        date.checked_sub_days(Days::new(days.unsigned_abs()))
+    };
+    shifted.ok_or_else(|| DateInputError::InvalidDate {
+        input: input.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse_absolute_dates() {
+        let today = date(2026, 1, 14);
+        let calendar = ProjectCalendar::default();
+        assert_eq!(
+            parse_date_input("2026-02-15", today, &calendar),
+            Ok(date(2026, 2, 15))
+        );
+        assert_eq!(
+            parse_date_input(" 15.02.2027 ", today, &calendar),
+            Ok(date(2027, 2, 15))
+        );
+        assert_eq!(
+            parse_date_input("15.02", today, &calendar),
+            Ok(date(2026, 2, 15))
+        );
+        assert_eq!(
+            parse_date_input("31.02", today, &calendar),
+            Err(DateInputError::InvalidDate {
+                input: "31.02".into()
+            })
+        );
+        assert_eq!(
+            parse_date_input("", today, &calendar),
+            Err(DateInputError::Empty)
+        );
+        assert_eq!(
+            parse_date_input("завтра", today, &calendar),
+            Err(DateInputError::Unrecognized {
+                input: "завтра".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_relative_dates() {
+        // Среда 14 января 2026
+        let today = date(2026, 1, 14);
+        let calendar = ProjectCalendar::default();
+        assert_eq!(parse_date_input("Сегодня", today, &calendar), Ok(today));
+        assert_eq!(
+            parse_date_input("+3d", today, &calendar),
+            Ok(date(2026, 1, 17))
+        );
+        assert_eq!(
+            parse_date_input("-1w", today, &calendar),
+            Ok(date(2026, 1, 7))
+        );
+        // Рабочие дни пропускают выходные
+        assert_eq!(
+            parse_date_input("+5wd", today, &calendar),
+            Ok(date(2026, 1, 21))
+        );
+        assert_eq!(
+            parse_date_input("-3рд", today, &calendar),
+            Ok(date(2026, 1, 9))
+        );
+        assert_eq!(
+            parse_date_input("пн", today, &calendar),
+            Ok(date(2026, 1, 19))
+        );
+        assert_eq!(parse_date_input("wed", today, &calendar), Ok(today));
+        assert!(parse_date_input("+5x", today, &calendar).is_err());
+    }
+
+    #[test]
+    fn test_parsed_end_date_is_inclusive_in_calendar_timezone() {
+        use crate::TimeWindow;
+
+        let mut calendar = ProjectCalendar::default();
+        calendar.set_timezone(chrono_tz::Asia::Tokyo);
+        let today = date(2026, 2, 9);
+        let start = parse_date_input("09.02", today, &calendar).unwrap();
+        let end = parse_date_input("+4wd", today, &calendar).unwrap();
+        assert_eq!(end, date(2026, 2, 13));
+        let window =
+            TimeWindow::new(calendar.local_midnight(start), calendar.local_midnight(end)).unwrap();
+        // Полночь по Токио - 15:00 UTC предыдущего дня, но окно по-прежнему с пн по пт
+        assert_eq!(calendar.local_date(window.date_start), start);
+        assert_eq!(calendar.count_working_days(&window), 5);
+    }
+
+    #[test]
+    fn test_parse_huge_or_unreachable_shifts() {
+        use crate::cust_exceptions::CalendarErrors;
+
+        let today = date(2026, 1, 14);
+        let calendar = ProjectCalendar::default();
+        assert!(matches!(
+            parse_date_input("+999999999wd", today, &calendar),
+            Err(DateInputError::Calendar(CalendarErrors::ShiftTooLarge {
+                days: 999_999_999,
+                ..
+            }))
+        ));
+        assert!(parse_date_input("+999999999999999999w", today, &calendar).is_err());
+        assert!(parse_date_input("+999999999d", today, &calendar).is_err());
+
+        // Календарь без рабочих дней: ошибка вместо бесконечного поиска
+        let mut closed = ProjectCalendar::default();
+        closed.set_working_days([]);
+        assert!(matches!(
+            parse_date_input("+1wd", today, &closed),
+            Err(DateInputError::Calendar(_))
+        ));
+    }
+}
@@ -0,0 +1,26 @@
+/// Экспорт проекта во внешние форматы
+///
+/// Растровая диаграмма Ганта (PNG) собирается только с фичей `png-export`, выгрузка
+/// в xlsx - с фичей `xlsx-export`, чтобы не тянуть `plotters` и `rust_xlsxwriter`
+/// в сборки, которым они не нужны.
+mod html_report;
+#[cfg(feature = "png-export")]
+mod png_gantt;
+mod portfolio;
+mod profile;
+mod redaction;
+mod tsv;
+#[cfg(feature = "xlsx-export")]
+mod xlsx;
+
+pub use html_report::html_report;
+
+#[cfg(feature = "png-export")]
+pub use png_gantt::png_gantt;
+pub use portfolio::portfolio_html;
+pub use profile::{ExportArtifactResult, ExportKind, ExportLocale, ExportProfile};
+pub use redaction::ExportRedaction;
+pub(crate) use redaction::Pseudonyms;
+pub use tsv::tasks_tsv;
+#[cfg(feature = "xlsx-export")]
+pub use xlsx::portfolio_xlsx;
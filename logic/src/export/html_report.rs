@@ -0,0 +1,306 @@
+use std::collections::BTreeMap;
+
+use uuid::Uuid;
+
+use crate::{
+    BasicGettersForStructures, ProjectContainer,
+    export::{
+        ExportLocale,
+        redaction::{ExportRedaction, Pseudonyms},
+    },
+};
+
+/// Отчет по проекту в HTML: задачи с исполнителями и стоимостью, сводка по ресурсам
+///
+/// Скрытые через `redaction` поля в отчет не попадают вовсе (колонки убираются),
+/// имена ресурсов заменяются инициалами одинаково во всех таблицах отчета.
+/// Даты и суммы форматируются по `locale`.
+pub fn html_report<C: ProjectContainer>(
+    container: &C,
+    project_id: &Uuid,
+    redaction: &ExportRedaction,
+    locale: ExportLocale,
+) -> anyhow::Result<String> {
+    let project = container
+        .get_project(project_id)
+        .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+    let calendar = container
+        .calendar(project_id)
+        .ok_or_else(|| anyhow::anyhow!("Calendar for project {} not found", project_id))?;
+    let pool = container.resource_pool();
+    let mut pseudonyms = Pseudonyms::new(*redaction);
+
+    let mut tasks = project.get_project_tasks();
+    tasks.sort_by(|a, b| {
+        a.get_date_start()
+            .cmp(b.get_date_start())
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{0}</title></head>\n<body>\n<h1>{0}</h1>\n<p>{1}</p>\n",
+        escape(&project.name),
+        escape(&project.description)
+    );
+
+    html.push_str("<h2>Tasks</h2>\n<table>\n<tr><th>Id</th><th>Name</th><th>Start</th><th>End</th><th>Status</th><th>Assignees</th>");
+    if !redaction.hide_costs {
+        html.push_str("<th>Cost</th>");
+    }
+    html.push_str("</tr>\n");
+
+    // Стоимость по ресурсам: (ставка, единица ставки, валюта, стоимость)
+    let mut resources: BTreeMap<String, (f64, String, String, f64)> = BTreeMap::new();
+    for task in tasks {
+        let mut assignees = vec![];
+        let mut task_cost: BTreeMap<String, f64> = BTreeMap::new();
+        for allocation in task
+            .get_resource_allocations()
+            .iter()
+            .filter_map(|id| pool.get_allocation(id))
+        {
+            let Some(resource) = pool.get_resource(allocation.get_resource_id()) else {
+                continue;
+            };
+            let cost = pool.calculate_allocation_cost(
+                &allocation.get_id(),
+                calendar,
+                &project.rate_config,
+            )?;
+            let name = pseudonyms.resource_name(resource);
+            *task_cost
+                .entry(resource.get_currency().to_string())
+                .or_default() += cost;
+            resources
+                .entry(name.clone())
+                .or_insert((
+                    *resource.get_base_rate(),
+                    format!("{:?}", resource.get_rate_measure()),
+                    resource.get_currency().to_string(),
+                    0.0,
+                ))
+                .3 += cost;
+            assignees.push(name);
+        }
+        assignees.sort();
+        assignees.dedup();
+
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{:?}</td><td>{}</td>",
+            task.get_code().unwrap_or_default(),
+            escape(&task.name),
+            locale.format_date(task.get_date_start()),
+            locale.format_date(task.get_date_end()),
+            task.get_status(),
+            escape(&assignees.join(", "))
+        ));
+        if !redaction.hide_costs {
+            html.push_str(&format!("<td>{}</td>", format_costs(&task_cost, locale)));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>Resources</h2>\n<table>\n<tr><th>Name</th>");
+    if !redaction.hide_rates {
+        html.push_str("<th>Rate</th><th>Measure</th>");
+    }
+    if !redaction.hide_costs {
+        html.push_str("<th>Cost</th>");
+    }
+    html.push_str("</tr>\n");
+    for (name, (rate, measure, currency, cost)) in &resources {
+        html.push_str(&format!("<tr><td>{}</td>", escape(name)));
+        if !redaction.hide_rates {
+            html.push_str(&format!(
+                "<td>{}</td><td>{}</td>",
+                locale.format_number(*rate),
+                measure
+            ));
+        }
+        if !redaction.hide_costs {
+            html.push_str(&format!(
+                "<td>{} {}</td>",
+                locale.format_number(*cost),
+                currency
+            ));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+
+    if !redaction.hide_costs {
+        let mut total: BTreeMap<String, f64> = BTreeMap::new();
+        for (_, _, currency, cost) in resources.values() {
+            *total.entry(currency.clone()).or_default() += cost;
+        }
+        html.push_str(&format!(
+            "<p>Total cost: {}</p>\n",
+            format_costs(&total, locale)
+        ));
+    }
+    html.push_str("</body>\n</html>\n");
+    Ok(html)
+}
+
+/// Суммы по валютам через запятую: разные валюты не складываются
+fn format_costs(costs: &BTreeMap<String, f64>, locale: ExportLocale) -> String {
+    if costs.is_empty() {
+        return locale.format_number(0.0);
+    }
+    costs
+        .iter()
+        .map(|(currency, cost)| format!("{} {}", locale.format_number(*cost), currency))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub(super) fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::{Project, RateMeasure, ResourceService, SingleProjectContainer, TaskService};
+
+    fn setup() -> anyhow::Result<(SingleProjectContainer, Uuid)> {
+        let mut container = SingleProjectContainer::new();
+        let project = Project::new(
+            "Portal",
+            "Contractor <plan>",
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap(),
+        )?;
+        let project_id = *project.get_id();
+        container.add_project(project)?;
+
+        let mut resource_ids = vec![];
+        let mut resource_service = ResourceService::new(&mut container);
+        for (name, rate, measure) in [
+            ("Anna Ivanova", 1234.5, RateMeasure::Hourly),
+            ("Alex Ivanov", 9876.0, RateMeasure::Daily),
+        ] {
+            let resource = resource_service.create_resource(name, rate, measure)?;
+            resource_ids.push(resource.id);
+            resource_service.add_resource(resource)?;
+        }
+
+        let mut task_service = TaskService::new(&mut container);
+        let task = task_service.create_regular_task(
+            project_id,
+            "Design".into(),
+            Utc.with_ymd_and_hms(2025, 2, 3, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 2, 8, 0, 0, 0).unwrap(),
+            None,
+        )?;
+        let task_id = *task.get_id();
+        for resource_id in resource_ids {
+            task_service.allocate_resource(project_id, task_id, resource_id, 0.5, None, None)?;
+        }
+        Ok((container, project_id))
+    }
+
+    #[test]
+    fn test_html_report_lists_rates_and_costs() -> anyhow::Result<()> {
+        let (container, project_id) = setup()?;
+        let html = html_report(
+            &container,
+            &project_id,
+            &ExportRedaction::default(),
+            ExportLocale::En,
+        )?;
+        assert!(html.contains("Anna Ivanova"));
+        assert!(html.contains("1234.50"));
+        assert!(html.contains("2025-02-03"));
+        let ru = html_report(
+            &container,
+            &project_id,
+            &ExportRedaction::default(),
+            ExportLocale::Ru,
+        )?;
+        assert!(ru.contains("1234,50"));
+        assert!(ru.contains("03.02.2025"));
+        assert!(html.contains("Total cost"));
+        assert!(html.contains("Contractor &lt;plan&gt;"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_html_report_totals_by_currency() -> anyhow::Result<()> {
+        let (mut container, project_id) = setup()?;
+        let mut resource_service = ResourceService::new(&mut container);
+        let mut john =
+            resource_service.create_resource("John Smith", 100.0, RateMeasure::Hourly)?;
+        john.set_currency("USD")?;
+        let john_id = john.id;
+        resource_service.add_resource(john)?;
+        let task_id = *container
+            .get_project(&project_id)
+            .unwrap()
+            .get_project_tasks()[0]
+            .get_id();
+        TaskService::new(&mut container)
+            .allocate_resource(project_id, task_id, john_id, 1.0, None, None)?;
+
+        let html = html_report(
+            &container,
+            &project_id,
+            &ExportRedaction::default(),
+            ExportLocale::En,
+        )?;
+        // Anna: 40 часов * 1234.5 * 0.5, Alex: 5 дней * 9876 * 0.5, John: 40 часов * 100
+        assert!(html.contains("<td>4000.00 USD</td>"), "{}", html);
+        assert!(
+            html.contains("<td>49380.00 RUB, 4000.00 USD</td>"),
+            "{}",
+            html
+        );
+        assert!(
+            html.contains("<p>Total cost: 49380.00 RUB, 4000.00 USD</p>"),
+            "{}",
+            html
+        );
+        assert!(html.contains("Contractor &lt;plan&gt;"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_redacted_html_report_has_no_rates() -> anyhow::Result<()> {
+        let (container, project_id) = setup()?;
+        let html = html_report(
+            &container,
+            &project_id,
+            &ExportRedaction::all(),
+            ExportLocale::En,
+        )?;
+
+        for resource in container.resource_pool().resources() {
+            assert!(!html.contains(&resource.name), "{}", html);
+            let rate = *resource.get_base_rate();
+            for measure in [
+                RateMeasure::Hourly,
+                RateMeasure::Daily,
+                RateMeasure::Monthly,
+            ] {
+                let converted = resource.get_converted_rate(measure);
+                for value in [
+                    format!("{}", converted),
+                    format!("{:.2}", converted),
+                    format!("{}", rate),
+                ] {
+                    assert!(!html.contains(&value), "rate {} leaked: {}", value, html);
+                }
+            }
+        }
+        assert!(html.contains("A.I. (2)"));
+        assert!(!html.contains("Cost"));
+        Ok(())
+    }
+}
@@ -0,0 +1,264 @@
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use plotters::prelude::*;
+
+use crate::{
+    BasicGettersForStructures, Project, Task, base_structures::ProjectCalendar,
+    export::ExportRedaction,
+};
+
+const MARGIN: i32 = 10;
+const BAR_PADDING: f64 = 0.2;
+
+const BACKGROUND: RGBColor = RGBColor(255, 255, 255);
+const NON_WORKING_DAY: RGBColor = RGBColor(230, 230, 230);
+const REGULAR_TASK: RGBColor = RGBColor(173, 216, 230);
+const SUMMARY_TASK: RGBColor = RGBColor(128, 0, 128);
+const MILESTONE: RGBColor = RGBColor(0, 0, 0);
+
+/// Отрисовка диаграммы Ганта в PNG файл
+///
+/// По оси X - время (по дням), по оси Y - задачи, отсортированные по дате начала и имени.
+/// Дни шкалы - локальные даты календаря проекта от полуночи до полуночи его часового пояса,
+/// нерабочие дни затеняются, задачи нулевой длительности рисуются ромбом.
+/// Для проекта без задач возвращается ошибка, пустая картинка не создается.
+/// Подписей на диаграмме нет (ни имен, ни ставок), поэтому `redaction` картинку не меняет.
+pub fn png_gantt(
+    project: &Project,
+    calendar: &ProjectCalendar,
+    _redaction: &ExportRedaction,
+    path: impl AsRef<Path>,
+    width: u32,
+    height: u32,
+) -> anyhow::Result<()> {
+    let mut tasks: Vec<&Task> = project.get_project_tasks();
+    if tasks.is_empty() {
+        anyhow::bail!("Project '{}' has no tasks to draw", project.name);
+    }
+    if width as i32 <= 2 * MARGIN || height as i32 <= 2 * MARGIN {
+        anyhow::bail!(
+            "Image size {}x{} is too small for Gantt chart",
+            width,
+            height
+        );
+    }
+    tasks.sort_by(|a, b| {
+        a.get_date_start()
+            .cmp(b.get_date_start())
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    // Границы шкалы выравниваем по локальным полуночам календаря
+    let min_date = tasks.iter().map(|t| *t.get_date_start()).min().unwrap();
+    let max_date = tasks.iter().map(|t| *t.get_date_end()).max().unwrap();
+    let first_day = calendar.local_date(min_date);
+    let mut last_day = calendar.local_date(max_date);
+    if calendar.local_midnight(last_day) < max_date || last_day == first_day {
+        last_day = last_day.succ_opt().unwrap_or(last_day);
+    }
+    let axis_start = calendar.local_midnight(first_day);
+    let axis_end = calendar.local_midnight(last_day);
+
+    let plot_left = MARGIN;
+    let plot_top = MARGIN;
+    let plot_width = (width as i32 - 2 * MARGIN) as f64;
+    let plot_height = (height as i32 - 2 * MARGIN) as f64;
+    let total_seconds = (axis_end - axis_start).num_seconds() as f64;
+    let x_of = |dt: DateTime<Utc>| -> i32 {
+        plot_left + ((dt - axis_start).num_seconds() as f64 / total_seconds * plot_width) as i32
+    };
+    let row_height = plot_height / tasks.len() as f64;
+
+    let root = BitMapBackend::new(path.as_ref(), (width, height)).into_drawing_area();
+    root.fill(&BACKGROUND)
+        .map_err(|e| anyhow::anyhow!("Gantt PNG drawing error: {}", e))?;
+
+    // Затеняем нерабочие дни. При переводе часов локальный день короче или длиннее суток
+    for day in first_day.iter_days().take_while(|d| *d < last_day) {
+        if calendar.is_working_day(day) {
+            continue;
+        }
+        let next = day.succ_opt().unwrap_or(day);
+        root.draw(&Rectangle::new(
+            [
+                (x_of(calendar.local_midnight(day)), plot_top),
+                (
+                    x_of(calendar.local_midnight(next)),
+                    plot_top + plot_height as i32,
+                ),
+            ],
+            NON_WORKING_DAY.filled(),
+        ))
+        .map_err(|e| anyhow::anyhow!("Gantt PNG drawing error: {}", e))?;
+    }
+
+    for (row, task) in tasks.iter().enumerate() {
+        let top = plot_top + (row as f64 * row_height + row_height * BAR_PADDING) as i32;
+        let bottom = plot_top + ((row + 1) as f64 * row_height - row_height * BAR_PADDING) as i32;
+        let x_start = x_of(*task.get_date_start());
+        let x_end = x_of(*task.get_date_end());
+
+        if task.get_date_start() == task.get_date_end() {
+            // Веха - ромб в точке даты
+            let middle = (top + bottom) / 2;
+            let half = ((bottom - top) / 2).max(1);
+            root.draw(&Polygon::new(
+                vec![
+                    (x_start, top),
+                    (x_start + half, middle),
+                    (x_start, bottom),
+                    (x_start - half, middle),
+                ],
+                MILESTONE.filled(),
+            ))
+        } else {
+            let color = if task.is_summary {
+                SUMMARY_TASK
+            } else {
+                REGULAR_TASK
+            };
+            root.draw(&Rectangle::new(
+                [(x_start, top), (x_end.max(x_start + 1), bottom)],
+                color.filled(),
+            ))
+        }
+        .map_err(|e| anyhow::anyhow!("Gantt PNG drawing error: {}", e))?;
+    }
+
+    root.present()
+        .map_err(|e| anyhow::anyhow!("Gantt PNG writing error: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    use super::*;
+
+    fn temp_png(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("rsproject_{}_{}.png", name, uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_png_gantt_writes_image_of_requested_size() {
+        let mut project = Project::new(
+            "Gantt",
+            "PNG export",
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap(),
+        )
+        .unwrap();
+        for (name, start, end) in [("Design", 6, 17), ("Build", 20, 31)] {
+            let task = Task::new_regular(
+                name,
+                Utc.with_ymd_and_hms(2025, 1, start, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 1, end, 0, 0, 0).unwrap(),
+                None,
+            )
+            .unwrap();
+            project.tasks.insert(*task.get_id(), task);
+        }
+
+        let path = temp_png("gantt");
+        let calendar = ProjectCalendar::default();
+        png_gantt(
+            &project,
+            &calendar,
+            &ExportRedaction::default(),
+            &path,
+            640,
+            240,
+        )
+        .unwrap();
+
+        assert!(std::fs::metadata(&path).unwrap().len() > 0);
+        let image = image::open(&path).unwrap();
+        assert_eq!((image.width(), image.height()), (640, 240));
+
+        // Текста на картинке нет - скрытие данных не меняет ни одного пикселя
+        let redacted = temp_png("gantt_redacted");
+        png_gantt(
+            &project,
+            &calendar,
+            &ExportRedaction::all(),
+            &redacted,
+            640,
+            240,
+        )
+        .unwrap();
+        assert_eq!(
+            std::fs::read(&path).unwrap(),
+            std::fs::read(&redacted).unwrap()
+        );
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&redacted).unwrap();
+    }
+
+    #[test]
+    fn test_png_gantt_shades_local_weekend() {
+        let mut calendar = ProjectCalendar::default();
+        calendar.set_timezone(chrono_tz::Asia::Tokyo);
+        let mut project = Project::new(
+            "Tokyo",
+            "",
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap(),
+        )
+        .unwrap();
+        // Неделя с понедельника 6 января по местному времени: в UTC это воскресенье 15:00
+        let week = |day| calendar.local_midnight(NaiveDate::from_ymd_opt(2025, 1, day).unwrap());
+        let task = Task::new_regular("Week", week(6), week(13), None).unwrap();
+        project.tasks.insert(*task.get_id(), task);
+
+        let path = temp_png("tokyo");
+        png_gantt(
+            &project,
+            &calendar,
+            &ExportRedaction::default(),
+            &path,
+            640,
+            240,
+        )
+        .unwrap();
+        let image = image::open(&path).unwrap().to_rgb8();
+        std::fs::remove_file(&path).unwrap();
+
+        // Шкала - ровно семь местных дней, пиксель над полосой задачи в середине дня
+        let pixel = |day: f64| {
+            let x = MARGIN as f64 + (day + 0.5) / 7.0 * (640 - 2 * MARGIN) as f64;
+            image.get_pixel(x as u32, MARGIN as u32 + 2).0
+        };
+        let RGBColor(r, g, b) = NON_WORKING_DAY;
+        let RGBColor(br, bg, bb) = BACKGROUND;
+        assert_eq!(pixel(0.0), [br, bg, bb], "local Monday");
+        assert_eq!(pixel(4.0), [br, bg, bb], "local Friday");
+        assert_eq!(pixel(5.0), [r, g, b], "local Saturday");
+        assert_eq!(pixel(6.0), [r, g, b], "local Sunday");
+    }
+
+    #[test]
+    fn test_png_gantt_rejects_project_without_tasks() {
+        let project = Project::new(
+            "Empty",
+            "",
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap(),
+        )
+        .unwrap();
+
+        let path = temp_png("empty");
+        let result = png_gantt(
+            &project,
+            &ProjectCalendar::default(),
+            &ExportRedaction::default(),
+            &path,
+            640,
+            240,
+        );
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("no tasks"));
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{
+    PortfolioReport,
+    export::{ExportLocale, ExportRedaction, Pseudonyms, html_report::escape},
+};
+
+/// Портфельный отчет в HTML: проекты окна и потребность по навыкам против мощности
+///
+/// В ячейке навыка - "потребность / мощность" в FTE, корзины с нехваткой помечаются
+/// классом `shortage`. Даты и числа форматируются по `locale`. Ставок и стоимости в отчете
+/// нет, из `redaction` учитывается скрытие имен: владельцы проектов выводятся инициалами.
+pub fn portfolio_html(
+    report: &PortfolioReport,
+    redaction: &ExportRedaction,
+    locale: ExportLocale,
+) -> String {
+    let owners = project_owners(report, redaction);
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>Portfolio</title></head>\n<body>\n<h1>Portfolio</h1>\n<p>{} - {}</p>\n",
+        locale.format_date(&report.window.date_start),
+        locale.format_date(&report.window.date_end)
+    );
+
+    html.push_str("<h2>Projects</h2>\n<table>\n<tr><th>Name</th><th>Status</th><th>Owner</th><th>Start</th><th>End</th><th>Allocated FTE</th><th>Unfilled FTE</th></tr>\n");
+    for (row, owner) in report.projects.iter().zip(&owners) {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{:?}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape(&row.name),
+            row.status,
+            escape(owner),
+            locale.format_date(&row.date_start),
+            locale.format_date(&row.date_end),
+            locale.format_number(row.allocated_fte),
+            locale.format_number(row.unfilled_fte)
+        ));
+    }
+    html.push_str("</table>\n");
+
+    let table = &report.skill_demand;
+    html.push_str("<h2>Demand by skill</h2>\n<table>\n<tr><th>Skill</th>");
+    for bucket in &table.buckets {
+        html.push_str(&format!(
+            "<th>{}</th>",
+            locale.format_date(&bucket.date_start)
+        ));
+    }
+    html.push_str("</tr>\n");
+    for row in &table.rows {
+        html.push_str(&format!("<tr><td>{}</td>", escape(&row.skill)));
+        for (i, shortage) in row.shortage().into_iter().enumerate() {
+            let class = if shortage > 0.0 {
+                " class=\"shortage\""
+            } else {
+                ""
+            };
+            html.push_str(&format!(
+                "<td{}>{} / {}</td>",
+                class,
+                locale.format_number(row.demand[i]),
+                locale.format_number(row.capacity[i])
+            ));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n</body>\n</html>\n");
+    html
+}
+
+/// Владельцы проектов отчета в порядке `report.projects`, без владельца - пустая строка.
+/// При `hide_resource_names` - инициалы
+pub(crate) fn project_owners(report: &PortfolioReport, redaction: &ExportRedaction) -> Vec<String> {
+    let mut pseudonyms = Pseudonyms::new(*redaction);
+    report
+        .projects
+        .iter()
+        .map(|row| {
+            row.owner
+                .as_deref()
+                .map(|owner| pseudonyms.person_name(owner))
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, TimeZone, Utc};
+
+    use super::*;
+    use crate::{
+        Project, ProjectContainer, ReportService, RolePlaceholder, SingleProjectContainer,
+        TimeWindow,
+    };
+
+    #[test]
+    fn test_portfolio_html_marks_shortage() -> anyhow::Result<()> {
+        let day = |d: u32| Utc.with_ymd_and_hms(2026, 2, d, 0, 0, 0).unwrap();
+        let mut project = Project::new(
+            "R&D <core>",
+            "",
+            Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap(),
+        )?;
+        project.add_role_placeholder(RolePlaceholder::new(
+            "qa",
+            1.0,
+            TimeWindow::new(day(2), day(9))?,
+        ))?;
+        let mut container = SingleProjectContainer::new();
+        container.add_project(project)?;
+
+        let report = ReportService::new(&container)
+            .portfolio(TimeWindow::new(day(2), day(16))?, TimeDelta::days(7))?;
+        let html = portfolio_html(&report, &ExportRedaction::default(), ExportLocale::Ru);
+        assert!(html.contains("<td>R&amp;D &lt;core&gt;</td>"));
+        assert!(html.contains("<th>02.02.2026</th><th>09.02.2026</th>"));
+        assert!(
+            html.contains("<td>qa</td><td class=\"shortage\">1,00 / 0,00</td><td>0,00 / 0,00</td>")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_portfolio_html_redacts_owner_names() -> anyhow::Result<()> {
+        let mut project = Project::new(
+            "Portal",
+            "",
+            Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap(),
+        )?;
+        project.set_owner(Some("Maria Petrova".into()));
+        let mut container = SingleProjectContainer::new();
+        container.add_project(project)?;
+        let window = TimeWindow::new(
+            Utc.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 2, 16, 0, 0, 0).unwrap(),
+        )?;
+        let report = ReportService::new(&container).portfolio(window, TimeDelta::days(7))?;
+
+        let plain = portfolio_html(&report, &ExportRedaction::default(), ExportLocale::En);
+        assert!(plain.contains("Maria Petrova"));
+        let html = portfolio_html(&report, &ExportRedaction::all(), ExportLocale::En);
+        assert!(!html.contains("Maria"));
+        assert!(html.contains("<td>M.P.</td>"));
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::export::ExportRedaction;
+
+/// Вид выгружаемого файла
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportKind {
+    TasksCsv,
+    TasksTsv,
+    HtmlReport,
+    /// Требует сборки с фичей `png-export`
+    GanttPng,
+}
+
+impl ExportKind {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportKind::TasksCsv => "csv",
+            ExportKind::TasksTsv => "tsv",
+            ExportKind::HtmlReport => "html",
+            ExportKind::GanttPng => "png",
+        }
+    }
+
+    /// Часть имени файла, по которой видно содержимое
+    pub fn file_stem(&self) -> &'static str {
+        match self {
+            ExportKind::TasksCsv | ExportKind::TasksTsv => "tasks",
+            ExportKind::HtmlReport => "report",
+            ExportKind::GanttPng => "gantt",
+        }
+    }
+}
+
+/// Региональный формат выгрузки: даты и десятичный разделитель в TSV и HTML.
+/// CSV всегда пишется в ISO-8601, чтобы его можно было загрузить обратно
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportLocale {
+    /// 2025-02-07, 1234.50
+    #[default]
+    En,
+    /// 07.02.2025, 1234,50
+    Ru,
+}
+
+impl ExportLocale {
+    pub fn format_date(&self, date: &DateTime<Utc>) -> String {
+        match self {
+            ExportLocale::En => date.format("%Y-%m-%d").to_string(),
+            ExportLocale::Ru => date.format("%d.%m.%Y").to_string(),
+        }
+    }
+
+    /// Число с двумя знаками после запятой
+    pub fn format_number(&self, value: f64) -> String {
+        let text = format!("{:.2}", value);
+        match self {
+            ExportLocale::En => text,
+            ExportLocale::Ru => text.replace('.', ","),
+        }
+    }
+}
+
+/// Настройка регулярной выгрузки: какие файлы и куда складывать
+/// run_on_save - выполнять профиль при каждом сохранении проекта в приложении
+/// redaction - какие данные скрывать в файлах профиля (например, для подрядчиков)
+/// locale - формат дат и чисел в файлах профиля
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ExportProfile {
+    pub name: String,
+    pub artifacts: Vec<ExportKind>,
+    pub destination_dir: PathBuf,
+    #[serde(default)]
+    pub run_on_save: bool,
+    #[serde(default)]
+    pub redaction: ExportRedaction,
+    #[serde(default)]
+    pub locale: ExportLocale,
+}
+
+/// Результат выгрузки одного файла профиля: путь записанного файла или текст ошибки
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportArtifactResult {
+    pub profile: String,
+    pub project_id: Uuid,
+    pub kind: ExportKind,
+    pub result: Result<PathBuf, String>,
+}
+
+impl ExportProfile {
+    pub fn new(
+        name: impl Into<String>,
+        artifacts: Vec<ExportKind>,
+        destination_dir: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            artifacts,
+            destination_dir: destination_dir.into(),
+            run_on_save: false,
+            redaction: ExportRedaction::default(),
+            locale: ExportLocale::default(),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::base_structures::Resource;
+
+/// Скрытие чувствительных данных при выгрузке (для подрядчиков и внешних участников)
+///
+/// hide_rates - не выводить ставки ресурсов, hide_costs - не выводить стоимость,
+/// hide_resource_names - заменять имена ресурсов инициалами.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExportRedaction {
+    pub hide_rates: bool,
+    pub hide_costs: bool,
+    pub hide_resource_names: bool,
+}
+
+impl ExportRedaction {
+    /// Скрыть все: ставки, стоимость и имена
+    pub fn all() -> Self {
+        Self {
+            hide_rates: true,
+            hide_costs: true,
+            hide_resource_names: true,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.hide_rates && !self.hide_costs && !self.hide_resource_names
+    }
+}
+
+/// Псевдонимы ресурсов в пределах одной выгрузки
+///
+/// Один и тот же ресурс (или человек, например владелец проекта) всегда получает один
+/// псевдоним, совпадающие инициалы различаются номером: "A.I.", "A.I. (2)".
+#[derive(Default)]
+pub(crate) struct Pseudonyms {
+    redaction: ExportRedaction,
+    names: HashMap<Uuid, String>,
+    people: HashMap<String, String>,
+    used: HashMap<String, usize>,
+}
+
+impl Pseudonyms {
+    pub(crate) fn new(redaction: ExportRedaction) -> Self {
+        Self {
+            redaction,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn resource_name(&mut self, resource: &Resource) -> String {
+        if !self.redaction.hide_resource_names {
+            return resource.name.clone();
+        }
+        if let Some(name) = self.names.get(&resource.id) {
+            return name.clone();
+        }
+        let name = self.alias(&resource.name);
+        self.names.insert(resource.id, name.clone());
+        name
+    }
+
+    /// Имя человека, не заведенного ресурсом (владелец проекта)
+    pub(crate) fn person_name(&mut self, person: &str) -> String {
+        if !self.redaction.hide_resource_names {
+            return person.to_string();
+        }
+        if let Some(name) = self.people.get(person) {
+            return name.clone();
+        }
+        let name = self.alias(person);
+        self.people.insert(person.to_string(), name.clone());
+        name
+    }
+
+    fn alias(&mut self, name: &str) -> String {
+        let initials = initials(name);
+        let counter = self.used.entry(initials.clone()).or_insert(0);
+        *counter += 1;
+        if *counter == 1 {
+            initials
+        } else {
+            format!("{} ({})", initials, counter)
+        }
+    }
+}
+
+fn initials(name: &str) -> String {
+    let initials: String = name
+        .split_whitespace()
+        .filter_map(|word| word.chars().next())
+        .flat_map(|c| c.to_uppercase().chain(std::iter::once('.')))
+        .collect();
+    if initials.is_empty() {
+        "?".to_string()
+    } else {
+        initials
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RateMeasure;
+
+    #[test]
+    fn test_pseudonyms_are_stable_and_unique() {
+        let anna = Resource::new("Anna Ivanova".into(), 100.0, RateMeasure::Hourly).unwrap();
+        let alex = Resource::new("alex ivanov".into(), 100.0, RateMeasure::Hourly).unwrap();
+        let mut pseudonyms = Pseudonyms::new(ExportRedaction::all());
+
+        assert_eq!(pseudonyms.resource_name(&anna), "A.I.");
+        assert_eq!(pseudonyms.resource_name(&alex), "A.I. (2)");
+        assert_eq!(pseudonyms.resource_name(&anna), "A.I.");
+        assert_eq!(pseudonyms.person_name("Anna Ivanova"), "A.I. (3)");
+        assert_eq!(pseudonyms.person_name("Anna Ivanova"), "A.I. (3)");
+
+        let mut plain = Pseudonyms::new(ExportRedaction::default());
+        assert_eq!(plain.resource_name(&anna), "Anna Ivanova");
+        assert_eq!(plain.person_name("Anna Ivanova"), "Anna Ivanova");
+    }
+}
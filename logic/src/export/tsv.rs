@@ -0,0 +1,146 @@
+use uuid::Uuid;
+
+use crate::{
+    BasicGettersForStructures, ProjectContainer, Task,
+    export::{
+        ExportLocale,
+        redaction::{ExportRedaction, Pseudonyms},
+    },
+};
+
+/// Экспорт выбранных задач в текст с разделителями-табуляциями (TSV)
+///
+/// Колонки: номер (T-142), имя, начало, окончание, статус, исполнители. Первая строка - заголовок.
+/// Порядок строк совпадает с порядком `task_ids`, неизвестные id пропускаются.
+/// Ставок и стоимости в выгрузке нет, из `redaction` учитывается только скрытие имен.
+/// Даты форматируются по `locale`.
+pub fn tasks_tsv<C: ProjectContainer>(
+    container: &C,
+    project_id: &Uuid,
+    task_ids: &[Uuid],
+    redaction: &ExportRedaction,
+    locale: ExportLocale,
+) -> anyhow::Result<String> {
+    let mut pseudonyms = Pseudonyms::new(*redaction);
+    let project = container
+        .get_project(project_id)
+        .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+
+    let mut lines = vec![["Id", "Name", "Start", "End", "Status", "Assignees"].join("\t")];
+    for task in task_ids.iter().filter_map(|id| project.tasks.get(id)) {
+        lines.push(
+            [
+                task.get_code().unwrap_or_default(),
+                tsv_cell(&task.name),
+                locale.format_date(task.get_date_start()),
+                locale.format_date(task.get_date_end()),
+                format!("{:?}", task.get_status()),
+                tsv_cell(&task_assignees(container, task, &mut pseudonyms).join(", ")),
+            ]
+            .join("\t"),
+        );
+    }
+    Ok(lines.join("\n"))
+}
+
+fn task_assignees<C: ProjectContainer>(
+    container: &C,
+    task: &Task,
+    pseudonyms: &mut Pseudonyms,
+) -> Vec<String> {
+    let pool = container.resource_pool();
+    let mut names: Vec<String> = task
+        .get_resource_allocations()
+        .iter()
+        .filter_map(|alloc_id| pool.get_allocation(alloc_id))
+        .filter_map(|allocation| pool.get_resource(allocation.get_resource_id()))
+        .map(|resource| pseudonyms.resource_name(resource))
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Табуляции и переводы строк внутри значения сломают таблицу - заменяем пробелами
+fn tsv_cell(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::{Project, RateMeasure, ResourceService, SingleProjectContainer, TaskService};
+
+    #[test]
+    fn test_tasks_tsv() -> anyhow::Result<()> {
+        let mut container = SingleProjectContainer::new();
+        let project = Project::new(
+            "Test",
+            "Desc",
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap(),
+        )?;
+        let project_id = *project.get_id();
+        container.add_project(project)?;
+
+        let resource_id = {
+            let mut resource_service = ResourceService::new(&mut container);
+            let resource = resource_service.create_resource("Max", 1000.0, RateMeasure::Hourly)?;
+            let resource_id = resource.id;
+            resource_service.add_resource(resource)?;
+            resource_id
+        };
+
+        let mut task_service = TaskService::new(&mut container);
+        let design = task_service.create_regular_task(
+            project_id,
+            "Design\tdraft".into(),
+            Utc.with_ymd_and_hms(2025, 2, 3, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 2, 7, 0, 0, 0).unwrap(),
+            None,
+        )?;
+        let build = task_service.create_regular_task(
+            project_id,
+            "Build".into(),
+            Utc.with_ymd_and_hms(2025, 2, 10, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 2, 14, 0, 0, 0).unwrap(),
+            None,
+        )?;
+        task_service.allocate_resource(
+            project_id,
+            *build.get_id(),
+            resource_id,
+            0.5,
+            None,
+            None,
+        )?;
+
+        let tsv = tasks_tsv(
+            &container,
+            &project_id,
+            &[*build.get_id(), Uuid::new_v4(), *design.get_id()],
+            &ExportRedaction::default(),
+            ExportLocale::En,
+        )?;
+        let lines: Vec<&str> = tsv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "Id\tName\tStart\tEnd\tStatus\tAssignees");
+        assert_eq!(lines[1], "T-2\tBuild\t2025-02-10\t2025-02-14\tNew\tMax");
+        assert_eq!(lines[2], "T-1\tDesign draft\t2025-02-03\t2025-02-07\tNew\t");
+
+        let redacted = tasks_tsv(
+            &container,
+            &project_id,
+            &[*build.get_id()],
+            &ExportRedaction::all(),
+            ExportLocale::Ru,
+        )?;
+        assert_eq!(
+            redacted.lines().nth(1),
+            Some("T-2\tBuild\t10.02.2025\t14.02.2025\tNew\tM.")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+use crate::{
+    PortfolioReport,
+    export::{ExportLocale, ExportRedaction, portfolio::project_owners},
+};
+
+/// Портфельный отчет в xlsx: лист "Portfolio" с проектами и лист "Skill demand"
+///
+/// Потребность по навыкам выгружается построчно (навык, корзина), чтобы ее было удобно
+/// сводить в таблице. Числа пишутся числами, даты - строками по `locale`. Как и в
+/// `portfolio_html`, из `redaction` учитывается скрытие имен владельцев проектов.
+pub fn portfolio_xlsx(
+    report: &PortfolioReport,
+    redaction: &ExportRedaction,
+    locale: ExportLocale,
+) -> anyhow::Result<Vec<u8>> {
+    let mut workbook = Workbook::new();
+    write_projects(
+        &mut workbook,
+        report,
+        &project_owners(report, redaction),
+        locale,
+    )?;
+    write_skill_demand(&mut workbook, report, locale)?;
+    Ok(workbook.save_to_buffer()?)
+}
+
+fn write_projects(
+    workbook: &mut Workbook,
+    report: &PortfolioReport,
+    owners: &[String],
+    locale: ExportLocale,
+) -> Result<(), XlsxError> {
+    let sheet = workbook.add_worksheet().set_name("Portfolio")?;
+    let header = [
+        "Name",
+        "Status",
+        "Owner",
+        "Start",
+        "End",
+        "Allocated FTE",
+        "Unfilled FTE",
+    ];
+    for (col, title) in header.iter().enumerate() {
+        sheet.write_string(0, col as u16, *title)?;
+    }
+    for (i, (row, owner)) in report.projects.iter().zip(owners).enumerate() {
+        let r = i as u32 + 1;
+        sheet.write_string(r, 0, &row.name)?;
+        sheet.write_string(r, 1, format!("{:?}", row.status))?;
+        sheet.write_string(r, 2, owner)?;
+        sheet.write_string(r, 3, locale.format_date(&row.date_start))?;
+        sheet.write_string(r, 4, locale.format_date(&row.date_end))?;
+        sheet.write_number(r, 5, row.allocated_fte)?;
+        sheet.write_number(r, 6, row.unfilled_fte)?;
+    }
+    Ok(())
+}
+
+fn write_skill_demand(
+    workbook: &mut Workbook,
+    report: &PortfolioReport,
+    locale: ExportLocale,
+) -> Result<(), XlsxError> {
+    let sheet = workbook.add_worksheet().set_name("Skill demand")?;
+    let header = [
+        "Skill", "Start", "End", "Demand", "Unfilled", "Capacity", "Shortage",
+    ];
+    for (col, title) in header.iter().enumerate() {
+        sheet.write_string(0, col as u16, *title)?;
+    }
+    let table = &report.skill_demand;
+    let mut r = 1;
+    for row in &table.rows {
+        for (i, shortage) in row.shortage().into_iter().enumerate() {
+            let bucket = &table.buckets[i];
+            sheet.write_string(r, 0, &row.skill)?;
+            sheet.write_string(r, 1, locale.format_date(&bucket.date_start))?;
+            sheet.write_string(r, 2, locale.format_date(&bucket.date_end))?;
+            sheet.write_number(r, 3, row.demand[i])?;
+            sheet.write_number(r, 4, row.unfilled[i])?;
+            sheet.write_number(r, 5, row.capacity[i])?;
+            sheet.write_number(r, 6, shortage)?;
+            r += 1;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, TimeZone, Utc};
+
+    use super::*;
+    use crate::{Project, ProjectContainer, ReportService, SingleProjectContainer, TimeWindow};
+
+    #[test]
+    fn test_portfolio_xlsx_writes_workbook() -> anyhow::Result<()> {
+        let mut project = Project::new(
+            "Portfolio",
+            "",
+            Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap(),
+        )?;
+        project.set_owner(Some("Maria Petrova".into()));
+        let mut container = SingleProjectContainer::new();
+        container.add_project(project)?;
+        let window = TimeWindow::new(
+            Utc.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 2, 16, 0, 0, 0).unwrap(),
+        )?;
+        let report = ReportService::new(&container).portfolio(window, TimeDelta::days(7))?;
+
+        let bytes = portfolio_xlsx(&report, &ExportRedaction::default(), ExportLocale::En)?;
+        // xlsx - zip-архив
+        assert_eq!(&bytes[..2], b"PK");
+        let bytes = portfolio_xlsx(&report, &ExportRedaction::all(), ExportLocale::En)?;
+        assert_eq!(&bytes[..2], b"PK");
+
+        // Листы сжаты, поэтому имена проверяем по строкам, которые уходят в книгу
+        assert_eq!(
+            project_owners(&report, &ExportRedaction::default()),
+            vec!["Maria Petrova".to_string()]
+        );
+        assert_eq!(
+            project_owners(&report, &ExportRedaction::all()),
+            vec!["M.P.".to_string()]
+        );
+        Ok(())
+    }
+}
@@ -2,13 +2,39 @@
 #![allow(unused_variables)]
 mod base_structures;
 pub mod cust_exceptions;
+pub mod date_input;
+pub mod export;
 mod services;
+pub mod templates;
+pub mod testing;
 
 pub use base_structures::BasicGettersForStructures;
-pub use base_structures::{Dependency, DependencyType};
 pub use base_structures::{
-    ExceptionPeriod, ExceptionType, Project, ProjectContainer, RateMeasure, SingleProjectContainer,
-    Task, TimeWindow,
+    ArchiveFilter, AvailabilityException, CONTAINER_FILE_VERSION, DEFAULT_TASK_PRIORITY,
+    ExceptionPeriod, ExceptionType, LocalResourcePool, MultiProjectContainer, Project,
+    ProjectBuilder, ProjectCalendar, ProjectContainer, ProjectFilter, ProjectProgress,
+    ProjectStats, ProjectStatus, ProjectUpdate, RateConfig, RateMeasure, ResourceConflict,
+    ResourceCosts, RolePlaceholder, SchedulePerformance, SharedProjectContainer,
+    SingleProjectContainer, SnapDirection, Task, TaskStatus, TimeWindow, ValidationPolicy,
+    merge_overlapping,
+};
+pub use base_structures::{
+    BaselineVariance, BudgetPeriod, BudgetPeriodStatus, BudgetStatus, CalendarDiff, CalendarKind,
+    DayKind, MergePolicy, TaskVariance,
+};
+pub use base_structures::{
+    ContainerEvent, CountryPreset, Dependency, DependencyType, DependencyViolation, EarnedValue,
+    IdGenerator, RandomIds, SequentialIds,
+};
+pub use base_structures::{DEFAULT_CURRENCY, convert_costs, normalize_currency};
+pub use base_structures::{
+    DuplicateAllocation, MergePreview, MergeReport, MergeResolution, MergeResolutions,
+    MergeStrategy, NameCollision, WindowConflict,
 };
 
-pub use services::{ResourceService, Scheduler, TaskService};
+pub use services::{
+    BulkResult, BulkUndo, CalendarService, MaintenanceService, MergeService, OverallocationReport,
+    PortfolioReport, PortfolioRow, ProjectService, ReportService, ResourceGroup, ResourcePage,
+    ResourceQuery, ResourceRow, ResourceService, ResourceSort, Scheduler, SkillDemandRow,
+    SkillDemandTable, SwapConflict, SwapReport, TaskService, UNSPECIFIED_SKILL,
+};
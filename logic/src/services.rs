@@ -1,7 +1,22 @@
+mod calendar_service;
+mod maintenance_service;
+mod merge_service;
+mod project_service;
+mod report_service;
+mod resource_query;
 mod resource_service;
 mod scheduler;
+mod skill_demand;
 mod task_service;
 
-pub use resource_service::ResourceService;
+pub use calendar_service::CalendarService;
+pub use maintenance_service::MaintenanceService;
+pub use merge_service::MergeService;
+pub use project_service::ProjectService;
+pub use report_service::{PortfolioReport, PortfolioRow, ReportService};
+pub use resource_query::{ResourceGroup, ResourcePage, ResourceQuery, ResourceRow, ResourceSort};
+pub use resource_service::{OverallocationReport, ResourceService, SwapConflict, SwapReport};
 pub use scheduler::Scheduler;
-pub use task_service::TaskService;
+pub(crate) use scheduler::total_float;
+pub use skill_demand::{SkillDemandRow, SkillDemandTable, UNSPECIFIED_SKILL};
+pub use task_service::{BulkResult, BulkUndo, TaskService};
@@ -0,0 +1,254 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use chrono::{NaiveDate, NaiveTime, Weekday};
+use uuid::Uuid;
+
+use crate::{
+    TimeWindow,
+    base_structures::{
+        BasicGettersForStructures, ContainerEvent, ProjectCalendar, ProjectContainer,
+    },
+};
+
+/// Изменение календаря проекта после создания
+///
+/// Календарь хранится в контейнере и копией в самом проекте, сервис меняет обе копии.
+/// Изменение, после которого в датах проекта не остается рабочих дней, отклоняется
+/// и не применяется.
+pub struct CalendarService<'a, C: ProjectContainer> {
+    container: &'a mut C,
+}
+
+impl<'a, C: ProjectContainer> CalendarService<'a, C> {
+    pub fn new(container: &'a mut C) -> Self {
+        Self { container }
+    }
+
+    pub fn add_holiday(&mut self, project_id: Uuid, date: NaiveDate) -> Result<()> {
+        self.update_calendar(project_id, |calendar| calendar.add_holiday(date))
+    }
+
+    pub fn remove_holiday(&mut self, project_id: Uuid, date: NaiveDate) -> Result<()> {
+        self.update_calendar(project_id, |calendar| calendar.remove_holiday(date))
+    }
+
+    /// Задать рабочие дни недели: прежние рабочие дни сохраняют свои часы
+    pub fn set_working_days(&mut self, project_id: Uuid, days: HashSet<Weekday>) -> Result<()> {
+        self.update_calendar(project_id, |calendar| calendar.set_working_days(days))
+    }
+
+    /// Рабочие часы одного дня недели, остальные дни не меняются. 0 - день становится нерабочим
+    pub fn set_hours_for(&mut self, project_id: Uuid, weekday: Weekday, hours: u32) -> Result<()> {
+        self.update_calendar(project_id, |calendar| {
+            calendar.set_hours_for(weekday, hours)
+        })
+    }
+
+    /// Одинаковое число часов для всех рабочих дней недели
+    pub fn set_working_hours(&mut self, project_id: Uuid, hours: u32) -> Result<()> {
+        self.update_calendar(project_id, |calendar| {
+            for weekday in calendar.get_working_days() {
+                calendar.set_hours_for(weekday, hours);
+            }
+        })
+    }
+
+    pub fn get_calendar(&self, project_id: Uuid) -> Result<&ProjectCalendar> {
+        self.container
+            .calendar(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Calendar for project {} not found", project_id))
+    }
+
+    /// Рабочие дни между датами включительно по календарю проекта
+    pub fn working_days_between(
+        &self,
+        project_id: Uuid,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<u32> {
+        let calendar = self.get_calendar(project_id)?;
+        let window = TimeWindow {
+            date_start: start.and_time(NaiveTime::MIN).and_utc(),
+            date_end: end.and_time(NaiveTime::MIN).and_utc(),
+        };
+        Ok(calendar.count_working_days(&window))
+    }
+
+    fn update_calendar(
+        &mut self,
+        project_id: Uuid,
+        change: impl FnOnce(&mut ProjectCalendar),
+    ) -> Result<()> {
+        let project = self
+            .container
+            .get_project(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Project with id {} not found", project_id))?;
+        let window = TimeWindow {
+            date_start: *project.get_date_start(),
+            date_end: *project.get_date_end(),
+        };
+        let mut updated = self.get_calendar(project_id)?.clone();
+        change(&mut updated);
+        updated.validate_for_window(&window).inspect_err(
+            |e| tracing::debug!(%project_id, error = %e, "calendar change rejected"),
+        )?;
+        tracing::debug!(%project_id, "calendar updated");
+
+        if let Some(calendar) = self.container.calendar_mut(&project_id) {
+            *calendar = updated.clone();
+        }
+        if let Some(project) = self.container.get_project_mut(&project_id) {
+            project.calendar = updated;
+        }
+        self.container
+            .emit(ContainerEvent::ProjectUpdated { project_id });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::{BasicGettersForStructures, Project, SingleProjectContainer};
+
+    #[test]
+    fn test_working_days_and_hours_are_validated() {
+        let mut container = SingleProjectContainer::new();
+        let project = Project::new(
+            "Calendar",
+            "",
+            Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap(),
+        )
+        .unwrap();
+        let project_id = *project.get_id();
+        container.add_project(project).unwrap();
+        container.take_events();
+
+        let mut service = CalendarService::new(&mut container);
+        let four_days = HashSet::from([Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu]);
+        service
+            .set_working_days(project_id, four_days.clone())
+            .unwrap();
+        service.set_working_hours(project_id, 10).unwrap();
+        let calendar = service.get_calendar(project_id).unwrap();
+        assert_eq!(calendar.get_working_days(), four_days);
+        assert_eq!(calendar.hours_for(Weekday::Mon), 10);
+        assert_eq!(calendar.hours_for(Weekday::Fri), 0);
+
+        // Изменения без рабочих дней отклоняются, календарь остается прежним
+        assert!(
+            service
+                .set_working_days(project_id, HashSet::new())
+                .is_err()
+        );
+        assert!(service.set_working_hours(project_id, 0).is_err());
+        assert_eq!(
+            service.get_calendar(project_id).unwrap().get_working_days(),
+            four_days
+        );
+        assert!(service.get_calendar(Uuid::new_v4()).is_err());
+
+        let events = container.take_events();
+        assert_eq!(
+            events,
+            vec![ContainerEvent::ProjectUpdated { project_id }; 2]
+        );
+        assert_eq!(
+            container
+                .get_project(&project_id)
+                .unwrap()
+                .calendar
+                .hours_for(Weekday::Tue),
+            10
+        );
+    }
+
+    #[test]
+    fn test_hours_for_one_weekday_keep_other_days() {
+        let mut container = SingleProjectContainer::new();
+        let project = Project::new(
+            "Short Friday",
+            "",
+            Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap(),
+        )
+        .unwrap();
+        let project_id = *project.get_id();
+        container.add_project(project).unwrap();
+
+        let mut service = CalendarService::new(&mut container);
+        service.set_hours_for(project_id, Weekday::Fri, 6).unwrap();
+        service.set_hours_for(project_id, Weekday::Mon, 10).unwrap();
+        let calendar = service.get_calendar(project_id).unwrap();
+        assert_eq!(calendar.hours_for(Weekday::Mon), 10);
+        assert_eq!(calendar.hours_for(Weekday::Tue), 8);
+        assert_eq!(calendar.hours_for(Weekday::Fri), 6);
+
+        // 0 часов - день недели перестает быть рабочим
+        service.set_hours_for(project_id, Weekday::Fri, 0).unwrap();
+        let calendar = service.get_calendar(project_id).unwrap();
+        assert!(!calendar.get_working_days().contains(&Weekday::Fri));
+        assert_eq!(calendar.hours_for(Weekday::Mon), 10);
+    }
+
+    #[test]
+    fn test_holiday_reduces_working_days() {
+        let mut container = SingleProjectContainer::new();
+        let project = Project::new(
+            "Calendar",
+            "",
+            Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap(),
+        )
+        .unwrap();
+        let project_id = *project.get_id();
+        container.add_project(project).unwrap();
+
+        let monday = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let friday = NaiveDate::from_ymd_opt(2026, 3, 6).unwrap();
+        let wednesday = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        let mut service = CalendarService::new(&mut container);
+        assert_eq!(
+            service
+                .working_days_between(project_id, monday, friday)
+                .unwrap(),
+            5
+        );
+
+        service.add_holiday(project_id, wednesday).unwrap();
+        service.add_holiday(project_id, monday).unwrap();
+        service.remove_holiday(project_id, monday).unwrap();
+        assert_eq!(
+            service.get_calendar(project_id).unwrap().holidays(),
+            vec![wednesday]
+        );
+        assert_eq!(
+            service
+                .working_days_between(project_id, monday, friday)
+                .unwrap(),
+            4
+        );
+        assert!(service.add_holiday(Uuid::new_v4(), wednesday).is_err());
+        // Копия календаря в проекте тоже изменилась
+        assert!(
+            !container
+                .get_project(&project_id)
+                .unwrap()
+                .calendar
+                .is_working_day(wednesday)
+        );
+
+        let mut service = CalendarService::new(&mut container);
+        service.remove_holiday(project_id, wednesday).unwrap();
+        assert_eq!(
+            service
+                .working_days_between(project_id, monday, friday)
+                .unwrap(),
+            5
+        );
+    }
+}
@@ -0,0 +1,323 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use uuid::Uuid;
+
+use crate::{
+    BasicGettersForStructures, Project, ProjectContainer,
+    cust_exceptions::AllocationErrors,
+    export::{ExportArtifactResult, ExportKind, ExportProfile, html_report, tasks_tsv},
+};
+
+/// Сервис регулярного обслуживания контейнера: плановые выгрузки и т.п.
+pub struct MaintenanceService<'a, C: ProjectContainer> {
+    container: &'a C,
+}
+
+impl<'a, C: ProjectContainer> MaintenanceService<'a, C> {
+    pub fn new(container: &'a C) -> Self {
+        Self { container }
+    }
+
+    /// Выполнить профиль выгрузки для всех проектов контейнера
+    ///
+    /// Имена файлов содержат проект, вид выгрузки и момент `now`; при совпадении имени
+    /// добавляется суффикс. Ошибка одного файла не прерывает остальные: результат
+    /// (путь или ошибка) возвращается для каждого файла. Ошибка - только если профиля нет.
+    pub fn run_export_profile(
+        &self,
+        name: &str,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<ExportArtifactResult>> {
+        let profile = self
+            .container
+            .export_profiles()
+            .iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| anyhow::anyhow!("Export profile '{}' not found", name))?;
+        Ok(self.run_profile(profile, now))
+    }
+
+    /// Выполнить все профили с признаком run_on_save
+    pub fn run_on_save_profiles(&self, now: DateTime<Utc>) -> Vec<ExportArtifactResult> {
+        self.container
+            .export_profiles()
+            .iter()
+            .filter(|p| p.run_on_save)
+            .flat_map(|profile| self.run_profile(profile, now))
+            .collect()
+    }
+
+    /// Назначения пула, задачи которых нет в их проекте (после импорта, слияния и т.п.)
+    ///
+    /// Возвращает пары (id назначения, ошибка) для всех проектов контейнера.
+    pub fn find_misplaced_allocations(&self) -> Vec<(Uuid, AllocationErrors)> {
+        let mut findings = vec![];
+        for project in self.container.list_projects() {
+            for allocation in self
+                .container
+                .resource_pool()
+                .get_project_allocations(project.get_id())
+            {
+                if !project.tasks.contains_key(allocation.get_task_id()) {
+                    findings.push((
+                        allocation.get_id(),
+                        AllocationErrors::TaskNotInProject {
+                            task_id: *allocation.get_task_id(),
+                            project_id: *project.get_id(),
+                        },
+                    ));
+                }
+            }
+        }
+        findings
+    }
+
+    fn run_profile(
+        &self,
+        profile: &ExportProfile,
+        now: DateTime<Utc>,
+    ) -> Vec<ExportArtifactResult> {
+        // Без каталога не получится ни один файл - ошибка попадает в результат каждого
+        let dir_error = std::fs::create_dir_all(&profile.destination_dir)
+            .err()
+            .map(|e| format!("{}: {}", profile.destination_dir.display(), e));
+
+        let mut results = vec![];
+        for project in self.container.list_projects() {
+            for kind in &profile.artifacts {
+                let result = match &dir_error {
+                    Some(e) => Err(e.clone()),
+                    None => {
+                        let path = unique_path(&profile.destination_dir, project, *kind, now);
+                        self.write_artifact(project, *kind, profile, &path)
+                            .map(|()| path)
+                            .map_err(|e| e.to_string())
+                    }
+                };
+                if let Err(e) = &result {
+                    tracing::warn!(
+                        profile = %profile.name,
+                        project_id = %project.get_id(),
+                        kind = ?kind,
+                        error = %e,
+                        "export artifact failed"
+                    );
+                }
+                results.push(ExportArtifactResult {
+                    profile: profile.name.clone(),
+                    project_id: *project.get_id(),
+                    kind: *kind,
+                    result,
+                });
+            }
+        }
+        results
+    }
+
+    fn write_artifact(
+        &self,
+        project: &Project,
+        kind: ExportKind,
+        profile: &ExportProfile,
+        path: &Path,
+    ) -> anyhow::Result<()> {
+        let (redaction, locale) = (&profile.redaction, profile.locale);
+        match kind {
+            ExportKind::TasksCsv => std::fs::write(
+                path,
+                project.export_tasks_csv(self.container.resource_pool(), redaction),
+            )?,
+            ExportKind::TasksTsv => {
+                let mut tasks = project.get_project_tasks();
+                tasks.sort_by(|a, b| {
+                    a.get_date_start()
+                        .cmp(b.get_date_start())
+                        .then_with(|| a.name.cmp(&b.name))
+                });
+                let ids: Vec<_> = tasks.iter().map(|t| *t.get_id()).collect();
+                std::fs::write(
+                    path,
+                    tasks_tsv(self.container, project.get_id(), &ids, redaction, locale)?,
+                )?
+            }
+            ExportKind::HtmlReport => std::fs::write(
+                path,
+                html_report(self.container, project.get_id(), redaction, locale)?,
+            )?,
+            #[cfg(feature = "png-export")]
+            ExportKind::GanttPng => {
+                let calendar = self
+                    .container
+                    .calendar(project.get_id())
+                    .ok_or_else(|| anyhow::anyhow!("Calendar for project not found"))?;
+                crate::export::png_gantt(project, calendar, redaction, path, 1280, 720)?
+            }
+            #[cfg(not(feature = "png-export"))]
+            ExportKind::GanttPng => anyhow::bail!("PNG export is not enabled in this build"),
+        }
+        Ok(())
+    }
+}
+
+/// Имя файла вида <проект>_<вид>_<YYYYMMDD-HHMMSS>.<ext>, при занятом имени - с суффиксом _1, _2...
+fn unique_path(dir: &Path, project: &Project, kind: ExportKind, now: DateTime<Utc>) -> PathBuf {
+    let project_name: String = project
+        .name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    let base = format!(
+        "{}_{}_{}",
+        project_name,
+        kind.file_stem(),
+        now.format("%Y%m%d-%H%M%S")
+    );
+    let mut path = dir.join(format!("{}.{}", base, kind.extension()));
+    let mut counter = 1;
+    while path.exists() {
+        path = dir.join(format!("{}_{}.{}", base, counter, kind.extension()));
+        counter += 1;
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::{SingleProjectContainer, TaskService};
+
+    fn setup() -> (SingleProjectContainer, PathBuf) {
+        let mut container = SingleProjectContainer::new();
+        let project = Project::new(
+            "Weekly report",
+            "",
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap(),
+        )
+        .unwrap();
+        let project_id = *project.get_id();
+        container.add_project(project).unwrap();
+        TaskService::new(&mut container)
+            .create_regular_task(
+                project_id,
+                "Design".into(),
+                Utc.with_ymd_and_hms(2025, 2, 3, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 2, 7, 0, 0, 0).unwrap(),
+                None,
+            )
+            .unwrap();
+        let dir = std::env::temp_dir().join(format!("rsproject_export_{}", uuid::Uuid::new_v4()));
+        (container, dir)
+    }
+
+    #[test]
+    fn test_find_misplaced_allocations() {
+        let (mut container, _) = setup();
+        let project_id = container.list_project_ids()[0];
+        let task_id = *container
+            .get_project(&project_id)
+            .unwrap()
+            .tasks
+            .keys()
+            .next()
+            .unwrap();
+        let mut resource_service = crate::ResourceService::new(&mut container);
+        let resource = resource_service
+            .create_resource("Anna", 100.0, crate::RateMeasure::Hourly)
+            .unwrap();
+        let resource_id = resource.id;
+        resource_service.add_resource(resource).unwrap();
+        let allocation_id = TaskService::new(&mut container)
+            .allocate_resource(project_id, task_id, resource_id, 0.5, None, None)
+            .unwrap();
+        assert!(
+            MaintenanceService::new(&container)
+                .find_misplaced_allocations()
+                .is_empty()
+        );
+
+        // Задача пропала из проекта в обход сервиса, назначение в пуле осталось
+        container
+            .get_project_mut(&project_id)
+            .unwrap()
+            .tasks
+            .remove(&task_id);
+        assert_eq!(
+            MaintenanceService::new(&container).find_misplaced_allocations(),
+            vec![(
+                allocation_id,
+                AllocationErrors::TaskNotInProject {
+                    task_id,
+                    project_id
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn test_run_export_profile_with_collisions() {
+        let (mut container, dir) = setup();
+        container.export_profiles_mut().push(ExportProfile::new(
+            "weekly",
+            vec![ExportKind::TasksCsv, ExportKind::TasksTsv],
+            &dir,
+        ));
+        let now = Utc.with_ymd_and_hms(2025, 2, 7, 18, 0, 0).unwrap();
+        let service = MaintenanceService::new(&container);
+
+        let written = |results: Vec<ExportArtifactResult>| -> Vec<PathBuf> {
+            results.into_iter().map(|r| r.result.unwrap()).collect()
+        };
+        let first = written(service.run_export_profile("weekly", now).unwrap());
+        let file_names: Vec<String> = first
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            file_names,
+            [
+                "Weekly_report_tasks_20250207-180000.csv",
+                "Weekly_report_tasks_20250207-180000.tsv"
+            ]
+        );
+        assert!(
+            std::fs::read_to_string(&first[0])
+                .unwrap()
+                .contains("Design")
+        );
+
+        // Повторный запуск в ту же секунду не перезаписывает файлы
+        let second = written(service.run_export_profile("weekly", now).unwrap());
+        assert!(second[0].ends_with("Weekly_report_tasks_20250207-180000_1.csv"));
+        assert!(service.run_export_profile("daily", now).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(not(feature = "png-export"))]
+    #[test]
+    fn test_run_export_profile_collects_failures() {
+        let (mut container, dir) = setup();
+        container.export_profiles_mut().push(ExportProfile::new(
+            "weekly",
+            vec![ExportKind::GanttPng, ExportKind::TasksCsv],
+            &dir,
+        ));
+        let now = Utc.with_ymd_and_hms(2025, 2, 7, 18, 0, 0).unwrap();
+
+        let results = MaintenanceService::new(&container)
+            .run_export_profile("weekly", now)
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].kind, ExportKind::GanttPng);
+        assert!(results[0].result.as_ref().unwrap_err().contains("PNG"));
+        // CSV выгружен, несмотря на ошибку PNG, и его путь не потерян
+        let csv = results[1].result.as_ref().unwrap();
+        assert!(csv.exists());
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
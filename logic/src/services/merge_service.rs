@@ -0,0 +1,599 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use uuid::Uuid;
+
+use crate::{
+    BasicGettersForStructures,
+    base_structures::{
+        AllocationRequest, ContainerEvent, DuplicateAllocation, MergePolicy, MergePreview,
+        MergeReport, MergeResolution, MergeResolutions, MergeStrategy, NameCollision, Project,
+        ProjectCalendar, ProjectContainer, Resource, ResourceAllocation, TimeWindow,
+        WindowConflict, fits_capacity,
+    },
+    cust_exceptions::MergeErrors,
+};
+
+/// Слияние проекта из другого файла (контейнера) в проект контейнера
+pub struct MergeService<'a, C: ProjectContainer> {
+    container: &'a mut C,
+}
+
+/// Слияние с примененными решениями; контейнер при подготовке не меняется
+struct MergePlan {
+    preview: MergePreview,
+    incoming: Project,
+    skipped_tasks: Vec<Uuid>,
+    replaced_tasks: Vec<Uuid>,
+    new_resources: Vec<Resource>,
+    updated_resources: Vec<Resource>,
+    requests: Vec<AllocationRequest>,
+    forced: Vec<ResourceAllocation>,
+    skipped_allocations: Vec<Uuid>,
+    calendar: Option<ProjectCalendar>,
+}
+
+impl<'a, C: ProjectContainer> MergeService<'a, C> {
+    pub fn new(container: &'a mut C) -> Self {
+        Self { container }
+    }
+
+    /// Конфликты слияния проекта `source_id` из `incoming` в проект `target_id`
+    ///
+    /// Учитывает уже выбранные решения: например, перегрузка ресурса пропадает,
+    /// если ресурс файла добавляется отдельно (Rename).
+    pub fn preview_merge<S: ProjectContainer>(
+        &self,
+        target_id: Uuid,
+        incoming: &S,
+        source_id: Uuid,
+        resolutions: &MergeResolutions,
+    ) -> Result<MergePreview> {
+        Ok(self
+            .plan(target_id, incoming, source_id, resolutions)?
+            .preview)
+    }
+
+    /// Слить проект `source_id` из `incoming` в проект `target_id` по решениям `resolutions`
+    ///
+    /// Задачи переносятся с сохранением id (совпавшие с задачами проекта получают новые),
+    /// ресурсы сопоставляются по имени, назначения
+    /// создаются заново в пуле контейнера. Слияние выполняется по принципу "все или ничего":
+    /// при ошибке проект, пул и календарь не меняются.
+    #[tracing::instrument(skip_all, fields(%target_id, %source_id))]
+    pub fn merge_from<S: ProjectContainer>(
+        &mut self,
+        target_id: Uuid,
+        incoming: &S,
+        source_id: Uuid,
+        resolutions: &MergeResolutions,
+    ) -> Result<MergeReport> {
+        let plan = self.plan(target_id, incoming, source_id, resolutions)?;
+        let target = self
+            .container
+            .get_project(&target_id)
+            .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+        let calendar = match &plan.calendar {
+            Some(calendar) => calendar.clone(),
+            None => self
+                .container
+                .calendar(&target_id)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("Calendar not found"))?,
+        };
+
+        let mut merged = target.clone();
+        let replaced_allocations: Vec<Uuid> = plan
+            .replaced_tasks
+            .iter()
+            .filter_map(|id| merged.remove_task_detached(id))
+            .flat_map(|task| task.get_resource_allocations().clone())
+            .collect();
+        let strategy = MergeStrategy {
+            task_names: NameCollision::Rename,
+            window: WindowConflict::Expand,
+            allocations: DuplicateAllocation::Reject,
+        };
+        let mut report = merged.merge(plan.incoming, strategy)?;
+        merged.calendar = calendar.clone();
+
+        let pool = self.container.resource_pool_mut();
+        let snapshot: Vec<ResourceAllocation> = pool
+            .get_project_allocations(&target_id)
+            .into_iter()
+            .cloned()
+            .collect();
+        let originals: Vec<Resource> = plan
+            .updated_resources
+            .iter()
+            .filter_map(|r| pool.get_resource(&r.id).cloned())
+            .collect();
+        for resource in &plan.new_resources {
+            pool.add_resource(resource.clone())?;
+        }
+        for resource in &plan.updated_resources {
+            if let Some(existing) = pool.get_mut_resource_by_uuid(resource.id) {
+                *existing = resource.clone();
+            }
+        }
+        // Устаревшие ссылки задач на назначения пропускаем
+        for allocation_id in replaced_allocations {
+            pool.deallocate(allocation_id).ok();
+        }
+        if let Err(e) = pool.allocate_batch(&plan.requests, &calendar) {
+            pool.restore_project_allocations(&target_id, snapshot);
+            for resource in originals {
+                if let Some(existing) = pool.get_mut_resource_by_uuid(resource.id) {
+                    *existing = resource;
+                }
+            }
+            for resource in &plan.new_resources {
+                pool.remove_resource(&resource.id)?;
+            }
+            return Err(e);
+        }
+        if !plan.forced.is_empty() {
+            let mut allocations: Vec<ResourceAllocation> = pool
+                .get_project_allocations(&target_id)
+                .into_iter()
+                .cloned()
+                .collect();
+            report.forced_allocations = plan.forced.iter().map(|a| a.get_id()).collect();
+            allocations.extend(plan.forced);
+            pool.restore_project_allocations(&target_id, allocations);
+        }
+
+        if plan.calendar.is_some()
+            && let Some(existing) = self.container.calendar_mut(&target_id)
+        {
+            *existing = calendar;
+        }
+        if let Some(project) = self.container.get_project_mut(&target_id) {
+            *project = merged;
+        }
+
+        report.skipped_tasks = plan.skipped_tasks;
+        report.replaced_tasks = plan.replaced_tasks;
+        report.added_resources = plan.new_resources.iter().map(|r| r.id).collect();
+        report.skipped_allocations.extend(plan.skipped_allocations);
+        tracing::info!(
+            added = report.added_tasks.len(),
+            skipped = report.skipped_tasks.len(),
+            replaced = report.replaced_tasks.len(),
+            "project merged from file"
+        );
+        self.container.emit(ContainerEvent::ProjectUpdated {
+            project_id: target_id,
+        });
+        Ok(report)
+    }
+
+    fn plan<S: ProjectContainer>(
+        &self,
+        target_id: Uuid,
+        incoming: &S,
+        source_id: Uuid,
+        resolutions: &MergeResolutions,
+    ) -> Result<MergePlan> {
+        let target = self
+            .container
+            .get_project(&target_id)
+            .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+        let source = incoming
+            .get_project(&source_id)
+            .ok_or_else(|| anyhow::anyhow!("Project {} not found in merged file", source_id))?;
+        let pool = self.container.resource_pool();
+        let source_pool = incoming.resource_pool();
+        let mut preview = MergePreview::default();
+        let mut project = source.clone();
+
+        // Задачи. Если файл - копия этого же проекта, совпавшие id задач заменяются новыми
+        let id_map: HashMap<Uuid, Uuid> = project
+            .tasks
+            .keys()
+            .filter(|id| target.tasks.contains_key(id))
+            .map(|id| (*id, Uuid::new_v4()))
+            .collect();
+        if !id_map.is_empty() {
+            project.tasks = std::mem::take(&mut project.tasks)
+                .into_values()
+                .map(|mut task| {
+                    task.remap_ids(&id_map);
+                    (*task.get_id(), task)
+                })
+                .collect();
+        }
+        let mut task_names: Vec<String> = project
+            .tasks
+            .values()
+            .filter(|t| target.tasks.values().any(|mine| mine.name == t.name))
+            .map(|t| t.name.clone())
+            .collect();
+        task_names.sort();
+        task_names.dedup();
+        let mut skipped_tasks = vec![];
+        let mut replaced_tasks = vec![];
+        for name in &task_names {
+            match resolutions.task(name) {
+                MergeResolution::Rename => {}
+                MergeResolution::TakeTheirs => replaced_tasks.extend(
+                    target
+                        .tasks
+                        .values()
+                        .filter(|t| &t.name == name)
+                        .map(|t| *t.get_id()),
+                ),
+                MergeResolution::KeepMine | MergeResolution::Skip => {
+                    let ids: Vec<Uuid> = project
+                        .tasks
+                        .values()
+                        .filter(|t| &t.name == name)
+                        .map(|t| *t.get_id())
+                        .collect();
+                    for id in ids {
+                        project.remove_task_detached(&id);
+                        skipped_tasks.push(id);
+                    }
+                }
+            }
+        }
+        preview.task_names = task_names;
+
+        // Ресурсы: ресурс файла -> ресурс пула, None - его назначения не переносятся
+        let mut source_allocations = source_pool.get_project_allocations(&source_id);
+        source_allocations.sort_by_key(|a| (a.get_time_window().date_start, a.get_id()));
+        let mut taken_names: HashSet<String> =
+            pool.resources().iter().map(|r| r.name.clone()).collect();
+        let mut resource_map: HashMap<Uuid, Option<Uuid>> = HashMap::new();
+        let mut new_resources: Vec<Resource> = vec![];
+        let mut updated_resources = vec![];
+        for allocation in &source_allocations {
+            let resource_id = *allocation.get_resource_id();
+            if resource_map.contains_key(&resource_id) {
+                continue;
+            }
+            let theirs = source_pool.get_resource(&resource_id).ok_or_else(|| {
+                anyhow::anyhow!("Resource {} not found in merged file", resource_id)
+            })?;
+            let mine = pool.resources().into_iter().find(|r| r.name == theirs.name);
+            let mapped = match mine {
+                None => {
+                    let mut resource = theirs.clone();
+                    if pool.get_resource(&resource.id).is_some() {
+                        resource.id = Uuid::new_v4();
+                    }
+                    taken_names.insert(resource.name.clone());
+                    new_resources.push(resource);
+                    new_resources.last().map(|r| r.id)
+                }
+                Some(mine) => {
+                    preview.resources.push(theirs.name.clone());
+                    match resolutions.resource(&theirs.name) {
+                        MergeResolution::KeepMine => Some(mine.id),
+                        MergeResolution::TakeTheirs => {
+                            let mut resource = theirs.clone();
+                            resource.id = mine.id;
+                            updated_resources.push(resource);
+                            Some(mine.id)
+                        }
+                        MergeResolution::Rename => {
+                            let mut resource = theirs.clone();
+                            resource.id = Uuid::new_v4();
+                            resource.name = (2..)
+                                .map(|n| format!("{} ({})", theirs.name, n))
+                                .find(|candidate| !taken_names.contains(candidate))
+                                .unwrap();
+                            taken_names.insert(resource.name.clone());
+                            new_resources.push(resource);
+                            new_resources.last().map(|r| r.id)
+                        }
+                        MergeResolution::Skip => None,
+                    }
+                }
+            };
+            resource_map.insert(resource_id, mapped);
+        }
+        preview.resources.sort();
+
+        // Календарь
+        let my_calendar = self
+            .container
+            .calendar(&target_id)
+            .ok_or_else(|| anyhow::anyhow!("Calendar not found"))?;
+        let their_calendar = incoming.calendar(&source_id).unwrap_or(&source.calendar);
+        preview.calendar = my_calendar.diff(their_calendar);
+        let calendar = match resolutions.calendar() {
+            MergeResolution::TakeTheirs if !preview.calendar.is_empty() => {
+                let mut calendar = my_calendar.clone();
+                calendar.merge(their_calendar, MergePolicy::PreferOther);
+                Some(calendar)
+            }
+            MergeResolution::Rename => return Err(unsupported("calendar")),
+            _ => None,
+        };
+
+        // Назначения: создаются заново с новыми id, ссылки задач переписываются
+        for task in project.tasks.values_mut() {
+            for allocation_id in task.get_resource_allocations().clone() {
+                task.remove_resource_allocation(&allocation_id);
+            }
+        }
+        let mut placed: HashMap<Uuid, Vec<(TimeWindow, f64)>> = HashMap::new();
+        let mut requests = vec![];
+        let mut forced = vec![];
+        let mut skipped_allocations = vec![];
+        for allocation in source_allocations {
+            let task_id = id_map
+                .get(allocation.get_task_id())
+                .copied()
+                .unwrap_or(*allocation.get_task_id());
+            let (Some(task), Some(Some(resource_id))) = (
+                project.tasks.get_mut(&task_id),
+                resource_map.get(allocation.get_resource_id()).copied(),
+            ) else {
+                skipped_allocations.push(allocation.get_id());
+                continue;
+            };
+            let window = *allocation.get_time_window();
+            let rate = *allocation.get_engagement_rate();
+            let slots = placed.entry(resource_id).or_insert_with(|| {
+                pool.counted_allocations(&resource_id)
+                    .into_iter()
+                    .filter(|a| {
+                        !(a.get_project_id() == &target_id
+                            && replaced_tasks.contains(a.get_task_id()))
+                    })
+                    .map(|a| (*a.get_time_window(), *a.get_engagement_rate()))
+                    .collect()
+            });
+            let allocation_id = Uuid::new_v4();
+            let request = AllocationRequest::new(resource_id, task_id, target_id, rate, window)?
+                .with_id(allocation_id)
+                .with_priority(allocation.get_priority());
+            if fits_capacity(&window, rate, slots) {
+                requests.push(request);
+            } else {
+                let name = new_resources
+                    .iter()
+                    .chain(pool.get_resource(&resource_id))
+                    .find(|r| r.id == resource_id)
+                    .map(|r| r.name.clone())
+                    .unwrap_or_default();
+                if !preview.overallocations.contains(&name) {
+                    preview.overallocations.push(name.clone());
+                }
+                match resolutions.overallocation(&name) {
+                    MergeResolution::TakeTheirs => forced.push(ResourceAllocation::new(request)),
+                    MergeResolution::Rename => return Err(unsupported("overallocation")),
+                    MergeResolution::KeepMine | MergeResolution::Skip => {
+                        skipped_allocations.push(allocation.get_id());
+                        continue;
+                    }
+                }
+            }
+            slots.push((window, rate));
+            task.set_resource_allocation(allocation_id);
+        }
+        preview.overallocations.sort();
+
+        Ok(MergePlan {
+            preview,
+            incoming: project,
+            skipped_tasks,
+            replaced_tasks,
+            new_resources,
+            updated_resources,
+            requests,
+            forced,
+            skipped_allocations,
+            calendar,
+        })
+    }
+}
+
+/// Rename применим только к задачам и ресурсам
+fn unsupported(item: &str) -> anyhow::Error {
+    MergeErrors::UnsupportedResolution {
+        item: item.to_string(),
+        resolution: "rename".to_string(),
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+
+    use super::*;
+    use crate::{RateMeasure, ResourceService, SingleProjectContainer, TaskService};
+
+    fn day(d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 2, d, 0, 0, 0).unwrap()
+    }
+
+    /// Контейнер с проектом и задачами: (задача, ресурс, занятость)
+    fn container(name: &str, tasks: &[(&str, &str, f64)]) -> (SingleProjectContainer, Uuid) {
+        let mut container = SingleProjectContainer::new();
+        let project = Project::new(name, "", day(1), day(28)).unwrap();
+        let project_id = *project.get_id();
+        container.add_project(project).unwrap();
+        for (task_name, resource_name, rate) in tasks {
+            let resource_id = match container
+                .resource_pool()
+                .resources()
+                .into_iter()
+                .find(|r| &r.name == resource_name)
+            {
+                Some(resource) => resource.id,
+                None => {
+                    let mut service = ResourceService::new(&mut container);
+                    let resource = service
+                        .create_resource(*resource_name, 100.0, RateMeasure::Hourly)
+                        .unwrap();
+                    let id = resource.id;
+                    service.add_resource(resource).unwrap();
+                    id
+                }
+            };
+            let mut task_service = TaskService::new(&mut container);
+            let task = task_service
+                .create_regular_task(project_id, task_name.to_string(), day(2), day(13), None)
+                .unwrap();
+            task_service
+                .allocate_resource(project_id, *task.get_id(), resource_id, *rate, None, None)
+                .unwrap();
+        }
+        (container, project_id)
+    }
+
+    fn setup() -> (SingleProjectContainer, Uuid, SingleProjectContainer, Uuid) {
+        let (mine, target_id) =
+            container("Mine", &[("Design", "Anna", 0.5), ("Build", "Ivan", 0.5)]);
+        let (mut theirs, source_id) =
+            container("Theirs", &[("Design", "Anna", 1.0), ("QA", "Boris", 0.5)]);
+        theirs
+            .calendar_mut(&source_id)
+            .unwrap()
+            .add_holiday(NaiveDate::from_ymd_opt(2026, 2, 23).unwrap());
+        (mine, target_id, theirs, source_id)
+    }
+
+    fn task_names(container: &SingleProjectContainer, project_id: &Uuid) -> Vec<String> {
+        let mut names: Vec<String> = container
+            .get_project(project_id)
+            .unwrap()
+            .tasks
+            .values()
+            .map(|t| t.name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_merge_preview_and_defaults() {
+        let (mut mine, target_id, theirs, source_id) = setup();
+        let resolutions = MergeResolutions::default();
+
+        let preview = MergeService::new(&mut mine)
+            .preview_merge(target_id, &theirs, source_id, &resolutions)
+            .unwrap();
+        assert_eq!(preview.task_names, vec!["Design"]);
+        assert_eq!(preview.resources, vec!["Anna"]);
+        assert_eq!(preview.overallocations, vec!["Anna"]);
+        assert_eq!(
+            preview.calendar.holidays_only_right,
+            vec![NaiveDate::from_ymd_opt(2026, 2, 23).unwrap()]
+        );
+
+        // По умолчанию: задача переименована, перегружающее назначение не перенесено,
+        // новый ресурс добавлен, календарь не меняется
+        let report = MergeService::new(&mut mine)
+            .merge_from(target_id, &theirs, source_id, &resolutions)
+            .unwrap();
+        assert_eq!(
+            task_names(&mine, &target_id),
+            vec!["Build", "Design", "Design (2)", "QA"]
+        );
+        assert_eq!(report.renamed_tasks.len(), 1);
+        assert_eq!(report.skipped_allocations.len(), 1);
+        assert_eq!(report.added_resources.len(), 1);
+        assert_eq!(mine.resource_pool().resource_count(), 3);
+        assert_eq!(
+            mine.resource_pool()
+                .get_project_allocations(&target_id)
+                .len(),
+            3
+        );
+        assert!(mine.resource_pool().find_conflicts().is_empty());
+        assert!(
+            mine.calendar(&target_id)
+                .unwrap()
+                .diff(&ProjectCalendar::default())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_merge_with_resolutions_from_json() {
+        let (mut mine, target_id, theirs, source_id) = setup();
+        let resolutions: MergeResolutions = serde_json::from_str(
+            r#"{"tasks": {"Design": "take_theirs"}, "resources": {"Anna": "rename"}, "calendar": "take_theirs"}"#,
+        )
+        .unwrap();
+
+        let preview = MergeService::new(&mut mine)
+            .preview_merge(target_id, &theirs, source_id, &resolutions)
+            .unwrap();
+        // Ресурс файла добавляется отдельно, перегрузки нет
+        assert!(preview.overallocations.is_empty());
+
+        let report = MergeService::new(&mut mine)
+            .merge_from(target_id, &theirs, source_id, &resolutions)
+            .unwrap();
+        assert_eq!(task_names(&mine, &target_id), vec!["Build", "Design", "QA"]);
+        assert_eq!(report.replaced_tasks.len(), 1);
+        let pool = mine.resource_pool();
+        assert!(pool.resources().iter().any(|r| r.name == "Anna (2)"));
+        // Назначение замененной задачи снято, назначения файла созданы в этом проекте
+        assert_eq!(pool.get_project_allocations(&target_id).len(), 3);
+        let calendar = mine.calendar(&target_id).unwrap();
+        assert!(!calendar.is_working_day(NaiveDate::from_ymd_opt(2026, 2, 23).unwrap()));
+        assert!(
+            !mine
+                .get_project(&target_id)
+                .unwrap()
+                .calendar
+                .is_working_day(NaiveDate::from_ymd_opt(2026, 2, 23).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_merge_forced_overallocation_and_rejected_resolution() {
+        let (mut mine, target_id, theirs, source_id) = setup();
+        let mut resolutions = MergeResolutions::default();
+        resolutions
+            .overallocations
+            .insert("Anna".into(), MergeResolution::Rename);
+        assert!(
+            MergeService::new(&mut mine)
+                .merge_from(target_id, &theirs, source_id, &resolutions)
+                .is_err()
+        );
+        assert_eq!(task_names(&mine, &target_id), vec!["Build", "Design"]);
+        assert_eq!(mine.resource_pool().resource_count(), 2);
+
+        resolutions
+            .overallocations
+            .insert("Anna".into(), MergeResolution::TakeTheirs);
+        let report = MergeService::new(&mut mine)
+            .merge_from(target_id, &theirs, source_id, &resolutions)
+            .unwrap();
+        assert_eq!(report.forced_allocations.len(), 1);
+        assert_eq!(mine.resource_pool().find_conflicts().len(), 1);
+    }
+
+    #[test]
+    fn test_merge_copy_of_same_project() {
+        let (mut mine, target_id, _, _) = setup();
+        let copy = SingleProjectContainer::from_versioned_json(&mine.to_versioned_json().unwrap())
+            .unwrap();
+
+        let report = MergeService::new(&mut mine)
+            .merge_from(target_id, &copy, target_id, &MergeResolutions::default())
+            .unwrap();
+        assert_eq!(
+            task_names(&mine, &target_id),
+            vec!["Build", "Build (2)", "Design", "Design (2)"]
+        );
+        // Ресурсы сопоставлены по имени, по 0.5 + 0.5 - без перегрузки
+        assert!(report.skipped_allocations.is_empty());
+        assert_eq!(mine.resource_pool().resource_count(), 2);
+        assert_eq!(
+            mine.resource_pool()
+                .get_project_allocations(&target_id)
+                .len(),
+            4
+        );
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    BasicGettersForStructures,
+    base_structures::{
+        BudgetStatus, ContainerEvent, EarnedValue, ProjectContainer, ProjectProgress, ProjectUpdate,
+    },
+    cust_exceptions::ContainerErrors,
+};
+
+pub struct ProjectService<'a, C: ProjectContainer> {
+    container: &'a mut C,
+}
+
+impl<'a, C: ProjectContainer> ProjectService<'a, C> {
+    pub fn new(container: &'a mut C) -> Self {
+        Self { container }
+    }
+
+    /// Изменение свойств проекта с проверкой дат задач
+    ///
+    /// Новое имя, как и при добавлении проекта, должно быть уникальным без учета регистра
+    #[tracing::instrument(skip_all, fields(%project_id))]
+    pub fn update_project(&mut self, project_id: Uuid, changes: ProjectUpdate) -> Result<()> {
+        if let Some(name) = &changes.name
+            && self
+                .container
+                .get_project_by_name_ignore_case(name)
+                .is_some_and(|p| *p.get_id() != project_id)
+        {
+            tracing::debug!(%name, "project rename rejected: name taken");
+            return Err(ContainerErrors::DuplicateProjectName { name: name.clone() }.into());
+        }
+        let project = self
+            .container
+            .get_project_mut(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+        project
+            .update(changes)
+            .inspect_err(|e| tracing::debug!(error = %e, "project update rejected"))?;
+        self.container
+            .emit(ContainerEvent::ProjectUpdated { project_id });
+        Ok(())
+    }
+
+    /// Выполнение проекта по статусам задач и ожидаемое по графику
+    pub fn progress(&self, project_id: Uuid, as_of: DateTime<Utc>) -> Result<ProjectProgress> {
+        let project = self
+            .container
+            .get_project(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+        let calendar = self
+            .container
+            .calendar(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Calendar not found"))?;
+        Ok(project.progress(as_of, calendar))
+    }
+
+    /// Бюджет проекта против стоимости его назначений
+    pub fn budget_status(&self, project_id: Uuid) -> Result<BudgetStatus> {
+        let project = self
+            .container
+            .get_project(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+        let calendar = self
+            .container
+            .calendar(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Calendar not found"))?;
+        project.budget_status(calendar, self.container.resource_pool())
+    }
+
+    /// Освоенный объем проекта на дату по календарю и пулу контейнера, по валютам
+    pub fn earned_value(
+        &self,
+        project_id: Uuid,
+        as_of: DateTime<Utc>,
+    ) -> Result<HashMap<String, EarnedValue>> {
+        let project = self
+            .container
+            .get_project(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+        let calendar = self
+            .container
+            .calendar(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Calendar not found"))?;
+        project.earned_value(as_of, calendar, self.container.resource_pool())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::{
+        MultiProjectContainer, Project, SingleProjectContainer, TaskService,
+        cust_exceptions::ProjectUpdateErrors,
+    };
+
+    #[test]
+    fn test_update_project_rejects_duplicate_name() -> Result<()> {
+        let mut container = MultiProjectContainer::new();
+        let mut ids = vec![];
+        for name in ["Website", "Mobile"] {
+            let project = Project::new(
+                name,
+                "",
+                Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap(),
+            )?;
+            ids.push(*project.get_id());
+            container.add_project(project)?;
+        }
+        let rename = |name: &str| ProjectUpdate {
+            name: Some(name.into()),
+            ..Default::default()
+        };
+
+        let mut service = ProjectService::new(&mut container);
+        let error = service
+            .update_project(ids[1], rename("WEBSITE"))
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ContainerErrors>(),
+            Some(&ContainerErrors::DuplicateProjectName {
+                name: "WEBSITE".into()
+            })
+        );
+        // Смена регистра собственного имени - не конфликт
+        service.update_project(ids[0], rename("WEBSITE"))?;
+        assert_eq!(
+            container
+                .get_project_by_name_ignore_case("website")
+                .map(|p| *p.get_id()),
+            Some(ids[0])
+        );
+        assert_eq!(container.get_project(&ids[1]).unwrap().name, "Mobile");
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_project_rejects_shrink() -> Result<()> {
+        let mut container = SingleProjectContainer::new();
+        let project = Project::new(
+            "Test",
+            "Desc",
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap(),
+        )?;
+        let project_id = *project.get_id();
+        container.add_project(project)?;
+        let task = TaskService::new(&mut container).create_regular_task(
+            project_id,
+            "Task".into(),
+            Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 2, 15, 0, 0, 0).unwrap(),
+            None,
+        )?;
+
+        let mut project_service = ProjectService::new(&mut container);
+        let error = project_service
+            .update_project(
+                project_id,
+                ProjectUpdate {
+                    date_start: Some(Utc.with_ymd_and_hms(2025, 2, 10, 0, 0, 0).unwrap()),
+                    ..Default::default()
+                },
+            )
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ProjectUpdateErrors>(),
+            Some(&ProjectUpdateErrors::TasksOutsideProject {
+                task_ids: vec![*task.get_id()]
+            })
+        );
+
+        project_service.update_project(
+            project_id,
+            ProjectUpdate {
+                description: Some("New desc".into()),
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(
+            container.get_project(&project_id).unwrap().description,
+            "New desc"
+        );
+        assert!(
+            ProjectService::new(&mut container)
+                .update_project(Uuid::new_v4(), ProjectUpdate::default())
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_budget_status() -> Result<()> {
+        use crate::{
+            RateConfig, RateMeasure, ResourceService, base_structures::Resource,
+            cust_exceptions::BudgetErrors,
+        };
+
+        let day = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap();
+        let mut container = SingleProjectContainer::new();
+        let project = Project::new("Budget", "", day(2), day(31))?;
+        let project_id = *project.get_id();
+        container.add_project(project)?;
+        // Пн 2 - Пт 6: 5 рабочих дней по 8 часов
+        let task_id = *TaskService::new(&mut container)
+            .create_regular_task(project_id, "Build".into(), day(2), day(6), None)?
+            .get_id();
+
+        // 800 в день = 100 в час, 35200 в месяц = 35200 / 22 / 8 = 200 в час
+        let daily = Resource::new("Daily".into(), 800.0, RateMeasure::Daily)?;
+        let monthly = Resource::new("Monthly".into(), 35200.0, RateMeasure::Monthly)?;
+        let (daily_id, monthly_id) = (daily.id, monthly.id);
+        let mut resource_service = ResourceService::new(&mut container);
+        resource_service.add_resource(daily)?;
+        resource_service.add_resource(monthly)?;
+        let mut task_service = TaskService::new(&mut container);
+        task_service.allocate_resource(project_id, task_id, daily_id, 0.5, None, None)?;
+        let monthly_allocation =
+            task_service.allocate_resource(project_id, task_id, monthly_id, 1.0, None, None)?;
+
+        let status = ProjectService::new(&mut container).budget_status(project_id)?;
+        assert_eq!(status.budget, None);
+        assert_eq!(status.remaining, None);
+        assert!(!status.over_budget);
+        // 0.5 * 40 * 100 + 1.0 * 40 * 200
+        assert_eq!(status.committed_cost, 10000.0);
+        // Бюджет и статистика проекта считают стоимость одинаково
+        let project = container.get_project(&project_id).unwrap();
+        let calendar = container.calendar(&project_id).unwrap();
+        let stats = project.stats(container.resource_pool(), calendar)?;
+        assert_eq!(stats.total_cost["RUB"], status.committed_cost);
+
+        // Месячная ставка делится на дни месяца из коэффициентов проекта: 35200 / 20 * 5
+        container.get_project_mut(&project_id).unwrap().rate_config = RateConfig {
+            hours_per_day: 8.0,
+            days_per_month: 20.0,
+        };
+        let status = ProjectService::new(&mut container).budget_status(project_id)?;
+        assert_eq!(status.committed_cost, 2000.0 + 8800.0);
+        let project = container.get_project(&project_id).unwrap();
+        let calendar = container.calendar(&project_id).unwrap();
+        let stats = project.stats(container.resource_pool(), calendar)?;
+        assert_eq!(stats.total_cost["RUB"], status.committed_cost);
+        container.get_project_mut(&project_id).unwrap().rate_config = RateConfig::default();
+
+        let project = container.get_project_mut(&project_id).unwrap();
+        assert_eq!(
+            project.set_budget(Some(0.0), "RUB"),
+            Err(BudgetErrors::NonPositiveBudget { amount: 0.0 })
+        );
+        project.set_budget(Some(9000.0), "rub")?;
+        assert_eq!(project.get_budget_currency(), "RUB");
+        let status = ProjectService::new(&mut container).budget_status(project_id)?;
+        assert_eq!(status.remaining, Some(-1000.0));
+        assert!(status.over_budget);
+
+        // Назначение удаленного ресурса не оценивается
+        ResourceService::new(&mut container).delete_resource(monthly_id)?;
+        let status = ProjectService::new(&mut container).budget_status(project_id)?;
+        assert_eq!(status.committed_cost, 2000.0);
+        assert_eq!(status.remaining, Some(7000.0));
+        assert!(!status.over_budget);
+        assert_eq!(status.unpriced, vec![monthly_allocation]);
+        Ok(())
+    }
+}
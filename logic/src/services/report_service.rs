@@ -0,0 +1,315 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use chrono::{DateTime, TimeDelta, Utc};
+use uuid::Uuid;
+
+use crate::{
+    BasicGettersForStructures, Project, ProjectContainer, ProjectStatus, TimeWindow,
+    services::skill_demand::{
+        SkillDemandRow, SkillDemandTable, covered_fraction, skill_shares, split_buckets,
+    },
+};
+
+/// Строка портфельного отчета по одному проекту
+/// allocated_fte / unfilled_fte - средняя занятость за окно отчета по назначениям
+/// и по ролям без ресурсов
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortfolioRow {
+    pub project_id: Uuid,
+    pub name: String,
+    pub status: ProjectStatus,
+    pub owner: Option<String>,
+    pub date_start: DateTime<Utc>,
+    pub date_end: DateTime<Utc>,
+    pub allocated_fte: f64,
+    pub unfilled_fte: f64,
+}
+
+/// Портфельный отчет: проекты, пересекающиеся с окном, и потребность по навыкам
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortfolioReport {
+    pub window: TimeWindow,
+    pub projects: Vec<PortfolioRow>,
+    pub skill_demand: SkillDemandTable,
+}
+
+/// Сводные отчеты по всем проектам контейнера. Архивные проекты в отчеты не попадают
+pub struct ReportService<'a, C: ProjectContainer> {
+    container: &'a C,
+}
+
+impl<'a, C: ProjectContainer> ReportService<'a, C> {
+    pub fn new(container: &'a C) -> Self {
+        Self { container }
+    }
+
+    /// Потребность в ресурсах по навыкам по всем проектам пула
+    ///
+    /// Окно делится на корзины длиной `bucket`. Загрузка каждого назначения (FTE) относится
+    /// к навыкам его ресурса (см. `Resource::primary_skill`), ресурсы без навыков попадают
+    /// в UNSPECIFIED_SKILL - сумма по навыкам равна сумме по ресурсам. Роли проектов без
+    /// ресурсов добавляют потребность в навык с именем роли. Мощность навыка - те же доли
+    /// ресурсов за вычетом периодов недоступности. Доли считаются по календарному времени
+    /// корзины.
+    pub fn skill_demand(&self, window: TimeWindow, bucket: TimeDelta) -> Result<SkillDemandTable> {
+        if bucket <= TimeDelta::zero() {
+            anyhow::bail!("Bucket length must be positive");
+        }
+        let buckets = split_buckets(&window, bucket);
+        let mut rows: BTreeMap<String, SkillDemandRow> = BTreeMap::new();
+        let pool = self.container.resource_pool();
+
+        for resource in pool.resources() {
+            let allocations = pool.counted_allocations(&resource.id);
+            let unavailable: Vec<TimeWindow> = resource
+                .get_unavailable_periods()
+                .iter()
+                .map(|e| e.window)
+                .collect();
+            let mut demand = vec![0.0; buckets.len()];
+            let mut capacity = vec![0.0; buckets.len()];
+            for (i, b) in buckets.iter().enumerate() {
+                demand[i] = allocations
+                    .iter()
+                    .map(|a| a.get_engagement_rate() * covered_fraction(b, a.get_time_window()))
+                    .sum();
+                let free = b.subtract_all(&unavailable);
+                capacity[i] = free.iter().map(|w| covered_fraction(b, w)).sum();
+            }
+
+            for (skill, share) in skill_shares(resource) {
+                let row = rows
+                    .entry(skill.clone())
+                    .or_insert_with(|| SkillDemandRow::empty(skill, buckets.len()));
+                for i in 0..buckets.len() {
+                    row.demand[i] += demand[i] * share;
+                    row.capacity[i] += capacity[i] * share;
+                }
+            }
+        }
+
+        for project in self.counted_projects() {
+            for placeholder in project.get_role_placeholders() {
+                let row = rows.entry(placeholder.role.clone()).or_insert_with(|| {
+                    SkillDemandRow::empty(placeholder.role.clone(), buckets.len())
+                });
+                for (i, b) in buckets.iter().enumerate() {
+                    let fte =
+                        placeholder.engagement_rate * covered_fraction(b, &placeholder.window);
+                    row.demand[i] += fte;
+                    row.unfilled[i] += fte;
+                }
+            }
+        }
+
+        Ok(SkillDemandTable {
+            buckets,
+            rows: rows.into_values().collect(),
+        })
+    }
+
+    /// Портфельный отчет за окно: проекты по дате начала и потребность по навыкам
+    pub fn portfolio(&self, window: TimeWindow, bucket: TimeDelta) -> Result<PortfolioReport> {
+        let skill_demand = self.skill_demand(window, bucket)?;
+        let pool = self.container.resource_pool();
+        let mut projects: Vec<PortfolioRow> = self
+            .counted_projects()
+            .into_iter()
+            .filter(|p| {
+                window.overlaps(&TimeWindow {
+                    date_start: *p.get_date_start(),
+                    date_end: *p.get_date_end(),
+                })
+            })
+            .map(|p| PortfolioRow {
+                project_id: *p.get_id(),
+                name: p.name.clone(),
+                status: *p.get_status(),
+                owner: p.get_owner().map(str::to_string),
+                date_start: *p.get_date_start(),
+                date_end: *p.get_date_end(),
+                allocated_fte: pool
+                    .get_project_allocations(p.get_id())
+                    .iter()
+                    .map(|a| {
+                        a.get_engagement_rate() * covered_fraction(&window, a.get_time_window())
+                    })
+                    .sum(),
+                unfilled_fte: p
+                    .get_role_placeholders()
+                    .iter()
+                    .map(|r| r.engagement_rate * covered_fraction(&window, &r.window))
+                    .sum(),
+            })
+            .collect();
+        projects.sort_by(|a, b| {
+            a.date_start
+                .cmp(&b.date_start)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        Ok(PortfolioReport {
+            window,
+            projects,
+            skill_demand,
+        })
+    }
+
+    fn counted_projects(&self) -> Vec<&'a Project> {
+        self.container
+            .list_projects()
+            .into_iter()
+            .filter(|p| !self.container.is_archived(p.get_id()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ExceptionPeriod, ExceptionType, MultiProjectContainer, RateMeasure, ResourceService,
+        RolePlaceholder, SingleProjectContainer, TaskService, UNSPECIFIED_SKILL,
+    };
+    use chrono::TimeZone;
+
+    fn day(d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 2, d, 0, 0, 0).unwrap()
+    }
+
+    fn project(name: &str) -> Project {
+        Project::new(
+            name,
+            "",
+            Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_skill_demand_reconciles_with_resources() {
+        let mut container = SingleProjectContainer::new();
+        let project = project("Hiring");
+        let project_id = *project.get_id();
+        container.add_project(project).unwrap();
+
+        let mut resource_service = ResourceService::new(&mut container);
+        let mut ids = vec![];
+        for name in ["Anna", "Boris", "Vera"] {
+            let resource = resource_service
+                .create_resource(name, 100.0, RateMeasure::Hourly)
+                .unwrap();
+            ids.push(resource.id);
+            resource_service.add_resource(resource).unwrap();
+        }
+        resource_service
+            .set_resource_skills(ids[0], vec!["rust".into(), "sql".into()], None)
+            .unwrap();
+        resource_service
+            .set_resource_skills(ids[1], vec!["rust".into()], Some("design".into()))
+            .unwrap();
+        resource_service
+            .add_unavailable_period(
+                ids[2],
+                ExceptionPeriod {
+                    period: TimeWindow::new(day(9), day(16)).unwrap(),
+                    exception_type: ExceptionType::Vacation,
+                },
+            )
+            .unwrap();
+
+        let mut task_service = TaskService::new(&mut container);
+        let task = task_service
+            .create_regular_task(project_id, "Build".into(), day(2), day(16), None)
+            .unwrap();
+        task_service
+            .allocate_resource(project_id, *task.get_id(), ids[0], 1.0, None, None)
+            .unwrap();
+        task_service
+            .allocate_resource(project_id, *task.get_id(), ids[1], 0.5, None, None)
+            .unwrap();
+
+        let table = ReportService::new(&container)
+            .skill_demand(
+                TimeWindow::new(day(2), day(16)).unwrap(),
+                TimeDelta::days(7),
+            )
+            .unwrap();
+        assert_eq!(table.buckets.len(), 2);
+        let skills: Vec<&str> = table.rows.iter().map(|r| r.skill.as_str()).collect();
+        assert_eq!(skills, vec!["design", "rust", "sql", UNSPECIFIED_SKILL]);
+
+        // Основной навык забирает всю загрузку Бориса, у Анны она делится поровну
+        assert_eq!(table.row("design").unwrap().demand, vec![0.5, 0.5]);
+        assert_eq!(table.row("rust").unwrap().demand, vec![0.5, 0.5]);
+        assert_eq!(table.row("sql").unwrap().capacity, vec![0.5, 0.5]);
+        // Вера без навыков и в отпуске во второй неделе
+        let unspecified = table.row(UNSPECIFIED_SKILL).unwrap();
+        assert_eq!(unspecified.demand, vec![0.0, 0.0]);
+        assert_eq!(unspecified.capacity, vec![1.0, 0.0]);
+        assert_eq!(table.row("design").unwrap().shortage(), vec![0.0, 0.0]);
+
+        assert_eq!(table.total_demand(0), 1.5);
+        assert!(
+            ReportService::new(&container)
+                .skill_demand(TimeWindow::new(day(2), day(16)).unwrap(), TimeDelta::zero())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_skill_demand_counts_placeholders_and_skips_archived() {
+        let mut container = MultiProjectContainer::new();
+        let mut current = project("Current");
+        let current_id = *current.get_id();
+        current
+            .add_role_placeholder(RolePlaceholder::new(
+                "rust",
+                2.0,
+                TimeWindow::new(day(9), day(16)).unwrap(),
+            ))
+            .unwrap();
+        let old = project("Old");
+        let old_id = *old.get_id();
+        container.add_project(current).unwrap();
+        container.add_project(old).unwrap();
+
+        let mut resource_service = ResourceService::new(&mut container);
+        let resource = resource_service
+            .create_resource("Anna", 100.0, RateMeasure::Hourly)
+            .unwrap();
+        let anna = resource.id;
+        resource_service.add_resource(resource).unwrap();
+        resource_service
+            .set_resource_skills(anna, vec!["rust".into()], None)
+            .unwrap();
+
+        let mut task_service = TaskService::new(&mut container);
+        for (project_id, rate) in [(current_id, 0.5), (old_id, 0.5)] {
+            let task = task_service
+                .create_regular_task(project_id, "Build".into(), day(2), day(16), None)
+                .unwrap();
+            task_service
+                .allocate_resource(project_id, *task.get_id(), anna, rate, None, None)
+                .unwrap();
+        }
+        container.archive_project(&old_id).unwrap();
+
+        let window = TimeWindow::new(day(2), day(16)).unwrap();
+        let report = ReportService::new(&container)
+            .portfolio(window, TimeDelta::days(7))
+            .unwrap();
+        let rust = report.skill_demand.row("rust").unwrap();
+        // Архивный проект не учитывается, роль без ресурса добавляется во вторую неделю
+        assert_eq!(rust.demand, vec![0.5, 2.5]);
+        assert_eq!(rust.unfilled, vec![0.0, 2.0]);
+        assert_eq!(rust.capacity, vec![1.0, 1.0]);
+        assert_eq!(rust.shortage(), vec![0.0, 1.5]);
+
+        assert_eq!(report.projects.len(), 1);
+        assert_eq!(report.projects[0].project_id, current_id);
+        assert_eq!(report.projects[0].allocated_fte, 0.5);
+        assert_eq!(report.projects[0].unfilled_fte, 1.0);
+    }
+}
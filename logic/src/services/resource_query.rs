@@ -0,0 +1,64 @@
+use crate::{RateMeasure, TimeWindow, base_structures::Resource};
+
+/// Порядок ресурсов в списке
+/// ByName - по имени
+/// ByRate - по ставке, приведенной к часовой
+/// ByUtilization - по суммарной загрузке, при заданном окне учитываются только пересекающиеся с ним назначения
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ResourceSort {
+    #[default]
+    ByName,
+    ByRate,
+    ByUtilization(Option<TimeWindow>),
+}
+
+/// Группировка ресурсов в списке
+/// ByRateMeasure - по типу ставки
+/// ByActive - есть ли у ресурса назначения
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResourceGroup {
+    ByRateMeasure,
+    ByActive,
+}
+
+/// Запрос списка ресурсов: сортировка, группировка, фильтр по имени и страница
+///
+/// Порядок всегда детерминирован: при равенстве ключей ресурсы упорядочиваются по имени и id.
+#[derive(Debug, Clone, Default)]
+pub struct ResourceQuery {
+    pub sort: ResourceSort,
+    pub group: Option<ResourceGroup>,
+    /// Подстрока имени без учета регистра
+    pub filter: Option<String>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+/// Строка результата запроса
+#[derive(Debug, Clone)]
+pub struct ResourceRow<'a> {
+    pub resource: &'a Resource,
+    /// Название группы, если в запросе задана группировка
+    pub group: Option<String>,
+    pub utilization: f64,
+}
+
+/// Страница результата запроса
+/// total - количество ресурсов после фильтра, до применения offset/limit
+#[derive(Debug, Clone)]
+pub struct ResourcePage<'a> {
+    pub total: usize,
+    pub rows: Vec<ResourceRow<'a>>,
+}
+
+pub(crate) fn group_label(group: ResourceGroup, resource: &Resource, utilization: f64) -> String {
+    match group {
+        ResourceGroup::ByRateMeasure => match resource.rate_measure {
+            RateMeasure::Daily => "Daily".to_string(),
+            RateMeasure::Hourly => "Hourly".to_string(),
+            RateMeasure::Monthly => "Monthly".to_string(),
+        },
+        ResourceGroup::ByActive if utilization > 0.0 => "Active".to_string(),
+        ResourceGroup::ByActive => "Idle".to_string(),
+    }
+}
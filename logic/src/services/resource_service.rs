@@ -1,13 +1,78 @@
 use crate::{
     BasicGettersForStructures, TimeWindow,
-    base_structures::{ExceptionPeriod, ProjectCalendar, ProjectContainer, RateMeasure, Resource},
+    base_structures::{
+        AvailabilityException, ContainerEvent, ProjectCalendar, ProjectContainer, RateMeasure,
+        Resource, SharedProjectContainer, SingleProjectContainer,
+    },
+    services::{
+        resource_query::{ResourcePage, ResourceQuery, ResourceRow, ResourceSort, group_label},
+        skill_demand::{covered_fraction, split_buckets},
+    },
 };
 use anyhow::Result;
+use chrono::{DateTime, TimeDelta, Utc};
+use std::collections::HashMap;
 use uuid::Uuid;
+
+/// Аллокация, которую не удалось переназначить, и причина
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapConflict {
+    pub allocation_id: Uuid,
+    pub task_id: Uuid,
+    pub reason: String,
+}
+
+/// Результат обмена назначениями между двумя ресурсами
+/// swapped - переназначенные аллокации (пусто, если есть конфликты)
+/// conflicts - аллокации, из-за которых обмен не выполнен
+#[derive(Debug, Clone, Default)]
+pub struct SwapReport {
+    pub swapped: Vec<Uuid>,
+    pub conflicts: Vec<SwapConflict>,
+}
+
+/// Перегрузка ресурса в окне по всем проектам контейнера
+/// contributions - (проект, задача, аллокация) для каждого назначения, дающего загрузку в окне
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverallocationReport {
+    pub resource_id: Uuid,
+    pub resource_name: String,
+    pub window: TimeWindow,
+    pub total_engagement: f64,
+    pub contributions: Vec<(Uuid, Uuid, Uuid)>,
+}
+
+/// Сервис работы с ресурсами общего пула контейнера
+///
+/// # Examples
+///
+/// ```
+/// use logic::{RateMeasure, ResourceService, SingleProjectContainer};
+///
+/// let mut container = SingleProjectContainer::new();
+/// let mut resource_service = ResourceService::new(&mut container);
+/// let boris = resource_service.create_resource("Boris", 800.0, RateMeasure::Daily)?;
+/// resource_service.add_resource(boris)?;
+///
+/// let resources = resource_service.list_resources();
+/// assert_eq!(resources[0].get_converted_rate(RateMeasure::Hourly), 100.0);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
 pub struct ResourceService<'a, C: ProjectContainer> {
     container: &'a mut C,
 }
 
+impl ResourceService<'_, SingleProjectContainer> {
+    /// Работа с сервисом над общим контейнером; блокировка на запись держится только
+    /// на время вызова `f`, так как сервису нужен изменяемый доступ
+    pub fn with_shared<R>(
+        shared: &SharedProjectContainer,
+        f: impl FnOnce(&mut ResourceService<'_, SingleProjectContainer>) -> R,
+    ) -> R {
+        shared.write(|container| f(&mut ResourceService::new(container)))
+    }
+}
+
 impl<'a, C: ProjectContainer> ResourceService<'a, C> {
     pub fn new(container: &'a mut C) -> Self {
         Self { container }
@@ -48,6 +113,68 @@ impl<'a, C: ProjectContainer> ResourceService<'a, C> {
         if let Some(m) = measure {
             resource.rate_measure = m;
         }
+        self.container
+            .emit(ContainerEvent::ResourceUpdated { resource_id });
+        Ok(())
+    }
+
+    /// Задать валюту ставки ресурса (код ISO-4217)
+    pub fn set_resource_currency(&mut self, resource_id: Uuid, currency: &str) -> Result<()> {
+        self.container
+            .resource_pool_mut()
+            .get_mut_resource_by_uuid(resource_id)
+            .ok_or_else(|| anyhow::anyhow!("Resource not found in pool"))?
+            .set_currency(currency)?;
+        self.container
+            .emit(ContainerEvent::ResourceUpdated { resource_id });
+        Ok(())
+    }
+
+    /// Задать собственный календарь ресурса, None - ресурс работает по календарю проекта
+    pub fn set_resource_calendar(
+        &mut self,
+        resource_id: Uuid,
+        calendar: Option<ProjectCalendar>,
+    ) -> Result<()> {
+        self.container
+            .resource_pool_mut()
+            .get_mut_resource_by_uuid(resource_id)
+            .ok_or_else(|| anyhow::anyhow!("Resource not found in pool"))?
+            .set_calendar(calendar);
+        self.container
+            .emit(ContainerEvent::ResourceUpdated { resource_id });
+        Ok(())
+    }
+
+    /// Задать навыки ресурса; основной навык добавляется в список навыков, если его там нет
+    pub fn set_resource_skills(
+        &mut self,
+        resource_id: Uuid,
+        skills: Vec<String>,
+        primary_skill: Option<String>,
+    ) -> Result<()> {
+        let resource = self
+            .container
+            .resource_pool_mut()
+            .get_mut_resource_by_uuid(resource_id)
+            .ok_or_else(|| anyhow::anyhow!("Resource not found in pool"))?;
+        let mut skills: Vec<String> = skills
+            .into_iter()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        let primary_skill = primary_skill
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        if let Some(primary) = &primary_skill
+            && !skills.contains(primary)
+        {
+            skills.push(primary.clone());
+        }
+        resource.skills = skills;
+        resource.primary_skill = primary_skill;
+        self.container
+            .emit(ContainerEvent::ResourceUpdated { resource_id });
         Ok(())
     }
 
@@ -62,13 +189,207 @@ impl<'a, C: ProjectContainer> ResourceService<'a, C> {
     }
 
     pub fn list_resources(&self) -> Vec<&Resource> {
-        self.container.resource_pool().get_resources()
+        self.container.resource_pool().resources()
+    }
+
+    /// Список ресурсов в заданном порядке с группировкой и постраничной выдачей
+    ///
+    /// Загрузка считается один раз на ресурс, а не при каждом сравнении.
+    pub fn query_resources(&self, query: &ResourceQuery) -> ResourcePage<'_> {
+        let window = match query.sort {
+            ResourceSort::ByUtilization(window) => window,
+            _ => None,
+        };
+        let filter = query.filter.as_ref().map(|f| f.to_lowercase());
+
+        let mut rows: Vec<ResourceRow> = self
+            .list_resources()
+            .into_iter()
+            .filter(|r| {
+                filter
+                    .as_ref()
+                    .is_none_or(|f| r.name.to_lowercase().contains(f))
+            })
+            .map(|resource| {
+                let utilization = self.utilization_in_window(resource.id, window.as_ref());
+                ResourceRow {
+                    resource,
+                    group: query.group.map(|g| group_label(g, resource, utilization)),
+                    utilization,
+                }
+            })
+            .collect();
+
+        rows.sort_by(|a, b| {
+            let by_key = match query.sort {
+                ResourceSort::ByName => std::cmp::Ordering::Equal,
+                ResourceSort::ByRate => a
+                    .resource
+                    .get_converted_rate(RateMeasure::Hourly)
+                    .total_cmp(&b.resource.get_converted_rate(RateMeasure::Hourly)),
+                ResourceSort::ByUtilization(_) => a.utilization.total_cmp(&b.utilization),
+            };
+            a.group
+                .cmp(&b.group)
+                .then(by_key)
+                .then_with(|| a.resource.name.cmp(&b.resource.name))
+                .then_with(|| a.resource.id.cmp(&b.resource.id))
+        });
+
+        let total = rows.len();
+        let rows = rows
+            .into_iter()
+            .skip(query.offset)
+            .take(query.limit.unwrap_or(usize::MAX))
+            .collect();
+        ResourcePage { total, rows }
+    }
+
+    /// Обмен назначениями двух ресурсов, начинающимися не раньше `from`
+    ///
+    /// Все аллокации проверяются заново на новом ресурсе (загрузка, отпуска) одним пакетом:
+    /// если хоть одна не проходит, ничего не меняется, а конфликты возвращаются в отчете.
+    #[tracing::instrument(skip_all, fields(%resource_a, %resource_b))]
+    pub fn swap_assignments(
+        &mut self,
+        resource_a: Uuid,
+        resource_b: Uuid,
+        from: DateTime<Utc>,
+    ) -> Result<SwapReport> {
+        if resource_a == resource_b {
+            anyhow::bail!("Cannot swap assignments of resource with itself");
+        }
+        let pool = self.container.resource_pool();
+        for resource_id in [&resource_a, &resource_b] {
+            if pool.get_resource(resource_id).is_none() {
+                anyhow::bail!("Resource with id {} not found", resource_id);
+            }
+        }
+
+        let mut changes = vec![];
+        let mut task_ids = HashMap::new();
+        for (from_resource, to_resource) in [(resource_a, resource_b), (resource_b, resource_a)] {
+            let mut allocations = pool.get_resource_existing_allocations(&from_resource);
+            allocations.retain(|a| a.get_time_window().date_start >= from);
+            allocations.sort_by_key(|a| (a.get_time_window().date_start, a.get_id()));
+            for allocation in allocations {
+                let calendar = self
+                    .container
+                    .calendar(allocation.get_project_id())
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Calendar for project {} not found",
+                            allocation.get_project_id()
+                        )
+                    })?
+                    .clone();
+                task_ids.insert(allocation.get_id(), *allocation.get_task_id());
+                changes.push((allocation.get_id(), to_resource, calendar));
+            }
+        }
+
+        let errors = self
+            .container
+            .resource_pool_mut()
+            .reassign_allocations(&changes);
+        if errors.is_empty() {
+            tracing::debug!(swapped = changes.len(), "assignments swapped");
+            return Ok(SwapReport {
+                swapped: changes.into_iter().map(|(id, _, _)| id).collect(),
+                conflicts: vec![],
+            });
+        }
+        tracing::debug!(conflicts = errors.len(), "swap rejected");
+        Ok(SwapReport {
+            swapped: vec![],
+            conflicts: errors
+                .into_iter()
+                .map(|(allocation_id, reason)| SwapConflict {
+                    allocation_id,
+                    task_id: task_ids.get(&allocation_id).copied().unwrap_or_default(),
+                    reason,
+                })
+                .collect(),
+        })
+    }
+
+    /// Выравнивание загрузки ресурса по календарю проекта `project_id`
+    ///
+    /// Граница сдвига каждого назначения - текущее окончание его задачи в контейнере,
+    /// поэтому ресурс можно выравнивать сразу после переноса задач.
+    #[tracing::instrument(skip_all, fields(%resource_id, %project_id))]
+    pub fn level_resource(&mut self, resource_id: Uuid, project_id: &Uuid) -> Result<Vec<Uuid>> {
+        let calendar = self
+            .container
+            .calendar(project_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Calendar for project {} not found", project_id))?;
+        let task_ends: HashMap<Uuid, DateTime<Utc>> = self
+            .container
+            .list_projects()
+            .into_iter()
+            .flat_map(|project| project.tasks.values())
+            .map(|task| (*task.get_id(), *task.get_date_end()))
+            .collect();
+        let moved = self.container.resource_pool_mut().level_resource(
+            resource_id,
+            &calendar,
+            &task_ends,
+        )?;
+        for allocation_id in &moved {
+            tracing::warn!(%resource_id, %allocation_id, "allocation moved by leveling");
+        }
+        self.container
+            .emit(ContainerEvent::ResourceUpdated { resource_id });
+        Ok(moved)
+    }
+
+    /// Перегрузки ресурсов по всем проектам общего пула
+    ///
+    /// Время делится по границам всех назначений, поэтому частичные пересечения попадают
+    /// в отчет только своей общей частью. При заданном `window` отчеты обрезаются по нему.
+    /// Только анализ - назначения не снимаются.
+    pub fn detect_overallocations(&self, window: Option<TimeWindow>) -> Vec<OverallocationReport> {
+        let pool = self.container.resource_pool();
+        pool.find_conflicts()
+            .into_iter()
+            .filter_map(|conflict| {
+                let report_window = match &window {
+                    Some(window) => conflict.window.intersection(window)?,
+                    None => conflict.window,
+                };
+                let contributions = conflict
+                    .allocation_ids
+                    .iter()
+                    .filter_map(|id| pool.get_allocation(id))
+                    .map(|a| (*a.get_project_id(), *a.get_task_id(), a.get_id()))
+                    .collect();
+                Some(OverallocationReport {
+                    resource_id: conflict.resource_id,
+                    resource_name: conflict.resource_name,
+                    window: report_window,
+                    total_engagement: conflict.total_engagement,
+                    contributions,
+                })
+            })
+            .collect()
+    }
+
+    /// Суммарная занятость ресурса по назначениям, пересекающимся с окном (или по всем)
+    fn utilization_in_window(&self, resource_id: Uuid, window: Option<&TimeWindow>) -> f64 {
+        self.container
+            .resource_pool()
+            .get_resource_existing_allocations(&resource_id)
+            .iter()
+            .filter(|ra| window.is_none_or(|w| w.overlaps(ra.get_time_window())))
+            .map(|ra| *ra.get_engagement_rate())
+            .sum()
     }
 
     pub fn add_unavailable_period(
         &mut self,
         resource_id: Uuid,
-        exception_period: ExceptionPeriod,
+        exception_period: impl Into<AvailabilityException>,
     ) -> Result<()> {
         match self
             .container
@@ -77,6 +398,8 @@ impl<'a, C: ProjectContainer> ResourceService<'a, C> {
         {
             Some(r) => {
                 let _: () = r.add_unavailable_period(exception_period);
+                self.container
+                    .emit(ContainerEvent::ResourceUpdated { resource_id });
                 Ok(())
             }
             None => Err(anyhow::Error::msg("Resource not found in poll")),
@@ -110,10 +433,10 @@ impl<'a, C: ProjectContainer> ResourceService<'a, C> {
 
         for exeprion_period in resource.get_unavailable_periods() {
             let overlap_period_start = exeprion_period
-                .period
+                .window
                 .date_start
                 .max(project_window.date_start);
-            let overlap_period_end = exeprion_period.period.date_end.min(project_window.date_end);
+            let overlap_period_end = exeprion_period.window.date_end.min(project_window.date_end);
             if overlap_period_start < overlap_period_end {
                 let overlap_window = TimeWindow::new(overlap_period_start, overlap_period_end)?;
                 let exception_hours = overlap_window.duration_hours(calendar);
@@ -135,6 +458,59 @@ impl<'a, C: ProjectContainer> ResourceService<'a, C> {
         Ok(used_hours / availible_hours as f64)
     }
 
+    /// Над чем работает ресурс: задачи с занятостью и окнами назначений
+    pub fn tasks_for_resource(&self, resource_id: Uuid) -> Vec<(Uuid, f64, TimeWindow)> {
+        self.container
+            .resource_pool()
+            .tasks_for_resource(resource_id)
+    }
+
+    /// Гистограмма загрузки ресурса по всем назначениям: (начало корзины, FTE)
+    ///
+    /// Корзины длиной `bucket` идут от начала самого раннего назначения до конца самого
+    /// позднего, последняя обрезается. Назначение дает корзине свою занятость, умноженную
+    /// на покрытую долю корзины; корзины без назначений - 0. Без назначений или при
+    /// неположительной длине корзины - пустой список.
+    pub fn utilization_histogram(
+        &self,
+        resource_id: Uuid,
+        bucket: TimeDelta,
+    ) -> Vec<(DateTime<Utc>, f64)> {
+        let allocations = self
+            .container
+            .resource_pool()
+            .get_resource_existing_allocations(&resource_id);
+        let (Some(start), Some(end)) = (
+            allocations
+                .iter()
+                .map(|a| a.get_time_window().date_start)
+                .min(),
+            allocations
+                .iter()
+                .map(|a| a.get_time_window().date_end)
+                .max(),
+        ) else {
+            return vec![];
+        };
+        if bucket <= TimeDelta::zero() {
+            return vec![];
+        }
+        let timeline = TimeWindow {
+            date_start: start,
+            date_end: end,
+        };
+        split_buckets(&timeline, bucket)
+            .into_iter()
+            .map(|b| {
+                let load = allocations
+                    .iter()
+                    .map(|a| a.get_engagement_rate() * covered_fraction(&b, a.get_time_window()))
+                    .sum();
+                (b.date_start, load)
+            })
+            .collect()
+    }
+
     pub fn get_resource_utilization(&self, resource_id: Uuid) -> f64 {
         self.container
             .resource_pool()
@@ -156,6 +532,11 @@ impl<'a, C: ProjectContainer> ResourceService<'a, C> {
             .container
             .calendar(project_id)
             .ok_or_else(|| anyhow::anyhow!("Calendar for project {} not found", project_id))?;
+        let rate_config = self
+            .container
+            .get_project(project_id)
+            .ok_or_else(|| anyhow::anyhow!("Project {} not found", project_id))?
+            .rate_config;
 
         let allocations = self
             .container
@@ -164,10 +545,11 @@ impl<'a, C: ProjectContainer> ResourceService<'a, C> {
 
         let mut total_cost = 0.0;
         for alloc in allocations {
-            total_cost += self
-                .container
-                .resource_pool()
-                .calculate_allocation_cost(&alloc.get_id(), calendar)?;
+            total_cost += self.container.resource_pool().calculate_allocation_cost(
+                &alloc.get_id(),
+                calendar,
+                &rate_config,
+            )?;
         }
 
         Ok(total_cost)
@@ -181,7 +563,7 @@ mod tests {
         BasicGettersForStructures, ExceptionPeriod, ExceptionType, Project, RateMeasure,
         SingleProjectContainer, TimeWindow,
     };
-    use chrono::{TimeZone, Utc};
+    use chrono::{DateTime, TimeZone, Utc};
 
     #[test]
     fn test_create_resource() {
@@ -291,4 +673,347 @@ mod tests {
             resource_service.container.calendar(&project_id).unwrap(),
         ))
     }
+
+    #[test]
+    fn test_query_resources() {
+        let mut container = SingleProjectContainer::new();
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap();
+        let project = Project::new("Test", "Desc", start, end).unwrap();
+        let project_id = *project.get_id();
+        container.add_project(project).unwrap();
+
+        let mut ids = vec![];
+        {
+            let mut resource_service = ResourceService::new(&mut container);
+            for (name, rate, measure) in [
+                ("Charlie", 100.0, RateMeasure::Hourly),
+                ("alice", 1000.0, RateMeasure::Daily),
+                ("Bob", 50.0, RateMeasure::Hourly),
+            ] {
+                let resource = resource_service
+                    .create_resource(name, rate, measure)
+                    .unwrap();
+                ids.push(resource.id);
+                resource_service.add_resource(resource).unwrap();
+            }
+        }
+        {
+            let mut task_service = crate::TaskService::new(&mut container);
+            let task = task_service
+                .create_regular_task(
+                    project_id,
+                    "Task".into(),
+                    Utc.with_ymd_and_hms(2025, 2, 3, 0, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2025, 2, 7, 0, 0, 0).unwrap(),
+                    None,
+                )
+                .unwrap();
+            task_service
+                .allocate_resource(project_id, *task.get_id(), ids[2], 0.5, None, None)
+                .unwrap();
+        }
+
+        let resource_service = ResourceService::new(&mut container);
+        let names = |page: ResourcePage| -> Vec<String> {
+            page.rows.iter().map(|r| r.resource.name.clone()).collect()
+        };
+
+        let by_name = resource_service.query_resources(&ResourceQuery::default());
+        assert_eq!(by_name.total, 3);
+        assert_eq!(names(by_name), ["Bob", "Charlie", "alice"]);
+
+        // 1000 в день = 125 в час
+        let by_rate = resource_service.query_resources(&ResourceQuery {
+            sort: ResourceSort::ByRate,
+            ..Default::default()
+        });
+        assert_eq!(names(by_rate), ["Bob", "Charlie", "alice"]);
+
+        let by_utilization = resource_service.query_resources(&ResourceQuery {
+            sort: ResourceSort::ByUtilization(None),
+            group: Some(crate::ResourceGroup::ByActive),
+            ..Default::default()
+        });
+        assert_eq!(by_utilization.rows[0].group.as_deref(), Some("Active"));
+        assert_eq!(by_utilization.rows[0].utilization, 0.5);
+        assert_eq!(names(by_utilization), ["Bob", "Charlie", "alice"]);
+
+        // Окно без назначений - загрузка нулевая у всех
+        let window = TimeWindow::new(
+            Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 7, 1, 0, 0, 0).unwrap(),
+        )
+        .unwrap();
+        let in_window = resource_service.query_resources(&ResourceQuery {
+            sort: ResourceSort::ByUtilization(Some(window)),
+            ..Default::default()
+        });
+        assert!(in_window.rows.iter().all(|r| r.utilization == 0.0));
+
+        let page = resource_service.query_resources(&ResourceQuery {
+            filter: Some("A".into()),
+            offset: 1,
+            limit: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(page.total, 2);
+        assert_eq!(names(page), ["alice"]);
+    }
+
+    // Два ресурса и три задачи: январь (Анна), февраль (Анна), март (Борис)
+    fn setup_swap() -> (SingleProjectContainer, Uuid, Uuid, Uuid, Vec<Uuid>) {
+        let mut container = SingleProjectContainer::new();
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap();
+        let project = Project::new("Test", "Desc", start, end).unwrap();
+        let project_id = *project.get_id();
+        container.add_project(project).unwrap();
+
+        let mut resource_ids = vec![];
+        {
+            let mut resource_service = ResourceService::new(&mut container);
+            for name in ["Anna", "Boris"] {
+                let resource = resource_service
+                    .create_resource(name, 100.0, RateMeasure::Hourly)
+                    .unwrap();
+                resource_ids.push(resource.id);
+                resource_service.add_resource(resource).unwrap();
+            }
+        }
+        let mut task_ids = vec![];
+        let mut task_service = crate::TaskService::new(&mut container);
+        for (month, resource_id) in [
+            (1, resource_ids[0]),
+            (2, resource_ids[0]),
+            (3, resource_ids[1]),
+        ] {
+            let task = task_service
+                .create_regular_task(
+                    project_id,
+                    format!("Task {}", month),
+                    Utc.with_ymd_and_hms(2025, month, 3, 0, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2025, month, 7, 0, 0, 0).unwrap(),
+                    None,
+                )
+                .unwrap();
+            task_service
+                .allocate_resource(project_id, *task.get_id(), resource_id, 0.5, None, None)
+                .unwrap();
+            task_ids.push(*task.get_id());
+        }
+        (
+            container,
+            project_id,
+            resource_ids[0],
+            resource_ids[1],
+            task_ids,
+        )
+    }
+
+    #[test]
+    fn test_swap_assignments() {
+        let (mut container, _, anna, boris, _) = setup_swap();
+        let from = Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap();
+
+        let report = ResourceService::new(&mut container)
+            .swap_assignments(anna, boris, from)
+            .unwrap();
+        assert_eq!(report.swapped.len(), 2);
+        assert!(report.conflicts.is_empty());
+
+        let pool = container.resource_pool();
+        let months = |resource_id: &Uuid| -> Vec<u32> {
+            let mut months: Vec<u32> = pool
+                .get_resource_existing_allocations(resource_id)
+                .iter()
+                .map(|a| chrono::Datelike::month(&a.get_time_window().date_start))
+                .collect();
+            months.sort();
+            months
+        };
+        // Январь до `from` остается у Анны
+        assert_eq!(months(&anna), [1, 3]);
+        assert_eq!(months(&boris), [2]);
+    }
+
+    #[test]
+    fn test_swap_assignments_all_or_nothing() {
+        let (mut container, project_id, anna, boris, task_ids) = setup_swap();
+        // Борис уже занят в феврале на 80% задачей, начавшейся до `from`
+        let busy = {
+            let mut task_service = crate::TaskService::new(&mut container);
+            let busy = task_service
+                .create_regular_task(
+                    project_id,
+                    "Busy".into(),
+                    Utc.with_ymd_and_hms(2025, 1, 27, 0, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2025, 2, 28, 0, 0, 0).unwrap(),
+                    None,
+                )
+                .unwrap();
+            task_service
+                .allocate_resource(project_id, *busy.get_id(), boris, 0.8, None, None)
+                .unwrap();
+            busy
+        };
+        let from = Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap();
+
+        let report = ResourceService::new(&mut container)
+            .swap_assignments(anna, boris, from)
+            .unwrap();
+        assert!(report.swapped.is_empty());
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].task_id, task_ids[1]);
+        assert!(report.conflicts[0].reason.contains("100%"));
+
+        // Ничего не изменилось
+        let pool = container.resource_pool();
+        assert_eq!(pool.get_resource_existing_allocations(&anna).len(), 2);
+        assert_eq!(pool.get_resource_existing_allocations(&boris).len(), 2);
+        let task = container.get_project(&project_id).unwrap().tasks[busy.get_id()].clone();
+        let allocation = pool
+            .get_allocation(&task.get_resource_allocations()[0])
+            .unwrap();
+        assert_eq!(allocation.get_resource_id(), &boris);
+    }
+
+    #[test]
+    fn test_detect_overallocations_across_projects() {
+        use crate::MultiProjectContainer;
+
+        let day = |d: u32| Utc.with_ymd_and_hms(2025, 2, d, 0, 0, 0).unwrap();
+        let mut container = MultiProjectContainer::new();
+        let mut project_ids = vec![];
+        for name in ["Alpha", "Beta"] {
+            let project = Project::new(
+                name,
+                "",
+                Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap(),
+            )
+            .unwrap();
+            project_ids.push(*project.get_id());
+            container.add_project(project).unwrap();
+        }
+        let anna = {
+            let mut resource_service = ResourceService::new(&mut container);
+            let resource = resource_service
+                .create_resource("Anna", 100.0, RateMeasure::Hourly)
+                .unwrap();
+            let id = resource.id;
+            resource_service.add_resource(resource).unwrap();
+            id
+        };
+
+        // Назначения проходят проверку с малой загрузкой, затем загрузка поднимается
+        // правкой сохраненного файла - как у импортированных данных
+        let mut planned = vec![];
+        let mut task_service = crate::TaskService::new(&mut container);
+        for (project_id, start, end, engagement) in [
+            (project_ids[0], 3, 28, 0.5),
+            (project_ids[1], 10, 24, 0.4),
+            (project_ids[1], 7, 24, 0.4),
+        ] {
+            let task = task_service
+                .create_regular_task(project_id, "Task".into(), day(start), day(end), None)
+                .unwrap();
+            let allocation_id = task_service
+                .allocate_resource(project_id, *task.get_id(), anna, 0.1, None, None)
+                .unwrap();
+            planned.push((project_id, *task.get_id(), allocation_id, engagement));
+        }
+        let mut json = serde_json::to_value(&container).unwrap();
+        for (_, _, allocation_id, engagement) in &planned {
+            json["resource_pool"]["allocations"][allocation_id.to_string()]["engagement_rate"] =
+                serde_json::json!(engagement);
+        }
+        let mut container: MultiProjectContainer = serde_json::from_value(json).unwrap();
+
+        let service = ResourceService::new(&mut container);
+        let reports = service.detect_overallocations(None);
+        assert_eq!(reports.len(), 1);
+        let report = &reports[0];
+        assert_eq!(report.resource_id, anna);
+        assert_eq!(report.resource_name, "Anna");
+        // До 10-го работают только два назначения (0.9), перегрузка - ровно две недели
+        assert_eq!(report.window, TimeWindow::new(day(10), day(24)).unwrap());
+        assert!((report.total_engagement - 1.3).abs() < 1e-9);
+        let mut expected: Vec<(Uuid, Uuid, Uuid)> = planned
+            .iter()
+            .map(|(project_id, task_id, allocation_id, _)| (*project_id, *task_id, *allocation_id))
+            .collect();
+        expected.sort_by_key(|(_, _, allocation_id)| *allocation_id);
+        let mut contributions = report.contributions.clone();
+        contributions.sort_by_key(|(_, _, allocation_id)| *allocation_id);
+        assert_eq!(contributions, expected);
+
+        // Окно анализа обрезает отчет
+        let clipped =
+            service.detect_overallocations(Some(TimeWindow::new(day(17), day(28)).unwrap()));
+        assert_eq!(
+            clipped[0].window,
+            TimeWindow::new(day(17), day(24)).unwrap()
+        );
+        assert!(
+            service
+                .detect_overallocations(Some(TimeWindow::new(day(1), day(10)).unwrap()))
+                .is_empty()
+        );
+        // Анализ ничего не снимает
+        assert_eq!(
+            container
+                .resource_pool()
+                .get_resource_existing_allocations(&anna)
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_weekly_utilization_histogram() {
+        let day = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap();
+        let mut container = SingleProjectContainer::new();
+        let project = Project::new("Support", "", day(1), day(31)).unwrap();
+        let project_id = *project.get_id();
+        container.add_project(project).unwrap();
+        let mut resource_service = ResourceService::new(&mut container);
+        let resource = resource_service
+            .create_resource("Anna", 100.0, RateMeasure::Hourly)
+            .unwrap();
+        let resource_id = resource.id;
+        resource_service.add_resource(resource).unwrap();
+
+        let mut task_service = crate::TaskService::new(&mut container);
+        for (start, end, engagement) in [(2, 12, 0.7), (23, 30, 1.0)] {
+            let task = task_service
+                .create_regular_task(project_id, "Task".into(), day(start), day(end), None)
+                .unwrap();
+            task_service
+                .allocate_resource(
+                    project_id,
+                    *task.get_id(),
+                    resource_id,
+                    engagement,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+
+        let resource_service = ResourceService::new(&mut container);
+        let histogram = resource_service.utilization_histogram(resource_id, TimeDelta::weeks(1));
+        let starts: Vec<DateTime<Utc>> = histogram.iter().map(|(start, _)| *start).collect();
+        assert_eq!(starts, vec![day(2), day(9), day(16), day(23)]);
+        let expected = [0.7, 0.3, 0.0, 1.0];
+        for ((_, load), expected) in histogram.iter().zip(expected) {
+            assert!((load - expected).abs() < 1e-9, "{} != {}", load, expected);
+        }
+
+        assert!(
+            resource_service
+                .utilization_histogram(Uuid::new_v4(), TimeDelta::weeks(1))
+                .is_empty()
+        );
+    }
 }
@@ -20,6 +20,54 @@ impl<'a, C: ProjectContainer> Scheduler<'a, C> {
         Self { container }
     }
 
+    /// Критический путь проекта: задачи, задержка которых сдвигает окончание проекта
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use logic::{
+    ///     BasicGettersForStructures, DependencyType, Project, ProjectContainer, Scheduler,
+    ///     SingleProjectContainer, TaskService,
+    /// };
+    ///
+    /// let mut container = SingleProjectContainer::new();
+    /// let project = Project::new(
+    ///     "Website",
+    ///     "",
+    ///     Utc.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap(),
+    ///     Utc.with_ymd_and_hms(2026, 3, 31, 0, 0, 0).unwrap(),
+    /// )?;
+    /// let project_id = *project.get_id();
+    /// container.add_project(project)?;
+    ///
+    /// let mut task_service = TaskService::new(&mut container);
+    /// let design = task_service.create_regular_task(
+    ///     project_id,
+    ///     "Design".into(),
+    ///     Utc.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap(),
+    ///     Utc.with_ymd_and_hms(2026, 2, 13, 0, 0, 0).unwrap(),
+    ///     None,
+    /// )?;
+    /// let build = task_service.create_regular_task(
+    ///     project_id,
+    ///     "Build".into(),
+    ///     Utc.with_ymd_and_hms(2026, 2, 16, 0, 0, 0).unwrap(),
+    ///     Utc.with_ymd_and_hms(2026, 3, 13, 0, 0, 0).unwrap(),
+    ///     None,
+    /// )?;
+    /// task_service.add_dependency(
+    ///     project_id,
+    ///     *build.get_id(),
+    ///     *design.get_id(),
+    ///     DependencyType::Blocking,
+    ///     None,
+    /// )?;
+    ///
+    /// let path = Scheduler::new(&container).critical_path(project_id)?;
+    /// assert_eq!(path, [*design.get_id(), *build.get_id()]);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
     pub fn critical_path(&self, project_id: Uuid) -> anyhow::Result<Vec<Uuid>> {
         let project = self
             .container
@@ -33,6 +81,23 @@ impl<'a, C: ProjectContainer> Scheduler<'a, C> {
     }
 }
 
+/// Обычные задачи проекта в порядке зависимостей (предшественники раньше последователей)
+pub(crate) fn dependency_order(project: &Project) -> anyhow::Result<Vec<Uuid>> {
+    topological_sort(&build_graph(project))
+}
+
+/// Полный резерв обычных задач: поздний старт минус ранний по проходам CPM
+pub(crate) fn total_float(project: &Project) -> anyhow::Result<HashMap<Uuid, TimeDelta>> {
+    let graph = build_graph(project);
+    if graph.durations.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let order = topological_sort(&graph)?;
+    let (es, ef) = forward_pass(*project.get_date_start(), &graph, &order)?;
+    let (ls, _) = backward_pass(*project.get_date_end(), &graph, &es, &ef, &order)?;
+    Ok(order.iter().map(|id| (*id, ls[id] - es[id])).collect())
+}
+
 fn build_graph(project: &Project) -> Graph {
     let tasks = project.get_project_tasks();
     let mut graph = Graph::default();
@@ -47,7 +112,7 @@ fn build_graph(project: &Project) -> Graph {
         let dependencies: Vec<(Uuid, TimeDelta)> = task
             .get_dependencies()
             .iter()
-            .map(|dep| (dep.depends_on, dep.lag.unwrap_or_else(TimeDelta::zero)))
+            .map(|dep| (dep.depends_on, dep.lag_or_zero()))
             .collect();
 
         // Сохраняем предшественников для task_id
@@ -0,0 +1,96 @@
+use chrono::TimeDelta;
+
+use crate::{TimeWindow, base_structures::Resource};
+
+/// Навык для ресурсов без навыков, чтобы итоги по навыкам сходились с итогами по ресурсам
+pub const UNSPECIFIED_SKILL: &str = "unspecified";
+
+/// Потребность и доступная мощность по одному навыку, в FTE по корзинам таблицы
+/// demand - вся потребность, unfilled - ее часть от ролей без назначенных ресурсов
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkillDemandRow {
+    pub skill: String,
+    pub demand: Vec<f64>,
+    pub unfilled: Vec<f64>,
+    pub capacity: Vec<f64>,
+}
+
+impl SkillDemandRow {
+    pub(crate) fn empty(skill: String, buckets: usize) -> Self {
+        Self {
+            skill,
+            demand: vec![0.0; buckets],
+            unfilled: vec![0.0; buckets],
+            capacity: vec![0.0; buckets],
+        }
+    }
+
+    /// Нехватка мощности по корзинам (0, если мощности хватает)
+    pub fn shortage(&self) -> Vec<f64> {
+        self.demand
+            .iter()
+            .zip(&self.capacity)
+            .map(|(demand, capacity)| (demand - capacity).max(0.0))
+            .collect()
+    }
+}
+
+/// Потребность в ресурсах по навыкам
+/// buckets - корзины окна отчета, rows - строки по навыкам в алфавитном порядке
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SkillDemandTable {
+    pub buckets: Vec<TimeWindow>,
+    pub rows: Vec<SkillDemandRow>,
+}
+
+impl SkillDemandTable {
+    pub fn row(&self, skill: &str) -> Option<&SkillDemandRow> {
+        self.rows.iter().find(|r| r.skill == skill)
+    }
+
+    /// Суммарная потребность по всем навыкам в корзине
+    pub fn total_demand(&self, bucket: usize) -> f64 {
+        self.rows.iter().map(|r| r.demand[bucket]).sum()
+    }
+}
+
+/// Доли загрузки ресурса по навыкам: основной навык получает всю загрузку,
+/// иначе она делится поровну между навыками
+pub(crate) fn skill_shares(resource: &Resource) -> Vec<(String, f64)> {
+    if let Some(primary) = &resource.primary_skill {
+        return vec![(primary.clone(), 1.0)];
+    }
+    if resource.skills.is_empty() {
+        return vec![(UNSPECIFIED_SKILL.to_string(), 1.0)];
+    }
+    let share = 1.0 / resource.skills.len() as f64;
+    resource
+        .skills
+        .iter()
+        .map(|skill| (skill.clone(), share))
+        .collect()
+}
+
+/// Разбить окно на корзины длиной `bucket`, последняя корзина обрезается по окну
+pub(crate) fn split_buckets(window: &TimeWindow, bucket: TimeDelta) -> Vec<TimeWindow> {
+    let mut buckets = vec![];
+    let mut start = window.date_start;
+    while start < window.date_end {
+        let end = (start + bucket).min(window.date_end);
+        buckets.push(TimeWindow {
+            date_start: start,
+            date_end: end,
+        });
+        start = end;
+    }
+    buckets
+}
+
+/// Доля корзины, покрытая окном
+pub(crate) fn covered_fraction(bucket: &TimeWindow, window: &TimeWindow) -> f64 {
+    let Some(covered) = bucket.intersection(window) else {
+        return 0.0;
+    };
+    (covered.date_end - covered.date_start).num_seconds() as f64
+        / (bucket.date_end - bucket.date_start).num_seconds() as f64
+}
@@ -2,18 +2,65 @@
 use crate::{
     Project, TimeWindow,
     base_structures::{
-        AllocationRequest, BasicGettersForStructures, Dependency, DependencyType, ProjectContainer,
-        Task,
+        AllocationRequest, BasicGettersForStructures, ContainerEvent, DEFAULT_ALLOCATION_PRIORITY,
+        Dependency, DependencyType, IdGenerator, ProjectCalendar, ProjectContainer, RandomIds,
+        ResourceAllocation, SharedProjectContainer, SingleProjectContainer, Task, TaskStatus,
     },
+    cust_exceptions::AllocationErrors,
+    services::scheduler::dependency_order,
 };
 use anyhow::Result;
-use chrono::{DateTime, TimeDelta, Utc};
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Результат массовой операции над задачами
+/// succeeded - задачи, к которым операция применена
+/// failures - задачи, на которых операция не прошла, с описанием ошибки
+/// undo - снимок для отмены всей операции одной записью, None - ничего не изменилось
+#[derive(Debug, Clone, Default)]
+pub struct BulkResult {
+    pub succeeded: Vec<Uuid>,
+    pub failures: Vec<(Uuid, String)>,
+    pub undo: Option<BulkUndo>,
+}
+
+/// Задачи и назначения проекта перед массовой операцией
+#[derive(Debug, Clone)]
+pub struct BulkUndo {
+    project_id: Uuid,
+    tasks: HashMap<Uuid, Task>,
+    allocations: Vec<ResourceAllocation>,
+}
+
+impl BulkResult {
+    fn record(&mut self, task_id: Uuid, result: Result<()>) {
+        match result {
+            Ok(()) => self.succeeded.push(task_id),
+            Err(e) => self.failures.push((task_id, e.to_string())),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
 pub struct TaskService<'a, C: ProjectContainer> {
     pub container: &'a mut C,
 }
 
+impl TaskService<'_, SingleProjectContainer> {
+    /// Работа с сервисом над общим контейнером; блокировка на запись держится только
+    /// на время вызова `f`
+    pub fn with_shared<R>(
+        shared: &SharedProjectContainer,
+        f: impl FnOnce(&mut TaskService<'_, SingleProjectContainer>) -> R,
+    ) -> R {
+        shared.write(|container| f(&mut TaskService::new(container)))
+    }
+}
+
 impl<'a, C: ProjectContainer> TaskService<'a, C> {
     pub fn new(container: &'a mut C) -> Self {
         Self { container }
@@ -61,9 +108,15 @@ impl<'a, C: ProjectContainer> TaskService<'a, C> {
                     .tasks
                     .get_mut(&current)
                     .ok_or_else(|| anyhow::anyhow!("Task not found"))?;
-                task.date_start = new_start;
-                task.date_end = new_end;
-                task.duration = new_end - new_start;
+                if task.date_start != new_start || task.date_end != new_end {
+                    task.date_start = new_start;
+                    task.date_end = new_end;
+                    task.duration = new_end - new_start;
+                    self.container.emit(ContainerEvent::TaskUpdated {
+                        project_id: *project_id,
+                        task_id: current,
+                    });
+                }
             }
 
             // Поднимаемся к родителю
@@ -82,7 +135,36 @@ impl<'a, C: ProjectContainer> TaskService<'a, C> {
         Ok(())
     }
 
-    // Создание задачи
+    /// Создание обычной задачи в границах проекта
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use logic::{BasicGettersForStructures, Project, ProjectContainer, SingleProjectContainer, TaskService};
+    ///
+    /// let mut container = SingleProjectContainer::new();
+    /// let project = Project::new(
+    ///     "Website",
+    ///     "",
+    ///     Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+    ///     Utc.with_ymd_and_hms(2026, 6, 30, 0, 0, 0).unwrap(),
+    /// )?;
+    /// let project_id = *project.get_id();
+    /// container.add_project(project)?;
+    ///
+    /// let mut task_service = TaskService::new(&mut container);
+    /// let task = task_service.create_regular_task(
+    ///     project_id,
+    ///     "Design".into(),
+    ///     Utc.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap(),
+    ///     Utc.with_ymd_and_hms(2026, 2, 6, 0, 0, 0).unwrap(),
+    ///     None,
+    /// )?;
+    /// assert_eq!(task.get_code().as_deref(), Some("T-1"));
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    #[tracing::instrument(skip_all, fields(%project_id))]
     pub fn create_regular_task(
         &mut self,
         project_id: Uuid,
@@ -96,20 +178,16 @@ impl<'a, C: ProjectContainer> TaskService<'a, C> {
             .get_project_mut(&project_id)
             .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
 
-        // Валидация дат задачи относительно проекта
-        if start < *project.get_date_start() || end > *project.get_date_end() {
-            anyhow::bail!("Task dates must be within project dates");
-        }
-
-        if let Some(p_id) = parent_id
-            && !project.tasks.contains_key(&p_id)
-        {
-            anyhow::bail!("Не найдена родительская задача")
-        }
-
-        let task = Task::new_regular(name, start, end, parent_id)?;
-        let task_id = *task.get_id();
-        project.tasks.insert(task_id, task.clone());
+        // Даты задачи, ограничения проекта и родитель проверяются при добавлении
+        let task = project
+            .add_task(Task::new_regular(name, start, end, parent_id)?)
+            .inspect_err(|e| tracing::debug!(error = %e, "task rejected"))?
+            .clone();
+        tracing::debug!(task_id = %task.get_id(), "task created");
+        self.container.emit(ContainerEvent::TaskCreated {
+            project_id,
+            task_id: *task.get_id(),
+        });
 
         if let Some(pid) = parent_id {
             self.update_summary_dates(&project_id, pid)?;
@@ -136,9 +214,13 @@ impl<'a, C: ProjectContainer> TaskService<'a, C> {
         let start = *project.get_date_start();
         let end = *project.get_date_end();
 
-        let task = Task::new_summary(name, start, end, parent_id)?;
-        let task_id = *task.get_id();
-        project.tasks.insert(task_id, task.clone());
+        let task = project
+            .add_task(Task::new_summary(name, start, end, parent_id)?)?
+            .clone();
+        self.container.emit(ContainerEvent::TaskCreated {
+            project_id,
+            task_id: *task.get_id(),
+        });
 
         if let Some(pid) = parent_id {
             self.update_summary_dates(&project_id, pid)?;
@@ -193,6 +275,7 @@ impl<'a, C: ProjectContainer> TaskService<'a, C> {
     }
 
     // Обновить задачу
+    #[tracing::instrument(skip_all, fields(%project_id, %task_id))]
     pub fn update_task(
         &mut self,
         project_id: Uuid,
@@ -209,6 +292,7 @@ impl<'a, C: ProjectContainer> TaskService<'a, C> {
 
         let project_start_date = *project.get_date_start();
         let project_end_date = *project.get_date_end();
+        let policy = project.validation_policy;
 
         let task = project
             .tasks
@@ -223,18 +307,29 @@ impl<'a, C: ProjectContainer> TaskService<'a, C> {
             anyhow::bail!("Cannot set start/end dates for summary task");
         }
 
+        // Ограничение длительности проверяем до изменения дат
+        policy.check_task(
+            start.unwrap_or(task.date_start),
+            end.unwrap_or(task.date_end),
+        )?;
         if let Some(s) = start {
             if s < project_start_date {
+                tracing::debug!(start = %s, "task start before project start");
                 anyhow::bail!("Task start date cannot be before project start date");
             }
             task.date_start = s;
         }
         if let Some(e) = end {
             if e > project_end_date {
+                tracing::debug!(end = %e, "task end after project end");
                 anyhow::bail!("Task end date cannot be after project end date");
             }
             task.date_end = e;
         }
+        self.container.emit(ContainerEvent::TaskUpdated {
+            project_id,
+            task_id,
+        });
 
         self.update_summary_dates(&project_id, task_id)?;
         if let Some(p_id) = parent_id {
@@ -250,25 +345,418 @@ impl<'a, C: ProjectContainer> TaskService<'a, C> {
             .get_project_mut(&project_id)
             .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
 
-        if !project.tasks.contains_key(&task_id) {
-            anyhow::bail!("Task not found");
-        }
+        // Удаляем задачу, ее родителя и назначения запоминаем до удаления
+        let task = project
+            .tasks
+            .remove(&task_id)
+            .ok_or_else(|| anyhow::anyhow!("Task not found"))?;
+        let parent_id = task.parent_id;
 
-        // Удаляем задачу
-        project.tasks.remove(&task_id);
+        // Дочерние задачи группы переходят к ее родителю
+        let children: Vec<Uuid> = project
+            .tasks
+            .values_mut()
+            .filter(|t| t.parent_id == Some(task_id))
+            .map(|t| {
+                t.parent_id = parent_id;
+                *t.get_id()
+            })
+            .collect();
+
+        // Назначения задачи освобождают ресурсы
+        let pool = self.container.resource_pool_mut();
+        for allocation_id in task.get_resource_allocations() {
+            if pool.get_allocation(allocation_id).is_some() {
+                pool.deallocate(*allocation_id)?;
+            }
+        }
 
-        // Если у задачи был родитель, обновляем его даты
-        if let Some(parent_id) = project.tasks.get(&task_id).and_then(|t| t.parent_id) {
+        if let Some(parent_id) = parent_id {
             self.update_summary_dates(&project_id, parent_id)?;
         }
+        for child_id in children {
+            self.container.emit(ContainerEvent::TaskUpdated {
+                project_id,
+                task_id: child_id,
+            });
+        }
+        self.container.emit(ContainerEvent::TaskDeleted {
+            project_id,
+            task_id,
+        });
+
+        Ok(())
+    }
+
+    /// Массовая смена статуса
+    pub fn bulk_set_status(
+        &mut self,
+        project_id: Uuid,
+        task_ids: &[Uuid],
+        status: TaskStatus,
+    ) -> Result<BulkResult> {
+        self.bulk_update(project_id, task_ids, |task| task.set_status(status.clone()))
+    }
+
+    /// Массовая смена приоритета
+    pub fn bulk_set_priority(
+        &mut self,
+        project_id: Uuid,
+        task_ids: &[Uuid],
+        priority: u8,
+    ) -> Result<BulkResult> {
+        self.bulk_update(project_id, task_ids, |task| {
+            task.set_priority(priority);
+            Ok(())
+        })
+    }
+
+    /// Массовое добавление тега, задачи с этим тегом не меняются
+    pub fn bulk_add_tag(
+        &mut self,
+        project_id: Uuid,
+        task_ids: &[Uuid],
+        tag: &str,
+    ) -> Result<BulkResult> {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            anyhow::bail!("Tag must not be empty");
+        }
+        self.bulk_update(project_id, task_ids, |task| {
+            task.add_tag(tag);
+            Ok(())
+        })
+    }
+
+    /// Массовое удаление тега, задача без этого тега попадает в ошибки
+    pub fn bulk_remove_tag(
+        &mut self,
+        project_id: Uuid,
+        task_ids: &[Uuid],
+        tag: &str,
+    ) -> Result<BulkResult> {
+        self.bulk_update(project_id, task_ids, |task| {
+            if task.remove_tag(tag) {
+                Ok(())
+            } else {
+                Err(anyhow::anyhow!("Task has no tag '{}'", tag))
+            }
+        })
+    }
+
+    /// Массовый сдвиг задач на `days` рабочих дней (отрицательное значение - назад)
+    ///
+    /// Начало переносится по календарю проекта, длительность сохраняется, назначения ресурсов
+    /// сдвигаются вместе с задачей. Последователи не сдвигаются каскадом, поэтому задачи
+    /// обрабатываются от последней к первой по направлению сдвига - цепочка из выделенных задач
+    /// сдвигается целиком. Группирующие задачи и задачи, выходящие за границы проекта или
+    /// наезжающие на последователей, остаются на месте и попадают в ошибки.
+    pub fn bulk_shift_working_days(
+        &mut self,
+        project_id: Uuid,
+        task_ids: &[Uuid],
+        days: i64,
+    ) -> Result<BulkResult> {
+        let calendar = self
+            .container
+            .calendar(&project_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Calendar for project {} not found", project_id))?;
+        self.with_undo(project_id, |service| {
+            let mut order: Vec<(usize, Uuid)> = task_ids.iter().copied().enumerate().collect();
+            order.sort_by_key(|(_, id)| {
+                service
+                    .get_task_by_id(&project_id, id)
+                    .map(|t| *t.get_date_start())
+            });
+            if days > 0 {
+                order.reverse();
+            }
+            let mut outcomes: Vec<(usize, Uuid, Result<()>)> = order
+                .into_iter()
+                .map(|(index, task_id)| {
+                    let outcome = service.shift_task(project_id, task_id, &calendar, days);
+                    (index, task_id, outcome)
+                })
+                .collect();
+            // В результате задачи идут в порядке запроса
+            outcomes.sort_by_key(|(index, _, _)| *index);
+            let mut result = BulkResult::default();
+            for (_, task_id, outcome) in outcomes {
+                result.record(task_id, outcome);
+            }
+            Ok(result)
+        })
+    }
+
+    /// Массовый перенос задач в группирующую задачу (этап), None - в корень проекта
+    pub fn bulk_set_parent(
+        &mut self,
+        project_id: Uuid,
+        task_ids: &[Uuid],
+        parent_id: Option<Uuid>,
+    ) -> Result<BulkResult> {
+        if let Some(parent_id) = parent_id {
+            match self.get_task_by_id(&project_id, &parent_id) {
+                Some(parent) if parent.is_summary => {}
+                Some(_) => anyhow::bail!("Parent task must be a summary task"),
+                None => anyhow::bail!("Parent task not found"),
+            }
+        }
+        self.with_undo(project_id, |service| {
+            let mut result = BulkResult::default();
+            for task_id in task_ids {
+                let outcome = match service.get_task_by_id(&project_id, task_id) {
+                    None => Err(anyhow::anyhow!("Task not found")),
+                    Some(_) if Some(*task_id) == parent_id => {
+                        Err(anyhow::anyhow!("Task cannot be its own parent"))
+                    }
+                    Some(task) => {
+                        let old_parent = task.parent_id;
+                        service
+                            .update_task(project_id, *task_id, None, None, None, parent_id)
+                            .and_then(|()| match old_parent {
+                                Some(old) if Some(old) != parent_id => {
+                                    service.update_summary_dates(&project_id, old)
+                                }
+                                _ => Ok(()),
+                            })
+                    }
+                };
+                result.record(*task_id, outcome);
+            }
+            Ok(result)
+        })
+    }
+
+    /// Массовое удаление задач
+    pub fn bulk_delete(&mut self, project_id: Uuid, task_ids: &[Uuid]) -> Result<BulkResult> {
+        self.with_undo(project_id, |service| {
+            let mut result = BulkResult::default();
+            for task_id in task_ids {
+                let outcome = service.delete_task(project_id, *task_id);
+                result.record(*task_id, outcome);
+            }
+            Ok(result)
+        })
+    }
+
+    /// Отменить массовую операцию: задачи и назначения проекта возвращаются к снимку,
+    /// сделанному перед операцией. Изменения проекта после операции тоже откатываются.
+    pub fn undo_bulk(&mut self, undo: BulkUndo) -> Result<()> {
+        let project = self
+            .container
+            .get_project_mut(&undo.project_id)
+            .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+        project.tasks = undo.tasks;
+        self.container
+            .resource_pool_mut()
+            .restore_project_allocations(&undo.project_id, undo.allocations);
+        self.container.emit(ContainerEvent::ProjectUpdated {
+            project_id: undo.project_id,
+        });
+        tracing::debug!(project_id = %undo.project_id, "bulk operation undone");
+        Ok(())
+    }
+
+    /// Применить `change` к каждой задаче из `task_ids` с общей записью отмены
+    fn bulk_update(
+        &mut self,
+        project_id: Uuid,
+        task_ids: &[Uuid],
+        mut change: impl FnMut(&mut Task) -> Result<()>,
+    ) -> Result<BulkResult> {
+        self.with_undo(project_id, |service| {
+            let project = service
+                .container
+                .get_project_mut(&project_id)
+                .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+            let mut result = BulkResult::default();
+            for task_id in task_ids {
+                let outcome = match project.tasks.get_mut(task_id) {
+                    Some(task) => change(task),
+                    None => Err(anyhow::anyhow!("Task not found")),
+                };
+                result.record(*task_id, outcome);
+            }
+            for task_id in &result.succeeded {
+                service.container.emit(ContainerEvent::TaskUpdated {
+                    project_id,
+                    task_id: *task_id,
+                });
+            }
+            Ok(result)
+        })
+    }
+
+    /// Выполнить массовую операцию, запомнив снимок проекта для отмены. Снимок попадает
+    /// в результат, только если операция что-то изменила
+    fn with_undo(
+        &mut self,
+        project_id: Uuid,
+        operation: impl FnOnce(&mut Self) -> Result<BulkResult>,
+    ) -> Result<BulkResult> {
+        let project = self
+            .container
+            .get_project(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+        let undo = BulkUndo {
+            project_id,
+            tasks: project.tasks.clone(),
+            allocations: self
+                .container
+                .resource_pool()
+                .get_project_allocations(&project_id)
+                .into_iter()
+                .cloned()
+                .collect(),
+        };
+        let mut result = operation(self)?;
+        tracing::debug!(
+            %project_id,
+            succeeded = result.succeeded.len(),
+            failed = result.failures.len(),
+            "bulk operation finished"
+        );
+        if !result.succeeded.is_empty() {
+            result.undo = Some(undo);
+        }
+        Ok(result)
+    }
 
+    /// Перенести назначения задач под их новые даты `dates` (задача -> начало, окончание).
+    /// Назначения сдвигаются вместе с началом задачи и обрезаются по ее окончанию.
+    /// Все или ничего: если какое-то назначение не помещается или ресурс занят,
+    /// пул не меняется, а в ошибке перечисляются назначения
+    fn move_task_allocations(
+        &mut self,
+        project_id: Uuid,
+        dates: &HashMap<Uuid, (DateTime<Utc>, DateTime<Utc>)>,
+    ) -> Result<()> {
+        let project = self
+            .container
+            .get_project(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+        let pool = self.container.resource_pool();
+        let mut moves = vec![];
+        let mut errors = vec![];
+        for (id, (start, end)) in dates {
+            let Some(task) = project.tasks.get(id) else {
+                continue;
+            };
+            let shift = *start - *task.get_date_start();
+            for allocation_id in task.get_resource_allocations() {
+                let Some(allocation) = pool.get_allocation(allocation_id) else {
+                    continue;
+                };
+                let window = allocation.get_time_window();
+                let moved_start = window.date_start + shift;
+                let moved_end = (window.date_end + shift).min(*end);
+                if moved_start >= moved_end {
+                    errors.push(format!(
+                        "{}: allocation {} no longer fits the task",
+                        task.get_display_name(),
+                        allocation_id
+                    ));
+                    continue;
+                }
+                if moved_end < window.date_end + shift {
+                    tracing::warn!(%allocation_id, end = %moved_end, "allocation trimmed to task end");
+                }
+                moves.push((*allocation_id, TimeWindow::new(moved_start, moved_end)?));
+            }
+        }
+        if !errors.is_empty() {
+            errors.sort();
+            tracing::debug!(?errors, "date change rejected: allocations would not fit");
+            anyhow::bail!("Allocations would become invalid: {}", errors.join("; "));
+        }
+        if moves.is_empty() {
+            return Ok(());
+        }
+        let calendar = self
+            .container
+            .calendar(&project_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Calendar for project {} not found", project_id))?;
+        let errors = self
+            .container
+            .resource_pool_mut()
+            .move_allocations(&moves, &calendar);
+        if !errors.is_empty() {
+            let errors: Vec<String> = errors
+                .into_iter()
+                .map(|(id, error)| format!("{}: {}", id, error))
+                .collect();
+            anyhow::bail!("Allocations would become invalid: {}", errors.join("; "));
+        }
         Ok(())
     }
 
+    /// Сдвиг одной задачи на `days` рабочих дней вместе с назначениями
+    fn shift_task(
+        &mut self,
+        project_id: Uuid,
+        task_id: Uuid,
+        calendar: &ProjectCalendar,
+        days: i64,
+    ) -> Result<()> {
+        let task = self
+            .get_task_by_id(&project_id, &task_id)
+            .ok_or_else(|| anyhow::anyhow!("Task not found"))?;
+        let (start, end) = (*task.get_date_start(), *task.get_date_end());
+        let local_start = calendar.local_date(start);
+        let delta = calendar.shift_working_days(local_start, days)? - local_start;
+        self.update_task_dates(project_id, task_id, start + delta, end + delta, false)
+    }
+
     // Присвоить задаче ресурс
     // Мы должны создать запрос на аллокацию ресурса и отправить его в ресурсы, чтобы мы смогли их назначить
     // Вообще предполагается, что ресурс назначается на весь промежуток задачи, однако мы можем явно указать период, на который ресурс будет зайствован
     // В этом случае надо бы проверить, что это окно входит в промежуток задачи
+    /// Назначение ресурса на задачу
+    ///
+    /// `priority` - приоритет назначения при выравнивании загрузки,
+    /// без него используется `DEFAULT_ALLOCATION_PRIORITY`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use logic::{
+    ///     BasicGettersForStructures, Project, ProjectContainer, RateMeasure, ResourceService,
+    ///     SingleProjectContainer, TaskService,
+    /// };
+    ///
+    /// let mut container = SingleProjectContainer::new();
+    /// let project = Project::new(
+    ///     "Website",
+    ///     "",
+    ///     Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+    ///     Utc.with_ymd_and_hms(2026, 6, 30, 0, 0, 0).unwrap(),
+    /// )?;
+    /// let project_id = *project.get_id();
+    /// container.add_project(project)?;
+    ///
+    /// let mut resource_service = ResourceService::new(&mut container);
+    /// let anna = resource_service.create_resource("Anna", 100.0, RateMeasure::Hourly)?;
+    /// let anna_id = anna.id;
+    /// resource_service.add_resource(anna)?;
+    ///
+    /// let mut task_service = TaskService::new(&mut container);
+    /// let task = task_service.create_regular_task(
+    ///     project_id,
+    ///     "Design".into(),
+    ///     Utc.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap(),
+    ///     Utc.with_ymd_and_hms(2026, 2, 7, 0, 0, 0).unwrap(),
+    ///     None,
+    /// )?;
+    /// task_service.allocate_resource(project_id, *task.get_id(), anna_id, 0.5, None, None)?;
+    ///
+    /// // 5 рабочих дней * 8 часов * 50% * 100 в час
+    /// assert_eq!(task_service.calculate_task_cost(&project_id, task.get_id())?, 2000.0);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
     pub fn allocate_resource(
         &mut self,
         project_id: Uuid,
@@ -276,37 +764,69 @@ impl<'a, C: ProjectContainer> TaskService<'a, C> {
         resource_id: Uuid,
         engagement: f64,
         time_window: Option<TimeWindow>,
+        priority: Option<u8>,
+    ) -> anyhow::Result<Uuid> {
+        self.allocate_resource_with_ids(
+            project_id,
+            task_id,
+            resource_id,
+            engagement,
+            time_window,
+            priority,
+            &mut RandomIds,
+        )
+    }
+
+    /// Назначение ресурса на задачу с id назначения из `ids` (например, SequentialIds
+    /// для воспроизводимых демо-данных)
+    #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(skip_all, fields(%project_id, %task_id, %resource_id, engagement = engagement))]
+    pub fn allocate_resource_with_ids(
+        &mut self,
+        project_id: Uuid,
+        task_id: Uuid,
+        resource_id: Uuid,
+        engagement: f64,
+        time_window: Option<TimeWindow>,
+        priority: Option<u8>,
+        ids: &mut impl IdGenerator,
     ) -> anyhow::Result<Uuid> {
         let (actual_window, task_start, task_end) = {
             let project = self
                 .container
                 .get_project(&project_id)
                 .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+            // Задача ищется в самом проекте, поэтому назначить задачу чужого проекта нельзя
             let task = project
                 .tasks
                 .get(&task_id)
-                .ok_or_else(|| anyhow::anyhow!("Task not found"))?;
+                .ok_or(AllocationErrors::TaskNotInProject {
+                    task_id,
+                    project_id,
+                })?;
 
             let task_start = *task.get_date_start();
             let task_end = *task.get_date_end();
+            let task_window = TimeWindow::new(task_start, task_end)?;
 
-            // Определяем окно: либо переданное, либо вся задача
+            // Определяем окно: либо переданное (часть задачи), либо вся задача
             let window = match time_window {
-                Some(w) => {
-                    // Проверка, что окно внутри задачи
-                    if w.date_start < task_start || w.date_end > task_end {
-                        anyhow::bail!(
-                            "Time window {:?} is not within task dates [{:?}, {:?}]",
-                            w,
-                            task_start,
-                            task_end
-                        );
-                    }
-                    w
+                Some(w) if !task_window.contains_window(&w) => {
+                    tracing::debug!(window = ?w, "allocation window outside the task");
+                    anyhow::bail!(
+                        "Time window {} - {} is not within task dates {} - {}",
+                        w.date_start.format("%Y-%m-%d %H:%M"),
+                        w.date_end.format("%Y-%m-%d %H:%M"),
+                        task_start.format("%Y-%m-%d %H:%M"),
+                        task_end.format("%Y-%m-%d %H:%M")
+                    );
                 }
-                None => TimeWindow::new(task_start, task_end)?,
+                Some(w) => w,
+                None => task_window,
             };
-
+            project
+                .validation_policy
+                .check_allocation(window.date_start, window.date_end)?;
             (window, task_start, task_end)
         };
 
@@ -318,13 +838,16 @@ impl<'a, C: ProjectContainer> TaskService<'a, C> {
 
         // Шаг 4: Создаём запрос
         let request =
-            AllocationRequest::new(resource_id, task_id, project_id, engagement, actual_window);
+            AllocationRequest::new(resource_id, task_id, project_id, engagement, actual_window)?
+                .with_priority(priority.unwrap_or(DEFAULT_ALLOCATION_PRIORITY))
+                .with_id(ids.next_id());
 
         // Шаг 5: Выделяем ресурс (мутабельно, но контейнер свободен)
         let allocation_id = self
             .container
             .resource_pool_mut()
-            .allocate(request, &calendar)?;
+            .allocate(request, &calendar)
+            .inspect_err(|e| tracing::debug!(error = %e, "allocation rejected"))?;
 
         // Шаг 6: Снова получаем мутабельный доступ к задаче и сохраняем ID
         {
@@ -343,6 +866,7 @@ impl<'a, C: ProjectContainer> TaskService<'a, C> {
     }
 
     // Добавить зависимость задач
+    #[tracing::instrument(skip_all, fields(%project_id, %task_id, %depends_on))]
     pub fn add_dependency(
         &mut self,
         project_id: Uuid,
@@ -384,7 +908,248 @@ impl<'a, C: ProjectContainer> TaskService<'a, C> {
             .ok_or_else(|| anyhow::anyhow!("Task not found"))?;
 
         task.add_dependency(dependency);
+        self.container.emit(ContainerEvent::TaskUpdated {
+            project_id,
+            task_id,
+        });
+
+        Ok(())
+    }
+
+    /// Сдвиг задач вперед по зависимостям
+    ///
+    /// В порядке зависимостей начало задачи переносится на максимум из окончаний предшественников
+    /// по блокирующим зависимостям с учетом lag (только вперед). Неблокирующие зависимости
+    /// не сдвигают задачи, их нарушения видны через `Project::dependency_violations`.
+    /// Длительность сохраняется, нерабочие дни по календарю проекта пропускаются (если рабочего
+    /// дня нет - ошибка). Назначения ресурсов переносятся вместе с задачами, как в
+    /// `update_task_dates`. Если какая-то задача выходит за окончание проекта или назначение
+    /// не помещается - ничего не меняется, а в ошибке перечисляются такие задачи и назначения.
+    #[tracing::instrument(skip_all, fields(%project_id))]
+    pub fn reschedule_forward(&mut self, project_id: Uuid) -> Result<()> {
+        let project = self
+            .container
+            .get_project(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+        let calendar = self
+            .container
+            .calendar(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Calendar for project {} not found", project_id))?;
+
+        let order = dependency_order(project)?;
+        let mut dates: HashMap<Uuid, (DateTime<Utc>, DateTime<Utc>)> = project
+            .tasks
+            .values()
+            .map(|t| (*t.get_id(), (*t.get_date_start(), *t.get_date_end())))
+            .collect();
+        let mut moved = vec![];
+        for task_id in order {
+            let task = &project.tasks[&task_id];
+            let earliest = task
+                .get_dependencies()
+                .iter()
+                .filter(|dep| dep.is_blocking())
+                .filter_map(|dep| {
+                    dates
+                        .get(&dep.depends_on)
+                        .map(|(_, end)| *end + dep.lag_or_zero())
+                })
+                .max();
+            let (start, end) = dates[&task_id];
+            let Some(new_start) = earliest.filter(|e| *e > start) else {
+                continue;
+            };
+            // Нельзя начинать в выходной или праздник
+            let new_start = calendar.snap_moment_forward(new_start)?;
+            dates.insert(task_id, (new_start, new_start + (end - start)));
+            moved.push(task_id);
+        }
 
+        let mut offenders: Vec<String> = moved
+            .iter()
+            .filter(|id| dates[id].1 > *project.get_date_end())
+            .map(|id| project.tasks[id].get_display_name())
+            .collect();
+        if !offenders.is_empty() {
+            offenders.sort();
+            tracing::debug!(?offenders, "reschedule rejected: tasks past project end");
+            anyhow::bail!(
+                "Tasks would end after project end: {}",
+                offenders.join(", ")
+            );
+        }
+
+        // Назначения переносятся вместе с задачами
+        let moved_dates: HashMap<Uuid, (DateTime<Utc>, DateTime<Utc>)> =
+            moved.iter().map(|id| (*id, dates[id])).collect();
+        self.move_task_allocations(project_id, &moved_dates)?;
+
+        let project = self
+            .container
+            .get_project_mut(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+        let mut parents = vec![];
+        for task_id in &moved {
+            let (start, end) = dates[task_id];
+            if let Some(task) = project.tasks.get_mut(task_id) {
+                tracing::warn!(
+                    %task_id,
+                    from = %task.date_start,
+                    to = %start,
+                    "task moved by reschedule"
+                );
+                task.date_start = start;
+                task.date_end = end;
+                if let Some(parent_id) = task.parent_id {
+                    parents.push(parent_id);
+                }
+            }
+        }
+        for task_id in moved {
+            self.container.emit(ContainerEvent::TaskUpdated {
+                project_id,
+                task_id,
+            });
+        }
+        for parent_id in parents {
+            self.update_summary_dates(&project_id, parent_id)?;
+        }
+        Ok(())
+    }
+
+    /// Изменить даты задачи
+    ///
+    /// Без `cascade` изменение отклоняется, если нарушит блокирующие зависимости последователей.
+    /// С `cascade` при сдвиге окончания вперед все последователи по блокирующим зависимостям
+    /// (включая транзитивных) сдвигаются на ту же величину; как и в `reschedule_forward`,
+    /// начало, попавшее на нерабочий день, переносится на следующий рабочий (длительность
+    /// сохраняется), а последователи такой задачи сдвигаются уже на ее итоговый сдвиг.
+    /// Назначения ресурсов переносятся вместе с задачами; если какое-то назначение не помещается
+    /// в новое окно или ресурс занят - ничего не меняется, а в ошибке перечисляются назначения.
+    #[tracing::instrument(skip_all, fields(%project_id, %task_id, cascade = cascade))]
+    pub fn update_task_dates(
+        &mut self,
+        project_id: Uuid,
+        task_id: Uuid,
+        new_start: DateTime<Utc>,
+        new_end: DateTime<Utc>,
+        cascade: bool,
+    ) -> Result<()> {
+        let project = self
+            .container
+            .get_project(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+        let calendar = self
+            .container
+            .calendar(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Calendar for project {} not found", project_id))?;
+        let task = project
+            .tasks
+            .get(&task_id)
+            .ok_or_else(|| anyhow::anyhow!("Task not found"))?;
+        if task.is_summary {
+            anyhow::bail!("Cannot set start/end dates for summary task");
+        }
+        if new_start >= new_end {
+            anyhow::bail!("Task start date must be before end date");
+        }
+        project.validation_policy.check_task(new_start, new_end)?;
+        if new_start < *project.get_date_start() || new_end > *project.get_date_end() {
+            anyhow::bail!("Task would be moved outside project dates");
+        }
+
+        // Последователи по блокирующим зависимостям
+        let successors = |id: Uuid| {
+            project.tasks.values().filter(move |t| {
+                t.get_dependencies()
+                    .iter()
+                    .any(|dep| dep.is_blocking() && dep.depends_on == id)
+            })
+        };
+        let mut dates = HashMap::new();
+        dates.insert(task_id, (new_start, new_end));
+        let delta = new_end - *task.get_date_end();
+        if !cascade {
+            let mut violated: Vec<String> = successors(task_id)
+                .filter(|s| {
+                    s.get_dependencies().iter().any(|dep| {
+                        dep.is_blocking()
+                            && dep.depends_on == task_id
+                            && *s.get_date_start() < new_end + dep.lag_or_zero()
+                    })
+                })
+                .map(|s| s.get_display_name())
+                .collect();
+            if !violated.is_empty() {
+                violated.sort();
+                tracing::debug!(?violated, "date change rejected: successors would overlap");
+                anyhow::bail!(
+                    "Dependent tasks would start before this task ends: {}",
+                    violated.join(", ")
+                );
+            }
+        } else if delta > TimeDelta::zero() {
+            let mut queue = vec![(task_id, delta)];
+            while let Some((id, shift)) = queue.pop() {
+                for successor in successors(id) {
+                    let successor_id = *successor.get_id();
+                    if successor_id != task_id && !dates.contains_key(&successor_id) {
+                        let (start, end) = (*successor.get_date_start(), *successor.get_date_end());
+                        // Нельзя начинать в выходной или праздник
+                        let new_start = calendar.snap_moment_forward(start + shift)?;
+                        dates.insert(successor_id, (new_start, new_start + (end - start)));
+                        queue.push((successor_id, new_start - start));
+                    }
+                }
+            }
+            let mut offenders: Vec<String> = dates
+                .iter()
+                .filter(|(_, (_, end))| *end > *project.get_date_end())
+                .map(|(id, _)| project.tasks[id].get_display_name())
+                .collect();
+            if !offenders.is_empty() {
+                offenders.sort();
+                tracing::debug!(?offenders, "cascade rejected: tasks past project end");
+                anyhow::bail!(
+                    "Tasks would end after project end: {}",
+                    offenders.join(", ")
+                );
+            }
+        }
+
+        self.move_task_allocations(project_id, &dates)?;
+
+        let project = self
+            .container
+            .get_project_mut(&project_id)
+            .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+        let mut parents = vec![];
+        for (id, (start, end)) in &dates {
+            if let Some(task) = project.tasks.get_mut(id) {
+                if *id != task_id {
+                    tracing::warn!(
+                        task_id = %id,
+                        from = %task.date_start,
+                        to = %start,
+                        "successor moved by cascade"
+                    );
+                }
+                task.date_start = *start;
+                task.date_end = *end;
+                if let Some(parent_id) = task.parent_id {
+                    parents.push(parent_id);
+                }
+            }
+        }
+        for task_id in dates.into_keys() {
+            self.container.emit(ContainerEvent::TaskUpdated {
+                project_id,
+                task_id,
+            });
+        }
+        for parent_id in parents {
+            self.update_summary_dates(&project_id, parent_id)?;
+        }
         Ok(())
     }
 
@@ -417,10 +1182,11 @@ impl<'a, C: ProjectContainer> TaskService<'a, C> {
             let resource_pool = self.container.resource_pool();
 
             for alloc_id in task.get_resource_allocations() {
-                let calendar = self.container.calendar(project_id).ok_or_else(|| {
-                    anyhow::anyhow!("Календарь для проекта {} не найден", project_id)
-                })?;
-                task_cost += resource_pool.calculate_allocation_cost(alloc_id, calendar)?;
+                task_cost += resource_pool.calculate_allocation_cost(
+                    alloc_id,
+                    calendar,
+                    &project.rate_config,
+                )?;
             }
 
             Ok(task_cost)
@@ -474,6 +1240,115 @@ impl<'a, C: ProjectContainer> TaskService<'a, C> {
         Ok(total)
     }
 
+    /// Импорт задач из CSV
+    ///
+    /// Ожидаются колонки name, start, end. Если первая строка - заголовок, колонки ищутся по именам
+    /// (подходит и файл из `Project::export_tasks_csv`). Даты - ISO-8601 или YYYY-MM-DD.
+    /// Любая ошибочная строка прерывает импорт целиком, в проект ничего не добавляется.
+    pub fn import_tasks_csv(&mut self, project_id: Uuid, csv: &str) -> Result<Vec<Uuid>> {
+        let (rows, errors) = self.parse_tasks_csv(&project_id, csv)?;
+        if let Some(error) = errors.into_iter().next() {
+            return Err(anyhow::Error::msg(error));
+        }
+        self.insert_imported_tasks(&project_id, rows)
+    }
+
+    /// Импорт задач из CSV с пропуском ошибочных строк
+    ///
+    /// Возвращает id созданных задач и описания пропущенных строк с номерами.
+    pub fn import_tasks_csv_skip_invalid(
+        &mut self,
+        project_id: Uuid,
+        csv: &str,
+    ) -> Result<(Vec<Uuid>, Vec<String>)> {
+        let (rows, errors) = self.parse_tasks_csv(&project_id, csv)?;
+        for error in &errors {
+            tracing::warn!(%project_id, %error, "csv row skipped on import");
+        }
+        let ids = self.insert_imported_tasks(&project_id, rows)?;
+        Ok((ids, errors))
+    }
+
+    fn parse_tasks_csv(&self, project_id: &Uuid, csv: &str) -> Result<(Vec<Task>, Vec<String>)> {
+        let project = self
+            .container
+            .get_project(project_id)
+            .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+
+        let mut lines = csv
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .filter(|(_, line)| !line.trim().is_empty())
+            .peekable();
+
+        // Колонки по заголовку, без заголовка - name, start, end
+        let mut columns = (0, 1, 2);
+        if let Some((_, header)) = lines.peek() {
+            let header: Vec<String> = split_csv_line(header)
+                .iter()
+                .map(|c| c.trim().to_lowercase())
+                .collect();
+            let find = |names: &[&str]| header.iter().position(|c| names.contains(&c.as_str()));
+            if let Some(name) = find(&["name"]) {
+                columns = (
+                    name,
+                    find(&["start", "date_start"])
+                        .ok_or_else(|| anyhow::anyhow!("CSV line 1: no start column"))?,
+                    find(&["end", "date_end"])
+                        .ok_or_else(|| anyhow::anyhow!("CSV line 1: no end column"))?,
+                );
+                lines.next();
+            }
+        }
+
+        let mut tasks = vec![];
+        let mut errors = vec![];
+        for (line_number, line) in lines {
+            let fields = split_csv_line(line);
+            let parsed = (|| -> Result<Task> {
+                let field = |index: usize| {
+                    fields
+                        .get(index)
+                        .map(|f| f.trim())
+                        .ok_or_else(|| anyhow::anyhow!("missing column {}", index + 1))
+                };
+                let start = parse_csv_date(field(columns.1)?)?;
+                let end = parse_csv_date(field(columns.2)?)?;
+                // То же правило, что и в create_regular_task
+                if start < *project.get_date_start() || end > *project.get_date_end() {
+                    anyhow::bail!("Task dates must be within project dates");
+                }
+                project.validation_policy.check_task(start, end)?;
+                Ok(Task::new_regular(field(columns.0)?, start, end, None)?)
+            })();
+            match parsed {
+                Ok(task) => tasks.push(task),
+                Err(e) => errors.push(format!("CSV line {}: {}", line_number, e)),
+            }
+        }
+        Ok((tasks, errors))
+    }
+
+    fn insert_imported_tasks(&mut self, project_id: &Uuid, tasks: Vec<Task>) -> Result<Vec<Uuid>> {
+        let project = self
+            .container
+            .get_project_mut(project_id)
+            .ok_or_else(|| anyhow::anyhow!("Project not found"))?;
+        // Импортированные задачи получают новые номера проекта
+        let ids: Vec<Uuid> = tasks
+            .into_iter()
+            .map(|task| *project.insert_task(task).get_id())
+            .collect();
+        for task_id in &ids {
+            self.container.emit(ContainerEvent::TaskCreated {
+                project_id: *project_id,
+                task_id: *task_id,
+            });
+        }
+        Ok(ids)
+    }
+
     pub fn calculate_project_time(&self, project_id: Uuid) -> anyhow::Result<f64> {
         let tasks = self.get_root_tasks(project_id);
         let mut total = 0.0;
@@ -484,12 +1359,43 @@ impl<'a, C: ProjectContainer> TaskService<'a, C> {
     }
 }
 
+/// Разбор строки CSV с учетом кавычек ("a, b" и "" внутри кавычек)
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+fn parse_csv_date(value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc())
+        .map_err(|_| anyhow::anyhow!("invalid date '{}'", value))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         RateMeasure, ResourceService,
-        base_structures::{Project, SingleProjectContainer},
+        base_structures::{MultiProjectContainer, Project, SingleProjectContainer},
+        export::ExportRedaction,
     };
     use chrono::{Duration, TimeZone, Utc};
 
@@ -575,6 +1481,25 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_delete_summary_task_reparents_children() -> anyhow::Result<()> {
+        let (mut container, project_id, task_id, _, _) = setup_task();
+        let mut task_service = TaskService::new(&mut container);
+        let outer_id = *task_service
+            .create_summary_task(project_id, "Outer".into(), None)?
+            .get_id();
+        let inner_id = *task_service
+            .create_summary_task(project_id, "Inner".into(), Some(outer_id))?
+            .get_id();
+        task_service.update_task(project_id, task_id, None, None, None, Some(inner_id))?;
+
+        task_service.delete_task(project_id, inner_id)?;
+
+        let task = task_service.get_task_by_id(&project_id, &task_id).unwrap();
+        assert_eq!(task.parent_id, Some(outer_id));
+        Ok(())
+    }
+
     // 1. Пользователь не передал окно → окно = всей задаче.
     #[test]
     fn test_allocate_resource_without_window() -> anyhow::Result<()> {
@@ -591,6 +1516,7 @@ mod tests {
                 resource_id,
                 engagement,
                 None,
+                None,
             )?;
             let task = task_service
                 .get_project(&project_id)
@@ -611,6 +1537,51 @@ mod tests {
         Ok(())
     }
 
+    // Задача другого проекта контейнера не назначается в этом проекте
+    #[test]
+    fn test_allocate_resource_rejects_task_from_other_project() -> anyhow::Result<()> {
+        let mut container = MultiProjectContainer::new();
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap();
+        let first = Project::new("First", "", start, end)?;
+        let second = Project::new("Second", "", start, end)?;
+        let (first_id, second_id) = (*first.get_id(), *second.get_id());
+        container.add_project(first)?;
+        container.add_project(second)?;
+        let mut resource_service = ResourceService::new(&mut container);
+        let resource = resource_service.create_resource("Dev", 100.0, RateMeasure::Hourly)?;
+        let resource_id = resource.id;
+        resource_service.add_resource(resource)?;
+
+        let mut task_service = TaskService::new(&mut container);
+        let task = task_service.create_regular_task(
+            second_id,
+            "Foreign".into(),
+            Utc.with_ymd_and_hms(2025, 2, 3, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 2, 7, 0, 0, 0).unwrap(),
+            None,
+        )?;
+        let task_id = *task.get_id();
+        let error = task_service
+            .allocate_resource(first_id, task_id, resource_id, 0.5, None, None)
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<AllocationErrors>(),
+            Some(&AllocationErrors::TaskNotInProject {
+                task_id,
+                project_id: first_id
+            })
+        );
+        assert!(
+            task_service
+                .container
+                .resource_pool()
+                .allocations()
+                .is_empty()
+        );
+        Ok(())
+    }
+
     // 2. Пользователь передал корректное окно (внутри задачи).
     #[test]
     fn test_allocate_resource_with_valid_window() -> anyhow::Result<()> {
@@ -627,6 +1598,7 @@ mod tests {
                 resource_id,
                 engagement,
                 Some(window),
+                None,
             )?;
             let task = task_service
                 .get_project(&project_id)
@@ -666,6 +1638,7 @@ mod tests {
             resource_id,
             engagement,
             Some(window_before),
+            None,
         );
         assert!(result.is_err());
 
@@ -678,6 +1651,7 @@ mod tests {
             resource_id,
             engagement,
             Some(window_after),
+            None,
         );
         assert!(result.is_err());
 
@@ -690,12 +1664,37 @@ mod tests {
             resource_id,
             engagement,
             Some(window_outside),
+            None,
         );
         assert!(result.is_err());
 
         Ok(())
     }
 
+    #[test]
+    fn test_allocate_resource_window_past_task_end() -> anyhow::Result<()> {
+        let (mut container, project_id, task_id, _, task_end) = setup_task();
+        let resource_id = setup_resource(&mut container);
+        let mut task_service = TaskService::new(&mut container);
+
+        // Окно выходит за окончание задачи на час
+        let window = TimeWindow::new(task_end - Duration::days(1), task_end + Duration::hours(1))?;
+        let err = task_service
+            .allocate_resource(project_id, task_id, resource_id, 0.5, Some(window), None)
+            .unwrap_err();
+        assert!(err.to_string().contains("is not within task dates"));
+        let task = task_service.get_task_by_id(&project_id, &task_id).unwrap();
+        assert!(task.get_resource_allocations().is_empty());
+        assert!(
+            task_service
+                .container
+                .resource_pool()
+                .get_resource_existing_allocations(&resource_id)
+                .is_empty()
+        );
+        Ok(())
+    }
+
     // 4. (Дополнительно) Пользователь передал окно, равное задаче — должно работать.
     #[test]
     fn test_allocate_resource_with_window_equal_task() -> anyhow::Result<()> {
@@ -711,6 +1710,7 @@ mod tests {
             resource_id,
             engagement,
             Some(window),
+            None,
         )?;
         let task = task_service
             .get_project(&project_id)
@@ -885,4 +1885,617 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_import_tasks_csv() -> anyhow::Result<()> {
+        let (mut container, project_id, _, _, _) = setup_task();
+        let mut task_service = TaskService::new(&mut container);
+
+        let csv = "name,start,end\n\
+                   Design,2025-03-03,2025-03-07\n\
+                   \"Build, test\",2025-03-10T00:00:00Z,2025-03-14T12:00:00Z\n";
+        let ids = task_service.import_tasks_csv(project_id, csv)?;
+        assert_eq!(ids.len(), 2);
+
+        let build = task_service.get_task_by_id(&project_id, &ids[1]).unwrap();
+        assert_eq!(build.name, "Build, test");
+        assert_eq!(
+            *build.get_date_end(),
+            Utc.with_ymd_and_hms(2025, 3, 14, 12, 0, 0).unwrap()
+        );
+        // Первая задача создана в setup_task, импортированные нумеруются дальше
+        assert_eq!(build.get_code().as_deref(), Some("T-3"));
+        assert_eq!(task_service.get_all_tasks(project_id).len(), 3);
+
+        // Выгрузка проекта читается обратно
+        let exported = task_service
+            .get_project(&project_id)
+            .unwrap()
+            .export_tasks_csv(
+                task_service.container.resource_pool(),
+                &ExportRedaction::default(),
+            );
+        let reimported = task_service.import_tasks_csv(project_id, &exported)?;
+        assert_eq!(reimported.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_tasks_csv_bad_date() -> anyhow::Result<()> {
+        let (mut container, project_id, _, _, _) = setup_task();
+        let mut task_service = TaskService::new(&mut container);
+
+        let csv = "Design,2025-03-03,2025-03-07\n\
+                   Build,2025-03-32,2025-04-07\n\
+                   Release,2025-12-01,2026-01-15\n";
+        let error = task_service
+            .import_tasks_csv(project_id, csv)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("CSV line 2"), "{}", error);
+        assert!(error.contains("invalid date"), "{}", error);
+        // Ничего не добавлено
+        assert_eq!(task_service.get_all_tasks(project_id).len(), 1);
+
+        let (ids, errors) = task_service.import_tasks_csv_skip_invalid(project_id, csv)?;
+        assert_eq!(ids.len(), 1);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[1].starts_with("CSV line 3"));
+        assert!(errors[1].contains("within project dates"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_reschedule_forward_moves_dependent_task() -> anyhow::Result<()> {
+        let (mut container, project_id, design_id, _, _) = setup_task();
+        let resource_id = setup_resource(&mut container);
+        let mut task_service = TaskService::new(&mut container);
+        // Проектирование 1-15 февраля, сборка запланирована раньше его окончания
+        let build = task_service.create_regular_task(
+            project_id,
+            "Build".into(),
+            Utc.with_ymd_and_hms(2025, 2, 10, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 2, 14, 0, 0, 0).unwrap(),
+            None,
+        )?;
+        let build_id = *build.get_id();
+        task_service.add_dependency(
+            project_id,
+            build_id,
+            design_id,
+            DependencyType::Blocking,
+            Some(Duration::days(1)),
+        )?;
+        let allocation_id =
+            task_service.allocate_resource(project_id, build_id, resource_id, 0.5, None, None)?;
+
+        task_service.reschedule_forward(project_id)?;
+
+        // 15 февраля + 1 день = воскресенье 16-го, переносим на понедельник 17-го
+        let build = task_service.get_task_by_id(&project_id, &build_id).unwrap();
+        let moved = TimeWindow::new(
+            Utc.with_ymd_and_hms(2025, 2, 17, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 2, 21, 0, 0, 0).unwrap(),
+        )?;
+        assert_eq!(*build.get_date_start(), moved.date_start);
+        assert_eq!(*build.get_date_end(), moved.date_end);
+        // Назначение переносится вместе с задачей
+        assert_eq!(
+            *task_service
+                .container
+                .resource_pool()
+                .get_allocation(&allocation_id)
+                .unwrap()
+                .get_time_window(),
+            moved
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_reschedule_forward_fails_without_working_days() -> anyhow::Result<()> {
+        let (mut container, project_id, design_id, _, _) = setup_task();
+        // После окончания проектирования рабочих дней больше нет
+        let calendar = container.calendar_mut(&project_id).unwrap();
+        for date in NaiveDate::from_ymd_opt(2025, 2, 16)
+            .unwrap()
+            .iter_days()
+            .take(400)
+        {
+            calendar.add_holiday(date);
+        }
+        let mut task_service = TaskService::new(&mut container);
+        let build_id = *task_service
+            .create_regular_task(
+                project_id,
+                "Build".into(),
+                Utc.with_ymd_and_hms(2025, 2, 10, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 2, 14, 0, 0, 0).unwrap(),
+                None,
+            )?
+            .get_id();
+        task_service.add_dependency(
+            project_id,
+            build_id,
+            design_id,
+            DependencyType::Blocking,
+            Some(Duration::days(1)),
+        )?;
+
+        assert!(task_service.reschedule_forward(project_id).is_err());
+        let build = task_service.get_task_by_id(&project_id, &build_id).unwrap();
+        assert_eq!(
+            *build.get_date_start(),
+            Utc.with_ymd_and_hms(2025, 2, 10, 0, 0, 0).unwrap()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_task_dates_cascade() -> anyhow::Result<()> {
+        let (mut container, project_id, task1_id, task2_id) = setup_two_tasks();
+        let resource_id = setup_resource(&mut container);
+        let mut task_service = TaskService::new(&mut container);
+        task_service.add_dependency(
+            project_id,
+            task2_id,
+            task1_id,
+            DependencyType::Blocking,
+            None,
+        )?;
+        let allocation_id =
+            task_service.allocate_resource(project_id, task2_id, resource_id, 0.5, None, None)?;
+
+        // Task1 заканчивается 13 февраля, Task2 начинается 11-го - без cascade отказ
+        let new_start = Utc.with_ymd_and_hms(2025, 2, 3, 0, 0, 0).unwrap();
+        let new_end = Utc.with_ymd_and_hms(2025, 2, 13, 0, 0, 0).unwrap();
+        let err = task_service
+            .update_task_dates(project_id, task1_id, new_start, new_end, false)
+            .unwrap_err();
+        assert!(err.to_string().contains("Task2"));
+        let task1 = task_service.get_task_by_id(&project_id, &task1_id).unwrap();
+        assert_eq!(
+            *task1.get_date_end(),
+            Utc.with_ymd_and_hms(2025, 2, 10, 0, 0, 0).unwrap()
+        );
+
+        // С cascade последователь и его назначение сдвигаются на те же 3 дня
+        task_service.update_task_dates(project_id, task1_id, new_start, new_end, true)?;
+        let task1 = task_service.get_task_by_id(&project_id, &task1_id).unwrap();
+        assert_eq!(*task1.get_date_start(), new_start);
+        assert_eq!(*task1.get_date_end(), new_end);
+        let task2 = task_service.get_task_by_id(&project_id, &task2_id).unwrap();
+        let moved = TimeWindow::new(
+            Utc.with_ymd_and_hms(2025, 2, 14, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 2, 23, 0, 0, 0).unwrap(),
+        )?;
+        assert_eq!(*task2.get_date_start(), moved.date_start);
+        assert_eq!(*task2.get_date_end(), moved.date_end);
+        let allocation = task_service
+            .container
+            .resource_pool()
+            .get_allocation(&allocation_id)
+            .unwrap();
+        assert_eq!(*allocation.get_time_window(), moved);
+        assert!(task2.is_resource_assigned(&allocation_id));
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_task_dates_cascade_skips_weekend() -> anyhow::Result<()> {
+        let (mut container, project_id, task1_id, task2_id) = setup_two_tasks();
+        let mut task_service = TaskService::new(&mut container);
+        let task3_id = *task_service
+            .create_regular_task(
+                project_id,
+                "Task3".into(),
+                Utc.with_ymd_and_hms(2025, 2, 21, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 2, 25, 0, 0, 0).unwrap(),
+                None,
+            )?
+            .get_id();
+        for (successor, predecessor) in [(task2_id, task1_id), (task3_id, task2_id)] {
+            task_service.add_dependency(
+                project_id,
+                successor,
+                predecessor,
+                DependencyType::Blocking,
+                None,
+            )?;
+        }
+
+        // Сдвиг на 4 дня: Task2 попал бы на субботу 15 февраля и переносится на понедельник 17-е,
+        // Task3 сдвигается на итоговые 6 дней Task2
+        task_service.update_task_dates(
+            project_id,
+            task1_id,
+            Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 2, 14, 0, 0, 0).unwrap(),
+            true,
+        )?;
+        let task2 = task_service.get_task_by_id(&project_id, &task2_id).unwrap();
+        assert_eq!(
+            *task2.get_date_start(),
+            Utc.with_ymd_and_hms(2025, 2, 17, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            *task2.get_date_end(),
+            Utc.with_ymd_and_hms(2025, 2, 26, 0, 0, 0).unwrap()
+        );
+        let task3 = task_service.get_task_by_id(&project_id, &task3_id).unwrap();
+        assert_eq!(
+            *task3.get_date_start(),
+            Utc.with_ymd_and_hms(2025, 2, 27, 0, 0, 0).unwrap()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_task_dates_rejects_allocation_outside_task() -> anyhow::Result<()> {
+        let (mut container, project_id, task_id, task_start, _) = setup_task();
+        let resource_id = setup_resource(&mut container);
+        let mut task_service = TaskService::new(&mut container);
+        let window = TimeWindow::new(
+            task_start + Duration::days(10),
+            task_start + Duration::days(12),
+        )?;
+        task_service.allocate_resource(
+            project_id,
+            task_id,
+            resource_id,
+            1.0,
+            Some(window),
+            None,
+        )?;
+
+        // Задача сокращается до 5 дней - назначение на 11-13 день в нее не помещается
+        let result = task_service.update_task_dates(
+            project_id,
+            task_id,
+            task_start,
+            task_start + Duration::days(5),
+            false,
+        );
+        assert!(result.is_err());
+        let task = task_service.get_task_by_id(&project_id, &task_id).unwrap();
+        assert_eq!(
+            *task.get_date_end(),
+            Utc.with_ymd_and_hms(2025, 2, 15, 0, 0, 0).unwrap()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_bulk_operations_report_failures_per_task() -> anyhow::Result<()> {
+        let (mut container, project_id, design_id, _, _) = setup_task();
+        let resource_id = setup_resource(&mut container);
+        let mut task_service = TaskService::new(&mut container);
+        let allocation_id =
+            task_service.allocate_resource(project_id, design_id, resource_id, 0.5, None, None)?;
+        let release = task_service.create_regular_task(
+            project_id,
+            "Release".into(),
+            Utc.with_ymd_and_hms(2025, 12, 22, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 12, 26, 0, 0, 0).unwrap(),
+            None,
+        )?;
+        let release_id = *release.get_id();
+        let phase = task_service.create_summary_task(project_id, "Phase".into(), None)?;
+        let phase_id = *phase.get_id();
+        let unknown = Uuid::new_v4();
+
+        let result = task_service.bulk_set_status(
+            project_id,
+            &[design_id, release_id, unknown],
+            TaskStatus::Processed,
+        )?;
+        assert_eq!(result.succeeded, [design_id, release_id]);
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].0, unknown);
+        assert_eq!(
+            task_service
+                .get_task_by_id(&project_id, &release_id)
+                .unwrap()
+                .get_status(),
+            &TaskStatus::Processed
+        );
+
+        // Сб 1 февраля переносится на пн 3 февраля, + 5 рабочих дней = пн 10 февраля,
+        // релиз выходит за конец проекта
+        let result = task_service.bulk_shift_working_days(
+            project_id,
+            &[design_id, release_id, phase_id],
+            5,
+        )?;
+        assert!(!result.is_complete());
+        assert_eq!(result.succeeded, [design_id]);
+        let failed: Vec<Uuid> = result.failures.iter().map(|(id, _)| *id).collect();
+        assert_eq!(failed, [release_id, phase_id]);
+        let design = task_service
+            .get_task_by_id(&project_id, &design_id)
+            .unwrap();
+        assert_eq!(
+            *design.get_date_start(),
+            Utc.with_ymd_and_hms(2025, 2, 10, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            *design.get_date_end(),
+            Utc.with_ymd_and_hms(2025, 2, 24, 0, 0, 0).unwrap()
+        );
+        // Назначение сдвигается вместе с задачей
+        let allocation = task_service
+            .container
+            .resource_pool()
+            .get_allocation(&allocation_id)
+            .unwrap();
+        assert_eq!(
+            allocation.get_time_window().date_start,
+            Utc.with_ymd_and_hms(2025, 2, 10, 0, 0, 0).unwrap()
+        );
+        // Огромный сдвиг - ошибка по задаче, а не паника
+        let result = task_service.bulk_shift_working_days(project_id, &[design_id], 999_999_999)?;
+        assert_eq!(result.failures.len(), 1);
+        assert!(result.undo.is_none());
+        let release = task_service
+            .get_task_by_id(&project_id, &release_id)
+            .unwrap();
+        assert_eq!(
+            *release.get_date_start(),
+            Utc.with_ymd_and_hms(2025, 12, 22, 0, 0, 0).unwrap()
+        );
+
+        let result =
+            task_service.bulk_set_parent(project_id, &[design_id, release_id], Some(phase_id))?;
+        assert!(result.is_complete());
+        let phase = task_service.get_task_by_id(&project_id, &phase_id).unwrap();
+        assert_eq!(
+            *phase.get_date_start(),
+            Utc.with_ymd_and_hms(2025, 2, 10, 0, 0, 0).unwrap()
+        );
+        assert!(
+            task_service
+                .bulk_set_parent(project_id, &[design_id], Some(release_id))
+                .is_err()
+        );
+
+        let result = task_service.bulk_delete(project_id, &[release_id, unknown])?;
+        assert_eq!(result.succeeded, [release_id]);
+        assert_eq!(result.failures.len(), 1);
+        assert!(
+            task_service
+                .get_task_by_id(&project_id, &release_id)
+                .is_none()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_bulk_priority_tags_and_undo() -> anyhow::Result<()> {
+        let (mut container, project_id, design_id, _, _) = setup_task();
+        let resource_id = setup_resource(&mut container);
+        let mut task_service = TaskService::new(&mut container);
+        let review = task_service.create_regular_task(
+            project_id,
+            "Review".into(),
+            Utc.with_ymd_and_hms(2025, 3, 3, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 3, 7, 0, 0, 0).unwrap(),
+            None,
+        )?;
+        let review_id = *review.get_id();
+        let allocation_id =
+            task_service.allocate_resource(project_id, review_id, resource_id, 1.0, None, None)?;
+
+        let result = task_service.bulk_set_priority(project_id, &[design_id, review_id], 200)?;
+        assert!(result.is_complete());
+        assert_eq!(
+            task_service
+                .get_task_by_id(&project_id, &review_id)
+                .unwrap()
+                .get_priority(),
+            200
+        );
+
+        task_service.bulk_add_tag(project_id, &[design_id, review_id], " backend ")?;
+        assert!(
+            task_service
+                .bulk_add_tag(project_id, &[design_id], " ")
+                .is_err()
+        );
+        let result = task_service.bulk_remove_tag(project_id, &[design_id], "backend")?;
+        assert!(result.is_complete());
+        let result =
+            task_service.bulk_remove_tag(project_id, &[design_id, review_id], "backend")?;
+        assert_eq!(result.succeeded, [review_id]);
+        assert_eq!(result.failures.len(), 1);
+
+        // Сдвиг возвращается одной записью вместе с назначением
+        let window = *task_service
+            .container
+            .resource_pool()
+            .get_allocation(&allocation_id)
+            .unwrap()
+            .get_time_window();
+        let result = task_service.bulk_shift_working_days(project_id, &[review_id], 3)?;
+        assert!(result.is_complete());
+        task_service.undo_bulk(result.undo.unwrap())?;
+        assert_eq!(
+            *task_service
+                .container
+                .resource_pool()
+                .get_allocation(&allocation_id)
+                .unwrap()
+                .get_time_window(),
+            window
+        );
+
+        // Удаление двух задач освобождает назначения и тоже отменяется одной записью
+        let result = task_service.bulk_delete(project_id, &[design_id, review_id])?;
+        assert!(result.is_complete());
+        assert!(
+            task_service
+                .container
+                .resource_pool()
+                .get_allocation(&allocation_id)
+                .is_none()
+        );
+        assert!(
+            task_service
+                .container
+                .resource_pool()
+                .get_project_allocations(&project_id)
+                .is_empty()
+        );
+        task_service.undo_bulk(result.undo.unwrap())?;
+        let review = task_service
+            .get_task_by_id(&project_id, &review_id)
+            .unwrap();
+        assert_eq!(review.get_priority(), 200);
+        assert!(review.get_tags().is_empty());
+        assert!(
+            task_service
+                .get_task_by_id(&project_id, &design_id)
+                .is_some()
+        );
+        assert!(
+            task_service
+                .container
+                .resource_pool()
+                .get_allocation(&allocation_id)
+                .is_some()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_reschedule_forward_enforces_only_blocking_dependencies() -> anyhow::Result<()> {
+        let (mut container, project_id, design_id, _, _) = setup_task();
+        let mut task_service = TaskService::new(&mut container);
+        // Обе задачи начинаются 10 февраля, до окончания проектирования 15-го
+        let mut ids = vec![];
+        for (name, dep_type) in [
+            ("Docs", DependencyType::NonBlocking),
+            ("Build", DependencyType::Blocking),
+        ] {
+            let task = task_service.create_regular_task(
+                project_id,
+                name.into(),
+                Utc.with_ymd_and_hms(2025, 2, 10, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 2, 14, 0, 0, 0).unwrap(),
+                None,
+            )?;
+            task_service.add_dependency(project_id, *task.get_id(), design_id, dep_type, None)?;
+            ids.push(*task.get_id());
+        }
+        let (docs_id, build_id) = (ids[0], ids[1]);
+        let violations = task_service
+            .get_project(&project_id)
+            .unwrap()
+            .dependency_violations();
+        assert_eq!(violations.len(), 2);
+
+        task_service.reschedule_forward(project_id)?;
+
+        // Неблокирующая зависимость допускает пересечение
+        let docs = task_service.get_task_by_id(&project_id, &docs_id).unwrap();
+        assert_eq!(
+            *docs.get_date_start(),
+            Utc.with_ymd_and_hms(2025, 2, 10, 0, 0, 0).unwrap()
+        );
+        // Блокирующая - нет: 15 февраля суббота, переносим на понедельник 17-го
+        let build = task_service.get_task_by_id(&project_id, &build_id).unwrap();
+        assert_eq!(
+            *build.get_date_start(),
+            Utc.with_ymd_and_hms(2025, 2, 17, 0, 0, 0).unwrap()
+        );
+
+        // Остается только рекомендательное нарушение
+        let violations = task_service
+            .get_project(&project_id)
+            .unwrap()
+            .dependency_violations();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].task_id, docs_id);
+        assert!(!violations[0].is_blocking());
+        assert_eq!(violations[0].overlap, Duration::days(5));
+        Ok(())
+    }
+
+    #[test]
+    fn test_reschedule_forward_rejects_tasks_past_project_end() -> anyhow::Result<()> {
+        let (mut container, project_id, design_id, _, _) = setup_task();
+        let mut task_service = TaskService::new(&mut container);
+        let release = task_service.create_regular_task(
+            project_id,
+            "Release".into(),
+            Utc.with_ymd_and_hms(2025, 12, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 12, 30, 0, 0, 0).unwrap(),
+            None,
+        )?;
+        task_service.add_dependency(
+            project_id,
+            *release.get_id(),
+            design_id,
+            DependencyType::Blocking,
+            Some(Duration::days(300)),
+        )?;
+
+        let error = task_service.reschedule_forward(project_id).unwrap_err();
+        assert!(error.to_string().contains("T-2 Release"), "{}", error);
+        let release = task_service
+            .get_task_by_id(&project_id, release.get_id())
+            .unwrap();
+        assert_eq!(
+            *release.get_date_start(),
+            Utc.with_ymd_and_hms(2025, 12, 1, 0, 0, 0).unwrap()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_task_duration_limit() -> anyhow::Result<()> {
+        let mut container = SingleProjectContainer::new();
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2035, 1, 1, 0, 0, 0).unwrap();
+        let project = Project::new("Long", "Desc", start, end)?;
+        let project_id = *project.get_id();
+        let limit = project.validation_policy.max_task_duration;
+        container.add_project(project)?;
+
+        let mut task_service = TaskService::new(&mut container);
+        let task = task_service.create_regular_task(
+            project_id,
+            "AtLimit".into(),
+            start,
+            start + limit,
+            None,
+        )?;
+        let error = task_service
+            .create_regular_task(
+                project_id,
+                "TooLong".into(),
+                start,
+                start + limit + Duration::days(1),
+                None,
+            )
+            .unwrap_err();
+        assert!(error.to_string().contains("exceeds limit"), "{}", error);
+
+        assert!(
+            task_service
+                .update_task(
+                    project_id,
+                    *task.get_id(),
+                    None,
+                    None,
+                    Some(start + limit + Duration::days(1)),
+                    None,
+                )
+                .is_err()
+        );
+        let task = task_service
+            .get_task_by_id(&project_id, task.get_id())
+            .unwrap();
+        assert_eq!(*task.get_date_end(), start + limit);
+        Ok(())
+    }
 }
@@ -0,0 +1,282 @@
+/// Шаблоны проектов
+///
+/// Шаблон хранит структуру задач без конкретных дат: смещения от начала проекта
+/// и длительности задаются в рабочих днях, поэтому при создании проекта по шаблону
+/// выходные и праздники нового периода учитываются автоматически.
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    BasicGettersForStructures, Dependency, DependencyType, IdGenerator, Project, RandomIds, Task,
+    ValidationPolicy,
+    base_structures::{ProjectCalendar, SnapDirection},
+};
+
+/// Зависимость внутри шаблона, depends_on - индекс задачи в шаблоне
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TemplateDependency {
+    pub depends_on: usize,
+    pub dependency_type: DependencyType,
+    pub lag: Option<TimeDelta>,
+}
+
+/// Задача шаблона
+/// offset_days - рабочих дней от начала проекта до начала задачи
+/// duration_days - длительность в рабочих днях
+/// parent - индекс группирующей задачи в шаблоне
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TemplateTask {
+    pub name: String,
+    pub offset_days: u32,
+    pub duration_days: u32,
+    pub is_summary: bool,
+    pub parent: Option<usize>,
+    pub dependencies: Vec<TemplateDependency>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProjectTemplate {
+    pub description: String,
+    /// Длительность проекта в рабочих днях
+    pub duration_days: u32,
+    pub tasks: Vec<TemplateTask>,
+}
+
+impl ProjectTemplate {
+    /// Шаблон по существующему проекту, задачи упорядочены по дате начала и имени
+    pub fn from_project(project: &Project, calendar: &ProjectCalendar) -> Self {
+        let mut tasks = project.get_project_tasks();
+        tasks.sort_by(|a, b| {
+            a.get_date_start()
+                .cmp(b.get_date_start())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        let index: HashMap<Uuid, usize> = tasks
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (*t.get_id(), i))
+            .collect();
+        let project_start = project.get_date_start().date_naive();
+
+        let tasks = tasks
+            .iter()
+            .map(|task| TemplateTask {
+                name: task.name.clone(),
+                offset_days: working_days_between(
+                    calendar,
+                    project_start,
+                    task.get_date_start().date_naive(),
+                ),
+                duration_days: working_days_between(
+                    calendar,
+                    task.get_date_start().date_naive(),
+                    task.get_date_end().date_naive(),
+                ),
+                is_summary: task.is_summary,
+                parent: task.parent_id.and_then(|id| index.get(&id).copied()),
+                dependencies: task
+                    .get_dependencies()
+                    .iter()
+                    .filter_map(|d| {
+                        index.get(&d.depends_on).map(|i| TemplateDependency {
+                            depends_on: *i,
+                            dependency_type: d.dependency_type,
+                            lag: d.lag,
+                        })
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            description: project.description.clone(),
+            duration_days: working_days_between(
+                calendar,
+                project_start,
+                project.get_date_end().date_naive(),
+            ),
+            tasks,
+        }
+    }
+
+    /// Создать проект по шаблону, начиная с `start_date`
+    ///
+    /// Смещения и длительности переводятся в даты по рабочим дням календаря.
+    pub fn instantiate(
+        &self,
+        name: impl Into<String>,
+        start_date: NaiveDate,
+        calendar: &ProjectCalendar,
+    ) -> anyhow::Result<Project> {
+        self.instantiate_with_ids(name, start_date, calendar, &mut RandomIds)
+    }
+
+    /// Создать проект по шаблону с id проекта и задач из `ids`
+    pub fn instantiate_with_ids(
+        &self,
+        name: impl Into<String>,
+        start_date: NaiveDate,
+        calendar: &ProjectCalendar,
+        ids: &mut impl IdGenerator,
+    ) -> anyhow::Result<Project> {
+        let first_day = calendar.snap_to_working_day(start_date, SnapDirection::Forward)?;
+        let date_of = |offset: u32| calendar.add_working_days(first_day, offset);
+
+        let mut dates: Vec<(DateTime<Utc>, DateTime<Utc>)> = vec![];
+        for task in &self.tasks {
+            let start = date_of(task.offset_days)?;
+            let end = calendar.add_working_days(start, task.duration_days)?;
+            dates.push((midnight(start), midnight(end)));
+        }
+        let project_end = dates
+            .iter()
+            .map(|(_, end)| *end)
+            .chain([midnight(date_of(self.duration_days)?)])
+            .max()
+            .unwrap();
+
+        let mut project = Project::new_with_ids(
+            name,
+            self.description.clone(),
+            midnight(start_date),
+            project_end,
+            ValidationPolicy::default(),
+            ids,
+        )?;
+        project.calendar = calendar.clone();
+
+        // Сначала создаем задачи, затем по их id проставляем родителей и зависимости
+        let mut new_tasks = vec![];
+        for (task, (start, end)) in self.tasks.iter().zip(dates) {
+            new_tasks.push(Task::new_with_ids(
+                task.name.clone(),
+                start,
+                end,
+                None,
+                task.is_summary,
+                ids,
+            )?);
+        }
+        let ids: Vec<Uuid> = new_tasks.iter().map(|t| *t.get_id()).collect();
+        for (task, new_task) in self.tasks.iter().zip(new_tasks.iter_mut()) {
+            new_task.parent_id = task.parent.and_then(|p| ids.get(p).copied());
+            for dependency in &task.dependencies {
+                let depends_on = *ids.get(dependency.depends_on).ok_or_else(|| {
+                    anyhow::anyhow!("Template task '{}' depends on unknown task", task.name)
+                })?;
+                new_task.add_dependency(Dependency::new(
+                    dependency.dependency_type,
+                    depends_on,
+                    dependency.lag,
+                ));
+            }
+        }
+        for task in new_tasks {
+            project.insert_task(task);
+        }
+        Ok(project)
+    }
+}
+
+pub(crate) fn midnight(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap().and_utc()
+}
+
+/// Количество рабочих дней в полуинтервале [from, to)
+fn working_days_between(calendar: &ProjectCalendar, from: NaiveDate, to: NaiveDate) -> u32 {
+    from.iter_days()
+        .take_while(|d| *d < to)
+        .filter(|d| calendar.is_working_day(*d))
+        .count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn day(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_template_round_trip_across_holiday() {
+        let calendar = ProjectCalendar::default();
+        let mut project = Project::new(
+            "Release",
+            "Template source",
+            day(2025, 1, 6),
+            day(2025, 1, 31),
+        )
+        .unwrap();
+        let analysis_id = *project
+            .insert_task(
+                Task::new_regular("Analysis", day(2025, 1, 6), day(2025, 1, 9), None).unwrap(),
+            )
+            .get_id();
+        let mut build =
+            Task::new_regular("Build", day(2025, 1, 9), day(2025, 1, 13), None).unwrap();
+        build.add_dependency(Dependency::new(DependencyType::Blocking, analysis_id, None));
+        let build_id = *project.insert_task(build).get_id();
+        let mut test = Task::new_regular("Test", day(2025, 1, 13), day(2025, 1, 20), None).unwrap();
+        test.add_dependency(Dependency::new(DependencyType::Blocking, build_id, None));
+        project.insert_task(test);
+
+        let template = ProjectTemplate::from_project(&project, &calendar);
+        let offsets: Vec<(u32, u32)> = template
+            .tasks
+            .iter()
+            .map(|t| (t.offset_days, t.duration_days))
+            .collect();
+        assert_eq!(offsets, [(0, 3), (3, 2), (5, 5)]);
+
+        // Шаблон сохраняется в файл как JSON
+        let json = serde_json::to_string(&template).unwrap();
+        let template: ProjectTemplate = serde_json::from_str(&json).unwrap();
+
+        // Новый запуск с праздником в четверг 1 мая
+        let mut may_calendar = ProjectCalendar::default();
+        may_calendar.add_holiday(NaiveDate::from_ymd_opt(2025, 5, 1).unwrap());
+        let start = NaiveDate::from_ymd_opt(2025, 4, 28).unwrap();
+        let copy = template
+            .instantiate("Release 2", start, &may_calendar)
+            .unwrap();
+
+        let by_name = |name: &str| copy.tasks.values().find(|t| t.name == name).unwrap();
+        let analysis = by_name("Analysis");
+        assert_eq!(*analysis.get_date_end(), day(2025, 5, 2));
+        let build = by_name("Build");
+        assert_eq!(*build.get_date_start(), day(2025, 5, 2));
+        assert_eq!(*build.get_date_end(), day(2025, 5, 6));
+        let test = by_name("Test");
+        assert_eq!(test.get_dependencies()[0].depends_on, *build.get_id());
+        assert_eq!(build.get_dependencies()[0].depends_on, *analysis.get_id());
+
+        for (task, original) in [(test, 5), (build, 3), (analysis, 0)] {
+            assert_eq!(
+                working_days_between(&may_calendar, start, task.get_date_start().date_naive()),
+                original
+            );
+        }
+    }
+
+    #[test]
+    fn test_instantiate_fails_without_working_days() {
+        let mut template = ProjectTemplate::from_project(
+            &Project::new("Empty", "", day(2025, 1, 6), day(2025, 1, 31)).unwrap(),
+            &ProjectCalendar::default(),
+        );
+        template.duration_days = 5;
+        // Весь год после старта - праздники
+        let mut closed = ProjectCalendar::default();
+        let start = NaiveDate::from_ymd_opt(2025, 4, 28).unwrap();
+        for date in start.iter_days().take(400) {
+            closed.add_holiday(date);
+        }
+        assert!(template.instantiate("Closed", start, &closed).is_err());
+    }
+}
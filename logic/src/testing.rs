@@ -0,0 +1,303 @@
+//! Демонстрационные данные
+//!
+//! Один и тот же пример проекта используется в приложении (Файл → Демо-проект), в командной
+//! строке (`rsproject demo > demo.rsp`), в тестах и документации. Структура, даты, назначения,
+//! бюджет и базовый план фиксированы, id выдаются последовательно от `DEMO_ID_SEED`,
+//! поэтому демо-файл получается одинаковым при каждом создании.
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::{
+    BasicGettersForStructures, DEFAULT_CURRENCY, DependencyType, ExceptionPeriod, ExceptionType,
+    ProjectCalendar, ProjectContainer, RateMeasure, ResourceService, SequentialIds, SnapDirection,
+    TaskService, TimeWindow,
+    base_structures::Resource,
+    templates::{ProjectTemplate, TemplateDependency, TemplateTask, midnight},
+};
+
+pub const DEMO_PROJECT_NAME: &str = "Демо: внедрение CRM";
+
+/// Первый id демо-данных: проект, затем задачи, ресурсы и назначения по порядку
+pub const DEMO_ID_SEED: u128 = 0xde00_0000_0000_0000_0000_0000_0000_0001;
+
+/// Бюджет демо-проекта в валюте по умолчанию, с запасом около 10% к стоимости назначений
+pub const DEMO_BUDGET: f64 = 1_450_000.0;
+
+/// Дата начала демо-проекта (понедельник)
+pub fn demo_start() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 1, 12).unwrap()
+}
+
+// (имя, смещение, длительность в рабочих днях, индекс этапа, зависимости (индекс, блокирующая))
+type DemoTask = (
+    &'static str,
+    u32,
+    u32,
+    Option<usize>,
+    &'static [(usize, bool)],
+);
+
+const PHASES: [(usize, &str, u32, u32); 3] = [
+    (0, "Анализ", 0, 15),
+    (6, "Разработка", 15, 35),
+    (17, "Внедрение", 50, 20),
+];
+
+const TASKS: [(usize, DemoTask); 23] = [
+    (1, ("Сбор требований", 0, 5, Some(0), &[])),
+    (2, ("Анализ конкурентов", 0, 4, Some(0), &[])),
+    (
+        3,
+        ("Интервью с пользователями", 5, 4, Some(0), &[(1, true)]),
+    ),
+    (4, ("Спецификация", 9, 4, Some(0), &[(3, true), (2, false)])),
+    (
+        5,
+        ("Согласование спецификации", 13, 2, Some(0), &[(4, true)]),
+    ),
+    (7, ("Архитектура", 15, 5, Some(6), &[(5, true)])),
+    (8, ("Дизайн интерфейса", 15, 8, Some(6), &[(5, true)])),
+    (9, ("База данных", 20, 6, Some(6), &[(7, true)])),
+    (10, ("Серверная часть", 26, 12, Some(6), &[(9, true)])),
+    (
+        11,
+        ("Клиентская часть", 23, 14, Some(6), &[(8, true), (7, true)]),
+    ),
+    (
+        12,
+        ("Интеграция", 38, 5, Some(6), &[(10, true), (11, true)]),
+    ),
+    (13, ("Модульные тесты", 26, 14, Some(6), &[(9, false)])),
+    (14, ("Документация API", 38, 6, Some(6), &[(10, true)])),
+    (15, ("Ревью кода", 43, 3, Some(6), &[(12, true)])),
+    (
+        16,
+        ("Нагрузочное тестирование", 46, 4, Some(6), &[(15, true)]),
+    ),
+    (
+        18,
+        ("Приемочное тестирование", 50, 5, Some(17), &[(16, true)]),
+    ),
+    (19, ("Исправление ошибок", 55, 5, Some(17), &[(18, true)])),
+    (
+        20,
+        ("Обучение пользователей", 52, 5, Some(17), &[(14, false)]),
+    ),
+    (
+        21,
+        ("Подготовка инфраструктуры", 50, 4, Some(17), &[(16, true)]),
+    ),
+    (22, ("Миграция данных", 54, 3, Some(17), &[(21, true)])),
+    (23, ("Релиз", 60, 2, Some(17), &[(19, true), (22, true)])),
+    (
+        24,
+        ("Поддержка после релиза", 62, 6, Some(17), &[(23, true)]),
+    ),
+    (25, ("Закрытие проекта", 68, 2, Some(17), &[(23, false)])),
+];
+
+// (имя, ставка, единица ставки, отпуск: смещение и длительность в рабочих днях)
+const RESOURCES: [(&str, f64, RateMeasure, u32, u32); 5] = [
+    ("Анна Смирнова", 1500.0, RateMeasure::Hourly, 60, 5),
+    ("Борис Ковалев", 16000.0, RateMeasure::Daily, 28, 5),
+    ("Вера Орлова", 1300.0, RateMeasure::Hourly, 40, 5),
+    ("Григорий Павлов", 250000.0, RateMeasure::Monthly, 3, 5),
+    ("Дарья Лебедева", 1100.0, RateMeasure::Hourly, 10, 5),
+];
+
+// (индекс задачи, индекс ресурса, загрузка)
+const ALLOCATIONS: [(usize, usize, f64); 26] = [
+    (1, 0, 0.6),
+    (2, 0, 0.4),
+    (3, 0, 1.0),
+    (4, 0, 1.0),
+    (5, 0, 0.5),
+    (5, 1, 0.5),
+    (7, 1, 1.0),
+    (8, 2, 1.0),
+    (9, 1, 0.5),
+    (9, 3, 0.5),
+    (10, 3, 1.0),
+    (11, 2, 0.5),
+    (12, 1, 0.5),
+    (12, 3, 0.5),
+    (13, 4, 0.5),
+    (14, 3, 0.5),
+    (15, 1, 1.0),
+    (16, 4, 1.0),
+    (18, 0, 0.5),
+    (18, 4, 0.5),
+    (19, 3, 1.0),
+    (20, 0, 0.5),
+    (21, 4, 0.5),
+    (22, 1, 1.0),
+    (23, 3, 0.5),
+    (24, 3, 0.3),
+];
+
+/// Шаблон демо-проекта: 3 этапа, 23 задачи с зависимостями
+pub fn demo_template() -> ProjectTemplate {
+    let mut tasks: Vec<Option<TemplateTask>> = vec![None; PHASES.len() + TASKS.len()];
+    for (index, name, offset_days, duration_days) in PHASES {
+        tasks[index] = Some(TemplateTask {
+            name: name.to_string(),
+            offset_days,
+            duration_days,
+            is_summary: true,
+            parent: None,
+            dependencies: vec![],
+        });
+    }
+    for (index, (name, offset_days, duration_days, parent, dependencies)) in TASKS {
+        tasks[index] = Some(TemplateTask {
+            name: name.to_string(),
+            offset_days,
+            duration_days,
+            is_summary: false,
+            parent,
+            dependencies: dependencies
+                .iter()
+                .map(|(depends_on, blocking)| TemplateDependency {
+                    depends_on: *depends_on,
+                    dependency_type: if *blocking {
+                        DependencyType::Blocking
+                    } else {
+                        DependencyType::NonBlocking
+                    },
+                    lag: None,
+                })
+                .collect(),
+        });
+    }
+    ProjectTemplate {
+        description: "Пример проекта: этапы, зависимости, ресурсы с отпусками и назначениями"
+            .to_string(),
+        duration_days: 80,
+        tasks: tasks.into_iter().map(|t| t.unwrap()).collect(),
+    }
+}
+
+/// Создать демо-проект в контейнере: задачи по шаблону, 5 ресурсов с отпусками, назначения,
+/// бюджет и базовый план
+///
+/// Ресурсы добавляются в общий пул контейнера. Возвращает id созданного проекта.
+pub fn create_demo_project<C: ProjectContainer>(
+    container: &mut C,
+    start: NaiveDate,
+) -> anyhow::Result<Uuid> {
+    let mut ids = SequentialIds::starting_at(DEMO_ID_SEED);
+    let calendar = ProjectCalendar::default();
+    let template = demo_template();
+    let mut project =
+        template.instantiate_with_ids(DEMO_PROJECT_NAME, start, &calendar, &mut ids)?;
+    project.set_budget(Some(DEMO_BUDGET), DEFAULT_CURRENCY)?;
+    let project_id = *project.get_id();
+    // Задачи шаблона идут в порядке индексов, номера T-1... совпадают с порядком
+    let mut task_ids: Vec<(u64, Uuid)> = project
+        .tasks
+        .values()
+        .map(|t| (t.get_number(), *t.get_id()))
+        .collect();
+    task_ids.sort();
+    let task_ids: Vec<Uuid> = task_ids.into_iter().map(|(_, id)| id).collect();
+    container.add_project(project)?;
+
+    let first_day = calendar.snap_to_working_day(start, SnapDirection::Forward)?;
+    let mut resource_ids = vec![];
+    let mut resource_service = ResourceService::new(container);
+    for (name, rate, measure, vacation_offset, vacation_days) in RESOURCES {
+        let resource = Resource::new_with_ids(name.to_string(), rate, measure, &mut ids)?;
+        let resource_id = resource.id;
+        // Пул переживает закрытие проекта, а id демо-ресурсов всегда одни и те же
+        if resource_service
+            .list_resources()
+            .iter()
+            .any(|r| r.id == resource_id)
+        {
+            anyhow::bail!("Demo resource {} is already in the pool", name);
+        }
+        resource_service.add_resource(resource)?;
+        let vacation_start = calendar.add_working_days(first_day, vacation_offset)?;
+        let vacation_end = calendar.add_working_days(vacation_start, vacation_days)?;
+        resource_service.add_unavailable_period(
+            resource_id,
+            ExceptionPeriod {
+                period: TimeWindow::new(midnight(vacation_start), midnight(vacation_end))?,
+                exception_type: ExceptionType::Vacation,
+            },
+        )?;
+        resource_ids.push(resource_id);
+    }
+
+    let mut task_service = TaskService::new(container);
+    for (task_index, resource_index, engagement) in ALLOCATIONS {
+        task_service.allocate_resource_with_ids(
+            project_id,
+            task_ids[task_index],
+            resource_ids[resource_index],
+            engagement,
+            None,
+            None,
+            &mut ids,
+        )?;
+    }
+    container
+        .get_project_mut(&project_id)
+        .ok_or_else(|| anyhow::anyhow!("Project not found"))?
+        .snapshot_baseline(calendar.local_midnight(start));
+    Ok(project_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ProjectService, Scheduler, SingleProjectContainer};
+
+    #[test]
+    fn test_demo_project() {
+        let mut container = SingleProjectContainer::new();
+        let project_id = create_demo_project(&mut container, demo_start()).unwrap();
+
+        let project = container.get_project(&project_id).unwrap();
+        assert_eq!(project.tasks.len(), 26);
+        assert_eq!(project.tasks.values().filter(|t| t.is_summary).count(), 3);
+        assert!(project.dependency_violations().is_empty());
+        assert_eq!(container.resource_pool().resources().len(), 5);
+        assert!(container.resource_pool().find_conflicts().is_empty());
+        assert_eq!(
+            container
+                .resource_pool()
+                .get_project_allocations(&project_id)
+                .len(),
+            ALLOCATIONS.len()
+        );
+
+        let critical_path = Scheduler::new(&container)
+            .critical_path(project_id)
+            .unwrap();
+        let names: Vec<&str> = critical_path
+            .iter()
+            .map(|id| project.tasks[id].name.as_str())
+            .collect();
+        assert_eq!(names.first(), Some(&"Сбор требований"));
+        assert!(names.contains(&"Релиз"));
+
+        assert_eq!(project_id, Uuid::from_u128(DEMO_ID_SEED));
+        assert!(project.has_baseline());
+        let budget = ProjectService::new(&mut container)
+            .budget_status(project_id)
+            .unwrap();
+        assert_eq!(budget.budget, Some(DEMO_BUDGET));
+        assert!(!budget.over_budget);
+    }
+
+    #[test]
+    fn test_demo_project_is_reproducible() {
+        let save = || {
+            let mut container = SingleProjectContainer::new();
+            create_demo_project(&mut container, demo_start()).unwrap();
+            container.to_versioned_json().unwrap()
+        };
+        assert_eq!(save(), save());
+    }
+}
@@ -1,7 +1,8 @@
 use chrono::{TimeZone, Utc};
 use logic::{
-    BasicGettersForStructures, ExceptionPeriod, ExceptionType, Project, ProjectContainer,
-    RateMeasure, ResourceService, SingleProjectContainer, TaskService, TimeWindow,
+    BasicGettersForStructures, ContainerEvent, ExceptionPeriod, ExceptionType,
+    MultiProjectContainer, Project, ProjectContainer, RateMeasure, ResourceService,
+    SingleProjectContainer, TaskService, TimeWindow,
 };
 
 #[test]
@@ -50,11 +51,38 @@ fn test_full_scenario() -> anyhow::Result<()> {
         resource.id
     };
 
-    {
+    let allocation_id = {
         let mut task_service = TaskService::new(&mut container);
         let time_window = TimeWindow::new(task_start, task_end)?;
-        task_service.allocate_resource(project_id, task_id, resource_id, 0.8, Some(time_window))?;
-    }
+        task_service.allocate_resource(
+            project_id,
+            task_id,
+            resource_id,
+            0.8,
+            Some(time_window),
+            None,
+        )?
+    };
+
+    // Каждое изменение записано в контейнере в порядке выполнения
+    assert_eq!(
+        container.take_events(),
+        vec![
+            ContainerEvent::ProjectAdded { project_id },
+            ContainerEvent::TaskCreated {
+                project_id,
+                task_id
+            },
+            ContainerEvent::ResourceAdded { resource_id },
+            ContainerEvent::ResourceUpdated { resource_id },
+            ContainerEvent::AllocationCreated {
+                allocation_id,
+                resource_id,
+                task_id
+            },
+        ]
+    );
+    assert!(container.take_events().is_empty());
 
     let utilization = {
         let resource_service = ResourceService::new(&mut container);
@@ -73,3 +101,77 @@ fn test_full_scenario() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_multi_project_shared_resource() -> anyhow::Result<()> {
+    let mut container = MultiProjectContainer::new();
+
+    let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap();
+    let mut project_ids = vec![];
+    for name in ["Alpha", "Beta"] {
+        let project = Project::new(name, "Multi project test", start, end)?;
+        project_ids.push(*project.get_id());
+        container.add_project(project)?;
+    }
+    assert!(
+        container
+            .add_project(Project::new("Alpha", "Duplicate", start, end)?)
+            .is_err()
+    );
+
+    // Один ресурс на весь контейнер
+    let resource_id = {
+        let mut resource_service = ResourceService::new(&mut container);
+        let resource = resource_service.create_resource("Max", 1000.0, RateMeasure::Hourly)?;
+        resource_service.add_resource(resource.clone())?;
+        resource.id
+    };
+
+    // По задаче в каждом проекте, ресурс занят в обеих наполовину
+    let task_start = Utc.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap();
+    let task_end = Utc.with_ymd_and_hms(2026, 2, 7, 0, 0, 0).unwrap();
+    let mut task_ids = vec![];
+    {
+        let mut task_service = TaskService::new(&mut container);
+        for project_id in &project_ids {
+            let task = task_service.create_regular_task(
+                *project_id,
+                "Review".into(),
+                task_start,
+                task_end,
+                None,
+            )?;
+            task_service.allocate_resource(
+                *project_id,
+                *task.get_id(),
+                resource_id,
+                0.5,
+                None,
+                None,
+            )?;
+            task_ids.push(*task.get_id());
+        }
+    }
+
+    for (project_id, task_id) in project_ids.iter().zip(&task_ids) {
+        let task_service = TaskService::new(&mut container);
+        assert_eq!(task_service.get_all_tasks(*project_id).len(), 1);
+        // 40 часов (5 рабочих дней) * 0.5 engagement rate * 1000 hourly rate
+        assert_eq!(
+            task_service.calculate_task_cost(project_id, task_id)?,
+            1000.0 * 0.5 * 40.0
+        );
+    }
+
+    let resource_service = ResourceService::new(&mut container);
+    assert_eq!(resource_service.get_resource_utilization(resource_id), 1.0);
+    assert_eq!(
+        container
+            .resource_pool()
+            .get_resource_existing_allocations(&resource_id)
+            .len(),
+        2
+    );
+    Ok(())
+}